@@ -3,7 +3,7 @@
 /// Walks source files, resolves each one's last-commit timestamp via git,
 /// and classifies as Active / Stale / Frozen based on configurable thresholds.
 pub mod analyzer;
-mod report;
+pub(crate) mod report;
 
 use std::error::Error;
 use std::path::PathBuf;
@@ -13,29 +13,39 @@ use chrono::Utc;
 use crate::cli::OutputMode;
 use crate::git::GitRepo;
 use crate::walk::{self, WalkConfig};
-use analyzer::{AgeStatus, AgeThresholds, classify};
+use analyzer::classify;
+
+pub use analyzer::{AgeStatus, AgeThresholds, FileAge};
 use report::{print_json, print_report, print_short, print_terse};
 
-/// Run code age analysis and print results.
+/// Classify every source file by the age of its last git modification.
 ///
 /// `active_days` / `frozen_days` define the Active/Stale/Frozen boundaries.
-/// `status_filter` restricts output to "active", "stale", or "frozen".
+/// `status_filter` restricts results to "active", "stale", or "frozen".
 /// Files not found in git history (e.g. untracked) are skipped with a warning.
-pub fn run(
+/// With a commit filter (`cfg.commits`), files outside its paths or not
+/// touched by the selected commits are skipped silently.
+pub fn analyze_project(
     cfg: &WalkConfig<'_>,
-    output: OutputMode,
-    active_days: u64,
-    frozen_days: u64,
+    thresholds: &AgeThresholds,
     sort_by: &str,
     status_filter: Option<&str>,
-) -> Result<(), Box<dyn Error>> {
+) -> Result<Vec<FileAge>, Box<dyn Error>> {
+    let AgeThresholds {
+        active_days,
+        frozen_days,
+    } = *thresholds;
     let git = GitRepo::open(cfg.path)
-        .map_err(|e| format!("not a git repository (or any parent): {e}"))?;
+        .map_err(|e| format!("not a git repository (or any parent): {e}"))?
+        .with_rename_threshold(cfg.rename_threshold)
+        .with_exclusions(cfg.exclusions)
+        .with_commit_filter(cfg.commits)?;
 
     let (walk_root, prefix) = git.walk_prefix(cfg.path)?;
 
-    let source_files: Vec<(PathBuf, &'static crate::loc::language::LanguageSpec)> =
+    let mut source_files: Vec<(PathBuf, &'static crate::loc::language::LanguageSpec)> =
         walk::source_files(&walk_root, cfg.exclude_tests(), cfg.filter);
+    source_files.retain(|(p, _)| git.includes_path(&GitRepo::to_git_path(&walk_root, &prefix, p)));
 
     // Build git-relative paths for the blame query.
     let git_paths: Vec<PathBuf> = source_files
@@ -53,17 +63,15 @@ pub fn run(
     }
 
     let now = Utc::now().timestamp();
-    let thresholds = AgeThresholds {
-        active_days,
-        frozen_days,
-    };
 
     let mut files: Vec<_> = source_files
         .into_iter()
         .filter_map(|(file_path, spec)| {
             let rel = GitRepo::to_git_path(&walk_root, &prefix, &file_path);
             match last_modified.get(&rel) {
-                Some(&ts) => Some(classify(rel, spec.name, ts, now, &thresholds)),
+                Some(&ts) => Some(classify(rel, spec.name, ts, now, thresholds)),
+                // Files the selected commits did not touch are left out.
+                None if cfg.commits.is_some() => None,
                 None => {
                     eprintln!("warning: no git history for {}", rel.display());
                     None
@@ -87,13 +95,40 @@ pub fn run(
         _ => files.sort_by_key(|f| f.last_modified), // "date" — oldest first
     }
 
+    Ok(files)
+}
+
+/// Run code age analysis and print results.
+///
+/// `active_days` / `frozen_days` define the Active/Stale/Frozen boundaries.
+/// `status_filter` restricts output to "active", "stale", or "frozen".
+/// Files not found in git history (e.g. untracked) are skipped with a warning.
+/// With a commit filter (`cfg.commits`), files outside its paths or not
+/// touched by the selected commits are skipped silently.
+pub fn run(
+    cfg: &WalkConfig<'_>,
+    output: OutputMode,
+    active_days: u64,
+    frozen_days: u64,
+    sort_by: &str,
+    status_filter: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+    let thresholds = AgeThresholds {
+        active_days,
+        frozen_days,
+    };
+    let files = analyze_project(cfg, &thresholds, sort_by, status_filter)?;
+
     match output {
         OutputMode::Json => print_json(&files),
         OutputMode::Short => print_short(&files),
         OutputMode::Terse => print_terse(&files),
-        OutputMode::Github | OutputMode::Codeclimate => {
+        OutputMode::Github | OutputMode::Codeclimate | OutputMode::Sarif => {
             return Err(crate::cli::ERR_CI_FORMAT_ONLY.into());
         }
+        OutputMode::Html | OutputMode::Markdown => {
+            return Err(crate::cli::ERR_REPORT_FORMAT_ONLY.into());
+        }
         OutputMode::Table => print_report(&files, &thresholds),
     }
 
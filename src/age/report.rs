@@ -82,8 +82,8 @@ struct JsonEntry {
     status: String,
 }
 
-/// Serialize file age data as pretty-printed JSON to stdout.
-pub fn print_json(files: &[FileAge]) {
+/// Serialize file age data as a pretty-printed JSON string.
+pub fn format_json(files: &[FileAge]) -> Result<String, Box<dyn std::error::Error>> {
     let entries: Vec<JsonEntry> = files
         .iter()
         .map(|f| JsonEntry {
@@ -95,7 +95,12 @@ pub fn print_json(files: &[FileAge]) {
         })
         .collect();
 
-    report_helpers::print_json_stdout(&entries).unwrap();
+    report_helpers::json_string(&entries)
+}
+
+/// Serialize file age data as pretty-printed JSON to stdout.
+pub fn print_json(files: &[FileAge]) {
+    println!("{}", format_json(files).unwrap());
 }
 
 /// Print age as a single compact line.
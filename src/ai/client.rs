@@ -1,13 +1,14 @@
 /// HTTP client for the Anthropic Messages API.
 ///
 /// Handles request serialization, authentication headers, timeout, and
-/// response deserialization for the AI-assisted analysis feature.
+/// response deserialization for the AI-assisted analysis feature. The
+/// message types here are also the provider-neutral conversation format:
+/// other backends (see `openai`) translate to and from them.
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::time::Duration;
 
-/// Anthropic Messages API endpoint.
-const API_URL: &str = "https://api.anthropic.com/v1/messages";
 /// API version header required by the Anthropic API.
 const ANTHROPIC_VERSION: &str = "2023-06-01";
 /// Maximum time to wait for a model response (5 minutes).
@@ -73,20 +74,30 @@ pub fn build_client() -> Result<reqwest::blocking::Client, Box<dyn std::error::E
         .build()?)
 }
 
-/// Send a request to the Anthropic Messages API and return the parsed response.
+/// Send a request to the Anthropic Messages API at `base_url` (e.g.
+/// `https://api.anthropic.com/v1`) and return the parsed response.
 /// Fails with a descriptive error on HTTP errors or deserialization failures.
 pub fn send_message(
     client: &reqwest::blocking::Client,
-    api_key: &str,
+    base_url: &str,
+    api_key: Option<&str>,
     request: &ApiRequest,
 ) -> Result<ApiResponse, Box<dyn std::error::Error>> {
-    let resp = client
-        .post(API_URL)
-        .header("x-api-key", api_key)
-        .header("anthropic-version", ANTHROPIC_VERSION)
-        .header("content-type", "application/json")
-        .json(request)
-        .send()?;
+    let mut builder = client
+        .post(format!("{base_url}/messages"))
+        .header("anthropic-version", ANTHROPIC_VERSION);
+    if let Some(key) = api_key {
+        builder = builder.header("x-api-key", key);
+    }
+    post_json(builder.json(request))
+}
+
+/// Send a prepared JSON request and deserialize the response body.
+/// Non-2xx statuses become errors carrying the response body.
+pub(super) fn post_json<T: DeserializeOwned>(
+    builder: reqwest::blocking::RequestBuilder,
+) -> Result<T, Box<dyn std::error::Error>> {
+    let resp = builder.send()?;
 
     let status = resp.status();
     if !status.is_success() {
@@ -94,6 +105,5 @@ pub fn send_message(
         return Err(format!("API error ({status}): {body}").into());
     }
 
-    let response: ApiResponse = resp.json()?;
-    Ok(response)
+    Ok(resp.json()?)
 }
@@ -1,70 +1,796 @@
 //! Tool executor for AI-powered analysis.
 //!
-//! Maps tool call names (e.g. `"km_loc"`) to `km` subprocess invocations.
-//! Each tool runs `km <subcmd> --json <path>` and returns the stdout output.
-//! Named parameters from the AI input (like `top`, `since`, `min_lines`)
-//! are converted to `--flag value` CLI arguments.
+//! Maps tool call names (e.g. `"km_loc"`) to in-process calls into the
+//! analysis modules. Each tool's input is deserialized into a typed struct
+//! mirroring the subcommand's CLI flags, the analysis runs against a
+//! `WalkConfig` rooted inside the project, and the result is returned as
+//! the same JSON document `km <subcmd> --format json` prints.
+//!
+//! Optional thresholds fall back to `.kimun.toml` and then to the CLI
+//! defaults, exactly as they do on the command line.
+
+use std::error::Error;
+use std::path::{Path, PathBuf};
 
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer};
 use serde_json::Value;
-use std::path::Path;
-use std::process::Command;
 
-/// Execute a `km` subcommand by name, passing `--json` and any extra arguments
-/// extracted from the AI tool input. Returns the JSON output or an error message.
+use super::schema;
+use crate::cli::CommitFilterArgs;
+use crate::config::KimunConfig;
+use crate::git::{AuthorAliases, CommitExclusions, CommitFilter, FixPatterns, GitRepo};
+use crate::walk::{ExcludeFilter, WalkConfig};
+use crate::{
+    age, authors, churn, cogcom, cycom, debt, defects, deps, dups, hal, history, hotspots, indent,
+    knowledge, loc, mi, miv, report, score, smells, tc,
+};
+
+/// Execute a `km` tool by name with the given AI tool input. Returns the
+/// tool's JSON output, or an error message the model can read and act on.
 pub fn execute_tool(tool_name: &str, input: &Value, project_path: &Path) -> String {
-    let km_binary = std::env::current_exe().unwrap_or_else(|_| "km".into());
-
-    let (subcmd, args) = match tool_name {
-        "km_loc" => ("loc", build_args(input, &[], project_path)),
-        "km_score" => ("score", build_args(input, &[], project_path)),
-        "km_hal" => ("hal", build_args(input, &["top"], project_path)),
-        "km_cycom" => ("cycom", build_args(input, &["top"], project_path)),
-        "km_indent" => ("indent", build_args(input, &[], project_path)),
-        "km_mi" => ("mi", build_args(input, &["top"], project_path)),
-        "km_miv" => ("miv", build_args(input, &["top"], project_path)),
-        "km_dups" => ("dups", build_args(input, &["min_lines"], project_path)),
-        "km_hotspots" => (
-            "hotspots",
-            build_args(input, &["top", "since"], project_path),
-        ),
-        "km_knowledge" => (
-            "knowledge",
-            build_args(input, &["top", "since"], project_path),
-        ),
-        "km_tc" => ("tc", build_args(input, &["top", "since"], project_path)),
-        _ => return format!("Unknown tool: {tool_name}"),
+    match run_tool(tool_name, input, project_path) {
+        Ok(Some(json)) => json,
+        Ok(None) => format!("Unknown tool: {tool_name}"),
+        Err(e) => format!("Error running {tool_name}: {e}"),
+    }
+}
+
+/// Dispatch to the tool runner. Returns `Ok(None)` for unknown tool names.
+pub(crate) fn run_tool(
+    tool_name: &str,
+    input: &Value,
+    project_path: &Path,
+) -> Result<Option<String>, Box<dyn Error>> {
+    let kcfg = KimunConfig::load();
+    let json = match tool_name {
+        "km_loc" => run_loc(parse(input)?, project_path, &kcfg),
+        "km_score" => run_score(parse(input)?, project_path, &kcfg),
+        "km_hal" => run_hal(parse(input)?, project_path),
+        "km_cycom" => run_cycom(parse(input)?, project_path),
+        "km_cogcom" => run_cogcom(parse(input)?, project_path),
+        "km_indent" => run_indent(parse(input)?, project_path),
+        "km_mi" => run_mi(parse(input)?, project_path),
+        "km_miv" => run_miv(parse(input)?, project_path),
+        "km_dups" => run_dups(parse(input)?, project_path, &kcfg),
+        "km_report" => run_report(parse(input)?, project_path, &kcfg),
+        "km_smells" => run_smells(parse(input)?, project_path, &kcfg),
+        "km_deps" => run_deps(parse(input)?, project_path),
+        "km_churn" => run_churn(parse(input)?, project_path, &kcfg),
+        "km_defects" => run_defects(parse(input)?, project_path, &kcfg),
+        "km_hotspots" => run_hotspots(parse(input)?, project_path, &kcfg),
+        "km_knowledge" => run_knowledge(parse(input)?, project_path, &kcfg),
+        "km_tc" => run_tc(parse(input)?, project_path, &kcfg),
+        "km_age" => run_age(parse(input)?, project_path, &kcfg),
+        "km_authors" => run_authors(parse(input)?, project_path, &kcfg),
+        "km_debt" => run_debt(parse(input)?, project_path, &kcfg),
+        "km_history" => run_history(parse(input)?, project_path, &kcfg),
+        _ => return Ok(None),
+    };
+    json.map(Some)
+}
+
+/// Deserialize the tool input into its typed parameter struct.
+fn parse<T: DeserializeOwned>(input: &Value) -> Result<T, Box<dyn Error>> {
+    // Models sometimes send `null` instead of `{}` for tools without arguments.
+    let input = if input.is_null() {
+        &Value::Object(Default::default())
+    } else {
+        input
     };
+    serde_json::from_value(input.clone()).map_err(|e| format!("invalid input: {e}").into())
+}
 
-    let mut cmd = Command::new(&km_binary);
-    cmd.arg(subcmd).arg("--json");
-    for arg in &args {
-        cmd.arg(arg);
+/// Check an enumerated string parameter against the values the schema allows.
+fn one_of<'a>(
+    name: &str,
+    value: Option<&'a str>,
+    allowed: &[&'a str],
+) -> Result<&'a str, Box<dyn Error>> {
+    match value {
+        None => Ok(allowed[0]),
+        Some(v) if allowed.contains(&v) => Ok(v),
+        Some(v) => Err(format!(
+            "invalid {name} '{v}' (expected one of: {})",
+            allowed.join(", ")
+        )
+        .into()),
     }
+}
 
-    match cmd.output() {
-        Ok(output) => {
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            if output.status.success() {
-                stdout.into_owned()
-            } else {
-                format!("Error running km {subcmd}: {stderr}")
-            }
+// ── inputs ───────────────────────────────────────────────────────────────────
+
+/// Parameters shared by every tool that walks the filesystem.
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct WalkInput {
+    path: Option<String>,
+    include_tests: bool,
+    include_ext: Vec<String>,
+    exclude_ext: Vec<String>,
+    exclude_dir: Vec<String>,
+    exclude: Vec<String>,
+}
+
+impl WalkInput {
+    fn filter(&self) -> ExcludeFilter {
+        ExcludeFilter::new(
+            &self.include_ext,
+            &self.exclude_ext,
+            &self.exclude_dir,
+            &self.exclude,
+        )
+    }
+
+    fn root(&self, project_path: &Path) -> PathBuf {
+        resolve_path(self.path.as_deref(), project_path)
+    }
+}
+
+/// Resolve the walk root and filter from `walk`, then run `f` with a
+/// `WalkConfig` built from them.
+fn with_walk<R>(walk: &WalkInput, project_path: &Path, f: impl FnOnce(&WalkConfig<'_>) -> R) -> R {
+    let root = walk.root(project_path);
+    let filter = walk.filter();
+    let cfg = WalkConfig::new(&root, walk.include_tests, &filter);
+    f(&cfg)
+}
+
+/// Like [`with_walk`], with the `[authors.aliases]`, `[defects]` and `[git]`
+/// settings of `kcfg` for the tools that read git history.
+fn with_git_walk<T>(
+    walk: &WalkInput,
+    project_path: &Path,
+    kcfg: &KimunConfig,
+    f: impl FnOnce(&WalkConfig<'_>) -> Result<T, Box<dyn Error>>,
+) -> Result<T, Box<dyn Error>> {
+    let aliases = AuthorAliases::resolve(&kcfg.authors)?;
+    let fix_patterns = FixPatterns::resolve(&kcfg.defects)?;
+    let exclusions = CommitExclusions::resolve(&kcfg.git, project_path)?;
+    with_walk(walk, project_path, |cfg| {
+        f(&WalkConfig {
+            aliases: Some(&aliases),
+            fix_patterns: Some(&fix_patterns),
+            exclusions: Some(&exclusions),
+            rename_threshold: kcfg.git.resolve_rename_threshold(),
+            ..*cfg
+        })
+    })
+}
+
+/// Commit filters accepted by the tools that read git history, as the
+/// CLI's `--range`, `--until`, `--author` and `--path`.
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct CommitFilterInput {
+    range: Option<String>,
+    until: Option<String>,
+    #[serde(deserialize_with = "one_or_many")]
+    author: Vec<String>,
+    paths: Vec<String>,
+}
+
+impl CommitFilterInput {
+    fn filter(&self) -> Result<Option<CommitFilter>, Box<dyn Error>> {
+        CommitFilterArgs {
+            range: self.range.clone(),
+            until: self.until.clone(),
+            author: self.author.clone(),
+            paths: self.paths.clone(),
+            ..Default::default()
         }
-        Err(e) => format!("Failed to execute km {subcmd}: {e}"),
+        .commit_filter()
     }
 }
 
-/// Resolve the `path` field from the AI input to a safe, canonical path
-/// within the project root. Falls back to the project root if the path
-/// is missing, invalid, or outside the project.
-fn resolve_path(input: &Value, project_path: &Path) -> String {
-    let raw = input.get("path").and_then(|v| v.as_str()).unwrap_or("");
+/// Accept a single string where a list of strings is expected.
+fn one_or_many<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(s) => vec![s],
+        OneOrMany::Many(v) => v,
+    })
+}
+
+/// Like [`with_git_walk`], restricting the history read to `commits`.
+fn with_history_walk<T>(
+    walk: &WalkInput,
+    commits: &CommitFilterInput,
+    project_path: &Path,
+    kcfg: &KimunConfig,
+    f: impl FnOnce(&WalkConfig<'_>) -> Result<T, Box<dyn Error>>,
+) -> Result<T, Box<dyn Error>> {
+    let filter = commits.filter()?;
+    with_git_walk(walk, project_path, kcfg, |cfg| {
+        f(&WalkConfig {
+            commits: filter.as_ref(),
+            ..*cfg
+        })
+    })
+}
+
+#[derive(Deserialize)]
+struct LocInput {
+    #[serde(flatten)]
+    walk: WalkInput,
+    #[serde(default)]
+    by_author: bool,
+}
+
+#[derive(Deserialize)]
+struct ScoreInput {
+    #[serde(flatten)]
+    walk: WalkInput,
+    bottom: Option<usize>,
+    min_lines: Option<usize>,
+    model: Option<String>,
+    trend: Option<String>,
+    top_changes: Option<usize>,
+    new_code_since: Option<String>,
+    plan_target: Option<String>,
+    by_dir: Option<usize>,
+    #[serde(default)]
+    by_package: bool,
+}
+
+/// Input for tools whose only options are `top` and `sort_by`.
+#[derive(Deserialize)]
+struct TopSortInput {
+    #[serde(flatten)]
+    walk: WalkInput,
+    top: Option<usize>,
+    sort_by: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ComplexityInput {
+    #[serde(flatten)]
+    walk: WalkInput,
+    min_complexity: Option<usize>,
+    top: Option<usize>,
+    sort_by: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct IndentInput {
+    #[serde(flatten)]
+    walk: WalkInput,
+}
+
+#[derive(Deserialize)]
+struct DupsInput {
+    #[serde(flatten)]
+    walk: WalkInput,
+    min_lines: Option<usize>,
+    #[serde(default)]
+    show_all: bool,
+}
+
+#[derive(Deserialize)]
+struct ReportInput {
+    #[serde(flatten)]
+    walk: WalkInput,
+    top: Option<usize>,
+    min_lines: Option<usize>,
+    #[serde(default)]
+    full: bool,
+    #[serde(default)]
+    with: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct SmellsInput {
+    #[serde(flatten)]
+    walk: WalkInput,
+    top: Option<usize>,
+    max_lines: Option<usize>,
+    max_params: Option<usize>,
+    #[serde(default)]
+    files: Vec<String>,
+    since_ref: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct DepsInput {
+    #[serde(flatten)]
+    walk: WalkInput,
+    #[serde(default)]
+    cycles_only: bool,
+    sort_by: Option<String>,
+    top: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct ChurnInput {
+    #[serde(flatten)]
+    walk: WalkInput,
+    #[serde(flatten)]
+    commits: CommitFilterInput,
+    top: Option<usize>,
+    sort_by: Option<String>,
+    since: Option<String>,
+    #[serde(default)]
+    by_author: bool,
+}
+
+#[derive(Deserialize)]
+struct DefectsInput {
+    #[serde(flatten)]
+    walk: WalkInput,
+    #[serde(flatten)]
+    commits: CommitFilterInput,
+    top: Option<usize>,
+    sort_by: Option<String>,
+    since: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct HotspotsInput {
+    #[serde(flatten)]
+    walk: WalkInput,
+    #[serde(flatten)]
+    commits: CommitFilterInput,
+    top: Option<usize>,
+    sort_by: Option<String>,
+    since: Option<String>,
+    complexity: Option<String>,
+    changes: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct KnowledgeInput {
+    #[serde(flatten)]
+    walk: WalkInput,
+    #[serde(flatten)]
+    commits: CommitFilterInput,
+    top: Option<usize>,
+    sort_by: Option<String>,
+    since: Option<String>,
+    #[serde(default)]
+    risk_only: bool,
+    #[serde(default)]
+    summary: bool,
+    #[serde(default)]
+    bus_factor: bool,
+}
+
+#[derive(Deserialize)]
+struct TcInput {
+    path: Option<String>,
+    #[serde(flatten)]
+    commits: CommitFilterInput,
+    #[serde(default)]
+    include_tests: bool,
+    top: Option<usize>,
+    sort_by: Option<String>,
+    since: Option<String>,
+    min_degree: Option<usize>,
+    min_strength: Option<f64>,
+}
+
+#[derive(Deserialize)]
+struct AgeInput {
+    #[serde(flatten)]
+    walk: WalkInput,
+    #[serde(flatten)]
+    commits: CommitFilterInput,
+    active_days: Option<u64>,
+    frozen_days: Option<u64>,
+    sort_by: Option<String>,
+    status: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct AuthorsInput {
+    #[serde(flatten)]
+    walk: WalkInput,
+    #[serde(flatten)]
+    commits: CommitFilterInput,
+    since: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct DebtInput {
+    #[serde(flatten)]
+    walk: WalkInput,
+    top: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct HistoryInput {
+    #[serde(flatten)]
+    walk: WalkInput,
+    every: Option<String>,
+    since: Option<String>,
+    min_lines: Option<usize>,
+    model: Option<String>,
+}
+
+/// Default `top` for list-style tools, matching the CLI.
+const DEFAULT_TOP: usize = 20;
+/// Default `bottom` for `km_score`, matching the CLI.
+const DEFAULT_BOTTOM: usize = 10;
 
-    if raw.is_empty() {
-        return project_path.to_string_lossy().into_owned();
+// ── runners ──────────────────────────────────────────────────────────────────
+
+fn run_loc(
+    input: LocInput,
+    project_path: &Path,
+    kcfg: &KimunConfig,
+) -> Result<String, Box<dyn Error>> {
+    if input.by_author {
+        with_git_walk(&input.walk, project_path, kcfg, |cfg| {
+            loc::report::format_author_json(loc::analyze_by_author(cfg)?)
+        })
+    } else {
+        with_walk(&input.walk, project_path, |cfg| {
+            loc::report::format_json(loc::analyze_project(cfg).languages)
+        })
     }
+}
+
+fn run_score(
+    input: ScoreInput,
+    project_path: &Path,
+    kcfg: &KimunConfig,
+) -> Result<String, Box<dyn Error>> {
+    let bottom = input.bottom.unwrap_or(DEFAULT_BOTTOM);
+    let min_lines = kcfg.dups.resolve_min_lines(input.min_lines);
+    let model = kcfg.score.resolve_model(input.model);
+    let model = score::ScoreModel::resolve(
+        one_of("model", Some(&model), schema::SCORE_MODEL)?,
+        &kcfg.score,
+    )?;
+    // Precedence: trend, new_code_since, plan_target, by_dir, then by_package.
+    let breakdown = input
+        .by_dir
+        .map(score::Breakdown::Dir)
+        .or(input.by_package.then_some(score::Breakdown::Package));
+    with_walk(&input.walk, project_path, |cfg| {
+        if let Some(git_ref) = &input.trend {
+            let top_changes = input.top_changes.unwrap_or(DEFAULT_BOTTOM);
+            let diff = score::analyze_diff(cfg, git_ref, top_changes, min_lines, &model)?;
+            score::diff_report::format_json(&diff)
+        } else if let Some(git_ref) = &input.new_code_since {
+            let result =
+                score::new_code::compute_new_code(cfg, git_ref, bottom, min_lines, &model)?;
+            score::new_code_report::format_json(&result)
+        } else if let Some(target) = &input.plan_target {
+            let target = score::analyzer::Grade::parse(target)?;
+            let healthy = score::plan::healthy_profile(&model, &kcfg.score.healthy)?;
+            let result = score::plan::compute_plan(cfg, min_lines, &model, target, healthy)?;
+            score::plan_report::format_json(&result)
+        } else if let Some(by) = breakdown {
+            let result = score::breakdown::compute_breakdown(cfg, min_lines, &model, by)?;
+            score::breakdown_report::format_json(&result)
+        } else {
+            let result = score::compute_score(cfg, bottom, min_lines, &model)?;
+            score::report::format_json(&result, None)
+        }
+    })
+}
+
+fn run_hal(input: TopSortInput, project_path: &Path) -> Result<String, Box<dyn Error>> {
+    let sort_by = one_of("sort_by", input.sort_by.as_deref(), schema::HAL_SORT)?;
+    let mut results = with_walk(&input.walk, project_path, |cfg| {
+        hal::analyze_project(cfg, sort_by)
+    });
+    results.truncate(input.top.unwrap_or(DEFAULT_TOP));
+    hal::report::format_json(&results)
+}
+
+fn run_cycom(input: ComplexityInput, project_path: &Path) -> Result<String, Box<dyn Error>> {
+    let sort_by = one_of("sort_by", input.sort_by.as_deref(), schema::COMPLEXITY_SORT)?;
+    let min_complexity = input.min_complexity.unwrap_or(1);
+    let mut results = with_walk(&input.walk, project_path, |cfg| {
+        cycom::analyze_project(cfg, min_complexity, sort_by)
+    });
+    results.truncate(input.top.unwrap_or(DEFAULT_TOP));
+    cycom::report::format_json(&results)
+}
+
+fn run_cogcom(input: ComplexityInput, project_path: &Path) -> Result<String, Box<dyn Error>> {
+    let sort_by = one_of("sort_by", input.sort_by.as_deref(), schema::COMPLEXITY_SORT)?;
+    let min_complexity = input.min_complexity.unwrap_or(1);
+    let mut results = with_walk(&input.walk, project_path, |cfg| {
+        cogcom::analyze_project(cfg, min_complexity, sort_by)
+    });
+    results.truncate(input.top.unwrap_or(DEFAULT_TOP));
+    cogcom::report::format_json(&results)
+}
+
+fn run_indent(input: IndentInput, project_path: &Path) -> Result<String, Box<dyn Error>> {
+    let results = with_walk(&input.walk, project_path, indent::analyze_project);
+    indent::report::format_json(&results)
+}
+
+fn run_mi(input: TopSortInput, project_path: &Path) -> Result<String, Box<dyn Error>> {
+    let sort_by = one_of("sort_by", input.sort_by.as_deref(), schema::MI_SORT)?;
+    let mut results = with_walk(&input.walk, project_path, |cfg| {
+        mi::analyze_project(cfg, sort_by)
+    });
+    results.truncate(input.top.unwrap_or(DEFAULT_TOP));
+    mi::report::format_json(&results)
+}
+
+fn run_miv(input: TopSortInput, project_path: &Path) -> Result<String, Box<dyn Error>> {
+    let sort_by = one_of("sort_by", input.sort_by.as_deref(), schema::MI_SORT)?;
+    let mut results = with_walk(&input.walk, project_path, |cfg| {
+        miv::analyze_project(cfg, sort_by)
+    });
+    results.truncate(input.top.unwrap_or(DEFAULT_TOP));
+    miv::report::format_json(&results)
+}
 
+fn run_dups(
+    input: DupsInput,
+    project_path: &Path,
+    kcfg: &KimunConfig,
+) -> Result<String, Box<dyn Error>> {
+    let min_lines = kcfg.dups.resolve_min_lines(input.min_lines);
+    let analysis = with_walk(&input.walk, project_path, |cfg| {
+        dups::analyze_project(cfg, min_lines, true)
+    });
+    let limit = dups::report::display_limit(analysis.groups.len(), input.show_all);
+    dups::report::format_json(&analysis.metrics, &analysis.groups[..limit])
+}
+
+fn run_report(
+    input: ReportInput,
+    project_path: &Path,
+    kcfg: &KimunConfig,
+) -> Result<String, Box<dyn Error>> {
+    let min_lines = kcfg.dups.resolve_min_lines(input.min_lines);
+    let top = if input.full {
+        usize::MAX
+    } else {
+        input.top.unwrap_or(DEFAULT_TOP)
+    };
+    let sections = report::ReportSections::select(&input.with, kcfg)?;
+    let build = |cfg: &WalkConfig<'_>| report::build_report_with(cfg, top, min_lines, &sections);
+    let result = if sections.reads_git() {
+        with_git_walk(&input.walk, project_path, kcfg, build)?
+    } else {
+        with_walk(&input.walk, project_path, build)?
+    };
+    report::json::format_json(&result)
+}
+
+fn run_smells(
+    input: SmellsInput,
+    project_path: &Path,
+    kcfg: &KimunConfig,
+) -> Result<String, Box<dyn Error>> {
+    let max_lines = kcfg.smells.resolve_max_lines(input.max_lines);
+    let max_params = kcfg.smells.resolve_max_params(input.max_params);
+    let mut results = if let Some(git_ref) = &input.since_ref {
+        let repo = GitRepo::open(&input.walk.root(project_path))?;
+        let changed = repo.files_changed_since(git_ref)?;
+        smells::analyze_files(&changed, max_lines, max_params)
+    } else if !input.files.is_empty() {
+        let files: Vec<PathBuf> = input
+            .files
+            .iter()
+            .filter_map(|f| confine(f, project_path))
+            .collect();
+        smells::analyze_files(&files, max_lines, max_params)
+    } else {
+        with_walk(&input.walk, project_path, |cfg| {
+            smells::analyze_project(cfg, max_lines, max_params)
+        })
+    };
+    results.truncate(input.top.unwrap_or(DEFAULT_TOP));
+    smells::report::format_json(&results)
+}
+
+fn run_deps(input: DepsInput, project_path: &Path) -> Result<String, Box<dyn Error>> {
+    let sort_by = one_of("sort_by", input.sort_by.as_deref(), schema::DEPS_SORT)?;
+    let result = with_walk(&input.walk, project_path, |cfg| {
+        deps::analyze_project(cfg, sort_by)
+    });
+    let entries = if input.cycles_only {
+        result.entries.into_iter().filter(|e| e.in_cycle).collect()
+    } else {
+        let mut entries = result.entries;
+        entries.truncate(input.top.unwrap_or(DEFAULT_TOP));
+        entries
+    };
+    deps::report::format_json(&deps::DepResult {
+        entries,
+        cycles: result.cycles,
+    })
+}
+
+fn run_churn(
+    input: ChurnInput,
+    project_path: &Path,
+    kcfg: &KimunConfig,
+) -> Result<String, Box<dyn Error>> {
+    let sort_by = one_of("sort_by", input.sort_by.as_deref(), schema::CHURN_SORT)?;
+    let mut files = with_history_walk(&input.walk, &input.commits, project_path, kcfg, |cfg| {
+        churn::analyze_project(cfg, sort_by, input.since.as_deref())
+    })?;
+    if input.by_author {
+        let mut authors = churn::analyzer::by_author(&files);
+        authors.truncate(input.top.unwrap_or(DEFAULT_TOP));
+        return churn::report::format_authors_json(&authors);
+    }
+    files.truncate(input.top.unwrap_or(DEFAULT_TOP));
+    churn::report::format_json(&files)
+}
+
+fn run_defects(
+    input: DefectsInput,
+    project_path: &Path,
+    kcfg: &KimunConfig,
+) -> Result<String, Box<dyn Error>> {
+    let sort_by = one_of("sort_by", input.sort_by.as_deref(), schema::DEFECTS_SORT)?;
+    let mut files = with_history_walk(&input.walk, &input.commits, project_path, kcfg, |cfg| {
+        defects::analyze_project(cfg, sort_by, input.since.as_deref())
+    })?;
+    files.truncate(input.top.unwrap_or(DEFAULT_TOP));
+    defects::report::format_json(&files)
+}
+
+fn run_hotspots(
+    input: HotspotsInput,
+    project_path: &Path,
+    kcfg: &KimunConfig,
+) -> Result<String, Box<dyn Error>> {
+    let sort_by = one_of("sort_by", input.sort_by.as_deref(), schema::HOTSPOTS_SORT)?;
+    let complexity = kcfg.hotspots.resolve_complexity(input.complexity);
+    let complexity = one_of("complexity", Some(&complexity), schema::HOTSPOTS_COMPLEXITY)?;
+    let changes = kcfg.hotspots.resolve_changes(input.changes);
+    let changes = one_of("changes", Some(&changes), schema::HOTSPOTS_CHANGES)?;
+    let mut results = with_history_walk(&input.walk, &input.commits, project_path, kcfg, |cfg| {
+        hotspots::analyze_project(cfg, sort_by, input.since.as_deref(), complexity, changes)
+    })?;
+    results.truncate(input.top.unwrap_or(DEFAULT_TOP));
+    hotspots::report::format_json(&results, complexity)
+}
+
+fn run_knowledge(
+    input: KnowledgeInput,
+    project_path: &Path,
+    kcfg: &KimunConfig,
+) -> Result<String, Box<dyn Error>> {
+    let sort_by = one_of("sort_by", input.sort_by.as_deref(), schema::KNOWLEDGE_SORT)?;
+    let top = input.top.unwrap_or(DEFAULT_TOP);
+    let map = with_history_walk(&input.walk, &input.commits, project_path, kcfg, |cfg| {
+        knowledge::analyze_project(cfg, input.since.as_deref())
+    })?;
+    if input.bus_factor {
+        let bf = knowledge::compute_bus_factor(&map.author_lines, 80.0);
+        return knowledge::report::format_bus_factor_json(&bf);
+    }
+    let opts = knowledge::KnowledgeOptions {
+        output: crate::cli::OutputMode::Json,
+        top,
+        sort_by,
+        since: input.since.as_deref(),
+        risk_only: input.risk_only,
+        summary: input.summary,
+        bus_factor: false,
+        authors: &input.commits.author,
+    };
+    let mut files = knowledge::select_files(map.files, &opts);
+    if input.summary {
+        let mut authors = knowledge::summarize(&files, sort_by);
+        authors.truncate(top);
+        knowledge::report::format_summary_json(&authors)
+    } else {
+        files.truncate(top);
+        knowledge::report::format_json(&files)
+    }
+}
+
+fn run_tc(
+    input: TcInput,
+    project_path: &Path,
+    kcfg: &KimunConfig,
+) -> Result<String, Box<dyn Error>> {
+    let sort_by = one_of("sort_by", input.sort_by.as_deref(), schema::TC_SORT)?;
+    let walk = WalkInput {
+        path: input.path.clone(),
+        include_tests: input.include_tests,
+        ..Default::default()
+    };
+    let mut pairs = with_history_walk(&walk, &input.commits, project_path, kcfg, |cfg| {
+        tc::analyze_project(
+            cfg,
+            sort_by,
+            input.since.as_deref(),
+            kcfg.tc.resolve_min_degree(input.min_degree),
+            kcfg.tc.resolve_min_strength(input.min_strength),
+        )
+    })?;
+    pairs.truncate(input.top.unwrap_or(DEFAULT_TOP));
+    tc::report::format_json(&pairs)
+}
+
+fn run_age(
+    input: AgeInput,
+    project_path: &Path,
+    kcfg: &KimunConfig,
+) -> Result<String, Box<dyn Error>> {
+    let sort_by = one_of("sort_by", input.sort_by.as_deref(), schema::AGE_SORT)?;
+    let status = match input.status.as_deref() {
+        Some(s) => Some(one_of("status", Some(s), schema::AGE_STATUS)?),
+        None => None,
+    };
+    let thresholds = age::AgeThresholds {
+        active_days: kcfg.age.resolve_active_days(input.active_days),
+        frozen_days: kcfg.age.resolve_frozen_days(input.frozen_days),
+    };
+    let files = with_history_walk(&input.walk, &input.commits, project_path, kcfg, |cfg| {
+        age::analyze_project(cfg, &thresholds, sort_by, status)
+    })?;
+    age::report::format_json(&files)
+}
+
+fn run_authors(
+    input: AuthorsInput,
+    project_path: &Path,
+    kcfg: &KimunConfig,
+) -> Result<String, Box<dyn Error>> {
+    let result = with_history_walk(&input.walk, &input.commits, project_path, kcfg, |cfg| {
+        authors::analyze_project(cfg, input.since.as_deref())
+    })?;
+    authors::report::format_json(&result)
+}
+
+fn run_debt(
+    input: DebtInput,
+    project_path: &Path,
+    kcfg: &KimunConfig,
+) -> Result<String, Box<dyn Error>> {
+    let model = debt::DebtModel::resolve(&kcfg.debt)?;
+    let thresholds = debt::DebtThresholds {
+        max_lines: kcfg.smells.resolve_max_lines(None),
+        max_params: kcfg.smells.resolve_max_params(None),
+        min_dup_lines: kcfg.dups.resolve_min_lines(None),
+    };
+    let mut report = with_walk(&input.walk, project_path, |cfg| {
+        debt::analyze_project(cfg, &model, &thresholds)
+    });
+    report.files.truncate(input.top.unwrap_or(DEFAULT_TOP));
+    debt::report::format_json(&report)
+}
+
+fn run_history(
+    input: HistoryInput,
+    project_path: &Path,
+    kcfg: &KimunConfig,
+) -> Result<String, Box<dyn Error>> {
+    let model = kcfg.score.resolve_model(input.model);
+    let model = score::ScoreModel::resolve(
+        one_of("model", Some(&model), schema::SCORE_MODEL)?,
+        &kcfg.score,
+    )?;
+    // Snapshots are a CLI convenience; tool calls always score afresh.
+    let opts = history::HistoryOptions {
+        every: input.every.as_deref().unwrap_or("1m"),
+        since: input.since.as_deref().unwrap_or("1y"),
+        min_lines: kcfg.dups.resolve_min_lines(input.min_lines),
+        model: &model,
+        snapshots: None,
+    };
+    let result = with_walk(&input.walk, project_path, |cfg| {
+        history::compute_history(cfg, &opts)
+    })?;
+    history::report::format_json(&result)
+}
+
+// ── paths ────────────────────────────────────────────────────────────────────
+
+/// Resolve `raw` to a canonical path inside `project_path`. Returns `None`
+/// (with a warning) if it does not exist or escapes the project root.
+fn confine(raw: &str, project_path: &Path) -> Option<PathBuf> {
     let candidate = if Path::new(raw).is_absolute() {
         Path::new(raw).to_path_buf()
     } else {
@@ -72,49 +798,27 @@ fn resolve_path(input: &Value, project_path: &Path) -> String {
     };
 
     // Canonicalize to resolve symlinks and ../ components
-    let resolved = match candidate.canonicalize() {
-        Ok(p) => p,
-        Err(_) => return project_path.to_string_lossy().into_owned(),
-    };
+    let resolved = candidate.canonicalize().ok()?;
 
     // Verify the resolved path is inside the project root
     if !resolved.starts_with(project_path) {
-        eprintln!(
-            "  Warning: path '{}' is outside project root, using project root instead",
-            raw
-        );
-        return project_path.to_string_lossy().into_owned();
+        eprintln!("  Warning: path '{raw}' is outside project root, ignoring it");
+        return None;
     }
 
-    resolved.to_string_lossy().into_owned()
-}
-
-/// Build the CLI argument list from the AI tool input. Converts named
-/// parameters to `--flag value` pairs and appends the resolved path.
-fn build_args(input: &Value, named: &[&str], project_path: &Path) -> Vec<String> {
-    let mut args = Vec::new();
-
-    for name in named {
-        if let Some(val) = input.get(name) {
-            let flag = format!("--{}", name.replace('_', "-"));
-            match val {
-                Value::Number(n) => {
-                    args.push(flag);
-                    args.push(n.to_string());
-                }
-                Value::String(s) => {
-                    args.push(flag);
-                    args.push(s.clone());
-                }
-                _ => {
-                    eprintln!("  Warning: ignoring invalid value for --{name}: {val}");
-                }
-            }
+    Some(resolved)
+}
+
+/// Resolve the optional `path` tool parameter to a safe, canonical path
+/// within the project root. Falls back to the project root if the path
+/// is missing, invalid, or outside the project.
+fn resolve_path(raw: Option<&str>, project_path: &Path) -> PathBuf {
+    match raw {
+        Some(raw) if !raw.is_empty() => {
+            confine(raw, project_path).unwrap_or_else(|| project_path.to_path_buf())
         }
+        _ => project_path.to_path_buf(),
     }
-
-    args.push(resolve_path(input, project_path));
-    args
 }
 
 #[cfg(test)]
@@ -1,7 +1,8 @@
 //! AI-powered code analysis module.
 //!
-//! Implements an agentic loop that calls an LLM provider (currently Claude)
-//! with access to `km` tool definitions. The LLM decides which tools to
+//! Implements an agentic loop that calls an LLM provider (Claude, OpenAI,
+//! or any OpenAI-compatible server such as Ollama) with access to `km`
+//! tool definitions. The LLM decides which tools to
 //! run, receives the JSON output, and produces a comprehensive analysis.
 //!
 //! The loop iterates up to `MAX_ITERATIONS` turns, executing tool calls
@@ -11,21 +12,24 @@
 
 /// HTTP client and API types for the Claude Messages API.
 mod client;
-/// Tool executor: maps tool calls to `km` subprocess invocations.
-mod executor;
+/// Tool executor: runs tool calls in-process against the analysis modules.
+pub(crate) mod executor;
+/// OpenAI-compatible chat-completions backend.
+mod openai;
 /// Claude Code permissions installer (`km ai permissions claude`).
 pub mod permissions;
-/// Tool definitions: JSON Schema descriptions of all 11 `km` subcommands.
+/// Provider presets, resolution from CLI/config, and backend dispatch.
+pub mod provider;
+/// Tool definitions: JSON Schema descriptions of every `km` analysis subcommand.
 pub(crate) mod schema;
 /// Claude Code skill installer (`km ai skill claude`).
 pub mod skill;
 
 use client::{ApiRequest, ContentBlock, Message, MessageContent};
+pub use provider::Provider;
 use std::fs;
 use std::path::Path;
 
-/// Default model used when `--model` is not specified.
-const DEFAULT_MODEL: &str = "claude-sonnet-4-5-20250929";
 /// Maximum tokens per API response (prevents runaway generation).
 const MAX_TOKENS: u32 = 4096;
 /// Safety limit on agentic loop iterations to prevent infinite loops.
@@ -49,28 +53,21 @@ relevant, then provide a comprehensive analysis including:
 Be specific — reference file names and metrics. Keep the analysis concise but thorough.";
 
 pub fn run(
-    provider: &str,
+    provider: &Provider,
     path: &Path,
-    model: Option<&str>,
     output: Option<&Path>,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    if provider != "claude" {
-        return Err(format!("Unsupported provider: {provider}. Supported: claude").into());
-    }
-
-    let api_key = std::env::var("ANTHROPIC_API_KEY").map_err(|_| {
-        "ANTHROPIC_API_KEY environment variable not set. \
-         Get your key at https://console.anthropic.com/"
-    })?;
-
     let canonical_path = path
         .canonicalize()
         .map_err(|e| format!("Cannot resolve path '{}': {e}", path.display()))?;
 
-    let model = model.unwrap_or(DEFAULT_MODEL).to_string();
     let tool_defs = schema::tool_definitions();
 
-    agentic_loop(&api_key, &model, &tool_defs, &canonical_path, output)
+    eprintln!(
+        "Using {} model {} at {}",
+        provider.label, provider.model, provider.base_url
+    );
+    agentic_loop(provider, &tool_defs, &canonical_path, output)
 }
 
 /// Extract all text blocks from a response into a single string.
@@ -113,8 +110,7 @@ fn execute_tool_uses(content: &[ContentBlock], project_path: &Path) -> Vec<Conte
 }
 
 fn agentic_loop(
-    api_key: &str,
-    model: &str,
+    provider: &Provider,
     tool_defs: &[serde_json::Value],
     project_path: &Path,
     output: Option<&Path>,
@@ -129,15 +125,15 @@ fn agentic_loop(
 
     for iteration in 0..MAX_ITERATIONS {
         let request = ApiRequest {
-            model: model.to_string(),
+            model: provider.model.clone(),
             max_tokens: MAX_TOKENS,
             system: SYSTEM_PROMPT.to_string(),
             tools: tool_defs.to_vec(),
             messages: messages.clone(),
         };
 
-        eprintln!("Calling Claude API (turn {})...", iteration + 1);
-        let response = client::send_message(&http_client, api_key, &request)?;
+        eprintln!("Calling {} API (turn {})...", provider.label, iteration + 1);
+        let response = provider.send(&http_client, &request)?;
 
         if response.stop_reason == "max_tokens" {
             eprintln!("warning: response truncated (max_tokens reached)");
@@ -0,0 +1,217 @@
+/// OpenAI-compatible chat-completions backend.
+///
+/// Translates the Anthropic-style conversation used by the agentic loop
+/// into `POST {base_url}/chat/completions` requests with function calling,
+/// and the replies back into content blocks. Works against OpenAI itself
+/// and against self-hosted servers exposing the same API (Ollama, vLLM,
+/// llama.cpp, LM Studio, ...).
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use super::client::{ApiRequest, ApiResponse, ContentBlock, MessageContent, post_json};
+
+/// Request payload for the chat-completions endpoint.
+#[derive(Serialize, Debug)]
+pub struct ChatRequest {
+    pub model: String,
+    pub max_tokens: u32,
+    pub messages: Vec<ChatMessage>,
+    pub tools: Vec<Value>,
+}
+
+/// One chat message. Tool calls travel on assistant messages; their
+/// results come back as separate `tool` messages keyed by call id.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ChatMessage {
+    pub role: String,
+    /// Text content; `null` on assistant messages that only call tools.
+    #[serde(default)]
+    pub content: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_calls: Vec<ToolCall>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_call_id: Option<String>,
+}
+
+impl ChatMessage {
+    fn text(role: &str, content: &str) -> Self {
+        Self {
+            role: role.to_string(),
+            content: Some(content.to_string()),
+            tool_calls: Vec::new(),
+            tool_call_id: None,
+        }
+    }
+}
+
+/// A function call requested by the model.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ToolCall {
+    /// Some local servers omit the id; one is synthesized on the way in.
+    #[serde(default)]
+    pub id: String,
+    #[serde(rename = "type", default = "function_type")]
+    pub kind: String,
+    pub function: FunctionCall,
+}
+
+fn function_type() -> String {
+    "function".to_string()
+}
+
+/// Function name plus arguments. The API sends arguments as a JSON-encoded
+/// string, but some servers send the object itself; both are accepted.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FunctionCall {
+    pub name: String,
+    #[serde(default)]
+    pub arguments: Value,
+}
+
+/// Response from the chat-completions endpoint.
+#[derive(Deserialize, Debug)]
+pub struct ChatResponse {
+    pub choices: Vec<Choice>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Choice {
+    pub message: ChatMessage,
+    #[serde(default)]
+    pub finish_reason: Option<String>,
+}
+
+/// Convert an Anthropic tool definition (`name`, `description`,
+/// `input_schema`) into an OpenAI function tool.
+fn to_function_tool(def: &Value) -> Value {
+    json!({
+        "type": "function",
+        "function": {
+            "name": def["name"],
+            "description": def["description"],
+            "parameters": def["input_schema"],
+        }
+    })
+}
+
+/// Flatten one Anthropic message into chat messages: tool results become
+/// `tool` messages, text and tool calls share a single message.
+fn push_blocks(out: &mut Vec<ChatMessage>, role: &str, blocks: &[ContentBlock]) {
+    let mut text = String::new();
+    let mut tool_calls = Vec::new();
+    for block in blocks {
+        match block {
+            ContentBlock::Text { text: t } => text.push_str(t),
+            ContentBlock::ToolUse { id, name, input } => tool_calls.push(ToolCall {
+                id: id.clone(),
+                kind: function_type(),
+                function: FunctionCall {
+                    name: name.clone(),
+                    arguments: Value::String(input.to_string()),
+                },
+            }),
+            ContentBlock::ToolResult {
+                tool_use_id,
+                content,
+            } => out.push(ChatMessage {
+                role: "tool".to_string(),
+                content: Some(content.clone()),
+                tool_calls: Vec::new(),
+                tool_call_id: Some(tool_use_id.clone()),
+            }),
+        }
+    }
+    if !text.is_empty() || !tool_calls.is_empty() {
+        out.push(ChatMessage {
+            role: role.to_string(),
+            content: (!text.is_empty()).then_some(text),
+            tool_calls,
+            tool_call_id: None,
+        });
+    }
+}
+
+/// Translate an Anthropic-style request into a chat-completions request.
+/// The system prompt becomes the leading `system` message.
+pub fn to_chat_request(request: &ApiRequest) -> ChatRequest {
+    let mut messages = vec![ChatMessage::text("system", &request.system)];
+    for message in &request.messages {
+        match &message.content {
+            MessageContent::Text(text) => messages.push(ChatMessage::text(&message.role, text)),
+            MessageContent::Blocks(blocks) => push_blocks(&mut messages, &message.role, blocks),
+        }
+    }
+    ChatRequest {
+        model: request.model.clone(),
+        max_tokens: request.max_tokens,
+        messages,
+        tools: request.tools.iter().map(to_function_tool).collect(),
+    }
+}
+
+/// Decode function arguments. Unparseable strings are passed through as
+/// strings so the executor reports the problem back to the model.
+fn decode_arguments(arguments: Value) -> Value {
+    match arguments {
+        Value::String(s) if s.trim().is_empty() => Value::Null,
+        Value::String(s) => serde_json::from_str(&s).unwrap_or(Value::String(s)),
+        other => other,
+    }
+}
+
+/// Translate a chat-completions response into Anthropic content blocks.
+/// `finish_reason` maps onto the equivalent `stop_reason`.
+pub fn from_chat_response(
+    response: ChatResponse,
+) -> Result<ApiResponse, Box<dyn std::error::Error>> {
+    let choice = response
+        .choices
+        .into_iter()
+        .next()
+        .ok_or("API returned no choices")?;
+    let mut content = Vec::new();
+    if let Some(text) = choice.message.content.filter(|t| !t.is_empty()) {
+        content.push(ContentBlock::Text { text });
+    }
+    for (i, call) in choice.message.tool_calls.into_iter().enumerate() {
+        let id = if call.id.is_empty() {
+            format!("call_{i}")
+        } else {
+            call.id
+        };
+        content.push(ContentBlock::ToolUse {
+            id,
+            name: call.function.name,
+            input: decode_arguments(call.function.arguments),
+        });
+    }
+    let stop_reason = match choice.finish_reason.as_deref() {
+        Some("length") => "max_tokens",
+        Some("tool_calls") => "tool_use",
+        _ => "end_turn",
+    };
+    Ok(ApiResponse {
+        content,
+        stop_reason: stop_reason.to_string(),
+    })
+}
+
+/// Send a request to `{base_url}/chat/completions`. The API key, when
+/// present, is sent as a bearer token.
+pub fn send_message(
+    client: &reqwest::blocking::Client,
+    base_url: &str,
+    api_key: Option<&str>,
+    request: &ApiRequest,
+) -> Result<ApiResponse, Box<dyn std::error::Error>> {
+    let mut builder = client.post(format!("{base_url}/chat/completions"));
+    if let Some(key) = api_key {
+        builder = builder.bearer_auth(key);
+    }
+    let response: ChatResponse = post_json(builder.json(&to_chat_request(request)))?;
+    from_chat_response(response)
+}
+
+#[cfg(test)]
+#[path = "openai_test.rs"]
+mod tests;
@@ -0,0 +1,232 @@
+use super::*;
+use crate::ai::client::Message;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+
+fn sample_request() -> ApiRequest {
+    ApiRequest {
+        model: "local-model".to_string(),
+        max_tokens: 1024,
+        system: "You are a test.".to_string(),
+        tools: vec![json!({
+            "name": "km_loc",
+            "description": "Count lines",
+            "input_schema": {"type": "object", "properties": {}}
+        })],
+        messages: vec![
+            Message {
+                role: "user".to_string(),
+                content: MessageContent::Text("Analyze".to_string()),
+            },
+            Message {
+                role: "assistant".to_string(),
+                content: MessageContent::Blocks(vec![
+                    ContentBlock::Text {
+                        text: "Counting.".to_string(),
+                    },
+                    ContentBlock::ToolUse {
+                        id: "call_1".to_string(),
+                        name: "km_loc".to_string(),
+                        input: json!({"path": "src"}),
+                    },
+                ]),
+            },
+            Message {
+                role: "user".to_string(),
+                content: MessageContent::Blocks(vec![ContentBlock::ToolResult {
+                    tool_use_id: "call_1".to_string(),
+                    content: "{\"languages\":[]}".to_string(),
+                }]),
+            },
+        ],
+    }
+}
+
+#[test]
+fn request_translates_system_tools_and_history() {
+    let chat = to_chat_request(&sample_request());
+    assert_eq!(chat.model, "local-model");
+    assert_eq!(chat.max_tokens, 1024);
+
+    let roles: Vec<&str> = chat.messages.iter().map(|m| m.role.as_str()).collect();
+    assert_eq!(roles, ["system", "user", "assistant", "tool"]);
+    assert_eq!(chat.messages[0].content.as_deref(), Some("You are a test."));
+
+    let assistant = &chat.messages[2];
+    assert_eq!(assistant.content.as_deref(), Some("Counting."));
+    assert_eq!(assistant.tool_calls.len(), 1);
+    assert_eq!(assistant.tool_calls[0].id, "call_1");
+    assert_eq!(assistant.tool_calls[0].function.name, "km_loc");
+    // Arguments are sent as a JSON-encoded string.
+    let args = assistant.tool_calls[0].function.arguments.as_str().unwrap();
+    assert_eq!(
+        serde_json::from_str::<Value>(args).unwrap(),
+        json!({"path": "src"})
+    );
+
+    let tool = &chat.messages[3];
+    assert_eq!(tool.tool_call_id.as_deref(), Some("call_1"));
+    assert_eq!(tool.content.as_deref(), Some("{\"languages\":[]}"));
+
+    assert_eq!(chat.tools[0]["type"], "function");
+    assert_eq!(chat.tools[0]["function"]["name"], "km_loc");
+    assert_eq!(chat.tools[0]["function"]["parameters"]["type"], "object");
+}
+
+#[test]
+fn tool_only_assistant_message_has_null_content() {
+    let mut request = sample_request();
+    request.messages[1].content = MessageContent::Blocks(vec![ContentBlock::ToolUse {
+        id: "c".to_string(),
+        name: "km_loc".to_string(),
+        input: json!({}),
+    }]);
+    let body = serde_json::to_value(to_chat_request(&request)).unwrap();
+    let assistant = &body["messages"][2];
+    assert!(assistant["content"].is_null());
+    assert!(assistant.get("tool_call_id").is_none());
+    assert_eq!(assistant["tool_calls"][0]["type"], "function");
+}
+
+fn parse_response(body: Value) -> ApiResponse {
+    from_chat_response(serde_json::from_value(body).unwrap()).unwrap()
+}
+
+#[test]
+fn response_with_tool_calls_becomes_tool_use_blocks() {
+    let resp = parse_response(json!({
+        "choices": [{
+            "message": {
+                "role": "assistant",
+                "content": null,
+                "tool_calls": [
+                    {"id": "a", "type": "function",
+                     "function": {"name": "km_score", "arguments": "{\"top\":5}"}},
+                    {"function": {"name": "km_loc", "arguments": {"path": "src"}}}
+                ]
+            },
+            "finish_reason": "tool_calls"
+        }]
+    }));
+    assert_eq!(resp.stop_reason, "tool_use");
+    assert_eq!(resp.content.len(), 2);
+    match &resp.content[0] {
+        ContentBlock::ToolUse { id, name, input } => {
+            assert_eq!(id, "a");
+            assert_eq!(name, "km_score");
+            assert_eq!(input, &json!({"top": 5}));
+        }
+        other => panic!("expected tool use, got {other:?}"),
+    }
+    match &resp.content[1] {
+        ContentBlock::ToolUse { id, input, .. } => {
+            assert_eq!(id, "call_1");
+            assert_eq!(input, &json!({"path": "src"}));
+        }
+        other => panic!("expected tool use, got {other:?}"),
+    }
+}
+
+#[test]
+fn response_text_and_finish_reasons() {
+    let resp = parse_response(json!({
+        "choices": [{"message": {"role": "assistant", "content": "Done."}, "finish_reason": "stop"}]
+    }));
+    assert_eq!(resp.stop_reason, "end_turn");
+    assert!(matches!(&resp.content[..], [ContentBlock::Text { text }] if text == "Done."));
+
+    let resp = parse_response(json!({
+        "choices": [{"message": {"role": "assistant", "content": "Trunc"}, "finish_reason": "length"}]
+    }));
+    assert_eq!(resp.stop_reason, "max_tokens");
+}
+
+#[test]
+fn malformed_arguments_are_passed_through() {
+    let resp = parse_response(json!({
+        "choices": [{"message": {"role": "assistant", "tool_calls": [
+            {"id": "x", "function": {"name": "km_loc", "arguments": "{not json"}},
+            {"id": "y", "function": {"name": "km_loc", "arguments": ""}}
+        ]}}]
+    }));
+    assert!(
+        matches!(&resp.content[0], ContentBlock::ToolUse { input: Value::String(s), .. } if s == "{not json")
+    );
+    assert!(matches!(
+        &resp.content[1],
+        ContentBlock::ToolUse {
+            input: Value::Null,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn empty_choices_is_an_error() {
+    let response: ChatResponse = serde_json::from_value(json!({"choices": []})).unwrap();
+    assert!(from_chat_response(response).is_err());
+}
+
+/// Serve a single HTTP response on localhost and return the raw request.
+fn serve_once(status: &str, body: &str) -> (String, std::thread::JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/v1", listener.local_addr().unwrap());
+    let response = format!(
+        "HTTP/1.1 {status}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    let handle = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut head = String::new();
+        let mut length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if let Some(v) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                length = v.trim().parse().unwrap();
+            }
+            head.push_str(&line);
+            if line == "\r\n" {
+                break;
+            }
+        }
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).unwrap();
+        reader.get_mut().write_all(response.as_bytes()).unwrap();
+        head + &String::from_utf8(body).unwrap()
+    });
+    (url, handle)
+}
+
+#[test]
+fn send_message_talks_to_local_server() {
+    let (url, server) = serve_once(
+        "200 OK",
+        r#"{"choices":[{"message":{"role":"assistant","content":"Looks healthy."},"finish_reason":"stop"}]}"#,
+    );
+    let client = crate::ai::client::build_client().unwrap();
+    let resp = send_message(&client, &url, Some("secret"), &sample_request()).unwrap();
+    assert!(matches!(&resp.content[..], [ContentBlock::Text { text }] if text == "Looks healthy."));
+
+    let raw = server.join().unwrap();
+    assert!(raw.starts_with("POST /v1/chat/completions "));
+    assert!(
+        raw.to_ascii_lowercase()
+            .contains("authorization: bearer secret")
+    );
+    assert!(raw.contains("\"tool_calls\""));
+}
+
+#[test]
+fn send_message_reports_http_errors() {
+    let (url, server) = serve_once(
+        "500 Internal Server Error",
+        r#"{"error":"model not loaded"}"#,
+    );
+    let client = crate::ai::client::build_client().unwrap();
+    let err = send_message(&client, &url, None, &sample_request()).unwrap_err();
+    assert!(err.to_string().contains("model not loaded"));
+    let raw = server.join().unwrap();
+    assert!(!raw.to_ascii_lowercase().contains("authorization:"));
+}
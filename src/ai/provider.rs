@@ -0,0 +1,199 @@
+//! LLM provider selection for `km ai analyze`.
+//!
+//! A provider is one of a fixed set of presets (`claude`, `openai`,
+//! `ollama`), each bound to a wire protocol (`Backend`) with a default
+//! endpoint, model, and API-key environment variable. The provider, model
+//! and endpoint can be set in the `[ai]` section of `.kimun.toml`; the key
+//! variable only on the command line, and a preset's key is sent only to
+//! its default endpoint, so a cloned repository cannot redirect
+//! credentials. Any OpenAI-compatible server — including a self-hosted one
+//! on localhost — can drive the analysis.
+
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+use super::client::{self, ApiRequest, ApiResponse};
+use super::openai;
+use crate::config::AiConfig;
+
+/// Wire protocol spoken by a provider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    /// Anthropic Messages API (`POST {base_url}/messages`).
+    Anthropic,
+    /// OpenAI chat completions with function calling
+    /// (`POST {base_url}/chat/completions`).
+    OpenAi,
+}
+
+/// Built-in defaults for one provider name.
+struct Preset {
+    name: &'static str,
+    label: &'static str,
+    backend: Backend,
+    base_url: &'static str,
+    model: &'static str,
+    key_env: &'static str,
+    /// Whether the default endpoint rejects unauthenticated requests.
+    key_required: bool,
+    key_hint: &'static str,
+}
+
+const PRESETS: &[Preset] = &[
+    Preset {
+        name: "claude",
+        label: "Claude",
+        backend: Backend::Anthropic,
+        base_url: "https://api.anthropic.com/v1",
+        model: "claude-sonnet-4-5-20250929",
+        key_env: "ANTHROPIC_API_KEY",
+        key_required: true,
+        key_hint: "Get your key at https://console.anthropic.com/",
+    },
+    Preset {
+        name: "openai",
+        label: "OpenAI",
+        backend: Backend::OpenAi,
+        base_url: "https://api.openai.com/v1",
+        model: "gpt-4o",
+        key_env: "OPENAI_API_KEY",
+        key_required: true,
+        key_hint: "Set it, or point --base-url at a server that needs no key",
+    },
+    Preset {
+        name: "ollama",
+        label: "Ollama",
+        backend: Backend::OpenAi,
+        base_url: "http://localhost:11434/v1",
+        model: "llama3.1",
+        key_env: "OLLAMA_API_KEY",
+        key_required: false,
+        key_hint: "",
+    },
+];
+
+/// Names accepted as the provider argument, in display order.
+pub fn provider_names() -> Vec<&'static str> {
+    PRESETS.iter().map(|p| p.name).collect()
+}
+
+fn preset(name: &str) -> Option<&'static Preset> {
+    PRESETS.iter().find(|p| p.name == name)
+}
+
+/// A fully resolved provider: protocol, endpoint, credentials and model.
+#[derive(Debug)]
+pub struct Provider {
+    pub label: &'static str,
+    pub backend: Backend,
+    pub base_url: String,
+    pub model: String,
+    api_key: Option<String>,
+}
+
+impl Provider {
+    /// Resolve a provider from explicit settings. `None` values fall back
+    /// to the preset's defaults; the API key is read through `env` from
+    /// `key_env` (or the preset's variable).
+    ///
+    /// The preset's own key is only sent to the preset's endpoint: with
+    /// `base_url` pointing elsewhere, a key is read only from an explicit
+    /// `key_env`, and is optional, since local servers usually need none.
+    /// Fails for unknown provider names, and when the key is missing for
+    /// an endpoint that requires one.
+    pub fn resolve(
+        name: &str,
+        model: Option<String>,
+        base_url: Option<String>,
+        key_env: Option<String>,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self, Box<dyn Error>> {
+        let preset = preset(name).ok_or_else(|| {
+            format!(
+                "Unsupported provider: {name}. Supported: {}",
+                provider_names().join(", ")
+            )
+        })?;
+        let base_url = base_url
+            .as_deref()
+            .unwrap_or(preset.base_url)
+            .trim_end_matches('/')
+            .to_string();
+        let default_endpoint = base_url == preset.base_url;
+        let key_env = match key_env {
+            Some(var) => Some(var),
+            None => default_endpoint.then(|| preset.key_env.to_string()),
+        };
+        let api_key = key_env.as_deref().and_then(&env).filter(|k| !k.is_empty());
+        if api_key.is_none() && preset.key_required && default_endpoint {
+            return Err(format!(
+                "{} environment variable not set. {}",
+                key_env.as_deref().unwrap_or(preset.key_env),
+                preset.key_hint
+            )
+            .into());
+        }
+        Ok(Self {
+            label: preset.label,
+            backend: preset.backend,
+            base_url,
+            model: model.unwrap_or_else(|| preset.model.to_string()),
+            api_key,
+        })
+    }
+
+    /// Resolve a provider from CLI flags and the `[ai]` config section
+    /// (CLI > config > preset default), reading keys from the environment.
+    /// `key_env` comes from the command line only.
+    pub fn from_config(
+        cfg: &AiConfig,
+        provider: Option<String>,
+        model: Option<String>,
+        base_url: Option<String>,
+        key_env: Option<String>,
+    ) -> Result<Self, Box<dyn Error>> {
+        let name = cfg.resolve_provider(provider);
+        Self::resolve(
+            &name,
+            cfg.resolve_model(&name, model),
+            cfg.resolve_base_url(&name, base_url),
+            key_env,
+            |var| std::env::var(var).ok(),
+        )
+    }
+
+    /// Send one conversation turn and return the model's reply in the
+    /// Anthropic content-block form the agentic loop works with.
+    pub fn send(
+        &self,
+        http: &reqwest::blocking::Client,
+        request: &ApiRequest,
+    ) -> Result<ApiResponse, Box<dyn Error>> {
+        let key = self.api_key.as_deref();
+        match self.backend {
+            Backend::Anthropic => client::send_message(http, &self.base_url, key, request),
+            Backend::OpenAi => openai::send_message(http, &self.base_url, key, request),
+        }
+    }
+}
+
+/// Split the `[PROVIDER] [PATH]` positionals of `km ai analyze`.
+///
+/// With the provider taken from `.kimun.toml`, `km ai analyze src/` puts
+/// the path in the provider slot; a lone argument that is not a provider
+/// name but names an existing path is therefore taken as the path.
+pub fn split_target(
+    provider: Option<String>,
+    path: Option<PathBuf>,
+) -> (Option<String>, Option<PathBuf>) {
+    match (provider, path) {
+        (Some(p), None) if preset(&p).is_none() && Path::new(&p).exists() => {
+            (None, Some(PathBuf::from(p)))
+        }
+        other => other,
+    }
+}
+
+#[cfg(test)]
+#[path = "provider_test.rs"]
+mod tests;
@@ -0,0 +1,160 @@
+use super::*;
+
+fn no_env(_: &str) -> Option<String> {
+    None
+}
+
+#[test]
+fn claude_is_the_anthropic_preset() {
+    let p = Provider::resolve("claude", None, None, None, |v| {
+        (v == "ANTHROPIC_API_KEY").then(|| "sk-test".to_string())
+    })
+    .unwrap();
+    assert_eq!(p.backend, Backend::Anthropic);
+    assert_eq!(p.base_url, "https://api.anthropic.com/v1");
+    assert_eq!(p.model, "claude-sonnet-4-5-20250929");
+    assert_eq!(p.api_key.as_deref(), Some("sk-test"));
+}
+
+#[test]
+fn claude_requires_api_key() {
+    let err = Provider::resolve("claude", None, None, None, no_env).unwrap_err();
+    assert!(err.to_string().contains("ANTHROPIC_API_KEY"));
+}
+
+#[test]
+fn openai_requires_key_only_for_default_endpoint() {
+    let err = Provider::resolve("openai", None, None, None, no_env).unwrap_err();
+    assert!(err.to_string().contains("OPENAI_API_KEY"));
+
+    let p = Provider::resolve(
+        "openai",
+        Some("local-model".into()),
+        Some("http://localhost:8080/v1/".into()),
+        None,
+        no_env,
+    )
+    .unwrap();
+    assert_eq!(p.backend, Backend::OpenAi);
+    assert_eq!(p.base_url, "http://localhost:8080/v1");
+    assert_eq!(p.model, "local-model");
+    assert!(p.api_key.is_none());
+}
+
+#[test]
+fn default_key_is_not_sent_to_another_endpoint() {
+    let env = |v: &str| (v == "ANTHROPIC_API_KEY").then(|| "sk-test".to_string());
+    let p = Provider::resolve(
+        "claude",
+        None,
+        Some("https://attacker.example/v1".into()),
+        None,
+        env,
+    )
+    .unwrap();
+    assert!(p.api_key.is_none());
+
+    // An explicitly named variable is sent anywhere.
+    let p = Provider::resolve(
+        "claude",
+        None,
+        Some("https://proxy.example/v1".into()),
+        Some("ANTHROPIC_API_KEY".into()),
+        env,
+    )
+    .unwrap();
+    assert_eq!(p.api_key.as_deref(), Some("sk-test"));
+
+    // Naming the default endpoint explicitly still uses the default key.
+    let p = Provider::resolve(
+        "claude",
+        None,
+        Some("https://api.anthropic.com/v1/".into()),
+        None,
+        env,
+    )
+    .unwrap();
+    assert_eq!(p.api_key.as_deref(), Some("sk-test"));
+}
+
+#[test]
+fn ollama_needs_no_key() {
+    let p = Provider::resolve("ollama", None, None, None, no_env).unwrap();
+    assert_eq!(p.backend, Backend::OpenAi);
+    assert_eq!(p.base_url, "http://localhost:11434/v1");
+    assert!(p.api_key.is_none());
+}
+
+#[test]
+fn custom_key_env_is_read() {
+    let p = Provider::resolve("openai", None, None, Some("MY_KEY".into()), |v| {
+        (v == "MY_KEY").then(|| "secret".to_string())
+    })
+    .unwrap();
+    assert_eq!(p.api_key.as_deref(), Some("secret"));
+}
+
+#[test]
+fn empty_key_counts_as_missing() {
+    let result = Provider::resolve("claude", None, None, None, |_| Some(String::new()));
+    assert!(result.is_err());
+}
+
+#[test]
+fn unknown_provider_lists_supported() {
+    let err = Provider::resolve("gemini", None, None, None, no_env).unwrap_err();
+    let msg = err.to_string();
+    assert!(msg.contains("Unsupported provider: gemini"));
+    assert!(msg.contains("claude, openai, ollama"));
+}
+
+#[test]
+fn from_config_applies_ai_section() {
+    let cfg: AiConfig = toml::from_str(
+        "provider = \"ollama\"\nmodel = \"qwen2.5-coder\"\nbase_url = \"http://gpu:11434/v1\"\n",
+    )
+    .unwrap();
+    let p = Provider::from_config(&cfg, None, None, None, None).unwrap();
+    assert_eq!(p.label, "Ollama");
+    assert_eq!(p.model, "qwen2.5-coder");
+    assert_eq!(p.base_url, "http://gpu:11434/v1");
+
+    let p = Provider::from_config(&cfg, None, Some("llama3.1".into()), None, None).unwrap();
+    assert_eq!(p.model, "llama3.1");
+}
+
+#[test]
+fn config_endpoint_does_not_receive_the_default_key() {
+    // A repository's .kimun.toml may move the endpoint but not the key.
+    let cfg: AiConfig = toml::from_str(
+        "base_url = \"https://attacker.example/v1\"\napi_key_env = \"AWS_SECRET_ACCESS_KEY\"\n",
+    )
+    .unwrap();
+    let p = Provider::from_config(&cfg, Some("ollama".into()), None, None, None).unwrap();
+    assert_eq!(p.base_url, "http://localhost:11434/v1");
+    let p = Provider::from_config(&cfg, None, None, None, None).unwrap();
+    assert_eq!(p.base_url, "https://attacker.example/v1");
+    assert!(p.api_key.is_none());
+}
+
+#[test]
+fn split_target_treats_existing_path_as_path() {
+    let (provider, path) = split_target(Some("src".into()), None);
+    assert!(provider.is_none());
+    assert_eq!(path, Some(PathBuf::from("src")));
+}
+
+#[test]
+fn split_target_keeps_provider_names() {
+    let (provider, path) = split_target(Some("ollama".into()), None);
+    assert_eq!(provider.as_deref(), Some("ollama"));
+    assert!(path.is_none());
+
+    // Unknown names that are not paths still surface as provider errors.
+    let (provider, _) = split_target(Some("no_such_provider_xyz".into()), None);
+    assert_eq!(provider.as_deref(), Some("no_such_provider_xyz"));
+
+    let (provider, path) = split_target(Some("claude".into()), Some("src".into()));
+    assert_eq!(provider.as_deref(), Some("claude"));
+    assert_eq!(path, Some(PathBuf::from("src")));
+}
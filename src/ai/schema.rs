@@ -1,13 +1,32 @@
 //! Tool schema definitions for the AI analysis module.
 //!
-//! Defines the 11 `km` subcommands as Claude tool-use schemas in JSON Schema
-//! format. Each tool has a name, description, and an `input_schema` object
-//! with the shared `path` property plus any tool-specific parameters.
-//! Shared property builders (`path_prop`, `top_prop`, `since_prop`) avoid
-//! repeating the same JSON property definitions across tools.
+//! Defines one Claude tool-use schema per `km` analysis subcommand, in JSON
+//! Schema format. Each tool has a name, description, and an `input_schema`
+//! object with the shared walk properties (`path`, `include_tests`, and the
+//! exclude filters) plus the subcommand's own flags. Shared property
+//! builders avoid repeating the same JSON property definitions across tools.
+//!
+//! The accepted values for enumerated flags (`sort_by`, `complexity`, …)
+//! live here as constants so the executor validates against the same lists
+//! the model is shown.
 
 use serde_json::{Value, json};
 
+pub const HAL_SORT: &[&str] = &["effort", "volume", "bugs"];
+pub const COMPLEXITY_SORT: &[&str] = &["total", "max", "avg"];
+pub const MI_SORT: &[&str] = &["mi", "volume", "complexity", "loc"];
+pub const CHURN_SORT: &[&str] = &["commits", "rate", "lines", "relative", "file"];
+pub const DEFECTS_SORT: &[&str] = &["fixes", "density", "ratio", "complexity", "file"];
+pub const HOTSPOTS_SORT: &[&str] = &["score", "commits", "complexity"];
+pub const HOTSPOTS_COMPLEXITY: &[&str] = &["indent", "cycom", "cogcom"];
+pub const HOTSPOTS_CHANGES: &[&str] = &["commits", "lines", "fixes"];
+pub const KNOWLEDGE_SORT: &[&str] = &["concentration", "diffusion", "risk"];
+pub const TC_SORT: &[&str] = &["strength", "shared"];
+pub const AGE_SORT: &[&str] = &["date", "status", "file"];
+pub const AGE_STATUS: &[&str] = &["active", "stale", "frozen"];
+pub const DEPS_SORT: &[&str] = &["fan-out", "fan-in"];
+pub const SCORE_MODEL: &[&str] = &["cogcom", "legacy"];
+
 /// JSON schema property for the `path` parameter shared by all tools.
 fn path_prop() -> Value {
     json!({"type": "string", "description": "Directory to analyze (default: project root)"})
@@ -23,6 +42,89 @@ fn since_prop() -> Value {
     json!({"type": "string", "description": "Only consider commits since this time (e.g. 6m, 1y, 30d)"})
 }
 
+/// JSON schema property for the `min_lines` duplicate block size.
+fn min_lines_prop() -> Value {
+    json!({"type": "integer", "description": "Minimum lines for a duplicate block (default: 6)"})
+}
+
+/// JSON schema property for a `sort_by` parameter with the given choices.
+fn sort_prop(choices: &[&str]) -> Value {
+    enum_prop(choices, &format!("Sort by (default: {})", choices[0]))
+}
+
+/// JSON schema property restricted to a fixed set of string values.
+fn enum_prop(choices: &[&str], desc: &str) -> Value {
+    json!({"type": "string", "enum": choices, "description": desc})
+}
+
+fn int_prop(desc: &str) -> Value {
+    json!({"type": "integer", "description": desc})
+}
+
+fn bool_prop(desc: &str) -> Value {
+    json!({"type": "boolean", "description": desc})
+}
+
+fn strings_prop(desc: &str) -> Value {
+    json!({"type": "array", "items": {"type": "string"}, "description": desc})
+}
+
+/// Properties shared by every tool that walks the filesystem: the test
+/// toggle and the same include/exclude filters as the CLI.
+fn walk_props() -> Vec<(&'static str, Value)> {
+    vec![
+        (
+            "include_tests",
+            bool_prop("Include test files and directories (excluded by default)"),
+        ),
+        (
+            "include_ext",
+            strings_prop("Only include files with these extensions (e.g. [\"rs\", \"toml\"])"),
+        ),
+        (
+            "exclude_ext",
+            strings_prop("Exclude files with these extensions (e.g. [\"js\"])"),
+        ),
+        (
+            "exclude_dir",
+            strings_prop("Exclude directories with these exact names (e.g. [\"vendor\"])"),
+        ),
+        (
+            "exclude",
+            strings_prop(
+                "Exclude files matching these glob patterns relative to the path (e.g. [\"*.min.js\"])",
+            ),
+        ),
+    ]
+}
+
+/// Commit filters shared by the tools that read git history, as the CLI's
+/// `--range`, `--until`, `--author` and `--path`.
+fn commit_props() -> Vec<(&'static str, Value)> {
+    vec![
+        (
+            "range",
+            json!({"type": "string", "description": "Only commits in this revision range, as in git log FROM..TO (e.g. v1.2..v1.3)"}),
+        ),
+        (
+            "until",
+            json!({"type": "string", "description": "Leave out commits made after this date (YYYY-MM-DD) or this long ago (e.g. 3m)"}),
+        ),
+        (
+            "author",
+            strings_prop(
+                "Only commits whose author name or email contains one of these (case-insensitive)",
+            ),
+        ),
+        (
+            "paths",
+            strings_prop(
+                "Only changes to these repository-relative files, directories, or globs (e.g. [\"src/api\"])",
+            ),
+        ),
+    ]
+}
+
 /// Build a tool definition JSON object with the standard `path` property
 /// plus any extra properties specific to that tool.
 fn tool(name: &str, desc: &str, extra_props: &[(&str, Value)]) -> Value {
@@ -42,78 +144,352 @@ fn tool(name: &str, desc: &str, extra_props: &[(&str, Value)]) -> Value {
     })
 }
 
-/// Return the list of all 11 `km` tool definitions for the AI provider,
-/// each with name, description, and input JSON schema.
+/// Like [`tool`], but also accepts the shared walk properties.
+fn walk_tool(name: &str, desc: &str, extra_props: &[(&str, Value)]) -> Value {
+    let mut props = walk_props();
+    props.extend(extra_props.iter().map(|(k, v)| (*k, v.clone())));
+    tool(name, desc, &props)
+}
+
+/// Like [`walk_tool`], but also accepts the commit filters.
+fn history_tool(name: &str, desc: &str, extra_props: &[(&str, Value)]) -> Value {
+    let mut props = commit_props();
+    props.extend(extra_props.iter().map(|(k, v)| (*k, v.clone())));
+    walk_tool(name, desc, &props)
+}
+
+/// Return one tool definition per `km` analysis subcommand for the AI
+/// provider, each with name, description, and input JSON schema.
 pub fn tool_definitions() -> Vec<Value> {
     vec![
-        tool(
+        walk_tool(
             "km_loc",
             "Count lines of code (blank, comment, code) by language. Returns per-language breakdown with totals.",
-            &[],
+            &[(
+                "by_author",
+                bool_prop("Break down lines by git author instead of language (requires git)"),
+            )],
         ),
-        tool(
+        walk_tool(
             "km_score",
-            "Compute an overall code health score (A++ to F--) across 6 dimensions: maintainability, complexity, duplication, indentation, Halstead effort, and file size.",
-            &[],
+            "Compute an overall code health score (A++ to F--) across weighted quality dimensions (cognitive complexity, duplication, indentation, Halstead effort, file size), plus the files that need the most attention.",
+            &[
+                (
+                    "bottom",
+                    int_prop("Number of worst files to list in needs_attention (default: 10)"),
+                ),
+                ("min_lines", min_lines_prop()),
+                (
+                    "model",
+                    enum_prop(
+                        SCORE_MODEL,
+                        "Scoring model: cogcom (default) or legacy (MI + cyclomatic)",
+                    ),
+                ),
+                (
+                    "trend",
+                    json!({"type": "string", "description": "Compare against this git ref (e.g. HEAD, origin/main) and return the per-dimension and per-file score diff instead"}),
+                ),
+                (
+                    "top_changes",
+                    int_prop("With trend: number of files to list per change kind (regressed, improved, added, removed) (default: 10)"),
+                ),
+                (
+                    "new_code_since",
+                    json!({"type": "string", "description": "Score only the code changed since this git ref (e.g. origin/main), next to the overall score (ignored with trend)"}),
+                ),
+                (
+                    "plan_target",
+                    json!({"type": "string", "description": "Return the fewest file refactorings that reach this grade (e.g. B) instead (ignored with trend or new_code_since)"}),
+                ),
+                (
+                    "by_dir",
+                    int_prop(
+                        "Return a hierarchical per-directory breakdown down to this depth instead (ignored with trend, new_code_since or plan_target)",
+                    ),
+                ),
+                (
+                    "by_package",
+                    bool_prop(
+                        "Return a per-package breakdown (Cargo.toml, package.json, go.mod) instead (ignored with trend, new_code_since, plan_target or by_dir)",
+                    ),
+                ),
+            ],
         ),
-        tool(
+        walk_tool(
             "km_hal",
             "Analyze Halstead complexity metrics per file: volume, difficulty, effort, estimated bugs, and development time.",
-            &[("top", top_prop())],
+            &[("top", top_prop()), ("sort_by", sort_prop(HAL_SORT))],
         ),
-        tool(
+        walk_tool(
             "km_cycom",
             "Analyze cyclomatic complexity per file: total, max, and average complexity with per-function breakdown.",
-            &[("top", top_prop())],
+            &[
+                (
+                    "min_complexity",
+                    int_prop("Minimum max-complexity to include a file (default: 1)"),
+                ),
+                ("top", top_prop()),
+                ("sort_by", sort_prop(COMPLEXITY_SORT)),
+            ],
         ),
-        tool(
+        walk_tool(
+            "km_cogcom",
+            "Analyze cognitive complexity (SonarSource method) per file: total, max, and average with per-function breakdown. Penalizes nesting more than cyclomatic complexity.",
+            &[
+                (
+                    "min_complexity",
+                    int_prop("Minimum max-complexity to include a file (default: 1)"),
+                ),
+                ("top", top_prop()),
+                ("sort_by", sort_prop(COMPLEXITY_SORT)),
+            ],
+        ),
+        walk_tool(
             "km_indent",
             "Analyze indentation complexity per file: standard deviation and max depth of indentation.",
             &[],
         ),
-        tool(
+        walk_tool(
             "km_mi",
             "Compute Maintainability Index per file (Visual Studio variant, 0-100 scale). Green (20-100), Yellow (10-19), Red (0-9).",
-            &[("top", top_prop())],
+            &[("top", top_prop()), ("sort_by", sort_prop(MI_SORT))],
         ),
-        tool(
+        walk_tool(
             "km_miv",
             "Compute Maintainability Index per file (verifysoft variant, with comment weight). Good (85+), Moderate (65-84), Difficult (<65).",
-            &[("top", top_prop())],
+            &[("top", top_prop()), ("sort_by", sort_prop(MI_SORT))],
         ),
-        tool(
+        walk_tool(
             "km_dups",
             "Detect duplicate code blocks across files. Shows duplicate percentage and group details.",
-            &[(
-                "min_lines",
-                json!({"type": "integer", "description": "Minimum lines for a duplicate block (default: 6)"}),
-            )],
+            &[
+                ("min_lines", min_lines_prop()),
+                (
+                    "show_all",
+                    bool_prop("Return all duplicate groups instead of the top 20"),
+                ),
+            ],
         ),
-        tool(
+        walk_tool(
+            "km_report",
+            "Generate a combined report of all per-file metrics (lines of code, duplication, indentation, Halstead, cyclomatic, cognitive, maintainability index), optionally with smells, dependencies, and git history sections.",
+            &[
+                (
+                    "top",
+                    int_prop("Show only the top N files per section (default: 20)"),
+                ),
+                ("min_lines", min_lines_prop()),
+                (
+                    "full",
+                    bool_prop("Return all files in every section instead of the top N"),
+                ),
+                (
+                    "with",
+                    json!({
+                        "type": "array",
+                        "items": {"type": "string", "enum": crate::report::SECTION_NAMES},
+                        "description": "Optional sections to add: smells, deps, hotspots, knowledge, tc, age, git (all history sections), or all. Git sections are skipped outside a repository."
+                    }),
+                ),
+            ],
+        ),
+        walk_tool(
+            "km_smells",
+            "Detect code smells per file: long functions, long parameter lists, magic numbers, TODO/FIXME debt, commented-out code.",
+            &[
+                ("top", top_prop()),
+                (
+                    "max_lines",
+                    int_prop("Maximum function length before flagging (default: 50)"),
+                ),
+                (
+                    "max_params",
+                    int_prop("Maximum parameter count before flagging (default: 4)"),
+                ),
+                (
+                    "files",
+                    strings_prop("Analyze only these files (paths relative to the project root)"),
+                ),
+                (
+                    "since_ref",
+                    json!({"type": "string", "description": "Analyze only files changed since this git ref (e.g. origin/main)"}),
+                ),
+            ],
+        ),
+        walk_tool(
+            "km_deps",
+            "Analyze internal module dependencies: fan-in, fan-out, and dependency cycles per file.",
+            &[
+                (
+                    "cycles_only",
+                    bool_prop("Return only files that are part of a dependency cycle"),
+                ),
+                ("sort_by", sort_prop(DEPS_SORT)),
+                ("top", top_prop()),
+            ],
+        ),
+        history_tool(
+            "km_churn",
+            "Analyze code churn: how often and how much each file changes (commits, commits per month, lines added/deleted, relative churn). Requires git repository.",
+            &[
+                ("top", top_prop()),
+                ("sort_by", sort_prop(CHURN_SORT)),
+                ("since", since_prop()),
+                (
+                    "by_author",
+                    bool_prop("Return lines added and deleted per author instead of per file"),
+                ),
+            ],
+        ),
+        history_tool(
+            "km_defects",
+            "Count bug-fix commits per file (classified by commit message), with fix share, fixes per 1,000 lines of code and cognitive complexity. Requires git repository.",
+            &[
+                ("top", top_prop()),
+                ("sort_by", sort_prop(DEFECTS_SORT)),
+                ("since", since_prop()),
+            ],
+        ),
+        history_tool(
             "km_hotspots",
-            "Find hotspots: files that change frequently AND have high complexity. Score = commits x complexity. Requires git repository.",
-            &[("top", top_prop()), ("since", since_prop())],
+            "Find hotspots: files that change frequently AND have high complexity. Score = commits (or churned lines, or bug-fix commits) x complexity. Requires git repository.",
+            &[
+                ("top", top_prop()),
+                ("sort_by", sort_prop(HOTSPOTS_SORT)),
+                ("since", since_prop()),
+                (
+                    "complexity",
+                    enum_prop(
+                        HOTSPOTS_COMPLEXITY,
+                        "Complexity metric: indent (default), cycom, or cogcom",
+                    ),
+                ),
+                (
+                    "changes",
+                    enum_prop(
+                        HOTSPOTS_CHANGES,
+                        "Change measure: commits (default), lines (added + deleted), or fixes (bug-fix commits)",
+                    ),
+                ),
+            ],
         ),
-        tool(
+        history_tool(
             "km_knowledge",
             "Analyze code ownership patterns via git blame (knowledge maps). Shows primary owner, concentration, and knowledge loss risk per file.",
             &[
                 ("top", top_prop()),
+                ("sort_by", sort_prop(KNOWLEDGE_SORT)),
                 (
                     "since",
                     json!({"type": "string", "description": "Only consider recent activity since this time for knowledge loss detection (e.g. 6m, 1y, 30d)"}),
                 ),
+                (
+                    "risk_only",
+                    bool_prop(
+                        "Return only files with knowledge loss risk (primary owner inactive)",
+                    ),
+                ),
+                (
+                    "summary",
+                    bool_prop("Aggregate by author: files owned, lines, languages, worst risk"),
+                ),
+                (
+                    "bus_factor",
+                    bool_prop(
+                        "Return the project bus factor (contributors covering 80% of the code)",
+                    ),
+                ),
+                (
+                    "author",
+                    strings_prop(
+                        "Only files owned by, and recent activity by, these authors (case-insensitive substrings)",
+                    ),
+                ),
             ],
         ),
         tool(
             "km_tc",
             "Analyze temporal coupling: files that change together in commits. Shows coupling strength between file pairs. Requires git repository.",
+            &[
+                commit_props(),
+                vec![
+                    (
+                        "include_tests",
+                        bool_prop("Include test files (excluded by default)"),
+                    ),
+                    (
+                        "top",
+                        json!({"type": "integer", "description": "Show only the top N file pairs (default: 20)"}),
+                    ),
+                    ("sort_by", sort_prop(TC_SORT)),
+                    ("since", since_prop()),
+                    (
+                        "min_degree",
+                        int_prop("Minimum commits per file to be included (default: 3)"),
+                    ),
+                    (
+                        "min_strength",
+                        json!({"type": "number", "description": "Only pairs with coupling strength >= this value (0.0-1.0)"}),
+                    ),
+                ],
+            ]
+            .concat(),
+        ),
+        history_tool(
+            "km_age",
+            "Classify files as active, stale, or frozen by their last git modification date. Requires git repository.",
             &[
                 (
-                    "top",
-                    json!({"type": "integer", "description": "Show only the top N file pairs (default: 20)"}),
+                    "active_days",
+                    int_prop("Files modified within this many days are Active (default: 90)"),
+                ),
+                (
+                    "frozen_days",
+                    int_prop(
+                        "Files not modified for more than this many days are Frozen (default: 365)",
+                    ),
+                ),
+                ("sort_by", sort_prop(AGE_SORT)),
+                (
+                    "status",
+                    enum_prop(AGE_STATUS, "Only files with this status"),
+                ),
+            ],
+        ),
+        history_tool(
+            "km_authors",
+            "Summarize code ownership by author: files owned, lines, languages, last active date. Requires git repository.",
+            &[(
+                "since",
+                json!({"type": "string", "description": "Only consider activity since this time (e.g. 6m, 1y, 30d)"}),
+            )],
+        ),
+        walk_tool(
+            "km_debt",
+            "Estimate technical debt as remediation time: smells, cognitive complexity above threshold, duplicated lines, and dependency cycles priced in minutes, with a debt ratio against development cost from LOC.",
+            &[("top", top_prop())],
+        ),
+        walk_tool(
+            "km_history",
+            "Sample first-parent git history at a fixed interval and score each sample: overall score, grade, dimension scores, and LOC, oldest first. Requires git repository.",
+            &[
+                (
+                    "every",
+                    json!({"type": "string", "description": "Interval between samples (e.g. 1w, 1m; default: 1m)"}),
+                ),
+                (
+                    "since",
+                    json!({"type": "string", "description": "How far back to sample (e.g. 6m, 1y; default: 1y)"}),
+                ),
+                ("min_lines", min_lines_prop()),
+                (
+                    "model",
+                    enum_prop(
+                        SCORE_MODEL,
+                        "Scoring model: cogcom (default) or legacy (MI + cyclomatic)",
+                    ),
                 ),
-                ("since", since_prop()),
             ],
         ),
     ]
@@ -1,78 +1,44 @@
 use super::*;
 use crate::ai::schema::tool_definitions;
+use git2::Repository;
 use serde_json::json;
+use std::fs;
 use std::path::PathBuf;
 
-#[test]
-fn build_args_no_named_uses_project_path() {
-    let project = PathBuf::from("/tmp/project");
-    let input = json!({});
-    let args = build_args(&input, &[], &project);
-    assert_eq!(args, vec!["/tmp/project"]);
-}
-
-#[test]
-fn build_args_with_named_integer() {
-    let project = PathBuf::from("/tmp/project");
-    let input = json!({"top": 10});
-    let args = build_args(&input, &["top"], &project);
-    assert_eq!(args, vec!["--top", "10", "/tmp/project"]);
-}
-
-#[test]
-fn build_args_with_named_string() {
-    let project = PathBuf::from("/tmp/project");
-    let input = json!({"since": "6m"});
-    let args = build_args(&input, &["since"], &project);
-    assert_eq!(args, vec!["--since", "6m", "/tmp/project"]);
-}
-
-#[test]
-fn build_args_ignores_missing_named() {
-    let project = PathBuf::from("/tmp/project");
-    let input = json!({});
-    let args = build_args(&input, &["top", "since"], &project);
-    assert_eq!(args, vec!["/tmp/project"]);
-}
-
 #[test]
 fn resolve_path_empty_returns_project() {
     let project = PathBuf::from("/tmp/project");
-    let input = json!({});
-    assert_eq!(resolve_path(&input, &project), "/tmp/project");
+    assert_eq!(resolve_path(None, &project), project);
+    assert_eq!(resolve_path(Some(""), &project), project);
 }
 
 #[test]
 fn resolve_path_rejects_absolute_outside_project() {
     let project = std::env::current_dir().unwrap();
-    let input = json!({"path": "/etc"});
-    let result = resolve_path(&input, &project);
-    assert_eq!(result, project.to_string_lossy());
+    let result = resolve_path(Some("/etc"), &project);
+    assert_eq!(result, project);
 }
 
 #[test]
 fn resolve_path_rejects_traversal() {
     let project = std::env::current_dir().unwrap();
-    let input = json!({"path": "../../../../etc"});
-    let result = resolve_path(&input, &project);
-    assert_eq!(result, project.to_string_lossy());
+    let result = resolve_path(Some("../../../../etc"), &project);
+    assert_eq!(result, project);
 }
 
 #[test]
 fn resolve_path_accepts_subdirectory() {
     let project = std::env::current_dir().unwrap();
-    let input = json!({"path": "src"});
-    let result = resolve_path(&input, &project);
+    let result = resolve_path(Some("src"), &project);
     let expected = project.join("src").canonicalize().unwrap();
-    assert_eq!(result, expected.to_string_lossy());
+    assert_eq!(result, expected);
 }
 
 #[test]
 fn resolve_path_nonexistent_falls_back() {
     let project = std::env::current_dir().unwrap();
-    let input = json!({"path": "nonexistent_dir_xyz_12345"});
-    let result = resolve_path(&input, &project);
-    assert_eq!(result, project.to_string_lossy());
+    let result = resolve_path(Some("nonexistent_dir_xyz_12345"), &project);
+    assert_eq!(result, project);
 }
 
 #[test]
@@ -84,9 +50,37 @@ fn execute_tool_unknown_returns_error() {
 }
 
 #[test]
-fn tool_definitions_has_11_tools() {
-    let defs = tool_definitions();
-    assert_eq!(defs.len(), 11);
+fn tool_definitions_cover_every_analysis_subcommand() {
+    let names: Vec<String> = tool_definitions()
+        .iter()
+        .map(|d| d["name"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(names.len(), 21);
+    for cmd in [
+        "loc",
+        "score",
+        "hal",
+        "cycom",
+        "cogcom",
+        "indent",
+        "mi",
+        "miv",
+        "dups",
+        "report",
+        "smells",
+        "deps",
+        "churn",
+        "defects",
+        "hotspots",
+        "knowledge",
+        "tc",
+        "age",
+        "authors",
+        "debt",
+        "history",
+    ] {
+        assert!(names.contains(&format!("km_{cmd}")), "missing km_{cmd}");
+    }
 }
 
 #[test]
@@ -97,3 +91,174 @@ fn tool_definitions_all_have_required_fields() {
         assert!(def.get("input_schema").is_some(), "missing input_schema");
     }
 }
+
+/// A committed git repository with a couple of small source files, so both
+/// filesystem and history-based tools have something to analyze.
+fn sample_repo() -> (tempfile::TempDir, PathBuf) {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().canonicalize().unwrap();
+    let repo = Repository::init(&root).unwrap();
+    let files = [
+        (
+            "src/main.rs",
+            "mod util;\n\nfn main() {\n    if util::ready() {\n        println!(\"go\");\n    }\n}\n",
+        ),
+        (
+            "src/util.rs",
+            "// TODO: make configurable\npub fn ready() -> bool {\n    let x = 42;\n    x > 0\n}\n",
+        ),
+    ];
+    let mut index = repo.index().unwrap();
+    for (path, content) in files {
+        fs::create_dir_all(root.join(path).parent().unwrap()).unwrap();
+        fs::write(root.join(path), content).unwrap();
+        index.add_path(std::path::Path::new(path)).unwrap();
+    }
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let sig = git2::Signature::now("Ana", "ana@example.com").unwrap();
+    repo.commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+        .unwrap();
+    (dir, root)
+}
+
+/// Build an input that sets every property of a tool's schema to a valid
+/// value, so each documented flag is exercised end to end.
+fn full_input(def: &serde_json::Value) -> serde_json::Value {
+    let mut input = serde_json::Map::new();
+    for (name, prop) in def["input_schema"]["properties"].as_object().unwrap() {
+        let value = if let Some(choices) = prop.get("enum") {
+            choices[0].clone()
+        } else {
+            match (name.as_str(), prop["type"].as_str().unwrap()) {
+                ("path", _) => json!("src"),
+                ("since" | "trend" | "since_ref" | "new_code_since", _) => {
+                    json!(match name.as_str() {
+                        "since" => "1y",
+                        _ => "HEAD",
+                    })
+                }
+                ("every", _) => json!("1m"),
+                ("range", _) => json!("..HEAD"),
+                ("until", _) => json!("2100-01-01"),
+                ("author", _) => json!("ana"),
+                ("plan_target", _) => json!("B"),
+                ("files", _) => json!(["src/util.rs"]),
+                ("frozen_days", _) => json!(365),
+                (_, "integer") => json!(1),
+                (_, "number") => json!(0.0),
+                // Booleans stay off so the default (full) output path is taken.
+                (_, "boolean") => json!(false),
+                (_, "array") => json!([]),
+                (_, _) => json!("x"),
+            }
+        };
+        input.insert(name.clone(), value);
+    }
+    serde_json::Value::Object(input)
+}
+
+#[test]
+fn every_tool_runs_in_process_and_returns_json() {
+    let (_dir, root) = sample_repo();
+    for def in tool_definitions() {
+        let name = def["name"].as_str().unwrap();
+        for input in [json!({}), full_input(&def)] {
+            let out = execute_tool(name, &input, &root);
+            assert!(
+                serde_json::from_str::<serde_json::Value>(&out).is_ok(),
+                "{name} with {input} did not return JSON: {out}"
+            );
+        }
+    }
+}
+
+#[test]
+fn execute_tool_returns_analysis_results() {
+    let (_dir, root) = sample_repo();
+    let out = execute_tool("km_loc", &json!({}), &root);
+    let v: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert_eq!(v["languages"][0]["name"], "Rust");
+    assert_eq!(v["totals"]["files"], 2);
+
+    let out = execute_tool("km_cogcom", &json!({"top": 1}), &root);
+    let v: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert_eq!(v.as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn execute_tool_applies_boolean_filters() {
+    let (_dir, root) = sample_repo();
+    let out = execute_tool("km_knowledge", &json!({"bus_factor": true}), &root);
+    let v: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert_eq!(v["factor"], 1);
+
+    let out = execute_tool("km_deps", &json!({"cycles_only": true}), &root);
+    let v: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert!(v["files"].as_array().unwrap().is_empty());
+}
+
+#[test]
+fn execute_tool_applies_commit_filters() {
+    let (_dir, root) = sample_repo();
+    let churned = |input: serde_json::Value| {
+        let out = execute_tool("km_churn", &input, &root);
+        let v: serde_json::Value = serde_json::from_str(&out).unwrap();
+        v.as_array().unwrap().len()
+    };
+    assert_eq!(churned(json!({})), 2);
+    assert_eq!(churned(json!({"paths": ["src/util.rs"]})), 1);
+    assert_eq!(churned(json!({"author": "nobody"})), 0);
+    assert_eq!(churned(json!({"author": ["nobody", "ANA"]})), 2);
+
+    let out = execute_tool("km_tc", &json!({"range": "HEAD"}), &root);
+    assert!(out.contains("FROM..TO"), "{out}");
+}
+
+#[test]
+fn execute_tool_rejects_invalid_enum_value() {
+    let (_dir, root) = sample_repo();
+    let out = execute_tool("km_hal", &json!({"sort_by": "size"}), &root);
+    assert!(
+        out.starts_with("Error running km_hal: invalid sort_by 'size'"),
+        "{out}"
+    );
+}
+
+#[test]
+fn execute_tool_rejects_mistyped_input() {
+    let (_dir, root) = sample_repo();
+    let out = execute_tool("km_hal", &json!({"top": "ten"}), &root);
+    assert!(
+        out.starts_with("Error running km_hal: invalid input"),
+        "{out}"
+    );
+}
+
+#[test]
+fn execute_tool_accepts_null_input() {
+    let (_dir, root) = sample_repo();
+    let out = execute_tool("km_indent", &serde_json::Value::Null, &root);
+    assert!(
+        serde_json::from_str::<serde_json::Value>(&out).is_ok(),
+        "{out}"
+    );
+}
+
+#[test]
+fn smells_files_outside_project_are_ignored() {
+    let (_dir, root) = sample_repo();
+    let out = execute_tool(
+        "km_smells",
+        &json!({"files": ["/etc/passwd", "src/util.rs"]}),
+        &root,
+    );
+    let v: serde_json::Value = serde_json::from_str(&out).unwrap();
+    let paths: Vec<&str> = v
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| f["path"].as_str().unwrap())
+        .collect();
+    assert!(paths.iter().all(|p| p.ends_with("util.rs")), "{paths:?}");
+}
@@ -3,7 +3,7 @@
 /// Walks source files, runs git blame on each, and aggregates per-author:
 /// files owned (primary contributor), total lines, languages, last active date.
 pub mod analyzer;
-mod report;
+pub(crate) mod report;
 
 use std::error::Error;
 
@@ -12,16 +12,25 @@ use crate::git::GitRepo;
 use crate::util::parse_since;
 use crate::walk::{self, WalkConfig};
 use analyzer::compute_authors;
+
+pub use analyzer::AuthorSummary;
 use report::{print_json, print_report, print_short, print_terse};
 
-/// Run author summary analysis and print results.
-pub fn run(
+/// Blame every source file and aggregate per-author ownership. When `since`
+/// is set, only lines last touched after the cutoff are counted.
+///
+/// With a commit filter (`cfg.commits`), lines are blamed as of the end of
+/// its range, only files under its paths are counted, and only its authors
+/// are listed.
+pub fn analyze_project(
     cfg: &WalkConfig<'_>,
-    output: OutputMode,
     since: Option<&str>,
-) -> Result<(), Box<dyn Error>> {
+) -> Result<Vec<AuthorSummary>, Box<dyn Error>> {
     let git = GitRepo::open(cfg.path)
-        .map_err(|e| format!("not a git repository (or any parent): {e}"))?;
+        .map_err(|e| format!("not a git repository (or any parent): {e}"))?
+        .with_aliases(cfg.aliases)
+        .with_exclusions(cfg.exclusions)
+        .with_commit_filter(cfg.commits)?;
 
     let since_ts = since.map(parse_since).transpose()?;
 
@@ -32,6 +41,11 @@ pub fn run(
 
     for (file_path, spec) in walk::source_files(&walk_root, cfg.exclude_tests(), cfg.filter) {
         let rel = GitRepo::to_git_path(&walk_root, &prefix, &file_path);
+        // Skip files outside --path, and files added after the end of the
+        // commit range (nothing to blame).
+        if !git.includes_path(&rel) || cfg.commits.is_some() && !git.exists_at_tip(&rel) {
+            continue;
+        }
         let mut blames = match git.blame_file(&rel) {
             Ok(b) => b,
             Err(e) => {
@@ -51,7 +65,27 @@ pub fn run(
         file_blames.push((spec.name.to_string(), blames));
     }
 
-    if file_blames.is_empty() {
+    let refs: Vec<(&str, &[crate::git::BlameInfo])> = file_blames
+        .iter()
+        .map(|(lang, blames)| (lang.as_str(), blames.as_slice()))
+        .collect();
+
+    let mut authors = compute_authors(&refs);
+    if let Some(filter) = cfg.commits {
+        authors.retain(|a| filter.matches_author(&a.name, &a.email));
+    }
+    Ok(authors)
+}
+
+/// Run author summary analysis and print results.
+pub fn run(
+    cfg: &WalkConfig<'_>,
+    output: OutputMode,
+    since: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+    let authors = analyze_project(cfg, since)?;
+
+    if authors.is_empty() {
         if output == OutputMode::Json {
             println!("[]");
         } else {
@@ -60,20 +94,16 @@ pub fn run(
         return Ok(());
     }
 
-    let refs: Vec<(&str, &[crate::git::BlameInfo])> = file_blames
-        .iter()
-        .map(|(lang, blames)| (lang.as_str(), blames.as_slice()))
-        .collect();
-
-    let authors = compute_authors(&refs);
-
     match output {
         OutputMode::Json => print_json(&authors),
         OutputMode::Short => print_short(&authors),
         OutputMode::Terse => print_terse(&authors),
-        OutputMode::Github | OutputMode::Codeclimate => {
+        OutputMode::Github | OutputMode::Codeclimate | OutputMode::Sarif => {
             return Err(crate::cli::ERR_CI_FORMAT_ONLY.into());
         }
+        OutputMode::Html | OutputMode::Markdown => {
+            return Err(crate::cli::ERR_REPORT_FORMAT_ONLY.into());
+        }
         OutputMode::Table => print_report(&authors),
     }
 
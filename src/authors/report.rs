@@ -77,8 +77,8 @@ struct JsonEntry<'a> {
     last_active: String,
 }
 
-/// Serialize author summaries as pretty-printed JSON to stdout.
-pub fn print_json(authors: &[AuthorSummary]) {
+/// Serialize author summaries as a pretty-printed JSON string.
+pub fn format_json(authors: &[AuthorSummary]) -> Result<String, Box<dyn std::error::Error>> {
     let entries: Vec<JsonEntry<'_>> = authors
         .iter()
         .map(|a| JsonEntry {
@@ -91,7 +91,12 @@ pub fn print_json(authors: &[AuthorSummary]) {
         })
         .collect();
 
-    report_helpers::print_json_stdout(&entries).unwrap();
+    report_helpers::json_string(&entries)
+}
+
+/// Serialize author summaries as pretty-printed JSON to stdout.
+pub fn print_json(authors: &[AuthorSummary]) {
+    println!("{}", format_json(authors).unwrap());
 }
 
 /// Print authors as a single compact line.
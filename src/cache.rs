@@ -0,0 +1,146 @@
+//! Persistent on-disk analysis cache keyed by file content.
+//!
+//! Each entry stores the path-independent result of one analyzer for one
+//! file, addressed by the file's FNV-1a content hash (`util::hash_file`),
+//! its detected language, and the analyzer name. Entries live under a
+//! directory named after the crate version, so upgrading `km` invalidates
+//! everything at once; stale version directories are pruned on open.
+//!
+//! Layout: `<dir>/<version>/<analyzer>/<hash[0..2]>/<hash>-<language>.json`
+//!
+//! The cache is strictly best-effort: unreadable or corrupt entries are
+//! recomputed, and write failures are ignored. Writes go through a temp
+//! file and an atomic rename, so parallel workers never observe partial
+//! entries.
+use std::error::Error;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::loc::language::LanguageSpec;
+use crate::util::hash_file;
+
+/// Default cache directory name, relative to the project root.
+pub const DEFAULT_DIR: &str = ".kimun-cache";
+
+/// Cache format version: the crate version, so any release invalidates
+/// results produced by older analyzers.
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Create the cache directory with a `.gitignore` that hides it from git.
+/// Also used for the `km history` snapshot file, which lives alongside.
+pub(crate) fn create_dir(dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    let gitignore = dir.join(".gitignore");
+    if !gitignore.exists() {
+        fs::write(&gitignore, "*\n")?;
+    }
+    Ok(())
+}
+
+/// Handle to an opened cache directory for the running `km` version.
+#[derive(Debug)]
+pub struct AnalysisCache {
+    /// `<dir>/<version>` — all entries for this version live below it.
+    root: PathBuf,
+}
+
+impl AnalysisCache {
+    /// Open (creating if needed) the cache rooted at `dir`.
+    ///
+    /// Writes a `.gitignore` so the cache never shows up in `git status`,
+    /// and removes entry directories left behind by other `km` versions.
+    pub fn open(dir: &Path) -> io::Result<Self> {
+        create_dir(dir)?;
+        prune_other_versions(dir);
+        let root = dir.join(VERSION);
+        fs::create_dir_all(&root)?;
+        Ok(Self { root })
+    }
+
+    /// Location of the entry for `analyzer` on content `hash` in `language`.
+    fn entry_path(&self, analyzer: &str, language: &str, hash: u64) -> PathBuf {
+        let hex = format!("{hash:016x}");
+        self.root
+            .join(analyzer)
+            .join(&hex[..2])
+            .join(format!("{hex}-{}.json", sanitize(language)))
+    }
+
+    /// Look up a cached value. Missing or undecodable entries yield `None`.
+    pub fn get<T: DeserializeOwned>(&self, analyzer: &str, language: &str, hash: u64) -> Option<T> {
+        let bytes = fs::read(self.entry_path(analyzer, language, hash)).ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+
+    /// Store a value, replacing any previous entry atomically.
+    pub fn put<T: Serialize>(
+        &self,
+        analyzer: &str,
+        language: &str,
+        hash: u64,
+        value: &T,
+    ) -> io::Result<()> {
+        let path = self.entry_path(analyzer, language, hash);
+        let dir = path.parent().expect("entry path has a parent");
+        fs::create_dir_all(dir)?;
+        let mut tmp = tempfile::NamedTempFile::new_in(dir)?;
+        serde_json::to_writer(&mut tmp, value)?;
+        tmp.flush()?;
+        tmp.persist(&path).map_err(|e| e.error)?;
+        Ok(())
+    }
+}
+
+/// Run `compute` for `path`, going through `cache` when one is configured.
+///
+/// On a hit the stored value is returned without calling `compute`. On a
+/// miss the result is computed and, if successful, stored for next time.
+/// Errors are never cached. Without a cache, or when the file cannot be
+/// hashed, this is just `compute()`.
+pub fn cached<T: Serialize + DeserializeOwned>(
+    cache: Option<&AnalysisCache>,
+    analyzer: &str,
+    path: &Path,
+    spec: &LanguageSpec,
+    compute: impl FnOnce() -> Result<T, Box<dyn Error>>,
+) -> Result<T, Box<dyn Error>> {
+    let Some((cache, hash)) = cache.and_then(|c| hash_file(path).map(|h| (c, h))) else {
+        return compute();
+    };
+    if let Some(hit) = cache.get(analyzer, spec.name, hash) {
+        return Ok(hit);
+    }
+    let value = compute()?;
+    // Best-effort: a failed write only costs a recomputation next run.
+    let _ = cache.put(analyzer, spec.name, hash, &value);
+    Ok(value)
+}
+
+/// Map a language name to a filename-safe token ("C++" → "C__").
+fn sanitize(language: &str) -> String {
+    language
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// Remove version directories other than the current one.
+fn prune_other_versions(dir: &Path) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let is_dir = entry.file_type().is_ok_and(|t| t.is_dir());
+        if is_dir && entry.file_name() != VERSION {
+            let _ = fs::remove_dir_all(entry.path());
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "cache_test.rs"]
+mod tests;
@@ -0,0 +1,162 @@
+use std::cell::Cell;
+use std::fs;
+
+use tempfile::tempdir;
+
+use super::*;
+use crate::loc::language::detect;
+
+#[test]
+fn open_creates_version_dir_and_gitignore() {
+    let dir = tempdir().unwrap();
+    let cache_dir = dir.path().join(DEFAULT_DIR);
+    AnalysisCache::open(&cache_dir).unwrap();
+    assert!(cache_dir.join(VERSION).is_dir());
+    assert_eq!(
+        fs::read_to_string(cache_dir.join(".gitignore")).unwrap(),
+        "*\n"
+    );
+}
+
+#[test]
+fn open_prunes_other_versions() {
+    let dir = tempdir().unwrap();
+    let stale = dir.path().join("0.0.1").join("cogcom");
+    fs::create_dir_all(&stale).unwrap();
+    AnalysisCache::open(dir.path()).unwrap();
+    assert!(!dir.path().join("0.0.1").exists());
+}
+
+#[test]
+fn put_then_get_roundtrips() {
+    let dir = tempdir().unwrap();
+    let cache = AnalysisCache::open(dir.path()).unwrap();
+    cache
+        .put("test", "C++", 0xabcdef, &vec![1usize, 2, 3])
+        .unwrap();
+    let hit: Option<Vec<usize>> = cache.get("test", "C++", 0xabcdef);
+    assert_eq!(hit, Some(vec![1, 2, 3]));
+    // Different language or analyzer is a different key.
+    assert!(cache.get::<Vec<usize>>("test", "C", 0xabcdef).is_none());
+    assert!(cache.get::<Vec<usize>>("other", "C++", 0xabcdef).is_none());
+}
+
+#[test]
+fn corrupt_entry_is_a_miss() {
+    let dir = tempdir().unwrap();
+    let cache = AnalysisCache::open(dir.path()).unwrap();
+    let path = cache.entry_path("test", "Rust", 42);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, "not json").unwrap();
+    assert!(cache.get::<usize>("test", "Rust", 42).is_none());
+}
+
+#[test]
+fn cached_computes_once_per_content() {
+    let dir = tempdir().unwrap();
+    let cache = AnalysisCache::open(&dir.path().join(DEFAULT_DIR)).unwrap();
+    let file = dir.path().join("a.rs");
+    fs::write(&file, "fn main() {}\n").unwrap();
+    let spec = detect(&file).unwrap();
+
+    let calls = Cell::new(0);
+    let compute = || {
+        calls.set(calls.get() + 1);
+        Ok(Some(7usize))
+    };
+    let first = cached(Some(&cache), "test", &file, spec, compute).unwrap();
+    let second = cached(Some(&cache), "test", &file, spec, compute).unwrap();
+    assert_eq!(first, Some(7));
+    assert_eq!(second, Some(7));
+    assert_eq!(calls.get(), 1, "second call should be served from cache");
+
+    // Changing the content invalidates the entry.
+    fs::write(&file, "fn main() { let x = 1; }\n").unwrap();
+    cached(Some(&cache), "test", &file, spec, compute).unwrap();
+    assert_eq!(calls.get(), 2);
+}
+
+#[test]
+fn cached_does_not_store_errors() {
+    let dir = tempdir().unwrap();
+    let cache = AnalysisCache::open(&dir.path().join(DEFAULT_DIR)).unwrap();
+    let file = dir.path().join("a.rs");
+    fs::write(&file, "fn main() {}\n").unwrap();
+    let spec = detect(&file).unwrap();
+
+    let err: Result<usize, _> = cached(Some(&cache), "test", &file, spec, || Err("boom".into()));
+    assert!(err.is_err());
+    let ok = cached(Some(&cache), "test", &file, spec, || Ok(3usize)).unwrap();
+    assert_eq!(ok, 3);
+}
+
+#[test]
+fn cached_without_cache_always_computes() {
+    let dir = tempdir().unwrap();
+    let file = dir.path().join("a.rs");
+    fs::write(&file, "fn main() {}\n").unwrap();
+    let spec = detect(&file).unwrap();
+    let calls = Cell::new(0);
+    for _ in 0..2 {
+        cached(None, "test", &file, spec, || {
+            calls.set(calls.get() + 1);
+            Ok(())
+        })
+        .unwrap();
+    }
+    assert_eq!(calls.get(), 2);
+}
+
+#[test]
+fn sanitize_keeps_languages_distinct() {
+    assert_eq!(sanitize("C++"), "C__");
+    assert_eq!(sanitize("C#"), "C_");
+    assert_eq!(sanitize("Bourne Again Shell"), "Bourne_Again_Shell");
+}
+
+/// Write a small project where two files share identical content, so a
+/// cache hit for one must come back attributed to the other's path.
+fn sample_project(root: &Path) {
+    let body = "fn f(x: i32) -> i32 {\n    if x > 0 {\n        for i in 0..x {\n            if i % 2 == 0 {\n                return i;\n            }\n        }\n    }\n    0\n}\n";
+    fs::write(root.join("a.rs"), body).unwrap();
+    fs::write(root.join("b.rs"), body).unwrap();
+    fs::write(
+        root.join("c.py"),
+        "def g(y):\n    while y:\n        y -= 1\n    return y\n",
+    )
+    .unwrap();
+}
+
+#[test]
+fn cached_analyses_match_uncached() {
+    let dir = tempdir().unwrap();
+    sample_project(dir.path());
+    let cache_dir = tempdir().unwrap();
+    let cache = AnalysisCache::open(cache_dir.path()).unwrap();
+    let filter = crate::walk::ExcludeFilter::default();
+    let plain = crate::walk::WalkConfig::new(dir.path(), false, &filter);
+    let with_cache =
+        crate::walk::WalkConfig::new(dir.path(), false, &filter).with_cache(Some(&cache));
+
+    let score = |cfg: &crate::walk::WalkConfig<'_>| {
+        let s =
+            crate::score::compute_score(cfg, 10, 2, &crate::score::ScoreModel::default()).unwrap();
+        serde_json::to_value(s).unwrap()
+    };
+    let report = |cfg: &crate::walk::WalkConfig<'_>| {
+        serde_json::to_value(crate::report::build_report(cfg, 10, 2).unwrap()).unwrap()
+    };
+    let cogcom = |cfg: &crate::walk::WalkConfig<'_>| {
+        crate::cogcom::analyze_project(cfg, 1, "total")
+            .into_iter()
+            .map(|f| (f.path, f.total_complexity))
+            .collect::<Vec<_>>()
+    };
+
+    // Run twice with the cache: once cold (populating), once warm (hits).
+    for _ in 0..2 {
+        assert_eq!(score(&with_cache), score(&plain));
+        assert_eq!(report(&with_cache), report(&plain));
+        assert_eq!(cogcom(&with_cache), cogcom(&plain));
+    }
+}
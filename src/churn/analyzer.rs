@@ -4,8 +4,17 @@
 /// number of months it has been active (first commit → last commit, minimum
 /// one month). High-churn files are "moving targets" — hard to reason about,
 /// easy to break, and worth monitoring even when their complexity is low.
+///
+/// Line churn measures the volume of those changes: lines added plus lines
+/// deleted. Relative churn divides it by the file's current size, so a small
+/// file rewritten many times over stands out next to a large one touched
+/// here and there.
+use std::cmp::Reverse;
+use std::collections::HashMap;
 use std::path::PathBuf;
 
+use crate::git::AuthorLines;
+
 /// Churn classification based on commits-per-month rate.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum ChurnLevel {
@@ -41,6 +50,78 @@ pub struct FileChurn {
     /// Unix timestamp of the most recent commit.
     pub last_commit: i64,
     pub level: ChurnLevel,
+    /// Lines added across those commits.
+    pub lines_added: usize,
+    /// Lines deleted across those commits.
+    pub lines_deleted: usize,
+    /// Churned lines (added + deleted) per line of the current file.
+    pub relative: f64,
+    /// Each author's added and deleted lines, most lines first.
+    pub authors: Vec<AuthorLines>,
+}
+
+impl FileChurn {
+    /// Lines added plus lines deleted.
+    pub fn churned_lines(&self) -> usize {
+        self.lines_added + self.lines_deleted
+    }
+
+    /// Attach line churn, given the file's current line count.
+    pub fn with_lines(
+        mut self,
+        added: usize,
+        deleted: usize,
+        current_lines: usize,
+        authors: Vec<AuthorLines>,
+    ) -> Self {
+        self.lines_added = added;
+        self.lines_deleted = deleted;
+        self.relative = (added + deleted) as f64 / current_lines.max(1) as f64;
+        self.authors = authors;
+        self
+    }
+}
+
+/// Line churn of one author across the analyzed files.
+pub struct AuthorChurn {
+    pub author: String,
+    pub email: String,
+    /// Number of files the author added or deleted lines in.
+    pub files: usize,
+    pub lines_added: usize,
+    pub lines_deleted: usize,
+}
+
+impl AuthorChurn {
+    /// Lines added plus lines deleted.
+    pub fn churned_lines(&self) -> usize {
+        self.lines_added + self.lines_deleted
+    }
+}
+
+/// Sum each author's line churn over `files`, most churned lines first.
+/// Authors are told apart by email.
+pub fn by_author(files: &[FileChurn]) -> Vec<AuthorChurn> {
+    let mut map: HashMap<&str, AuthorChurn> = HashMap::new();
+    for share in files.iter().flat_map(|f| &f.authors) {
+        let entry = map.entry(&share.email).or_insert_with(|| AuthorChurn {
+            author: share.author.clone(),
+            email: share.email.clone(),
+            files: 0,
+            lines_added: 0,
+            lines_deleted: 0,
+        });
+        entry.files += 1;
+        entry.lines_added += share.added;
+        entry.lines_deleted += share.deleted;
+    }
+    let mut authors: Vec<AuthorChurn> = map.into_values().collect();
+    authors.sort_by(|a, b| {
+        Reverse(a.churned_lines())
+            .cmp(&Reverse(b.churned_lines()))
+            .then_with(|| a.author.cmp(&b.author))
+    });
+    authors
 }
 
 const SECS_PER_MONTH: f64 = 30.0 * 24.0 * 3600.0;
@@ -73,6 +154,10 @@ pub fn classify(
         first_commit,
         last_commit,
         level,
+        lines_added: 0,
+        lines_deleted: 0,
+        relative: 0.0,
+        authors: Vec::new(),
     }
 }
 
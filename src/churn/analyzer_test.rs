@@ -63,3 +63,46 @@ fn fields_are_stored() {
     assert_eq!(f.first_commit, first);
     assert_eq!(f.last_commit, last);
 }
+
+fn share(author: &str, added: usize, deleted: usize) -> AuthorLines {
+    AuthorLines {
+        author: author.to_string(),
+        email: format!("{}@x.com", author.to_lowercase()),
+        added,
+        deleted,
+    }
+}
+
+#[test]
+fn relative_churn_is_churned_lines_per_current_line() {
+    let f = classify(path(), "Rust", 3, 0, MONTH).with_lines(30, 10, 20, Vec::new());
+    assert_eq!(f.churned_lines(), 40);
+    assert!((f.relative - 2.0).abs() < 1e-9);
+    let empty = classify(path(), "Rust", 1, 0, 0).with_lines(4, 4, 0, Vec::new());
+    assert!(
+        (empty.relative - 8.0).abs() < 1e-9,
+        "an empty file counts as one line"
+    );
+}
+
+#[test]
+fn by_author_sums_shares_across_files() {
+    let a = classify(PathBuf::from("a.rs"), "Rust", 2, 0, 0).with_lines(
+        12,
+        3,
+        10,
+        vec![share("Jane", 10, 2), share("Bob", 2, 1)],
+    );
+    let b = classify(PathBuf::from("b.rs"), "Rust", 1, 0, 0).with_lines(
+        20,
+        0,
+        20,
+        vec![share("Bob", 20, 0)],
+    );
+    let authors = by_author(&[a, b]);
+    let rows: Vec<_> = authors
+        .iter()
+        .map(|a| (a.author.as_str(), a.files, a.lines_added, a.lines_deleted))
+        .collect();
+    assert_eq!(rows, [("Bob", 2, 22, 1), ("Jane", 1, 10, 2)]);
+}
@@ -1,11 +1,12 @@
 /// Code churn analysis — pure change frequency per source file.
 ///
-/// Walks source files, resolves each one's commit count and first/last
-/// timestamps via git, computes a commits-per-month rate, and classifies
+/// Walks source files, resolves each one's commit count, first/last
+/// timestamps and lines added/deleted via git, computes a commits-per-month
+/// rate and relative churn (churned lines / current lines), and classifies
 /// as High / Medium / Low. Unlike hotspots (churn × complexity), churn
 /// shows velocity alone — useful for finding "moving targets".
 pub mod analyzer;
-mod report;
+pub(crate) mod report;
 
 use std::cmp::Reverse;
 use std::collections::HashMap;
@@ -14,78 +15,159 @@ use std::path::PathBuf;
 
 use crate::cli::OutputMode;
 use crate::git::GitRepo;
+use crate::loc::counter::count_lines;
+use crate::report_helpers::empty_history_note;
 use crate::util::parse_since;
 use crate::walk::{self, WalkConfig};
-use analyzer::{FileChurn, classify};
-use report::{print_json, print_report, print_short, print_terse};
+use analyzer::{by_author, classify};
 
-/// Run code churn analysis and print results.
+pub use analyzer::{AuthorChurn, ChurnLevel, FileChurn};
+use report::{
+    print_authors_json, print_authors_report, print_authors_short, print_authors_terse, print_json,
+    print_report, print_short, print_terse,
+};
+
+/// Compute churn for every source file with git history.
 ///
-/// Sorts by `sort_by` ("commits", "rate", or "file"), truncates to `top`,
+/// Sorts by `sort_by` ("commits", "rate", "lines", "relative", or "file")
 /// and optionally restricts to commits after `since` (e.g. "6m", "1y").
-pub fn run(
+/// Returns an empty list when the repository has no commits in range.
+pub fn analyze_project(
     cfg: &WalkConfig<'_>,
-    output: OutputMode,
-    top: usize,
     sort_by: &str,
     since: Option<&str>,
-) -> Result<(), Box<dyn Error>> {
+) -> Result<Vec<FileChurn>, Box<dyn Error>> {
     let git = GitRepo::open(cfg.path)
-        .map_err(|e| format!("not a git repository (or any parent): {e}"))?;
+        .map_err(|e| format!("not a git repository (or any parent): {e}"))?
+        .with_aliases(cfg.aliases)
+        .with_rename_threshold(cfg.rename_threshold)
+        .with_exclusions(cfg.exclusions)
+        .with_commit_filter(cfg.commits)?;
 
     let since_ts = since.map(parse_since).transpose()?;
-    let freqs = git.file_frequencies(since_ts)?;
+    let churn = git.line_churn(since_ts)?;
 
-    if freqs.is_empty() {
-        if since.is_some() {
-            eprintln!("No commits found in the specified time range.");
-        } else {
-            eprintln!("No commits found in the repository.");
-        }
-        return Ok(());
+    if churn.is_empty() {
+        return Ok(Vec::new());
     }
 
-    let freq_map: HashMap<PathBuf, _> = freqs.into_iter().map(|f| (f.path.clone(), f)).collect();
+    let mut churn_map: HashMap<PathBuf, _> = churn
+        .into_iter()
+        .map(|c| (c.frequency.path.clone(), c))
+        .collect();
 
     let (walk_root, walk_prefix) = git.walk_prefix(cfg.path)?;
 
-    let mut files: Vec<FileChurn> = walk::source_files(&walk_root, cfg.exclude_tests(), cfg.filter)
+    let candidates: Vec<_> = walk::source_files(&walk_root, cfg.exclude_tests(), cfg.filter)
         .into_iter()
         .filter_map(|(file_path, spec)| {
             let rel = GitRepo::to_git_path(&walk_root, &walk_prefix, &file_path);
-            freq_map.get(&rel).map(|freq| {
-                classify(
-                    rel,
-                    spec.name,
-                    freq.commits,
-                    freq.first_commit,
-                    freq.last_commit,
-                )
-            })
+            let churn = churn_map.remove(&rel)?;
+            Some((file_path, spec, churn))
+        })
+        .collect();
+
+    // Current size of each file, the base for relative churn.
+    let current_lines = cfg.par_map(&candidates, |(file_path, spec, _)| {
+        count_lines(file_path, spec)
+            .ok()
+            .flatten()
+            .map_or(0, |s| s.blank + s.comment + s.code)
+    });
+
+    let mut files: Vec<FileChurn> = candidates
+        .into_iter()
+        .zip(current_lines)
+        .map(|((_, spec, churn), lines)| {
+            let freq = churn.frequency;
+            classify(
+                freq.path,
+                spec.name,
+                freq.commits,
+                freq.first_commit,
+                freq.last_commit,
+            )
+            .with_lines(churn.added, churn.deleted, lines, churn.authors)
         })
         .collect();
 
     match sort_by {
         "rate" => files.sort_by(|a, b| b.rate.partial_cmp(&a.rate).unwrap()),
+        "lines" => files.sort_by_key(|f| Reverse(f.churned_lines())),
+        "relative" => files.sort_by(|a, b| b.relative.partial_cmp(&a.relative).unwrap()),
         "file" => files.sort_by_key(|f| f.path.clone()),
         _ => files.sort_by_key(|f| Reverse(f.commits)),
     }
 
+    Ok(files)
+}
+
+/// Run code churn analysis and print results.
+///
+/// Sorts by `sort_by` ("commits", "rate", "lines", "relative", or "file"),
+/// truncates to `top`, and optionally restricts to commits after `since`
+/// (e.g. "6m", "1y"). With `by_author`, prints the top authors by churned
+/// lines across all analyzed files instead.
+pub fn run(
+    cfg: &WalkConfig<'_>,
+    output: OutputMode,
+    top: usize,
+    sort_by: &str,
+    since: Option<&str>,
+    by_author: bool,
+) -> Result<(), Box<dyn Error>> {
+    let mut files = analyze_project(cfg, sort_by, since)?;
+    if files.is_empty()
+        && let Some(note) = empty_history_note(since.is_some(), cfg.commits.is_some())
+    {
+        eprintln!("{note}");
+        return Ok(());
+    }
+    if by_author {
+        return run_by_author(&files, output, top);
+    }
     files.truncate(top);
 
     match output {
         OutputMode::Json => print_json(&files),
         OutputMode::Short => print_short(&files),
         OutputMode::Terse => print_terse(&files),
-        OutputMode::Github | OutputMode::Codeclimate => {
+        OutputMode::Github | OutputMode::Codeclimate | OutputMode::Sarif => {
             return Err(crate::cli::ERR_CI_FORMAT_ONLY.into());
         }
+        OutputMode::Html | OutputMode::Markdown => {
+            return Err(crate::cli::ERR_REPORT_FORMAT_ONLY.into());
+        }
         OutputMode::Table => print_report(&files),
     }
 
     Ok(())
 }
 
+fn run_by_author(
+    files: &[FileChurn],
+    output: OutputMode,
+    top: usize,
+) -> Result<(), Box<dyn Error>> {
+    let mut authors = by_author(files);
+    authors.truncate(top);
+
+    match output {
+        OutputMode::Json => print_authors_json(&authors),
+        OutputMode::Short => print_authors_short(&authors),
+        OutputMode::Terse => print_authors_terse(&authors),
+        OutputMode::Github | OutputMode::Codeclimate | OutputMode::Sarif => {
+            return Err(crate::cli::ERR_CI_FORMAT_ONLY.into());
+        }
+        OutputMode::Html | OutputMode::Markdown => {
+            return Err(crate::cli::ERR_REPORT_FORMAT_ONLY.into());
+        }
+        OutputMode::Table => print_authors_report(&authors),
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 #[path = "mod_test.rs"]
 mod tests;
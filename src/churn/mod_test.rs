@@ -8,35 +8,35 @@ use std::path::Path as StdPath;
 fn run_on_current_repo() {
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(std::path::Path::new("."), false, &filter);
-    super::run(&cfg, OutputMode::Table, 20, "commits", None).unwrap();
+    super::run(&cfg, OutputMode::Table, 20, "commits", None, false).unwrap();
 }
 
 #[test]
 fn run_json_on_current_repo() {
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(std::path::Path::new("."), false, &filter);
-    super::run(&cfg, OutputMode::Json, 20, "commits", None).unwrap();
+    super::run(&cfg, OutputMode::Json, 20, "commits", None, false).unwrap();
 }
 
 #[test]
 fn run_sort_by_rate() {
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(std::path::Path::new("."), false, &filter);
-    super::run(&cfg, OutputMode::Table, 20, "rate", None).unwrap();
+    super::run(&cfg, OutputMode::Table, 20, "rate", None, false).unwrap();
 }
 
 #[test]
 fn run_sort_by_file() {
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(std::path::Path::new("."), false, &filter);
-    super::run(&cfg, OutputMode::Table, 20, "file", None).unwrap();
+    super::run(&cfg, OutputMode::Table, 20, "file", None, false).unwrap();
 }
 
 #[test]
 fn run_with_since() {
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(std::path::Path::new("."), false, &filter);
-    super::run(&cfg, OutputMode::Table, 20, "commits", Some("1y")).unwrap();
+    super::run(&cfg, OutputMode::Table, 20, "commits", Some("1y"), false).unwrap();
 }
 
 #[test]
@@ -46,7 +46,7 @@ fn run_on_non_git_dir() {
     fs::create_dir_all(&sub).unwrap();
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(&sub, false, &filter);
-    let err = super::run(&cfg, OutputMode::Table, 20, "commits", None).unwrap_err();
+    let err = super::run(&cfg, OutputMode::Table, 20, "commits", None, false).unwrap_err();
     assert!(
         err.to_string().contains("not a git repository"),
         "should mention not a git repository, got: {err}"
@@ -80,7 +80,7 @@ fn run_on_empty_repo_with_since() {
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(dir.path(), false, &filter);
     // 1d filter: commits from epoch 1_700_000_000 are old, so freqs will be empty
-    let result = super::run(&cfg, OutputMode::Table, 20, "commits", Some("1d"));
+    let result = super::run(&cfg, OutputMode::Table, 20, "commits", Some("1d"), false);
     assert!(
         result.is_ok(),
         "since-filtered empty repo should not crash: {:?}",
@@ -92,12 +92,64 @@ fn run_on_empty_repo_with_since() {
 fn run_short_format() {
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(std::path::Path::new("."), false, &filter);
-    super::run(&cfg, OutputMode::Short, 20, "commits", None).unwrap();
+    super::run(&cfg, OutputMode::Short, 20, "commits", None, false).unwrap();
 }
 
 #[test]
 fn run_terse_format() {
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(std::path::Path::new("."), false, &filter);
-    super::run(&cfg, OutputMode::Terse, 20, "commits", None).unwrap();
+    super::run(&cfg, OutputMode::Terse, 20, "commits", None, false).unwrap();
+}
+
+#[test]
+fn run_sort_by_lines_and_relative() {
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(std::path::Path::new("."), false, &filter);
+    super::run(&cfg, OutputMode::Table, 20, "lines", None, false).unwrap();
+    super::run(&cfg, OutputMode::Json, 20, "relative", None, false).unwrap();
+}
+
+#[test]
+fn run_by_author_formats() {
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(std::path::Path::new("."), false, &filter);
+    for output in [
+        OutputMode::Table,
+        OutputMode::Json,
+        OutputMode::Short,
+        OutputMode::Terse,
+    ] {
+        super::run(&cfg, output, 10, "commits", None, true).unwrap();
+    }
+}
+
+#[test]
+fn line_churn_is_measured_against_the_current_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let repo = Repository::init(dir.path()).unwrap();
+    let commit = |content: &str| {
+        let sig = git2::Signature::new("Test", "test@test.com", &git2::Time::new(1_700_000_000, 0))
+            .unwrap();
+        fs::write(dir.path().join("main.rs"), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(StdPath::new("main.rs")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, "change", &tree, &parents)
+            .unwrap();
+    };
+    commit("fn main() {\n    a();\n    b();\n}\n");
+    commit("fn main() {\n    c();\n}\n");
+
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(dir.path(), false, &filter);
+    let files = super::analyze_project(&cfg, "relative", None).unwrap();
+    assert_eq!(files.len(), 1);
+    let f = &files[0];
+    assert_eq!((f.lines_added, f.lines_deleted), (5, 2));
+    assert!((f.relative - 7.0 / 3.0).abs() < 1e-9, "{}", f.relative);
+    assert_eq!(f.authors.len(), 1);
 }
@@ -1,21 +1,35 @@
 /// Report formatters for churn analysis.
 ///
 /// Provides table and JSON output showing per-file commit frequency,
-/// churn rate (commits/month), and activity level classification.
+/// churn rate (commits/month), lines added/deleted, relative churn, and
+/// activity level classification, plus line churn per author.
 use chrono::{DateTime, Utc};
 use serde::Serialize;
 
-use super::analyzer::FileChurn;
+use super::analyzer::{AuthorChurn, FileChurn};
 use crate::report_helpers;
 
 const COL_LANG: usize = 10;
 const COL_COMMITS: usize = 7; // "Commits"
 const COL_RATE: usize = 10; // "Rate/Month"
+const COL_LINES: usize = 7; // "Deleted"
+const COL_RELATIVE: usize = 8; // "Relative"
 const COL_DATE: usize = 10; // "YYYY-MM-DD"
 const COL_LEVEL: usize = 6; // "MEDIUM"
-// 1 (lead) + 2 (after path) + 1 + 1 + 1 + 1 + 1 (between remaining cols)
-const COL_SPACING: usize = 8;
-const FIXED_WIDTH: usize = COL_SPACING + COL_LANG + COL_COMMITS + COL_RATE + COL_DATE + COL_LEVEL;
+// 1 (lead) + 2 (after path) + 1 × 7 (between remaining cols) + 1 ("Last
+// Commit" is one wider than its column)
+const COL_SPACING: usize = 11;
+const FIXED_WIDTH: usize = COL_SPACING
+    + COL_LANG
+    + COL_COMMITS
+    + COL_RATE
+    + 2 * COL_LINES
+    + COL_RELATIVE
+    + COL_DATE
+    + COL_LEVEL;
+
+const COL_FILES: usize = 5; // "Files"
+const COL_SHARE: usize = 6; // "100.0%"
 
 fn format_date(ts: i64) -> String {
     DateTime::<Utc>::from_timestamp(ts, 0)
@@ -37,18 +51,29 @@ pub fn print_report(files: &[FileChurn]) {
     println!("Code Churn — Change Frequency by File");
     println!("{separator}");
     println!(
-        " {:<col_path$}  {:>COL_LANG$} {:>COL_COMMITS$} {:>COL_RATE$} {:>COL_DATE$} {:>COL_LEVEL$}",
-        "File", "Language", "Commits", "Rate/Month", "Last Commit", "Level",
+        " {:<col_path$}  {:>COL_LANG$} {:>COL_COMMITS$} {:>COL_RATE$} {:>COL_LINES$} {:>COL_LINES$} {:>COL_RELATIVE$} {:>COL_DATE$} {:>COL_LEVEL$}",
+        "File",
+        "Language",
+        "Commits",
+        "Rate/Month",
+        "Added",
+        "Deleted",
+        "Relative",
+        "Last Commit",
+        "Level",
     );
     println!("{separator}");
 
     for f in files {
         println!(
-            " {:<col_path$}  {:>COL_LANG$} {:>COL_COMMITS$} {:>COL_RATE$.2} {:>COL_DATE$} {:>COL_LEVEL$}",
+            " {:<col_path$}  {:>COL_LANG$} {:>COL_COMMITS$} {:>COL_RATE$.2} {:>COL_LINES$} {:>COL_LINES$} {:>COL_RELATIVE$.2} {:>COL_DATE$} {:>COL_LEVEL$}",
             f.path.display(),
             f.language,
             f.commits,
             f.rate,
+            f.lines_added,
+            f.lines_deleted,
+            f.relative,
             format_date(f.last_commit),
             f.level.label(),
         );
@@ -70,6 +95,8 @@ fn print_summary(files: &[FileChurn]) {
     println!("  HIGH    {high:>5}  (> 4 commits/month — moving targets)");
     println!("  MEDIUM  {medium:>5}  (1–4 commits/month — active development)");
     println!("  LOW     {low:>5}  (< 1 commit/month — stable)");
+    println!();
+    println!("Relative = lines added + deleted per line of the current file.");
 }
 
 /// JSON-serializable representation of a single file's churn data.
@@ -79,13 +106,16 @@ struct JsonEntry {
     language: String,
     commits: usize,
     rate_per_month: f64,
+    lines_added: usize,
+    lines_deleted: usize,
+    relative_churn: f64,
     first_commit: String,
     last_commit: String,
     level: String,
 }
 
-/// Serialize churn data as pretty-printed JSON to stdout.
-pub fn print_json(files: &[FileChurn]) {
+/// Serialize churn data as a pretty-printed JSON string.
+pub fn format_json(files: &[FileChurn]) -> Result<String, Box<dyn std::error::Error>> {
     let entries: Vec<JsonEntry> = files
         .iter()
         .map(|f| JsonEntry {
@@ -93,13 +123,21 @@ pub fn print_json(files: &[FileChurn]) {
             language: f.language.clone(),
             commits: f.commits,
             rate_per_month: (f.rate * 100.0).round() / 100.0,
+            lines_added: f.lines_added,
+            lines_deleted: f.lines_deleted,
+            relative_churn: (f.relative * 100.0).round() / 100.0,
             first_commit: format_date(f.first_commit),
             last_commit: format_date(f.last_commit),
             level: f.level.label().to_string(),
         })
         .collect();
 
-    report_helpers::print_json_stdout(&entries).unwrap();
+    report_helpers::json_string(&entries)
+}
+
+/// Serialize churn data as pretty-printed JSON to stdout.
+pub fn print_json(files: &[FileChurn]) {
+    println!("{}", format_json(files).unwrap());
 }
 
 /// Print churn as a single compact line.
@@ -119,6 +157,98 @@ pub fn print_terse(files: &[FileChurn]) {
     println!("{total}");
 }
 
+/// Print a table of authors by churned lines, with each one's share of
+/// the total.
+pub fn print_authors_report(authors: &[AuthorChurn]) {
+    if authors.is_empty() {
+        println!("No authors found in git history.");
+        return;
+    }
+
+    let col_author = authors
+        .iter()
+        .map(|a| report_helpers::display_width(&a.author))
+        .max()
+        .unwrap_or(0)
+        .max(report_helpers::display_width("Author"));
+    let sep_width = col_author + COL_FILES + 2 * COL_LINES + COL_SHARE + 6;
+    let separator = report_helpers::separator(sep_width);
+    let total: usize = authors.iter().map(|a| a.churned_lines()).sum();
+
+    println!("Code Churn — Lines Changed by Author");
+    println!("{separator}");
+    println!(
+        " {}  {:>COL_FILES$} {:>COL_LINES$} {:>COL_LINES$} {:>COL_SHARE$}",
+        report_helpers::pad_to("Author", col_author),
+        "Files",
+        "Added",
+        "Deleted",
+        "Share",
+    );
+    println!("{separator}");
+
+    for a in authors {
+        let share = a.churned_lines() as f64 * 100.0 / total.max(1) as f64;
+        println!(
+            " {}  {:>COL_FILES$} {:>COL_LINES$} {:>COL_LINES$} {:>5.1}%",
+            report_helpers::pad_to(&a.author, col_author),
+            a.files,
+            a.lines_added,
+            a.lines_deleted,
+            share,
+        );
+    }
+
+    println!("{separator}");
+}
+
+/// JSON-serializable representation of one author's line churn.
+#[derive(Serialize)]
+struct JsonAuthor {
+    author: String,
+    email: String,
+    files: usize,
+    lines_added: usize,
+    lines_deleted: usize,
+}
+
+/// Serialize per-author churn as a pretty-printed JSON string.
+pub fn format_authors_json(authors: &[AuthorChurn]) -> Result<String, Box<dyn std::error::Error>> {
+    let entries: Vec<JsonAuthor> = authors
+        .iter()
+        .map(|a| JsonAuthor {
+            author: a.author.clone(),
+            email: a.email.clone(),
+            files: a.files,
+            lines_added: a.lines_added,
+            lines_deleted: a.lines_deleted,
+        })
+        .collect();
+
+    report_helpers::json_string(&entries)
+}
+
+/// Serialize per-author churn as pretty-printed JSON to stdout.
+pub fn print_authors_json(authors: &[AuthorChurn]) {
+    println!("{}", format_authors_json(authors).unwrap());
+}
+
+/// Print per-author churn as a single compact line.
+pub fn print_authors_short(authors: &[AuthorChurn]) {
+    let total: usize = authors.iter().map(|a| a.churned_lines()).sum();
+    let top = authors.first().map_or("-", |a| a.author.as_str());
+    println!(
+        "churn authors:{} total_lines:{total} top:{top}",
+        authors.len()
+    );
+}
+
+/// Print only the total churned lines.
+pub fn print_authors_terse(authors: &[AuthorChurn]) {
+    let total: usize = authors.iter().map(|a| a.churned_lines()).sum();
+    println!("{total}");
+}
+
 #[cfg(test)]
 #[path = "report_test.rs"]
 mod tests;
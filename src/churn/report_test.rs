@@ -1,5 +1,5 @@
 use super::*;
-use crate::churn::analyzer::{ChurnLevel, FileChurn, classify};
+use crate::churn::analyzer::{AuthorChurn, ChurnLevel, FileChurn, classify};
 use std::path::PathBuf;
 
 const NOW: i64 = 1_700_000_000;
@@ -58,12 +58,58 @@ fn separator_matches_row_width() {
     let sep_width = col_path + FIXED_WIDTH;
 
     let row = format!(
-        " {:<col_path$}  {:>COL_LANG$} {:>COL_COMMITS$} {:>COL_RATE$} {:>COL_DATE$} {:>COL_LEVEL$}",
-        "File", "Language", "Commits", "Rate/Month", "Last Commit", "Level",
+        " {:<col_path$}  {:>COL_LANG$} {:>COL_COMMITS$} {:>COL_RATE$} {:>COL_LINES$} {:>COL_LINES$} {:>COL_RELATIVE$} {:>COL_DATE$} {:>COL_LEVEL$}",
+        "File",
+        "Language",
+        "Commits",
+        "Rate/Month",
+        "Added",
+        "Deleted",
+        "Relative",
+        "Last Commit",
+        "Level",
     );
     assert_eq!(row.len(), sep_width);
 }
 
+fn sample_authors() -> Vec<AuthorChurn> {
+    vec![
+        AuthorChurn {
+            author: "Jane Doe".to_string(),
+            email: "jane@x.com".to_string(),
+            files: 3,
+            lines_added: 120,
+            lines_deleted: 30,
+        },
+        AuthorChurn {
+            author: "Bob".to_string(),
+            email: "bob@x.com".to_string(),
+            files: 1,
+            lines_added: 40,
+            lines_deleted: 10,
+        },
+    ]
+}
+
+#[test]
+fn authors_json_lists_lines_per_author() {
+    let json: serde_json::Value =
+        serde_json::from_str(&format_authors_json(&sample_authors()).unwrap()).unwrap();
+    assert_eq!(json[0]["author"], "Jane Doe");
+    assert_eq!(json[0]["email"], "jane@x.com");
+    assert_eq!(json[0]["files"], 3);
+    assert_eq!(json[0]["lines_added"], 120);
+    assert_eq!(json[1]["lines_deleted"], 10);
+}
+
+#[test]
+fn print_authors_does_not_panic() {
+    print_authors_report(&sample_authors());
+    print_authors_report(&[]);
+    print_authors_short(&sample_authors());
+    print_authors_terse(&sample_authors());
+}
+
 #[test]
 fn sample_levels_are_correct() {
     let files = sample();
@@ -2,22 +2,31 @@
 ///
 /// Defines all subcommands and their arguments using the `clap` derive macros.
 /// Long help text is stored in `cli_help.rs` to keep this file focused on structure.
+use std::error::Error;
 use std::path::PathBuf;
 
 use clap::{Args, Parser, Subcommand, ValueEnum};
 pub use clap_complete::Shell;
 
 use crate::cli_help;
+use crate::git::CommitFilter;
+use crate::util::parse_until;
 use crate::walk::ExcludeFilter;
 
 /// Error returned by commands that do not support CI annotation formats.
-pub const ERR_CI_FORMAT_ONLY: &str =
-    "--format github and --format codeclimate are only supported by cycom, cogcom, and smells";
+pub const ERR_CI_FORMAT_ONLY: &str = "--format github, codeclimate and sarif are only supported \
+     by cycom, cogcom, smells, dups, deps, hotspots, mi, miv, and knowledge";
+
+/// Error returned by commands that do not support the document formats.
+pub const ERR_REPORT_FORMAT_ONLY: &str = "--format html is only supported by report; \
+     --format markdown by report, score diff, and score --trend";
 
 /// Output format for analysis commands.
 ///
-/// Driven by `--format` on `CommonArgs`. `Github` and `Codeclimate` emit CI
-/// annotations and are only supported by cycom, cogcom, and smells.
+/// Driven by `--format` on `CommonArgs`. `Github`, `Codeclimate` and `Sarif`
+/// emit CI findings and are only supported by findings-producing commands;
+/// `Html` is only supported by `km report`, and `Markdown` by `km report`
+/// (same as `Table`) and the score diff.
 #[derive(Clone, Copy, Default, PartialEq, Eq, Debug, ValueEnum)]
 pub enum OutputMode {
     /// Human-readable table (default)
@@ -35,6 +44,25 @@ pub enum OutputMode {
     /// Also accepted as `--format gitlab` for discoverability.
     #[value(alias = "gitlab")]
     Codeclimate,
+    /// SARIF 2.1.0 log (GitHub code scanning and other SARIF consumers)
+    Sarif,
+    /// Self-contained HTML page (`km report` only)
+    Html,
+    /// GitHub-flavored markdown (`km report` and `km score diff` only)
+    Markdown,
+}
+
+/// Output format of `km history`, which has its own CSV output and none
+/// of the single-line or CI formats.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, ValueEnum)]
+pub enum HistoryFormat {
+    /// Sparklines per metric and one row per sample (default)
+    #[default]
+    Table,
+    /// Machine-readable JSON
+    Json,
+    /// One CSV row per sample, for spreadsheets and plotting
+    Csv,
 }
 
 /// Top-level CLI parser with a single subcommand selector.
@@ -98,17 +126,102 @@ impl ExcludeArgs {
     }
 }
 
+/// Flags controlling the persistent per-file result cache.
+#[derive(Args)]
+pub struct CacheArgs {
+    /// Reuse per-file results from the on-disk cache (default dir: .kimun-cache).
+    /// Entries are keyed by file content, so unchanged files are never re-analyzed.
+    #[arg(long, overrides_with = "no_cache")]
+    pub cache: bool,
+
+    /// Disable the on-disk cache even if enabled in .kimun.toml
+    #[arg(long, overrides_with = "cache")]
+    pub no_cache: bool,
+}
+
+impl CacheArgs {
+    /// The CLI's cache choice, or `None` to defer to `.kimun.toml`.
+    pub fn enabled(&self) -> Option<bool> {
+        match (self.cache, self.no_cache) {
+            (true, _) => Some(true),
+            (_, true) => Some(false),
+            _ => None,
+        }
+    }
+}
+
+/// Commit filters shared by the commands that read git history.
+#[derive(Args, Default)]
+pub struct CommitFilterArgs {
+    /// Only read the commits in this revision range, as in `git log FROM..TO`
+    /// (e.g. v1.2..v1.3). Either side may be left out: v1.2.. reads up to HEAD.
+    #[arg(long, value_name = "FROM..TO", conflicts_with_all = ["from", "to"])]
+    pub range: Option<String>,
+
+    /// Leave out commits reachable from this revision (e.g. the previous release tag)
+    #[arg(long, value_name = "REF")]
+    pub from: Option<String>,
+
+    /// Read history up to this revision instead of HEAD (or --rev)
+    #[arg(long, value_name = "REF")]
+    pub to: Option<String>,
+
+    /// Leave out commits made after this date (YYYY-MM-DD) or this long ago (e.g. 3m).
+    /// Blame-based commands attribute lines as of the last commit before it.
+    #[arg(long, value_name = "DATE")]
+    pub until: Option<String>,
+
+    /// Only read commits whose author name or email contains this (case-insensitive).
+    /// Repeatable: --author alice --author @team.example.com
+    #[arg(long, value_name = "NAME")]
+    pub author: Vec<String>,
+
+    /// Only read changes to these repository-relative files, directories, or globs.
+    /// Repeatable: --path src/api --path "*.sql"
+    #[arg(long = "path", id = "commit_path", value_name = "PATH")]
+    pub paths: Vec<String>,
+}
+
+impl CommitFilterArgs {
+    /// Build a `CommitFilter` from the flags, or `None` when none was given.
+    pub fn commit_filter(&self) -> Result<Option<CommitFilter>, Box<dyn Error>> {
+        if self.range.is_none()
+            && self.from.is_none()
+            && self.to.is_none()
+            && self.until.is_none()
+            && self.author.is_empty()
+            && self.paths.is_empty()
+        {
+            return Ok(None);
+        }
+        let mut filter = CommitFilter::default()
+            .with_revisions(self.from.as_deref(), self.to.as_deref())
+            .with_until(self.until.as_deref().map(parse_until).transpose()?)
+            .with_authors(&self.author)
+            .with_paths(&self.paths)?;
+        if let Some(range) = &self.range {
+            filter = filter.with_range(range)?;
+        }
+        Ok(Some(filter))
+    }
+}
+
 /// Common arguments shared by most analysis commands.
 #[derive(Args)]
 pub struct CommonArgs {
     /// Directory to analyze (default: current directory)
     pub path: Option<PathBuf>,
 
-    /// Output format: table (default), json, short, terse, github, or codeclimate.
+    /// Output format: table (default), json, short, terse, github, codeclimate, sarif, html, or
+    /// markdown.
     /// `short` emits a single compact line of key:value pairs (AI-friendly).
     /// `terse` emits a single headline metric value (for piping/embedding).
-    /// `github` emits GitHub Actions warning annotations (only cycom, cogcom, smells).
-    /// `codeclimate` (alias: `gitlab`) emits CodeClimate JSON for GitLab Code Quality (only cycom, cogcom, smells).
+    /// `github` emits GitHub Actions annotations (cycom, cogcom, smells, dups, deps, hotspots,
+    /// mi, miv, knowledge).
+    /// `codeclimate` (alias: `gitlab`) emits CodeClimate JSON for GitLab Code Quality (same commands).
+    /// `sarif` emits a SARIF 2.1.0 log for code scanning (same commands).
+    /// `html` emits a self-contained HTML page (only report).
+    /// `markdown` emits GitHub-flavored markdown (only report and score --trend).
     #[arg(long, value_enum, default_value_t)]
     pub format: OutputMode,
 
@@ -116,8 +229,22 @@ pub struct CommonArgs {
     #[arg(long)]
     pub include_tests: bool,
 
+    /// Number of worker threads for per-file analysis (default: all cores, 1 = sequential).
+    /// Output order does not depend on this value.
+    #[arg(long, short = 'j', value_name = "N")]
+    pub jobs: Option<usize>,
+
+    #[command(flatten)]
+    pub cache_args: CacheArgs,
+
     #[command(flatten)]
     pub exclude_args: ExcludeArgs,
+
+    /// Analyze the tree of this git revision (commit, branch, or tag), read
+    /// from the object database without touching the working tree.
+    /// Git-history commands read history up to this revision.
+    #[arg(long, value_name = "REF")]
+    pub rev: Option<String>,
 }
 
 impl CommonArgs {
@@ -266,6 +393,11 @@ pub enum Commands {
         /// Sort by metric: mi, volume, complexity, or loc (default: mi)
         #[arg(long, default_value = "mi", value_parser = ["mi", "volume", "complexity", "loc"])]
         sort_by: String,
+
+        /// With --format github/codeclimate, annotate files whose MI is below
+        /// this score (default: 20, i.e. yellow and red files)
+        #[arg(long, default_value = "20")]
+        mi_threshold: f64,
     },
 
     /// Generate a comprehensive report combining all code metrics
@@ -284,6 +416,12 @@ pub enum Commands {
         /// Show all files instead of truncating to top N
         #[arg(long)]
         full: bool,
+
+        /// Add optional sections, comma-separated: smells, deps, hotspots,
+        /// knowledge, tc, age, git (all four history sections), or all.
+        /// Git sections are skipped with a note outside a repository.
+        #[arg(long, value_delimiter = ',', value_parser = ["smells", "deps", "hotspots", "knowledge", "tc", "age", "git", "all"])]
+        with: Vec<String>,
     },
 
     /// Compute Maintainability Index per file (verifysoft variant, with comment weight)
@@ -299,6 +437,11 @@ pub enum Commands {
         /// Sort by metric: mi, volume, complexity, or loc (default: mi)
         #[arg(long, default_value = "mi", value_parser = ["mi", "volume", "complexity", "loc"])]
         sort_by: String,
+
+        /// With --format github/codeclimate, annotate files whose MI is below
+        /// this score (default: 65, i.e. difficult files)
+        #[arg(long, default_value = "65")]
+        mi_threshold: f64,
     },
 
     /// Analyze code churn: pure change frequency per file (git commits only)
@@ -306,12 +449,43 @@ pub enum Commands {
         #[command(flatten)]
         common: CommonArgs,
 
+        #[command(flatten)]
+        commits: CommitFilterArgs,
+
+        /// Show only the top N files (default: 20)
+        #[arg(long, default_value = "20")]
+        top: usize,
+
+        /// Sort by: commits (default), rate (commits/month), lines (added +
+        /// deleted), relative (churned lines / current lines), or file
+        #[arg(long, default_value = "commits", value_parser = ["commits", "rate", "lines", "relative", "file"])]
+        sort_by: String,
+
+        /// Only consider commits since this time (e.g. 6m, 1y, 30d)
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Show lines added and deleted per author instead of per file
+        #[arg(long)]
+        by_author: bool,
+    },
+
+    /// Count bug-fix commits per file, with fix density and cognitive complexity
+    #[command(long_about = cli_help::DEFECTS)]
+    Defects {
+        #[command(flatten)]
+        common: CommonArgs,
+
+        #[command(flatten)]
+        commits: CommitFilterArgs,
+
         /// Show only the top N files (default: 20)
         #[arg(long, default_value = "20")]
         top: usize,
 
-        /// Sort by: commits (default), rate (commits/month), or file
-        #[arg(long, default_value = "commits", value_parser = ["commits", "rate", "file"])]
+        /// Sort by: fixes (default), density (fixes/KLOC), ratio (fix share of
+        /// commits), complexity (cognitive), or file
+        #[arg(long, default_value = "fixes", value_parser = ["fixes", "density", "ratio", "complexity", "file"])]
         sort_by: String,
 
         /// Only consider commits since this time (e.g. 6m, 1y, 30d)
@@ -325,6 +499,9 @@ pub enum Commands {
         #[command(flatten)]
         common: CommonArgs,
 
+        #[command(flatten)]
+        commits: CommitFilterArgs,
+
         /// Show only the top N files (default: 20)
         #[arg(long, default_value = "20")]
         top: usize,
@@ -341,6 +518,11 @@ pub enum Commands {
         /// Overridable via .kimun.toml [hotspots] complexity.
         #[arg(long, value_parser = ["indent", "cycom", "cogcom"])]
         complexity: Option<String>,
+
+        /// Change measure: commits (default), lines (added + deleted), or fixes
+        /// (bug-fix commits). Overridable via .kimun.toml [hotspots] changes.
+        #[arg(long, value_parser = ["commits", "lines", "fixes"])]
+        changes: Option<String>,
     },
 
     /// Analyze code ownership patterns via git blame (knowledge maps)
@@ -349,6 +531,9 @@ pub enum Commands {
         #[command(flatten)]
         common: CommonArgs,
 
+        #[command(flatten)]
+        commits: CommitFilterArgs,
+
         /// Show only the top N files (default: 20)
         #[arg(long, default_value = "20")]
         top: usize,
@@ -374,10 +559,6 @@ pub enum Commands {
         /// means one person holds most knowledge — extremely high risk.
         #[arg(long)]
         bus_factor: bool,
-
-        /// Show only files owned by this author (substring match, case-insensitive)
-        #[arg(long, value_name = "NAME")]
-        author: Option<String>,
     },
 
     /// Analyze temporal coupling: files that change together in commits
@@ -386,6 +567,9 @@ pub enum Commands {
         #[command(flatten)]
         common: CommonArgs,
 
+        #[command(flatten)]
+        commits: CommitFilterArgs,
+
         /// Show only the top N file pairs (default: 20)
         #[arg(long, default_value = "20")]
         top: usize,
@@ -466,6 +650,11 @@ pub enum Commands {
         #[arg(long, num_args = 0..=1, default_missing_value = "HEAD", value_name = "REF")]
         trend: Option<String>,
 
+        /// Number of files to list per change kind (regressed, improved, added,
+        /// removed) in the --trend diff (default: 10)
+        #[arg(long, value_name = "N", default_value = "10")]
+        top_changes: usize,
+
         /// Exit with code 1 if the score is worse than the ref (requires --trend).
         /// Example: --trend origin/main --fail-if-worse
         #[arg(long, requires = "trend")]
@@ -477,6 +666,79 @@ pub enum Commands {
         /// Overridable via .kimun.toml [score] fail_below.
         #[arg(long, value_name = "GRADE", requires = "trend")]
         fail_below: Option<String>,
+
+        /// Score each directory down to DEPTH levels below the path and rank them.
+        /// Example: --by-dir 2
+        #[arg(long, value_name = "DEPTH", conflicts_with_all = ["trend", "by_package"])]
+        by_dir: Option<usize>,
+
+        /// Score each Cargo, npm, or Go package (nearest Cargo.toml, package.json,
+        /// or go.mod) and rank them.
+        #[arg(long, conflicts_with = "trend")]
+        by_package: bool,
+
+        /// Score only the code changed since REF: touched files and functions,
+        /// including uncommitted work. Example: --new-code-since origin/main
+        #[arg(long, value_name = "REF", conflicts_with_all = ["trend", "by_dir", "by_package"])]
+        new_code_since: Option<String>,
+
+        /// Exit with code 1 if the new-code score is below GRADE (requires --new-code-since).
+        /// Overridable via .kimun.toml [score] new_code_fail_below.
+        #[arg(long, value_name = "GRADE", requires = "new_code_since")]
+        new_code_fail_below: Option<String>,
+    },
+
+    /// Estimate technical debt as remediation time, with a debt ratio against development cost
+    #[command(long_about = cli_help::DEBT)]
+    Debt {
+        #[command(flatten)]
+        common: CommonArgs,
+
+        /// Show only the top N files (default: 20)
+        #[arg(long, default_value = "20")]
+        top: usize,
+    },
+
+    /// Sample the code health score along git history and show its trend
+    #[command(long_about = cli_help::HISTORY)]
+    History {
+        /// Directory to analyze (default: current directory)
+        path: Option<PathBuf>,
+
+        /// Output format: table (default, with sparklines), json, or csv
+        #[arg(long, value_enum, default_value_t)]
+        format: HistoryFormat,
+
+        /// Include test files and directories in analysis (excluded by default)
+        #[arg(long)]
+        include_tests: bool,
+
+        /// Number of worker threads for per-file analysis (default: all cores)
+        #[arg(long, short = 'j', value_name = "N")]
+        jobs: Option<usize>,
+
+        #[command(flatten)]
+        cache_args: CacheArgs,
+
+        #[command(flatten)]
+        exclude_args: ExcludeArgs,
+
+        /// Interval between samples (e.g. 1w, 2w, 1m)
+        #[arg(long, default_value = "1m")]
+        every: String,
+
+        /// How far back to sample (e.g. 6m, 1y, 90d)
+        #[arg(long, default_value = "1y")]
+        since: String,
+
+        /// Minimum lines for a duplicate block (default: 6, overridable via .kimun.toml)
+        #[arg(long)]
+        min_lines: Option<usize>,
+
+        /// Scoring model: cogcom (default, v0.14+) or legacy (MI + cyclomatic, v0.13).
+        /// Overridable via .kimun.toml [score] model.
+        #[arg(long, value_parser = ["cogcom", "legacy"])]
+        model: Option<String>,
     },
 
     /// Analyze code age: classify files as active, stale, or frozen by last git modification
@@ -484,6 +746,9 @@ pub enum Commands {
         #[command(flatten)]
         common: CommonArgs,
 
+        #[command(flatten)]
+        commits: CommitFilterArgs,
+
         /// Files modified within this many days are Active (default: 90, overridable via .kimun.toml)
         #[arg(long)]
         active_days: Option<u64>,
@@ -524,6 +789,9 @@ pub enum Commands {
         #[command(flatten)]
         common: CommonArgs,
 
+        #[command(flatten)]
+        commits: CommitFilterArgs,
+
         /// Only consider activity since this time (e.g. 6m, 1y, 30d)
         #[arg(long)]
         since: Option<String>,
@@ -535,6 +803,13 @@ pub enum Commands {
         command: AiCommands,
     },
 
+    /// Run a Model Context Protocol server over stdio exposing every analysis as a tool
+    #[command(long_about = cli_help::MCP)]
+    Mcp {
+        /// Project root the tools analyze (default: current directory)
+        path: Option<PathBuf>,
+    },
+
     /// Analyze the project and generate a calibrated .kimun.toml
     Init {
         /// Directory to analyze (default: current directory)
@@ -552,7 +827,7 @@ pub enum Commands {
     },
 }
 
-/// Score subcommands (diff).
+/// Score subcommands (diff, plan).
 #[derive(Subcommand)]
 pub enum ScoreCommands {
     /// Compare the current code health score against a git ref
@@ -565,7 +840,7 @@ pub enum ScoreCommands {
         /// Directory to analyze (default: current directory)
         path: Option<PathBuf>,
 
-        /// Output format: table (default), json, short, or terse
+        /// Output format: table (default), json, short, terse, or markdown
         #[arg(long, value_enum, default_value_t)]
         format: OutputMode,
 
@@ -573,6 +848,13 @@ pub enum ScoreCommands {
         #[arg(long)]
         include_tests: bool,
 
+        /// Number of worker threads for per-file analysis (default: all cores)
+        #[arg(long, short = 'j', value_name = "N")]
+        jobs: Option<usize>,
+
+        #[command(flatten)]
+        cache_args: CacheArgs,
+
         #[command(flatten)]
         exclude_args: ExcludeArgs,
 
@@ -580,6 +862,57 @@ pub enum ScoreCommands {
         #[arg(long, default_value = "10")]
         bottom: usize,
 
+        /// Number of files to list per change kind (regressed, improved, added,
+        /// removed) (default: 10)
+        #[arg(long, value_name = "N", default_value = "10")]
+        top_changes: usize,
+
+        /// Score this git revision instead of the working tree (compared against --git-ref)
+        #[arg(long, value_name = "REF")]
+        rev: Option<String>,
+
+        /// Minimum lines for a duplicate block (default: 6, overridable via .kimun.toml)
+        #[arg(long)]
+        min_lines: Option<usize>,
+
+        /// Scoring model: cogcom (default, v0.14+) or legacy (MI + cyclomatic, v0.13).
+        /// Overridable via .kimun.toml [score] model.
+        #[arg(long, value_parser = ["cogcom", "legacy"])]
+        model: Option<String>,
+    },
+
+    /// Plan the fewest file refactorings that reach a target grade
+    #[command(long_about = cli_help::SCORE_PLAN)]
+    Plan {
+        /// Grade to reach, e.g. B or A-
+        #[arg(long, value_name = "GRADE")]
+        target: String,
+
+        /// Directory to analyze (default: current directory)
+        path: Option<PathBuf>,
+
+        /// Output format: table (default), json, short, or terse
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputMode,
+
+        /// Include test files and directories in analysis (excluded by default)
+        #[arg(long)]
+        include_tests: bool,
+
+        /// Number of worker threads for per-file analysis (default: all cores)
+        #[arg(long, short = 'j', value_name = "N")]
+        jobs: Option<usize>,
+
+        #[command(flatten)]
+        cache_args: CacheArgs,
+
+        #[command(flatten)]
+        exclude_args: ExcludeArgs,
+
+        /// Analyze the tree of this git revision instead of the working tree
+        #[arg(long, value_name = "REF")]
+        rev: Option<String>,
+
         /// Minimum lines for a duplicate block (default: 6, overridable via .kimun.toml)
         #[arg(long)]
         min_lines: Option<usize>,
@@ -597,16 +930,26 @@ pub enum AiCommands {
     /// Analyze repository using an AI provider
     #[command(long_about = cli_help::AI_ANALYZE)]
     Analyze {
-        /// AI provider to use (e.g. claude)
-        provider: String,
+        /// AI provider: claude, openai, or ollama (default: .kimun.toml [ai] provider, else claude)
+        provider: Option<String>,
 
         /// Directory to analyze (default: current directory)
         path: Option<PathBuf>,
 
-        /// Model to use (default: claude-sonnet-4-5-20250929)
+        /// Model to use (default: provider-specific, overridable via .kimun.toml [ai] model)
         #[arg(long)]
         model: Option<String>,
 
+        /// API base URL, e.g. http://localhost:11434/v1 (default: provider-specific,
+        /// overridable via .kimun.toml [ai] base_url)
+        #[arg(long, value_name = "URL")]
+        base_url: Option<String>,
+
+        /// Environment variable holding the API key (default: provider-specific;
+        /// required to send a key to a non-default endpoint)
+        #[arg(long, value_name = "VAR")]
+        api_key_env: Option<String>,
+
         /// Save the report to a file
         #[arg(short, long)]
         output: Option<PathBuf>,
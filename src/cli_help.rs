@@ -42,7 +42,8 @@ Examples:
   km cogcom --format terse            # average complexity only
   km cogcom --format github           # GitHub Actions annotations (CI)
   km cogcom --format codeclimate      # CodeClimate JSON (GitLab Code Quality)
-  km cogcom --format gitlab           # alias for --format codeclimate";
+  km cogcom --format gitlab           # alias for --format codeclimate
+  km cogcom --format sarif            # SARIF 2.1.0 (code scanning)";
 
 /// Halstead complexity: operator/operand analysis per file.
 /// Shows volume, difficulty, effort, estimated bugs, and development time.
@@ -117,13 +118,16 @@ Based on Adam Thornhill's method (\"Your Code as a Crime Scene\"):
 
 By default, complexity is measured by total indentation (Thornhill's original
 method). Use --complexity cycom for cyclomatic complexity, or --complexity
-cogcom for cognitive complexity (SonarSource) instead.
+cogcom for cognitive complexity (SonarSource) instead. Use --changes lines
+to measure change in lines added + deleted instead of commits, or --changes
+fixes to count only bug-fix commits (see km defects).
 
 Files with high scores are both change-prone and complex \u{2014} they concentrate
 risk and are the highest-value refactoring targets.
 
 Requires a git repository. Use --since to limit the analysis window
-(approximations: 1 month = 30 days, 1 year = 365 days).
+(approximations: 1 month = 30 days, 1 year = 365 days), and --range,
+--until, --author and --path to analyze one release, team, or subtree.
 
 Examples:
   km hotspots                     # indentation complexity (default)
@@ -131,10 +135,43 @@ Examples:
   km hotspots --complexity cogcom # cognitive complexity
   km hotspots --since 6m          # last 6 months
   km hotspots --since 1y --sort-by commits
+  km hotspots --changes lines     # score = churned lines \u{00d7} complexity
+  km hotspots --changes fixes     # score = fix commits \u{00d7} complexity
+  km hotspots --range v1.2..v1.3  # commits of one release only
   km hotspots --format json       # machine-readable output
   km hotspots --format short      # compact: top file + score
   km hotspots --format terse      # top hotspot path only";
 
+/// Defects: bug-fix commits per file, classified by commit message.
+pub const DEFECTS: &str = "\
+Count bug-fix commits per file, next to fix density and cognitive complexity.
+
+A commit is a bug fix when its message matches one of the [defects] patterns
+in .kimun.toml (regular expressions). By default: fix/fixes/fixed/fixing,
+hotfix, words starting with \"bug\", and an issue key such as PROJ-123
+opening the message.
+
+Columns:
+  Fixes       -- bug-fix commits that touched the file
+  Fix %       -- share of the file's commits that were fixes
+  Fixes/KLOC  -- fix commits per 1,000 lines of code
+  Cognitive   -- total cognitive complexity (- when unsupported)
+
+Files that keep needing fixes and are hard to understand are where defects
+are most likely next. Only files with at least one fix are listed.
+
+Requires a git repository. Merge commits are excluded and renamed files are
+followed across history; copies are not (a copy starts its own history).
+
+Examples:
+  km defects                       # most fixed files first
+  km defects --sort-by density     # fixes per 1,000 lines of code
+  km defects --since 6m            # last 6 months only
+  km defects --range v1.2..v1.3    # fixes made for one release
+  km defects --format json         # machine-readable output
+  km defects --format short        # compact: files + fixes + top file
+  km defects --format terse        # most fixed file path only";
+
 /// Knowledge maps: code ownership analysis via git blame.
 /// Identifies bus factor risk and knowledge concentration per file.
 pub const KNOWLEDGE: &str = "\
@@ -151,6 +188,11 @@ Risk levels:
 
 Use --since to detect knowledge loss: files where the primary owner
 has not committed recently. Use --risk-only to show only those files.
+--author does two things here: it shows only files owned by matching
+authors, and, like in the other history commands, it reads only their
+commits, so --since judges recent activity by those commits alone. Blame
+still credits every author's lines. --to or --until attributes lines as of
+an earlier commit.
 
 Requires a git repository. Generated files (lock files, minified JS, etc.)
 are automatically excluded.
@@ -159,6 +201,7 @@ Examples:
   km knowledge                          # ownership by concentration
   km knowledge --sort-by risk           # highest risk first
   km knowledge --since 6m --risk-only   # knowledge loss detection
+  km knowledge --author alice           # files owned by alice
   km knowledge --format json            # machine-readable output
   km knowledge --format short           # compact: files + critical count
   km knowledge --format terse           # critical file count only";
@@ -182,12 +225,15 @@ Levels:
 High coupling between unrelated modules suggests hidden dependencies
 or architectural issues \u{2014} consider extracting shared abstractions.
 
-Requires a git repository. File renames are not tracked across history.
+Requires a git repository. Renamed files are followed across history
+([git] rename_threshold in .kimun.toml, default 50% similarity); copies
+are not, so a copied file starts its own history.
 
 Examples:
   km tc                          # default: min 3 shared commits
   km tc --min-degree 5           # stricter filter
   km tc --since 6m               # last 6 months only
+  km tc --path src/api           # only changes under src/api
   km tc --min-strength 0.5       # only strong coupling
   km tc --format json            # machine-readable output
   km tc --format short           # compact: pair count + max strength
@@ -218,7 +264,8 @@ Examples:
   km smells --format terse         # total smell count only
   km smells --format github        # GitHub Actions annotations (CI)
   km smells --format codeclimate   # CodeClimate JSON (GitLab Code Quality)
-  km smells --format gitlab        # alias for --format codeclimate";
+  km smells --format gitlab        # alias for --format codeclimate
+  km smells --format sarif         # SARIF 2.1.0 (code scanning)";
 
 /// Overall code health score: weighted aggregate of quality dimensions.
 /// Produces a letter grade from A++ (exceptional) to F-- (severe issues).
@@ -267,16 +314,18 @@ Examples:
   km score --format short        # compact: score + grade + dimensions
   km score --format terse        # score number only (e.g. 87.3)
   km score --bottom 20           # show 20 worst files
-  km score --include-tests       # include test files";
+  km score --include-tests       # include test files
+  km score --rev v1.0            # score a tag without checking it out";
 
 /// Score diff: compare current code health against a git ref.
 /// Shows per-dimension deltas with colored output.
 pub const SCORE_DIFF: &str = "\
 Compare the current code health score against a git ref.
 
-Extracts the file tree at the given ref into a temporary directory,
-computes the score for both snapshots, and displays a delta table
-showing how each dimension changed.
+Reads the file tree at the given ref straight from the git object
+database (nothing is extracted or checked out), computes the score for
+both snapshots, and displays a delta table showing how each dimension
+changed.
 
 Useful for checking how uncommitted changes (vs HEAD) or recent
 commits (vs HEAD~N, a branch, or a tag) impact code quality.
@@ -286,6 +335,9 @@ The output shows:
   - Files and LOC change
   - Per-dimension before/after with colored delta
     (green = improvement, red = regression)
+  - Files whose own score changed most, grouped as regressed,
+    improved, added, and removed (--top-changes N per group), with
+    the dimensions that moved
 
 Use --model legacy to compare using the v0.13 scoring model
 (MI + cyclomatic complexity, 6 dimensions).
@@ -298,9 +350,102 @@ Examples:
   km score diff --git-ref main           # compare vs main branch
   km score diff --model legacy           # use v0.13 model
   km score diff --format json            # machine-readable output
+  km score diff --format markdown        # for a PR comment
   km score diff --format short           # compact: before/after/delta
   km score diff --format terse           # delta only (e.g. +2.1)
-  km score diff src/                     # compare a subdirectory";
+  km score diff src/                     # compare a subdirectory
+  km score diff --rev main --git-ref v1.0  # compare two revisions";
+
+pub const SCORE_PLAN: &str = "\
+Plan the fewest file refactorings that reach a target grade.
+
+Simulates lifting one dimension of one file at a time (cognitive
+complexity, indentation, Halstead effort, file size; MI and cyclomatic
+complexity with --model legacy) to a healthy score, largest project
+gain first, until the projected grade reaches --target.
+
+The healthy score is 90 per dimension by default; tune it in
+.kimun.toml:
+
+  [score.healthy]
+  cognitive = 85
+  size      = 95
+
+The output shows each step (file, dimension, current and healthy
+score) with the projected score and grade after it. Duplication is
+project-level and is not simulated, so some targets are out of reach
+by refactoring files alone; the plan says so.
+
+Examples:
+  km score plan --target B               # steps from the current grade to B
+  km score plan --target A- src/         # plan for a subdirectory
+  km score plan --target B --format json # machine-readable steps";
+
+/// Score history: the health score sampled along git history.
+pub const HISTORY: &str = "\
+Sample the code health score along git history.
+
+Walks the first-parent history of HEAD (the commits the branch itself
+went through, merges included, not the commits of merged branches)
+and picks one commit per --every interval back to --since: the newest
+commit at or before each point in time. Each sampled tree is scored
+like km score, giving the score, dimension scores, and lines of code.
+
+Scores are stored in history.json in the cache directory
+(.kimun-cache by default), keyed by commit and analysis settings, so
+later runs only score new samples. --no-cache scores every sample
+again without reading or writing the file.
+
+Intervals and periods take d (days), w (weeks), m (30 days), and
+y (365 days).
+
+Examples:
+  km history                         # monthly samples over the last year
+  km history --every 1w --since 6m   # weekly samples over six months
+  km history src/                    # history of a subdirectory
+  km history --format csv > trend.csv
+  km history --format json           # samples with dimension scores";
+
+/// Technical debt: findings priced in remediation time.
+pub const DEBT: &str = "\
+Estimate technical debt as remediation time.
+
+Prices every finding in minutes of work and sums them per file:
+
+  Smells               per instance, by kind (see km smells)
+  Cognitive complexity per point above 15 in a function
+  Duplication          per duplicated line (later copies of a block)
+  Dependency cycles    per cycle, split across its files
+
+The debt ratio compares the total against the estimated development
+cost of the code, 30 minutes per line of code by default (SQALE).
+Durations are shown in minutes, hours, or 8-hour days.
+
+Costs are configurable in .kimun.toml (defaults shown):
+
+  [debt]
+  cognitive_threshold     = 15
+  cognitive_minutes       = 5
+  duplicated_line_minutes = 2
+  cycle_minutes           = 120
+  loc_minutes             = 30
+  hours_per_day           = 8
+
+  [debt.smells]
+  long-function       = 20
+  long-parameter-list = 15
+  todo-debt           = 10
+  magic-number        = 5
+  commented-out-code  = 5
+
+Smell and duplication thresholds come from [smells] and [dups].
+
+Examples:
+  km debt                        # totals and the 20 files with most debt
+  km debt src/ --top 10          # a subdirectory, top 10 files
+  km debt --format json          # minutes per file and source
+  km debt --format short         # compact: debt + ratio
+  km debt --format terse         # total minutes only";
 
 /// AI-powered repository analysis using an external LLM provider.
 /// The model runs km tools and produces a comprehensive report.
@@ -313,11 +458,23 @@ maintainability issues, and actionable recommendations.
 
 Supported providers:
   claude  \u{2014} Anthropic Claude (requires ANTHROPIC_API_KEY env var)
+  openai  \u{2014} OpenAI or any OpenAI-compatible chat-completions server
+            (OPENAI_API_KEY; optional when --base-url is given)
+  ollama  \u{2014} local Ollama server at http://localhost:11434/v1 (no key needed)
+
+The provider, model and endpoint default to the [ai] section of .kimun.toml;
+CLI arguments take precedence. The key variable is taken from the command
+line only. A provider's default key is sent only to its default endpoint:
+with another endpoint, name the key with --api-key-env.
 
 Examples:
   km ai analyze claude                           # analyze current directory
   km ai analyze claude src/                      # analyze a subdirectory
   km ai analyze claude --model claude-sonnet-4-5-20250929  # use specific model
+  km ai analyze ollama --model qwen2.5-coder     # local model via Ollama
+  km ai analyze openai --base-url http://localhost:8080/v1  # self-hosted server
+  km ai analyze openai --base-url https://gw.example/v1 --api-key-env GW_KEY
+  km ai analyze src/                             # provider from .kimun.toml
   km ai analyze claude --output report.md       # save report to file";
 
 /// Claude Code skill installer for km integration.
@@ -350,3 +507,20 @@ Supported providers:
 
 Examples:
   km ai permissions claude              # add km permissions to project";
+
+/// MCP server over stdio.
+pub const MCP: &str = "\
+Run a Model Context Protocol (MCP) server over stdio.
+
+Exposes every km analysis (loc, score, cycom, cogcom, hotspots, knowledge,
+deps, dups, ...) as a typed MCP tool with a JSON Schema input, so any
+MCP-capable editor or agent can query metrics without parsing CLI output.
+Tools return the same JSON as `km <command> --format json`; their `path`
+arguments are confined to the project root. Settings from .kimun.toml apply.
+
+Register it with a client, e.g. in an mcpServers configuration:
+  { \"command\": \"km\", \"args\": [\"mcp\", \"/path/to/project\"] }
+
+Examples:
+  km mcp                                # serve the current directory
+  km mcp ~/src/project                  # serve another project";
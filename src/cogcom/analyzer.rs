@@ -15,7 +15,7 @@
 //! Levels: Simple (0-4), Moderate (5-9), Complex (10-14),
 //! VeryComplex (15-24), Extreme (>=25).
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::loc::counter::LineKind;
 use crate::util::indent_level as indent_spaces;
@@ -25,7 +25,7 @@ use super::detection::detect_functions;
 use super::markers::CognitiveMarkers;
 
 /// Cognitive complexity level classification based on SonarQube thresholds.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum CognitiveLevel {
     Simple,
@@ -60,7 +60,7 @@ impl CognitiveLevel {
 }
 
 /// Per-function cognitive complexity result with source location.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FunctionCognitive {
     pub name: String,
     /// 1-based line number where the function declaration starts.
@@ -70,7 +70,7 @@ pub struct FunctionCognitive {
 }
 
 /// Aggregate cognitive complexity for an entire file.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileCognitive {
     pub functions: Vec<FunctionCognitive>,
     pub total_complexity: usize,
@@ -12,6 +12,7 @@ use std::cmp::Reverse;
 use std::error::Error;
 use std::path::Path;
 
+use crate::cache::{self, AnalysisCache};
 use crate::cli::OutputMode;
 use crate::loc::counter::LineKind;
 use crate::loc::language::LanguageSpec;
@@ -20,12 +21,15 @@ use crate::walk::WalkConfig;
 use analyzer::analyze;
 use markers::cognitive_markers_for;
 use report::{
-    FileCogcomMetrics, print_codeclimate, print_github, print_json, print_per_function,
-    print_report, print_short, print_terse,
+    print_codeclimate, print_github, print_json, print_per_function, print_report, print_sarif,
+    print_short, print_terse,
 };
 
+pub use analyzer::{CognitiveLevel, FileCognitive, FunctionCognitive};
+pub use report::FileCogcomMetrics;
+
 /// Analyze pre-read content (avoids re-reading the file).
-pub(crate) fn analyze_content(
+pub fn analyze_content(
     lines: &[String],
     kinds: &[LineKind],
     spec: &LanguageSpec,
@@ -35,23 +39,30 @@ pub(crate) fn analyze_content(
 }
 
 /// Read a file from disk, classify lines, and compute cognitive complexity.
-pub(crate) fn analyze_file(
+pub fn analyze_file(
     path: &Path,
     spec: &LanguageSpec,
 ) -> Result<Option<FileCogcomMetrics>, Box<dyn Error>> {
-    let cm = match cognitive_markers_for(spec.name) {
-        Some(m) => m,
-        None => return Ok(None),
-    };
+    analyze_file_cached(path, spec, None)
+}
 
-    let (lines, kinds) = match read_and_classify(path, spec)? {
-        Some(v) => v,
-        None => return Ok(None),
-    };
+/// Like [`analyze_file`], but reuses the per-function results stored in
+/// `cache` when the file content has not changed.
+pub fn analyze_file_cached(
+    path: &Path,
+    spec: &LanguageSpec,
+    cache: Option<&AnalysisCache>,
+) -> Result<Option<FileCogcomMetrics>, Box<dyn Error>> {
+    if cognitive_markers_for(spec.name).is_none() {
+        return Ok(None);
+    }
 
-    let fc = match analyze(&lines, &kinds, cm) {
-        Some(fc) => fc,
-        None => return Ok(None),
+    let fc = cache::cached(cache, "cogcom", path, spec, || {
+        Ok(read_and_classify(path, spec)?
+            .and_then(|(lines, kinds)| analyze_content(&lines, &kinds, spec)))
+    })?;
+    let Some(fc) = fc else {
+        return Ok(None);
     };
 
     Ok(Some(FileCogcomMetrics {
@@ -66,17 +77,15 @@ pub(crate) fn analyze_file(
     }))
 }
 
-/// Walk source files, compute cognitive complexity, filter/sort/truncate
-/// results, and print as a table, per-function breakdown, JSON, or GitHub annotations.
-pub fn run(
+/// Walk source files and compute cognitive complexity, keeping files whose
+/// max complexity reaches `min_complexity`, sorted by `sort_by`
+/// (`total`, `max`, or `avg`, all descending).
+pub fn analyze_project(
     cfg: &WalkConfig<'_>,
-    output: OutputMode,
     min_complexity: usize,
-    top: usize,
-    per_function: bool,
     sort_by: &str,
-) -> Result<(), Box<dyn Error>> {
-    let mut results = cfg.collect_analysis(analyze_file);
+) -> Vec<FileCogcomMetrics> {
+    let mut results = cfg.collect_analysis(|path, spec| analyze_file_cached(path, spec, cfg.cache));
 
     // Filter by min_complexity
     if min_complexity > 1 {
@@ -94,6 +103,21 @@ pub fn run(
         _ => results.sort_by_key(|r| Reverse(r.total_complexity)),
     }
 
+    results
+}
+
+/// Walk source files, compute cognitive complexity, filter/sort/truncate
+/// results, and print as a table, per-function breakdown, JSON, or GitHub annotations.
+pub fn run(
+    cfg: &WalkConfig<'_>,
+    output: OutputMode,
+    min_complexity: usize,
+    top: usize,
+    per_function: bool,
+    sort_by: &str,
+) -> Result<(), Box<dyn Error>> {
+    let mut results = analyze_project(cfg, min_complexity, sort_by);
+
     // Limit to top N
     results.truncate(top);
 
@@ -103,6 +127,10 @@ pub fn run(
         OutputMode::Terse => print_terse(&results),
         OutputMode::Github => print_github(&results, min_complexity),
         OutputMode::Codeclimate => print_codeclimate(&results, min_complexity)?,
+        OutputMode::Sarif => print_sarif(&results, min_complexity)?,
+        OutputMode::Html | OutputMode::Markdown => {
+            return Err(crate::cli::ERR_REPORT_FORMAT_ONLY.into());
+        }
         OutputMode::Table if per_function => print_per_function(&results),
         OutputMode::Table => print_report(&results),
     }
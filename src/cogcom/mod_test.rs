@@ -204,3 +204,29 @@ fn run_terse_format() {
     let cfg = WalkConfig::new(dir.path(), false, &filter);
     run(&cfg, OutputMode::Terse, 0, 20, false, "total").unwrap();
 }
+
+#[test]
+fn analyze_project_sorts_by_total_and_filters_min() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("simple.rs"),
+        "fn simple() {\n    println!(\"hi\");\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("nested.rs"),
+        "fn nested(x: i32) {\n    if x > 0 {\n        for i in 0..x {\n            if i % 2 == 0 {\n                println!(\"{i}\");\n            }\n        }\n    }\n}\n",
+    )
+    .unwrap();
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(dir.path(), false, &filter);
+
+    let all = analyze_project(&cfg, 1, "total");
+    assert_eq!(all.len(), 2);
+    assert!(all[0].path.ends_with("nested.rs"));
+    assert!(all[0].total_complexity >= all[1].total_complexity);
+
+    let complex = analyze_project(&cfg, 2, "total");
+    assert_eq!(complex.len(), 1);
+    assert!(complex[0].path.ends_with("nested.rs"));
+}
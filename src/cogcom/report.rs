@@ -8,6 +8,7 @@ use serde::Serialize;
 
 use super::analyzer::{CognitiveLevel, FunctionCognitive};
 use crate::report_helpers::{self, PerFunctionFile, PerFunctionRow};
+use crate::sarif;
 
 /// Per-file cognitive complexity metrics, including per-function breakdown.
 pub struct FileCogcomMetrics {
@@ -133,8 +134,8 @@ struct JsonFileEntry {
     functions: Vec<JsonFunctionEntry>,
 }
 
-/// Serialize per-file metrics as pretty-printed JSON to stdout.
-pub fn print_json(files: &[FileCogcomMetrics]) -> Result<(), Box<dyn std::error::Error>> {
+/// Serialize per-file metrics as a pretty-printed JSON string.
+pub fn format_json(files: &[FileCogcomMetrics]) -> Result<String, Box<dyn std::error::Error>> {
     let entries: Vec<JsonFileEntry> = files
         .iter()
         .map(|f| JsonFileEntry {
@@ -158,7 +159,13 @@ pub fn print_json(files: &[FileCogcomMetrics]) -> Result<(), Box<dyn std::error:
         })
         .collect();
 
-    report_helpers::print_json_stdout(&entries)
+    report_helpers::json_string(&entries)
+}
+
+/// Serialize per-file metrics as pretty-printed JSON to stdout.
+pub fn print_json(files: &[FileCogcomMetrics]) -> Result<(), Box<dyn std::error::Error>> {
+    println!("{}", format_json(files)?);
+    Ok(())
 }
 
 /// Emit a CodeClimate JSON array (GitLab Code Quality format) for functions
@@ -175,6 +182,56 @@ pub fn print_codeclimate(
     )
 }
 
+/// SARIF rules: one per cognitive complexity level.
+const SARIF_LEVELS: &[sarif::LevelRule] = &[
+    sarif::LevelRule {
+        label: "simple",
+        range: "0-4",
+        level: "note",
+    },
+    sarif::LevelRule {
+        label: "moderate",
+        range: "5-9",
+        level: "note",
+    },
+    sarif::LevelRule {
+        label: "complex",
+        range: "10-14",
+        level: "warning",
+    },
+    sarif::LevelRule {
+        label: "very complex",
+        range: "15-24",
+        level: "warning",
+    },
+    sarif::LevelRule {
+        label: "extreme",
+        range: "25+",
+        level: "error",
+    },
+];
+
+/// Build a SARIF 2.1.0 log with one result per function that meets the
+/// complexity threshold, filed under the rule for its level.
+pub fn format_sarif(
+    files: &[FileCogcomMetrics],
+    min_complexity: usize,
+) -> Result<String, Box<dyn std::error::Error>> {
+    sarif::format_log(
+        &sarif::complexity_rules("cogcom", "cognitive", SARIF_LEVELS),
+        &sarif::complexity_findings(files, min_complexity, "cogcom", "cognitive"),
+    )
+}
+
+/// Print the SARIF log for `format_sarif` to stdout.
+pub fn print_sarif(
+    files: &[FileCogcomMetrics],
+    min_complexity: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    println!("{}", format_sarif(files, min_complexity)?);
+    Ok(())
+}
+
 /// Emit one GitHub Actions warning annotation per function that exceeds
 /// the complexity threshold. Uses `start_line` for precise line linking.
 pub fn print_github(files: &[FileCogcomMetrics], min_complexity: usize) {
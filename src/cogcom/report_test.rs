@@ -83,3 +83,49 @@ fn print_github_multiple_files() {
     ];
     print_github(&files, 5);
 }
+
+#[test]
+fn format_sarif_files_functions_under_level_rules() {
+    let files = vec![
+        make_metrics("./src/a.rs", 3, 3),
+        make_metrics("./src/b.rs", 30, 30),
+    ];
+    let log: serde_json::Value = serde_json::from_str(&format_sarif(&files, 10).unwrap()).unwrap();
+    let run = &log["runs"][0];
+    let rule_ids: Vec<&str> = run["tool"]["driver"]["rules"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        rule_ids,
+        [
+            "cogcom/simple",
+            "cogcom/moderate",
+            "cogcom/complex",
+            "cogcom/very-complex",
+            "cogcom/extreme"
+        ]
+    );
+    let results = run["results"].as_array().unwrap();
+    assert_eq!(
+        results.len(),
+        1,
+        "functions below the threshold are skipped"
+    );
+    assert_eq!(results[0]["ruleId"], "cogcom/extreme");
+    assert_eq!(results[0]["level"], "error");
+    assert_eq!(
+        results[0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+        "src/b.rs"
+    );
+}
+
+#[test]
+fn sarif_levels_match_level_labels() {
+    for c in [0, 5, 10, 15, 25] {
+        let label = CognitiveLevel::from_complexity(c).as_str();
+        assert!(SARIF_LEVELS.iter().any(|l| l.label == label), "{label}");
+    }
+}
@@ -17,6 +17,29 @@
 /// [score]
 /// model      = "cogcom"  # scoring model: cogcom or legacy   (default: cogcom)
 /// fail_below = "B-"      # CI gate: fail if score is below this grade
+/// new_code_fail_below = "B"  # CI gate for --new-code-since
+///
+/// [score.weights]        # cogcom model only; must sum to 1.0
+/// duplication = 0.30
+/// size        = 0.05
+///
+/// [score.curves.duplication]  # [input, score] pairs, inputs ascending
+/// breakpoints = [[2, 100], [5, 70], [15, 20], [30, 0]]
+///
+/// [score.healthy]        # km score plan: score a refactored file reaches
+/// cognitive = 85         # per dimension (default: 90)
+///
+/// [debt]
+/// cognitive_threshold     = 15   # km debt: complexity allowed per function (default: 15)
+/// cognitive_minutes       = 5    # minutes per cognitive point above it      (default: 5)
+/// duplicated_line_minutes = 2    # minutes per duplicated line               (default: 2)
+/// cycle_minutes           = 120  # minutes per dependency cycle              (default: 120)
+/// loc_minutes             = 30   # development minutes per line of code      (default: 30)
+/// hours_per_day           = 8    # hours in a reported day                   (default: 8)
+///
+/// [debt.smells]          # minutes per smell instance
+/// long-function = 30
+/// magic-number  = 2
 ///
 /// [age]
 /// active_days = 90    # days threshold for Active status  (default: 90)
@@ -28,7 +51,34 @@
 ///
 /// [hotspots]
 /// complexity = "indent"  # complexity metric: indent, cycom, cogcom (default: indent)
+/// changes    = "fixes"   # change measure: commits, lines, fixes (default: commits)
+///
+/// [defects]             # commit messages that mark a bug fix (regexes)
+/// patterns = ['(?i)\bfix', '\bJIRA-\d+']
+///
+/// [authors.aliases]     # merge identities on top of .mailmap
+/// "jane@old-company.com" = "Jane Doe <jane@example.com>"
+///
+/// [git]
+/// rename_threshold   = 50  # % similarity to follow a rename; 0 = off (default: 50)
+/// ignore_authors     = ['\[bot\]']               # leave out bot commits (regexes)
+/// ignore_commits     = ".git-blame-ignore-revs"  # SHA list of mass changes to leave out
+/// max_changeset_size = 100  # leave out commits touching more files (default: no cap)
+///
+/// [analysis]
+/// jobs = 8  # worker threads for per-file analysis (default: all cores)
+///
+/// [cache]
+/// enabled = true            # reuse per-file results across runs (default: false)
+/// dir     = ".kimun-cache"  # cache location, relative to the project root
+///
+/// [ai]
+/// provider = "ollama"                     # claude, openai, or ollama (default: claude)
+/// model    = "qwen2.5-coder"              # model name (default: provider-specific)
+/// base_url = "http://localhost:11434/v1"  # API endpoint (default: provider-specific)
 /// ```
+use std::collections::BTreeMap;
+
 use serde::Deserialize;
 
 #[derive(Debug, Default, Deserialize)]
@@ -40,11 +90,25 @@ pub struct KimunConfig {
     #[serde(default)]
     pub score: ScoreConfig,
     #[serde(default)]
+    pub debt: DebtConfig,
+    #[serde(default)]
     pub age: AgeConfig,
     #[serde(default)]
     pub tc: TcConfig,
     #[serde(default)]
     pub hotspots: HotspotsConfig,
+    #[serde(default)]
+    pub authors: AuthorsConfig,
+    #[serde(default)]
+    pub defects: DefectsConfig,
+    #[serde(default)]
+    pub git: GitConfig,
+    #[serde(default)]
+    pub analysis: AnalysisConfig,
+    #[serde(default)]
+    pub cache: CacheConfig,
+    #[serde(default)]
+    pub ai: AiConfig,
 }
 
 /// Threshold overrides for `km smells`.
@@ -105,6 +169,28 @@ pub struct ScoreConfig {
     pub model: Option<String>,
     /// CI gate: fail if score is below this grade. CLI `--fail-below` takes precedence.
     pub fail_below: Option<String>,
+    /// CI gate for `--new-code-since`: fail if the new-code score is below
+    /// this grade. CLI `--new-code-fail-below` takes precedence.
+    pub new_code_fail_below: Option<String>,
+    /// Dimension weight overrides (`[score.weights]`), keyed by dimension:
+    /// `cognitive`, `duplication`, `indent`, `halstead`, `size`.
+    #[serde(default)]
+    pub weights: BTreeMap<String, f64>,
+    /// Normalization curve overrides (`[score.curves.<dimension>]`).
+    #[serde(default)]
+    pub curves: BTreeMap<String, CurveConfig>,
+    /// Healthy profile for `km score plan` (`[score.healthy]`): the score
+    /// 0–100 a refactored file reaches per dimension (default: 90).
+    #[serde(default)]
+    pub healthy: BTreeMap<String, f64>,
+}
+
+/// A replacement normalization curve for one score dimension.
+#[derive(Debug, Default, Deserialize)]
+pub struct CurveConfig {
+    /// `[input, score]` pairs: inputs strictly ascending, scores in 0–100
+    /// and never rising as the input grows.
+    pub breakpoints: Vec<[f64; 2]>,
 }
 
 impl ScoreConfig {
@@ -118,6 +204,33 @@ impl ScoreConfig {
     pub fn resolve_fail_below(&self, cli: Option<String>) -> Option<String> {
         cli.or_else(|| self.fail_below.clone())
     }
+
+    pub fn resolve_new_code_fail_below(&self, cli: Option<String>) -> Option<String> {
+        cli.or_else(|| self.new_code_fail_below.clone())
+    }
+}
+
+/// Remediation costs for `km debt`, in minutes. Unset values use the
+/// defaults of `crate::debt::DebtModel`.
+#[derive(Debug, Default, Deserialize)]
+pub struct DebtConfig {
+    /// Minutes per smell instance (`[debt.smells]`), keyed by smell:
+    /// `long-function`, `long-parameter-list`, `todo-debt`, `magic-number`,
+    /// `commented-out-code`.
+    #[serde(default)]
+    pub smells: BTreeMap<String, f64>,
+    /// Cognitive complexity a function may reach before it accrues debt.
+    pub cognitive_threshold: Option<usize>,
+    /// Minutes per cognitive complexity point above `cognitive_threshold`.
+    pub cognitive_minutes: Option<f64>,
+    /// Minutes per duplicated line.
+    pub duplicated_line_minutes: Option<f64>,
+    /// Minutes per dependency cycle, split across the files in it.
+    pub cycle_minutes: Option<f64>,
+    /// Development minutes per line of code, the base of the debt ratio.
+    pub loc_minutes: Option<f64>,
+    /// Working hours in a day, for reporting debt in days.
+    pub hours_per_day: Option<f64>,
 }
 
 /// Threshold overrides for `km age`.
@@ -171,15 +284,153 @@ pub struct HotspotsConfig {
     /// Complexity metric: `indent` (default), `cycom`, or `cogcom`.
     /// CLI `--complexity` takes precedence.
     pub complexity: Option<String>,
+    /// Change measure: `commits` (default), `lines` (added + deleted), or
+    /// `fixes` (bug-fix commits). CLI `--changes` takes precedence.
+    pub changes: Option<String>,
 }
 
 impl HotspotsConfig {
     pub const DEFAULT_COMPLEXITY: &'static str = "indent";
+    pub const DEFAULT_CHANGES: &'static str = "commits";
 
     pub fn resolve_complexity(&self, cli: Option<String>) -> String {
         cli.or_else(|| self.complexity.clone())
             .unwrap_or_else(|| Self::DEFAULT_COMPLEXITY.to_string())
     }
+
+    pub fn resolve_changes(&self, cli: Option<String>) -> String {
+        cli.or_else(|| self.changes.clone())
+            .unwrap_or_else(|| Self::DEFAULT_CHANGES.to_string())
+    }
+}
+
+/// Author identities for the blame-based commands (`knowledge`, `authors`,
+/// `loc --by-author`), applied on top of the repository's `.mailmap`.
+#[derive(Debug, Default, Deserialize)]
+pub struct AuthorsConfig {
+    /// Identity as committed (`email` or `Name <email>`) → canonical
+    /// identity (`Name <email>`). Parsed by `crate::git::AuthorAliases`.
+    #[serde(default)]
+    pub aliases: BTreeMap<String, String>,
+}
+
+/// Bug-fix commit classification for `km defects` and `km hotspots --changes fixes`.
+#[derive(Debug, Default, Deserialize)]
+pub struct DefectsConfig {
+    /// Regular expressions; a commit whose message matches any of them is a
+    /// fix. Replaces `crate::git::DEFAULT_FIX_PATTERNS` when set.
+    pub patterns: Option<Vec<String>>,
+}
+
+/// History settings for the git-based commands (`churn`, `hotspots`, `tc`,
+/// `age`, `knowledge`, ...). The `ignore_*` settings and the changeset cap
+/// are parsed by `crate::git::CommitExclusions`.
+#[derive(Debug, Default, Deserialize)]
+pub struct GitConfig {
+    /// Minimum similarity, in percent, for a deleted and an added file to
+    /// be followed as a rename. 0 turns rename tracking off.
+    pub rename_threshold: Option<u16>,
+    /// Regular expressions matched against `Name <email>`; commits by a
+    /// matching author (e.g. dependabot) are left out.
+    #[serde(default)]
+    pub ignore_authors: Vec<String>,
+    /// File listing commit SHAs to leave out, one per line, in the format
+    /// of `.git-blame-ignore-revs`. Relative to the project root.
+    pub ignore_commits: Option<String>,
+    /// Commits changing more files than this (formatter sweeps, license
+    /// headers) are left out.
+    pub max_changeset_size: Option<usize>,
+}
+
+impl GitConfig {
+    /// Resolve the rename threshold; values above 100 mean 100.
+    pub fn resolve_rename_threshold(&self) -> u16 {
+        self.rename_threshold
+            .unwrap_or(crate::git::DEFAULT_RENAME_THRESHOLD)
+            .min(100)
+    }
+}
+
+/// Execution settings shared by every command that walks the filesystem.
+#[derive(Debug, Default, Deserialize)]
+pub struct AnalysisConfig {
+    /// Worker threads for per-file analysis; 0 means all available cores.
+    /// CLI `--jobs` takes precedence.
+    pub jobs: Option<usize>,
+}
+
+impl AnalysisConfig {
+    /// Resolve the worker count: CLI flag > config file > available cores.
+    /// A value of 0 from either source also means "all available cores".
+    pub fn resolve_jobs(&self, cli: Option<usize>) -> usize {
+        match cli.or(self.jobs) {
+            Some(0) | None => crate::walk::default_jobs(),
+            Some(n) => n,
+        }
+    }
+}
+
+/// Persistent per-file result cache (see `crate::cache`).
+#[derive(Debug, Default, Deserialize)]
+pub struct CacheConfig {
+    /// Enable the cache. CLI `--cache` / `--no-cache` take precedence.
+    pub enabled: Option<bool>,
+    /// Cache directory; relative paths resolve against the project root.
+    pub dir: Option<String>,
+}
+
+impl CacheConfig {
+    /// Resolve whether caching is on: CLI flag > config file > disabled.
+    pub fn resolve_enabled(&self, cli: Option<bool>) -> bool {
+        cli.or(self.enabled).unwrap_or(false)
+    }
+
+    /// Resolve the cache directory. Relative paths (including the default
+    /// `.kimun-cache`) are anchored at `root`.
+    pub fn resolve_dir(&self, root: &std::path::Path) -> std::path::PathBuf {
+        let dir = self.dir.as_deref().unwrap_or(crate::cache::DEFAULT_DIR);
+        root.join(dir)
+    }
+}
+
+/// Provider settings for `km ai analyze`.
+///
+/// `model` and `base_url` describe the configured `provider`; they are
+/// ignored when the CLI selects a different provider. The API-key variable
+/// is CLI-only (`--api-key-env`), and a provider's default key is never sent
+/// to a non-default endpoint, so a repository's `.kimun.toml` cannot
+/// redirect your credentials.
+#[derive(Debug, Default, Deserialize)]
+pub struct AiConfig {
+    /// Provider: `claude`, `openai`, or `ollama`. The CLI positional takes precedence.
+    pub provider: Option<String>,
+    /// Model name. CLI `--model` takes precedence.
+    pub model: Option<String>,
+    /// API base URL, e.g. `http://localhost:8080/v1`. CLI `--base-url` takes precedence.
+    pub base_url: Option<String>,
+}
+
+impl AiConfig {
+    pub const DEFAULT_PROVIDER: &'static str = "claude";
+
+    pub fn resolve_provider(&self, cli: Option<String>) -> String {
+        cli.or_else(|| self.provider.clone())
+            .unwrap_or_else(|| Self::DEFAULT_PROVIDER.to_string())
+    }
+
+    /// Whether the remaining settings describe `provider`. A config without
+    /// an explicit provider describes the default one.
+    fn applies_to(&self, provider: &str) -> bool {
+        self.provider.as_deref().unwrap_or(Self::DEFAULT_PROVIDER) == provider
+    }
+
+    pub fn resolve_model(&self, provider: &str, cli: Option<String>) -> Option<String> {
+        cli.or_else(|| self.model.clone().filter(|_| self.applies_to(provider)))
+    }
+
+    pub fn resolve_base_url(&self, provider: &str, cli: Option<String>) -> Option<String> {
+        cli.or_else(|| self.base_url.clone().filter(|_| self.applies_to(provider)))
+    }
 }
 
 impl KimunConfig {
@@ -195,6 +446,15 @@ impl KimunConfig {
         toml::from_str(&content).ok()
     }
 
+    /// Directory that project-relative settings resolve against: the git
+    /// repository root when inside one, otherwise the current directory.
+    pub fn project_root() -> std::path::PathBuf {
+        git2::Repository::discover(".")
+            .ok()
+            .and_then(|repo| repo.workdir().map(|w| w.to_path_buf()))
+            .unwrap_or_else(|| std::path::PathBuf::from("."))
+    }
+
     /// Prefer the git repository root; fall back to the current directory.
     fn find() -> Option<std::path::PathBuf> {
         if let Ok(repo) = git2::Repository::discover(".")
@@ -252,6 +512,11 @@ mod tests {
             cfg.hotspots.resolve_complexity(None),
             HotspotsConfig::DEFAULT_COMPLEXITY
         );
+        assert_eq!(
+            cfg.hotspots.resolve_changes(None),
+            HotspotsConfig::DEFAULT_CHANGES
+        );
+        assert!(cfg.defects.patterns.is_none());
     }
 
     #[test]
@@ -282,9 +547,66 @@ mod tests {
 
     #[test]
     fn score_config_is_parsed() {
-        let cfg = parse("[score]\nmodel = \"legacy\"\nfail_below = \"B-\"\n");
+        let cfg = parse(
+            "[score]\nmodel = \"legacy\"\nfail_below = \"B-\"\nnew_code_fail_below = \"B\"\n",
+        );
         assert_eq!(cfg.score.resolve_model(None), "legacy");
         assert_eq!(cfg.score.resolve_fail_below(None).as_deref(), Some("B-"));
+        assert_eq!(
+            cfg.score.resolve_new_code_fail_below(None).as_deref(),
+            Some("B")
+        );
+        assert_eq!(
+            cfg.score
+                .resolve_new_code_fail_below(Some("A".into()))
+                .as_deref(),
+            Some("A")
+        );
+    }
+
+    #[test]
+    fn score_weights_and_curves_are_parsed() {
+        let cfg = parse(
+            "[score.weights]\ncognitive = 0.4\nsize = 0.05\n\n\
+             [score.curves.duplication]\nbreakpoints = [[0, 100], [10, 0]]\n",
+        );
+        assert_eq!(cfg.score.weights.get("cognitive"), Some(&0.4));
+        assert_eq!(cfg.score.weights.get("size"), Some(&0.05));
+        assert_eq!(
+            cfg.score.curves["duplication"].breakpoints,
+            vec![[0.0, 100.0], [10.0, 0.0]]
+        );
+    }
+
+    #[test]
+    fn score_healthy_profile_is_parsed() {
+        let cfg = parse(
+            "[score.healthy]
+cognitive = 85
+size = 95.5
+",
+        );
+        assert_eq!(cfg.score.healthy.get("cognitive"), Some(&85.0));
+        assert_eq!(cfg.score.healthy.get("size"), Some(&95.5));
+        assert!(parse("").score.healthy.is_empty());
+    }
+
+    #[test]
+    fn debt_config_is_parsed() {
+        let cfg = parse(
+            "[debt]
+cognitive_threshold = 10
+cycle_minutes = 60.5
+
+[debt.smells]
+magic-number = 1
+",
+        );
+        assert_eq!(cfg.debt.cognitive_threshold, Some(10));
+        assert_eq!(cfg.debt.cycle_minutes, Some(60.5));
+        assert_eq!(cfg.debt.smells.get("magic-number"), Some(&1.0));
+        assert!(cfg.debt.loc_minutes.is_none());
+        assert!(parse("").debt.smells.is_empty());
     }
 
     #[test]
@@ -303,8 +625,85 @@ mod tests {
 
     #[test]
     fn hotspots_config_is_parsed() {
-        let cfg = parse("[hotspots]\ncomplexity = \"cogcom\"\n");
+        let cfg = parse("[hotspots]\ncomplexity = \"cogcom\"\nchanges = \"fixes\"\n");
         assert_eq!(cfg.hotspots.resolve_complexity(None), "cogcom");
+        assert_eq!(cfg.hotspots.resolve_changes(None), "fixes");
+        assert_eq!(cfg.hotspots.resolve_changes(Some("lines".into())), "lines");
+    }
+
+    #[test]
+    fn defects_patterns_are_parsed() {
+        let cfg = parse("[defects]\npatterns = ['^fix:', '\\bBUG-\\d+']\n");
+        assert_eq!(
+            cfg.defects.patterns.as_deref(),
+            Some(&["^fix:".to_string(), "\\bBUG-\\d+".to_string()][..])
+        );
+    }
+
+    #[test]
+    fn git_exclusions_are_parsed() {
+        let git = KimunConfig::default().git;
+        assert!(git.ignore_authors.is_empty());
+        assert_eq!((git.ignore_commits, git.max_changeset_size), (None, None));
+        let cfg = parse(
+            "[git]\nignore_authors = ['\\[bot\\]']\nignore_commits = \".git-blame-ignore-revs\"\nmax_changeset_size = 40\n",
+        );
+        assert_eq!(cfg.git.ignore_authors, [r"\[bot\]"]);
+        assert_eq!(
+            cfg.git.ignore_commits.as_deref(),
+            Some(".git-blame-ignore-revs")
+        );
+        assert_eq!(cfg.git.max_changeset_size, Some(40));
+    }
+
+    #[test]
+    fn git_rename_threshold_is_parsed() {
+        assert_eq!(
+            KimunConfig::default().git.resolve_rename_threshold(),
+            crate::git::DEFAULT_RENAME_THRESHOLD
+        );
+        let cfg = parse("[git]\nrename_threshold = 80\n");
+        assert_eq!(cfg.git.resolve_rename_threshold(), 80);
+        let cfg = parse("[git]\nrename_threshold = 500\n");
+        assert_eq!(cfg.git.resolve_rename_threshold(), 100);
+    }
+
+    #[test]
+    fn authors_aliases_are_parsed() {
+        let cfg = parse(
+            "[authors.aliases]\n\"jane@old.com\" = \"Jane Doe <jane@example.com>\"\n\
+             \"J <j@laptop>\" = \"Jane Doe <jane@example.com>\"\n",
+        );
+        assert_eq!(cfg.authors.aliases.len(), 2);
+        assert_eq!(
+            cfg.authors.aliases["jane@old.com"],
+            "Jane Doe <jane@example.com>"
+        );
+        assert!(KimunConfig::default().authors.aliases.is_empty());
+    }
+
+    #[test]
+    fn analysis_jobs_is_parsed() {
+        let cfg = parse(
+            "[analysis]
+jobs = 3
+",
+        );
+        assert_eq!(cfg.analysis.resolve_jobs(None), 3);
+    }
+
+    #[test]
+    fn analysis_jobs_zero_means_all_cores() {
+        let cfg = parse(
+            "[analysis]
+jobs = 0
+",
+        );
+        assert_eq!(cfg.analysis.resolve_jobs(None), crate::walk::default_jobs());
+        assert_eq!(
+            KimunConfig::default().analysis.resolve_jobs(None),
+            crate::walk::default_jobs()
+        );
     }
 
     // ── CLI overrides config ─────────────────────────────────────────────────
@@ -357,6 +756,109 @@ mod tests {
         );
     }
 
+    #[test]
+    fn cache_is_disabled_by_default() {
+        let cfg = KimunConfig::default();
+        assert!(!cfg.cache.resolve_enabled(None));
+        assert_eq!(
+            cfg.cache.resolve_dir(std::path::Path::new("/proj")),
+            std::path::Path::new("/proj").join(crate::cache::DEFAULT_DIR)
+        );
+    }
+
+    #[test]
+    fn cache_dir_is_parsed_and_anchored() {
+        let cfg = parse("[cache]\ndir = \"target/km\"\n");
+        assert_eq!(
+            cfg.cache.resolve_dir(std::path::Path::new("/proj")),
+            std::path::Path::new("/proj/target/km")
+        );
+        let cfg = parse("[cache]\ndir = \"/tmp/km\"\n");
+        assert_eq!(
+            cfg.cache.resolve_dir(std::path::Path::new("/proj")),
+            std::path::Path::new("/tmp/km")
+        );
+    }
+
+    #[test]
+    fn cli_overrides_analysis_jobs() {
+        let cfg = parse(
+            "[analysis]
+jobs = 3
+",
+        );
+        assert_eq!(cfg.analysis.resolve_jobs(Some(1)), 1);
+    }
+
+    #[test]
+    fn cli_overrides_cache_enabled() {
+        let cfg = parse("[cache]\nenabled = true\n");
+        assert!(cfg.cache.resolve_enabled(None));
+        assert!(!cfg.cache.resolve_enabled(Some(false)));
+        assert!(KimunConfig::default().cache.resolve_enabled(Some(true)));
+    }
+
+    #[test]
+    fn ai_config_is_parsed() {
+        let cfg = parse(
+            "[ai]\nprovider = \"ollama\"\nmodel = \"qwen2.5-coder\"\nbase_url = \"http://gpu:11434/v1\"\n",
+        );
+        assert_eq!(cfg.ai.resolve_provider(None), "ollama");
+        assert_eq!(
+            cfg.ai.resolve_model("ollama", None).as_deref(),
+            Some("qwen2.5-coder")
+        );
+        assert_eq!(
+            cfg.ai.resolve_base_url("ollama", None).as_deref(),
+            Some("http://gpu:11434/v1")
+        );
+    }
+
+    #[test]
+    fn ai_defaults_to_claude_without_overrides() {
+        let cfg = KimunConfig::default();
+        assert_eq!(cfg.ai.resolve_provider(None), AiConfig::DEFAULT_PROVIDER);
+        assert!(cfg.ai.resolve_model("claude", None).is_none());
+        assert!(cfg.ai.resolve_base_url("claude", None).is_none());
+    }
+
+    #[test]
+    fn ai_settings_ignored_for_other_provider() {
+        let cfg = parse(
+            "[ai]\nprovider = \"ollama\"\nmodel = \"llama3.1\"\nbase_url = \"http://gpu:11434/v1\"\n",
+        );
+        assert_eq!(cfg.ai.resolve_provider(Some("claude".into())), "claude");
+        assert!(cfg.ai.resolve_model("claude", None).is_none());
+        assert!(cfg.ai.resolve_base_url("claude", None).is_none());
+        // Without an explicit provider the settings describe the default.
+        let cfg = parse("[ai]\nmodel = \"claude-opus-4-1\"\n");
+        assert_eq!(
+            cfg.ai.resolve_model("claude", None).as_deref(),
+            Some("claude-opus-4-1")
+        );
+        assert!(cfg.ai.resolve_model("openai", None).is_none());
+    }
+
+    #[test]
+    fn cli_overrides_ai_config() {
+        let cfg = parse(
+            "[ai]\nprovider = \"openai\"\nmodel = \"gpt-4o\"\nbase_url = \"http://a:8080/v1\"\n",
+        );
+        assert_eq!(cfg.ai.resolve_provider(Some("ollama".into())), "ollama");
+        assert_eq!(
+            cfg.ai
+                .resolve_model("openai", Some("gpt-4.1".into()))
+                .as_deref(),
+            Some("gpt-4.1")
+        );
+        assert_eq!(
+            cfg.ai
+                .resolve_base_url("openai", Some("http://b:8080/v1".into()))
+                .as_deref(),
+            Some("http://b:8080/v1")
+        );
+    }
+
     // ── partial config (missing fields use defaults) ─────────────────────────
 
     #[test]
@@ -9,7 +9,7 @@
 //! Levels: Simple (1-5), Moderate (6-10), Complex (11-20),
 //! HighlyComplex (21-50), Extreme (>50).
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::loc::counter::LineKind;
 use crate::util::mask_strings;
@@ -18,7 +18,7 @@ use super::detection::detect_functions;
 use super::markers::ComplexityMarkers;
 
 /// Complexity level classification based on McCabe thresholds.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum CyclomaticLevel {
     Simple,
@@ -53,7 +53,7 @@ impl CyclomaticLevel {
 }
 
 /// Per-function complexity result with source location.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FunctionComplexity {
     pub name: String,
     /// 1-based line number where the function declaration starts.
@@ -64,7 +64,7 @@ pub struct FunctionComplexity {
 
 /// Aggregate complexity for an entire file: per-function breakdown
 /// plus summary statistics (total, max, average).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileComplexity {
     pub functions: Vec<FunctionComplexity>,
     pub total_complexity: usize,
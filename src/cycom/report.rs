@@ -11,6 +11,7 @@ use serde::Serialize;
 
 use super::analyzer::{CyclomaticLevel, FunctionComplexity};
 use crate::report_helpers::{self, PerFunctionFile, PerFunctionRow};
+use crate::sarif;
 
 /// Per-file cyclomatic complexity metrics, including per-function breakdown.
 pub struct FileCycomMetrics {
@@ -155,6 +156,56 @@ pub fn print_codeclimate(
     )
 }
 
+/// SARIF rules: one per cyclomatic complexity level.
+const SARIF_LEVELS: &[sarif::LevelRule] = &[
+    sarif::LevelRule {
+        label: "simple",
+        range: "1-5",
+        level: "note",
+    },
+    sarif::LevelRule {
+        label: "moderate",
+        range: "6-10",
+        level: "note",
+    },
+    sarif::LevelRule {
+        label: "complex",
+        range: "11-20",
+        level: "warning",
+    },
+    sarif::LevelRule {
+        label: "highly complex",
+        range: "21-50",
+        level: "warning",
+    },
+    sarif::LevelRule {
+        label: "extreme",
+        range: "51+",
+        level: "error",
+    },
+];
+
+/// Build a SARIF 2.1.0 log with one result per function that meets the
+/// complexity threshold, filed under the rule for its level.
+pub fn format_sarif(
+    files: &[FileCycomMetrics],
+    min_complexity: usize,
+) -> Result<String, Box<dyn std::error::Error>> {
+    sarif::format_log(
+        &sarif::complexity_rules("cycom", "cyclomatic", SARIF_LEVELS),
+        &sarif::complexity_findings(files, min_complexity, "cycom", "cyclomatic"),
+    )
+}
+
+/// Print the SARIF log for `format_sarif` to stdout.
+pub fn print_sarif(
+    files: &[FileCycomMetrics],
+    min_complexity: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    println!("{}", format_sarif(files, min_complexity)?);
+    Ok(())
+}
+
 /// Emit one GitHub Actions warning annotation per function that exceeds
 /// the complexity threshold. Uses `start_line` for precise line linking.
 pub fn print_github(files: &[FileCycomMetrics], min_complexity: usize) {
@@ -179,8 +230,8 @@ pub fn print_github(files: &[FileCycomMetrics], min_complexity: usize) {
 }
 
 /// Serialize per-file metrics (including per-function detail) as
-/// pretty-printed JSON to stdout.
-pub fn print_json(files: &[FileCycomMetrics]) -> Result<(), Box<dyn std::error::Error>> {
+/// a pretty-printed JSON string.
+pub fn format_json(files: &[FileCycomMetrics]) -> Result<String, Box<dyn std::error::Error>> {
     let entries: Vec<JsonFileEntry> = files
         .iter()
         .map(|f| JsonFileEntry {
@@ -204,7 +255,14 @@ pub fn print_json(files: &[FileCycomMetrics]) -> Result<(), Box<dyn std::error::
         })
         .collect();
 
-    report_helpers::print_json_stdout(&entries)
+    report_helpers::json_string(&entries)
+}
+
+/// Serialize per-file metrics (including per-function detail) as
+/// pretty-printed JSON to stdout.
+pub fn print_json(files: &[FileCycomMetrics]) -> Result<(), Box<dyn std::error::Error>> {
+    println!("{}", format_json(files)?);
+    Ok(())
 }
 
 /// Average complexity across all functions in the result set.
@@ -108,3 +108,33 @@ fn json_structure_is_valid() {
     assert!(arr[0]["functions"].is_array());
     assert_eq!(arr[0]["functions"].as_array().unwrap().len(), 2);
 }
+
+#[test]
+fn format_sarif_uses_level_rules_and_threshold() {
+    let log: serde_json::Value =
+        serde_json::from_str(&format_sarif(&sample_files(), 12).unwrap()).unwrap();
+    let run = &log["runs"][0];
+    assert_eq!(run["tool"]["driver"]["rules"].as_array().unwrap().len(), 5);
+    let results = run["results"].as_array().unwrap();
+    assert!(!results.is_empty());
+    assert_eq!(results[0]["ruleId"], "cycom/highly-complex");
+    assert_eq!(results[0]["level"], "warning");
+    assert_eq!(
+        results[0]["locations"][0]["physicalLocation"]["region"]["startLine"],
+        10
+    );
+    assert!(results.iter().all(|r| {
+        !r["message"]["text"]
+            .as_str()
+            .unwrap()
+            .contains("complexity 1 ")
+    }));
+}
+
+#[test]
+fn sarif_levels_match_level_labels() {
+    for c in [1, 6, 11, 21, 51] {
+        let label = CyclomaticLevel::from_complexity(c).as_str();
+        assert!(SARIF_LEVELS.iter().any(|l| l.label == label), "{label}");
+    }
+}
@@ -0,0 +1,270 @@
+//! Debt model and per-file accounting.
+//!
+//! Every finding is converted to minutes of remediation work with the
+//! costs of a [`DebtModel`]; a [`DebtLedger`] sums them per file and
+//! source and turns the totals into a [`DebtReport`].
+
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::config::DebtConfig;
+use crate::smells::SmellKind;
+
+/// Default minutes per smell instance, in `km smells` column order.
+const DEFAULT_SMELL_MINUTES: [(SmellKind, f64); 5] = [
+    (SmellKind::LongFunction, 20.0),
+    (SmellKind::LongParameterList, 15.0),
+    (SmellKind::TodoDebt, 10.0),
+    (SmellKind::MagicNumber, 5.0),
+    (SmellKind::CommentedOutCode, 5.0),
+];
+
+/// Remediation costs used to turn findings into minutes of work.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DebtModel {
+    /// Minutes per smell instance, keyed by smell slug.
+    pub smells: BTreeMap<&'static str, f64>,
+    /// Cognitive complexity a function may reach before it accrues debt.
+    pub cognitive_threshold: usize,
+    /// Minutes per cognitive point above `cognitive_threshold`.
+    pub cognitive_minutes: f64,
+    /// Minutes per duplicated line.
+    pub duplicated_line_minutes: f64,
+    /// Minutes per dependency cycle, split evenly across its files.
+    pub cycle_minutes: f64,
+    /// Development minutes per line of code (SQALE uses 30).
+    pub loc_minutes: f64,
+    /// Working hours in a reported day.
+    pub hours_per_day: f64,
+}
+
+impl Default for DebtModel {
+    fn default() -> Self {
+        Self {
+            smells: DEFAULT_SMELL_MINUTES
+                .iter()
+                .map(|&(kind, minutes)| (kind.slug(), minutes))
+                .collect(),
+            // SonarQube's default for its cognitive complexity rule.
+            cognitive_threshold: 15,
+            cognitive_minutes: 5.0,
+            duplicated_line_minutes: 2.0,
+            cycle_minutes: 120.0,
+            loc_minutes: 30.0,
+            hours_per_day: 8.0,
+        }
+    }
+}
+
+impl DebtModel {
+    /// The default model with the overrides of `[debt]` applied. Rejects
+    /// unknown smell keys, negative costs, and non-positive rates.
+    pub fn resolve(config: &DebtConfig) -> Result<Self, Box<dyn Error>> {
+        let mut model = Self::default();
+        for (key, &minutes) in &config.smells {
+            let valid: Vec<&str> = model.smells.keys().copied().collect();
+            let Some(slot) = model.smells.get_mut(key.as_str()) else {
+                return Err(format!(
+                    "[debt.smells]: unknown smell '{key}' (expected one of: {})",
+                    valid.join(", ")
+                )
+                .into());
+            };
+            *slot = non_negative(&format!("[debt.smells] {key}"), minutes)?;
+        }
+        if let Some(threshold) = config.cognitive_threshold {
+            model.cognitive_threshold = threshold;
+        }
+        if let Some(v) = config.cognitive_minutes {
+            model.cognitive_minutes = non_negative("[debt] cognitive_minutes", v)?;
+        }
+        if let Some(v) = config.duplicated_line_minutes {
+            model.duplicated_line_minutes = non_negative("[debt] duplicated_line_minutes", v)?;
+        }
+        if let Some(v) = config.cycle_minutes {
+            model.cycle_minutes = non_negative("[debt] cycle_minutes", v)?;
+        }
+        if let Some(v) = config.loc_minutes {
+            model.loc_minutes = positive("[debt] loc_minutes", v)?;
+        }
+        if let Some(v) = config.hours_per_day {
+            model.hours_per_day = positive("[debt] hours_per_day", v)?;
+        }
+        Ok(model)
+    }
+
+    /// Minutes to fix one instance of `kind`.
+    pub fn smell_minutes(&self, kind: SmellKind) -> f64 {
+        self.smells.get(kind.slug()).copied().unwrap_or(0.0)
+    }
+
+    /// Minutes to bring a function of cognitive `complexity` down to the threshold.
+    pub fn cognitive_cost(&self, complexity: usize) -> f64 {
+        complexity.saturating_sub(self.cognitive_threshold) as f64 * self.cognitive_minutes
+    }
+
+    /// Render `minutes` as minutes, hours, or working days.
+    pub fn format_duration(&self, minutes: f64) -> String {
+        let hours = minutes / 60.0;
+        if minutes < 60.0 {
+            format!("{minutes:.0}min")
+        } else if hours < self.hours_per_day {
+            format!("{hours:.1}h")
+        } else {
+            format!("{:.1}d", hours / self.hours_per_day)
+        }
+    }
+}
+
+fn non_negative(key: &str, value: f64) -> Result<f64, Box<dyn Error>> {
+    if value.is_finite() && value >= 0.0 {
+        Ok(value)
+    } else {
+        Err(format!("{key} = {value} must be zero or more").into())
+    }
+}
+
+fn positive(key: &str, value: f64) -> Result<f64, Box<dyn Error>> {
+    if value.is_finite() && value > 0.0 {
+        Ok(value)
+    } else {
+        Err(format!("{key} = {value} must be greater than zero").into())
+    }
+}
+
+/// Where a file's debt comes from, in minutes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct DebtBreakdown {
+    pub smells: f64,
+    pub cognitive: f64,
+    pub duplication: f64,
+    pub cycles: f64,
+}
+
+impl DebtBreakdown {
+    pub fn total(&self) -> f64 {
+        self.smells + self.cognitive + self.duplication + self.cycles
+    }
+
+    fn add(&mut self, other: &Self) {
+        self.smells += other.smells;
+        self.cognitive += other.cognitive;
+        self.duplication += other.duplication;
+        self.cycles += other.cycles;
+    }
+}
+
+/// Debt of one file.
+#[derive(Debug, Clone, Serialize)]
+pub struct FileDebt {
+    /// Path relative to the walk root.
+    pub path: PathBuf,
+    /// Lines of code.
+    pub loc: usize,
+    /// Total remediation minutes.
+    pub minutes: f64,
+    /// Debt as a percentage of the file's development cost.
+    pub ratio: f64,
+    pub breakdown: DebtBreakdown,
+}
+
+/// Project debt: totals, per-file debt, and the model that priced it.
+#[derive(Debug, Clone, Serialize)]
+pub struct DebtReport {
+    /// Total remediation minutes.
+    pub minutes: f64,
+    /// Estimated development cost of the code, in minutes.
+    pub development_minutes: f64,
+    /// Debt as a percentage of the development cost.
+    pub ratio: f64,
+    pub files_analyzed: usize,
+    pub total_loc: usize,
+    pub breakdown: DebtBreakdown,
+    /// Files with debt, largest first.
+    pub files: Vec<FileDebt>,
+    pub model: DebtModel,
+}
+
+/// Remediation minutes accumulated per file, keyed by walk-root-relative path.
+#[derive(Debug, Default)]
+pub struct DebtLedger {
+    files: HashMap<PathBuf, DebtBreakdown>,
+}
+
+impl DebtLedger {
+    fn entry(&mut self, path: &Path) -> &mut DebtBreakdown {
+        self.files.entry(path.to_path_buf()).or_default()
+    }
+
+    pub fn add_smells(&mut self, path: &Path, minutes: f64) {
+        self.entry(path).smells += minutes;
+    }
+
+    pub fn add_cognitive(&mut self, path: &Path, minutes: f64) {
+        self.entry(path).cognitive += minutes;
+    }
+
+    pub fn add_duplication(&mut self, path: &Path, minutes: f64) {
+        self.entry(path).duplication += minutes;
+    }
+
+    pub fn add_cycles(&mut self, path: &Path, minutes: f64) {
+        self.entry(path).cycles += minutes;
+    }
+
+    /// Build the report. `loc` holds the lines of code of every analyzed
+    /// file; it sets the development cost that the debt ratio is taken against.
+    pub fn finish(self, loc: &[(PathBuf, usize)], model: &DebtModel) -> DebtReport {
+        let ratio = |minutes: f64, loc: usize| {
+            if loc == 0 {
+                0.0
+            } else {
+                minutes / (loc as f64 * model.loc_minutes) * 100.0
+            }
+        };
+        let loc_of: HashMap<&Path, usize> = loc.iter().map(|(p, n)| (p.as_path(), *n)).collect();
+
+        let mut breakdown = DebtBreakdown::default();
+        let mut files: Vec<FileDebt> = self
+            .files
+            .into_iter()
+            .filter(|(_, b)| b.total() > 0.0)
+            .map(|(path, b)| {
+                breakdown.add(&b);
+                let loc = loc_of.get(path.as_path()).copied().unwrap_or(0);
+                FileDebt {
+                    minutes: b.total(),
+                    ratio: ratio(b.total(), loc),
+                    loc,
+                    path,
+                    breakdown: b,
+                }
+            })
+            .collect();
+        files.sort_by(|a, b| {
+            b.minutes
+                .total_cmp(&a.minutes)
+                .then_with(|| a.path.cmp(&b.path))
+        });
+
+        let total_loc: usize = loc.iter().map(|(_, n)| n).sum();
+        let minutes = breakdown.total();
+        DebtReport {
+            minutes,
+            development_minutes: total_loc as f64 * model.loc_minutes,
+            ratio: ratio(minutes, total_loc),
+            files_analyzed: loc.len(),
+            total_loc,
+            breakdown,
+            files,
+            model: model.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "analyzer_test.rs"]
+mod tests;
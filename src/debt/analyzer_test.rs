@@ -0,0 +1,98 @@
+use super::*;
+
+fn config(toml: &str) -> DebtConfig {
+    toml::from_str(toml).expect("valid toml")
+}
+
+#[test]
+fn default_model_prices_every_smell() {
+    let model = DebtModel::resolve(&DebtConfig::default()).unwrap();
+    assert_eq!(model, DebtModel::default());
+    for (kind, minutes) in DEFAULT_SMELL_MINUTES {
+        assert_eq!(model.smell_minutes(kind), minutes);
+    }
+}
+
+#[test]
+fn config_overrides_costs() {
+    let model = DebtModel::resolve(&config(
+        "cognitive_threshold = 10\nloc_minutes = 20\n[smells]\nmagic-number = 1\n",
+    ))
+    .unwrap();
+    assert_eq!(model.smell_minutes(SmellKind::MagicNumber), 1.0);
+    assert_eq!(model.smell_minutes(SmellKind::LongFunction), 20.0);
+    assert_eq!(model.cognitive_threshold, 10);
+    assert_eq!(model.loc_minutes, 20.0);
+}
+
+#[test]
+fn config_rejects_bad_entries() {
+    for (toml, expected) in [
+        ("[smells]\ngod-class = 60\n", "unknown smell 'god-class'"),
+        ("[smells]\ntodo-debt = -1\n", "must be zero or more"),
+        ("cycle_minutes = -5\n", "cycle_minutes"),
+        ("loc_minutes = 0\n", "must be greater than zero"),
+        ("hours_per_day = 0\n", "hours_per_day"),
+    ] {
+        let err = DebtModel::resolve(&config(toml)).unwrap_err();
+        assert!(err.to_string().contains(expected), "{toml}: {err}");
+    }
+}
+
+#[test]
+fn cognitive_cost_counts_points_above_threshold() {
+    let model = DebtModel::default();
+    assert_eq!(model.cognitive_cost(3), 0.0);
+    assert_eq!(model.cognitive_cost(15), 0.0);
+    assert_eq!(model.cognitive_cost(18), 15.0);
+}
+
+#[test]
+fn format_duration_picks_unit() {
+    let model = DebtModel::default();
+    assert_eq!(model.format_duration(0.0), "0min");
+    assert_eq!(model.format_duration(45.0), "45min");
+    assert_eq!(model.format_duration(90.0), "1.5h");
+    assert_eq!(model.format_duration(8.0 * 60.0 * 2.5), "2.5d");
+}
+
+#[test]
+fn ledger_totals_files_and_ratio() {
+    let model = DebtModel::default();
+    let mut ledger = DebtLedger::default();
+    ledger.add_smells(Path::new("a.rs"), 30.0);
+    ledger.add_cognitive(Path::new("a.rs"), 10.0);
+    ledger.add_duplication(Path::new("b.rs"), 60.0);
+    ledger.add_cycles(Path::new("c.rs"), 0.0);
+    let loc = vec![
+        (PathBuf::from("a.rs"), 10),
+        (PathBuf::from("b.rs"), 10),
+        (PathBuf::from("c.rs"), 20),
+    ];
+    let report = ledger.finish(&loc, &model);
+
+    assert_eq!(report.minutes, 100.0);
+    assert_eq!(report.total_loc, 40);
+    assert_eq!(report.files_analyzed, 3);
+    assert_eq!(report.development_minutes, 1200.0);
+    assert!((report.ratio - 100.0 / 1200.0 * 100.0).abs() < 1e-9);
+    assert_eq!(report.breakdown.smells, 30.0);
+    assert_eq!(report.breakdown.duplication, 60.0);
+
+    let paths: Vec<&Path> = report.files.iter().map(|f| f.path.as_path()).collect();
+    assert_eq!(
+        paths,
+        [Path::new("b.rs"), Path::new("a.rs")],
+        "c.rs has no debt"
+    );
+    assert_eq!(report.files[1].minutes, 40.0);
+    assert!((report.files[1].ratio - 40.0 / 300.0 * 100.0).abs() < 1e-9);
+}
+
+#[test]
+fn empty_ledger_has_no_ratio() {
+    let report = DebtLedger::default().finish(&[], &DebtModel::default());
+    assert_eq!(report.minutes, 0.0);
+    assert_eq!(report.ratio, 0.0);
+    assert!(report.files.is_empty());
+}
@@ -0,0 +1,131 @@
+/// Technical debt estimate — how much remediation work do the findings represent?
+///
+/// Prices each smell instance, each cognitive complexity point above the
+/// threshold, each duplicated line, and each dependency cycle in minutes,
+/// and compares the total against the development cost of the code
+/// (minutes per line of code) to get a debt ratio.
+pub mod analyzer;
+pub(crate) mod report;
+
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+use crate::cli::OutputMode;
+use crate::walk::WalkConfig;
+use crate::{cogcom, deps, dups, loc, smells};
+
+pub use analyzer::{DebtBreakdown, DebtLedger, DebtModel, DebtReport, FileDebt};
+use report::{print_json, print_report, print_short, print_terse};
+
+/// Thresholds of the analyses whose findings are priced.
+#[derive(Debug, Clone, Copy)]
+pub struct DebtThresholds {
+    /// Smells: maximum function length.
+    pub max_lines: usize,
+    /// Smells: maximum parameter count.
+    pub max_params: usize,
+    /// Duplication: minimum duplicate block size.
+    pub min_dup_lines: usize,
+}
+
+/// Path of `path` relative to the walk root.
+fn relative(root: &Path, path: &Path) -> PathBuf {
+    path.strip_prefix(root).unwrap_or(path).to_path_buf()
+}
+
+/// Run the smell, cognitive complexity, duplication, and dependency
+/// analyses under `cfg` and price their findings with `model`.
+pub fn analyze_project(
+    cfg: &WalkConfig<'_>,
+    model: &DebtModel,
+    thresholds: &DebtThresholds,
+) -> DebtReport {
+    let root = cfg.path;
+    let mut ledger = DebtLedger::default();
+
+    for f in smells::analyze_project(cfg, thresholds.max_lines, thresholds.max_params) {
+        let minutes: f64 = f
+            .smells
+            .smells
+            .iter()
+            .map(|s| model.smell_minutes(s.kind))
+            .sum();
+        ledger.add_smells(&relative(root, &f.path), minutes);
+    }
+
+    for f in cogcom::analyze_project(cfg, 1, "total") {
+        let minutes: f64 = f
+            .functions
+            .iter()
+            .map(|func| model.cognitive_cost(func.complexity))
+            .sum();
+        ledger.add_cognitive(&relative(root, &f.path), minutes);
+    }
+
+    // As in `km score --by-dir`, the first copy of a block is the original:
+    // the later copies carry its duplicated lines.
+    let dups = dups::analyze_project(cfg, thresholds.min_dup_lines, true);
+    for group in &dups.groups {
+        let minutes = group.line_count as f64 * model.duplicated_line_minutes;
+        for location in group.locations.iter().skip(1) {
+            ledger.add_duplication(&relative(root, &location.file_path), minutes);
+        }
+    }
+
+    // Dependency paths are already relative to the walk root.
+    for cycle in deps::analyze_project(cfg, "fan-out").cycles {
+        let share = model.cycle_minutes / cycle.len() as f64;
+        for path in &cycle {
+            ledger.add_cycles(path, share);
+        }
+    }
+
+    let sources = cfg.source_files();
+    let counts = cfg.par_map(&sources, |(path, spec)| loc::count_lines(path, spec));
+    let loc: Vec<(PathBuf, usize)> = sources
+        .iter()
+        .zip(counts)
+        .filter_map(|((path, _), count)| match count {
+            Ok(Some(stats)) => Some((relative(root, path), stats.code)),
+            Ok(None) => None,
+            Err(err) => {
+                eprintln!("warning: {}: {err}", path.display());
+                None
+            }
+        })
+        .collect();
+
+    ledger.finish(&loc, model)
+}
+
+/// Estimate technical debt and print the totals with the `top` files
+/// carrying the most debt.
+pub fn run(
+    cfg: &WalkConfig<'_>,
+    output: OutputMode,
+    top: usize,
+    model: &DebtModel,
+    thresholds: &DebtThresholds,
+) -> Result<(), Box<dyn Error>> {
+    let mut report = analyze_project(cfg, model, thresholds);
+    report.files.truncate(top);
+
+    match output {
+        OutputMode::Json => print_json(&report)?,
+        OutputMode::Short => print_short(&report),
+        OutputMode::Terse => print_terse(&report),
+        OutputMode::Github | OutputMode::Codeclimate | OutputMode::Sarif => {
+            return Err(crate::cli::ERR_CI_FORMAT_ONLY.into());
+        }
+        OutputMode::Html | OutputMode::Markdown => {
+            return Err(crate::cli::ERR_REPORT_FORMAT_ONLY.into());
+        }
+        OutputMode::Table => print_report(&report),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "mod_test.rs"]
+mod tests;
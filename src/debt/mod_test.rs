@@ -0,0 +1,131 @@
+use super::*;
+use crate::walk::ExcludeFilter;
+use std::fs;
+
+const THRESHOLDS: DebtThresholds = DebtThresholds {
+    max_lines: 50,
+    max_params: 4,
+    min_dup_lines: 6,
+};
+
+const NESTED: &str = "\
+fn nested(v: &[i32]) -> i32 {
+    let mut n = 0;
+    for x in v {
+        if *x > 0 {
+            while n < 10 {
+                if n % 2 == 0 {
+                    if n > 4 {
+                        n += 3;
+                    } else {
+                        n += 1;
+                    }
+                } else {
+                    n += 2;
+                }
+            }
+        }
+    }
+    n
+}
+";
+
+fn project(files: &[(&str, &str)]) -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    for (name, content) in files {
+        fs::write(dir.path().join(name), content).unwrap();
+    }
+    dir
+}
+
+fn analyze(root: &Path) -> DebtReport {
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(root, false, &filter);
+    analyze_project(&cfg, &DebtModel::default(), &THRESHOLDS)
+}
+
+#[test]
+fn prices_each_source_per_file() {
+    let dir = project(&[
+        ("a.rs", "mod b;\n// TODO: split\nfn a() {}\n"),
+        ("b.rs", "mod a;\nfn b() {}\n"),
+        ("c.rs", NESTED),
+        ("d.rs", NESTED),
+    ]);
+    let report = analyze(dir.path());
+    let model = DebtModel::default();
+    let file = |name: &str| {
+        report
+            .files
+            .iter()
+            .find(|f| f.path == Path::new(name))
+            .unwrap_or_else(|| panic!("{name} has no debt: {:#?}", report.files))
+    };
+
+    assert_eq!(
+        file("a.rs").breakdown.smells,
+        model.smell_minutes(smells::SmellKind::TodoDebt)
+    );
+    assert_eq!(file("a.rs").breakdown.cycles, model.cycle_minutes / 2.0);
+    assert_eq!(file("b.rs").breakdown.cycles, model.cycle_minutes / 2.0);
+    assert!(file("c.rs").breakdown.cognitive > 0.0);
+    assert_eq!(file("c.rs").breakdown.duplication, 0.0, "first copy");
+    assert!(file("d.rs").breakdown.duplication > 0.0);
+    assert!(file("a.rs").path.is_relative());
+
+    assert_eq!(report.files_analyzed, 4);
+    assert!(report.ratio > 0.0);
+    let sum: f64 = report.files.iter().map(|f| f.minutes).sum();
+    assert!((report.minutes - sum).abs() < 1e-9);
+    assert!(
+        report
+            .files
+            .windows(2)
+            .all(|w| w[0].minutes >= w[1].minutes)
+    );
+}
+
+#[test]
+fn clean_project_has_no_debt() {
+    let dir = project(&[("a.rs", "fn a() -> i32 {\n    1\n}\n")]);
+    let report = analyze(dir.path());
+    assert_eq!(report.minutes, 0.0);
+    assert!(report.files.is_empty());
+    assert_eq!(report.total_loc, 3);
+}
+
+#[test]
+fn run_all_formats() {
+    let dir = project(&[("c.rs", NESTED), ("d.rs", NESTED)]);
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(dir.path(), false, &filter);
+    let model = DebtModel::default();
+    for output in [
+        OutputMode::Table,
+        OutputMode::Json,
+        OutputMode::Short,
+        OutputMode::Terse,
+    ] {
+        run(&cfg, output, 1, &model, &THRESHOLDS).unwrap();
+    }
+    for output in [OutputMode::Github, OutputMode::Markdown] {
+        assert!(run(&cfg, output, 1, &model, &THRESHOLDS).is_err());
+    }
+}
+
+#[test]
+fn json_carries_minutes_and_model() {
+    let dir = project(&[("c.rs", NESTED), ("d.rs", NESTED)]);
+    let report = analyze(dir.path());
+    let json: serde_json::Value =
+        serde_json::from_str(&report::format_json(&report).unwrap()).unwrap();
+    assert_eq!(json["files"][0]["path"], "d.rs");
+    assert!(
+        json["files"][0]["breakdown"]["duplication"]
+            .as_f64()
+            .unwrap()
+            > 0.0
+    );
+    assert_eq!(json["model"]["smells"]["magic-number"], 5.0);
+    assert_eq!(json["model"]["loc_minutes"], 30.0);
+}
@@ -0,0 +1,95 @@
+//! Report formatters for the technical debt estimate.
+//!
+//! Durations are shown in minutes, hours, or working days (see
+//! `DebtModel::format_duration`); JSON carries raw minutes.
+
+use super::analyzer::DebtReport;
+use crate::report_helpers;
+
+/// Print the debt totals and a per-file table, largest debt first.
+pub fn print_report(report: &DebtReport) {
+    let fmt = |minutes: f64| report.model.format_duration(minutes);
+    // A dash for sources that add nothing keeps the rows readable.
+    let cell = |minutes: f64| {
+        if minutes > 0.0 {
+            fmt(minutes)
+        } else {
+            "-".to_string()
+        }
+    };
+    let b = &report.breakdown;
+    let separator = report_helpers::separator(78);
+
+    println!(
+        "Technical Debt: {} ({:.1}% of {} development cost)",
+        fmt(report.minutes),
+        report.ratio,
+        fmt(report.development_minutes),
+    );
+    println!("{separator}");
+    println!(
+        " Smells {}  Cognitive {}  Duplication {}  Cycles {}",
+        fmt(b.smells),
+        fmt(b.cognitive),
+        fmt(b.duplication),
+        fmt(b.cycles),
+    );
+    println!(
+        " {} files, {} lines of code",
+        report.files_analyzed, report.total_loc
+    );
+    println!("{separator}");
+    if report.files.is_empty() {
+        println!(" No debt found.");
+        println!("{separator}");
+        return;
+    }
+
+    println!(
+        " {:>7} {:>6} {:>7} {:>7} {:>7} {:>7} {:>6}  File",
+        "Debt", "Ratio", "Smells", "Cognit", "Dups", "Cycles", "LOC"
+    );
+    println!("{separator}");
+    for f in &report.files {
+        let b = &f.breakdown;
+        println!(
+            " {:>7} {:>5.1}% {:>7} {:>7} {:>7} {:>7} {:>6}  {}",
+            fmt(f.minutes),
+            f.ratio,
+            cell(b.smells),
+            cell(b.cognitive),
+            cell(b.duplication),
+            cell(b.cycles),
+            f.loc,
+            f.path.display(),
+        );
+    }
+    println!("{separator}");
+}
+
+/// Print the debt totals as a single compact line.
+pub fn print_short(report: &DebtReport) {
+    println!(
+        "debt:{} minutes:{:.0} ratio:{:.1}% files:{}",
+        report.model.format_duration(report.minutes),
+        report.minutes,
+        report.ratio,
+        report.files_analyzed,
+    );
+}
+
+/// Print only the total debt in minutes.
+pub fn print_terse(report: &DebtReport) {
+    println!("{:.0}", report.minutes);
+}
+
+/// Serialize the report as a pretty-printed JSON string.
+pub fn format_json(report: &DebtReport) -> Result<String, Box<dyn std::error::Error>> {
+    report_helpers::json_string(report)
+}
+
+/// Serialize the report as pretty-printed JSON to stdout.
+pub fn print_json(report: &DebtReport) -> Result<(), Box<dyn std::error::Error>> {
+    println!("{}", format_json(report)?);
+    Ok(())
+}
@@ -0,0 +1,172 @@
+//! Defect analysis — bug-fix commits per source file.
+//!
+//! Classifies every commit as a bug fix or not by its message (`[defects]
+//! patterns` in `.kimun.toml`, or the defaults in
+//! [`DEFAULT_FIX_PATTERNS`](crate::git::DEFAULT_FIX_PATTERNS)) and counts
+//! the fixes that touched each file. Fix density (fixes per 1,000 lines of
+//! code) is shown next to cognitive complexity: files that keep needing
+//! fixes and are hard to understand are where defects are most likely to
+//! appear next.
+
+pub(crate) mod report;
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::PathBuf;
+
+use crate::cli::OutputMode;
+use crate::git::GitRepo;
+use crate::loc::counter::count_lines;
+use crate::report_helpers::empty_history_note;
+use crate::util::parse_since;
+use crate::walk::{self, WalkConfig};
+use report::{print_json, print_report, print_short, print_terse};
+
+/// Bug-fix history of one source file.
+pub struct FileDefects {
+    /// Repository-relative file path.
+    pub path: PathBuf,
+    pub language: String,
+    /// Non-merge commits that touched the file.
+    pub commits: usize,
+    /// Those commits classified as bug fixes.
+    pub fixes: usize,
+    /// Lines of code in the current file.
+    pub code_lines: usize,
+    /// Total cognitive complexity, for languages that support it.
+    pub complexity: Option<usize>,
+}
+
+impl FileDefects {
+    /// Share of the file's commits that were fixes (0.0–1.0).
+    pub fn fix_ratio(&self) -> f64 {
+        self.fixes as f64 / self.commits.max(1) as f64
+    }
+
+    /// Fix commits per 1,000 lines of code.
+    pub fn density(&self) -> f64 {
+        self.fixes as f64 * 1000.0 / self.code_lines.max(1) as f64
+    }
+}
+
+/// Count bug-fix commits for every source file touched by at least one.
+///
+/// Sorts by `sort_by` ("fixes", "density", "ratio", "complexity", or
+/// "file") and optionally restricts to commits after `since` (e.g. "6m",
+/// "1y"). Returns an empty list when no fix commits are in range.
+pub fn analyze_project(
+    cfg: &WalkConfig<'_>,
+    sort_by: &str,
+    since: Option<&str>,
+) -> Result<Vec<FileDefects>, Box<dyn Error>> {
+    let git = GitRepo::open(cfg.path)
+        .map_err(|e| format!("not a git repository (or any parent): {e}"))?
+        .with_fix_patterns(cfg.fix_patterns)
+        .with_rename_threshold(cfg.rename_threshold)
+        .with_exclusions(cfg.exclusions)
+        .with_commit_filter(cfg.commits)?;
+
+    let since_ts = since.map(parse_since).transpose()?;
+    let freq_map: HashMap<PathBuf, _> = git
+        .file_frequencies(since_ts)?
+        .into_iter()
+        .filter(|f| f.fixes > 0)
+        .map(|f| (f.path.clone(), f))
+        .collect();
+    if freq_map.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let (walk_root, walk_prefix) = git.walk_prefix(cfg.path)?;
+
+    let candidates: Vec<_> = walk::source_files(&walk_root, cfg.exclude_tests(), cfg.filter)
+        .into_iter()
+        .filter_map(|(file_path, spec)| {
+            let rel = GitRepo::to_git_path(&walk_root, &walk_prefix, &file_path);
+            let freq = freq_map.get(&rel)?;
+            Some((file_path, spec, rel, freq.commits, freq.fixes))
+        })
+        .collect();
+
+    // Size and complexity (only for files with fixes) in parallel.
+    let measures = cfg.par_map(&candidates, |(file_path, spec, ..)| {
+        let code_lines = count_lines(file_path, spec)
+            .map_err(|e| e.to_string())?
+            .map_or(0, |s| s.code);
+        let complexity = crate::cogcom::analyze_file_cached(file_path, spec, cfg.cache)
+            .map_err(|e| e.to_string())?
+            .map(|c| c.total_complexity);
+        Ok::<_, String>((code_lines, complexity))
+    });
+
+    let mut files = Vec::new();
+    for ((file_path, spec, path, commits, fixes), measure) in candidates.into_iter().zip(measures) {
+        let (code_lines, complexity) = match measure {
+            Ok(m) => m,
+            Err(err) => {
+                eprintln!("warning: {}: {err}", file_path.display());
+                continue;
+            }
+        };
+        files.push(FileDefects {
+            path,
+            language: spec.name.to_string(),
+            commits,
+            fixes,
+            code_lines,
+            complexity,
+        });
+    }
+
+    match sort_by {
+        "density" => files.sort_by(|a, b| b.density().partial_cmp(&a.density()).unwrap()),
+        "ratio" => files.sort_by(|a, b| b.fix_ratio().partial_cmp(&a.fix_ratio()).unwrap()),
+        "complexity" => files.sort_by_key(|f| Reverse(f.complexity)),
+        "file" => files.sort_by_key(|f| f.path.clone()),
+        _ => files.sort_by_key(|f| Reverse(f.fixes)),
+    }
+
+    Ok(files)
+}
+
+/// Run defect analysis and print results.
+///
+/// Sorts by `sort_by` ("fixes", "density", "ratio", "complexity", or
+/// "file"), truncates to `top`, and optionally restricts to commits after
+/// `since` (e.g. "6m", "1y").
+pub fn run(
+    cfg: &WalkConfig<'_>,
+    output: OutputMode,
+    top: usize,
+    sort_by: &str,
+    since: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+    let mut files = analyze_project(cfg, sort_by, since)?;
+    if files.is_empty()
+        && let Some(note) = empty_history_note(since.is_some(), cfg.commits.is_some())
+    {
+        eprintln!("{note}");
+        return Ok(());
+    }
+    files.truncate(top);
+
+    match output {
+        OutputMode::Json => print_json(&files),
+        OutputMode::Short => print_short(&files),
+        OutputMode::Terse => print_terse(&files),
+        OutputMode::Github | OutputMode::Codeclimate | OutputMode::Sarif => {
+            return Err(crate::cli::ERR_CI_FORMAT_ONLY.into());
+        }
+        OutputMode::Html | OutputMode::Markdown => {
+            return Err(crate::cli::ERR_REPORT_FORMAT_ONLY.into());
+        }
+        OutputMode::Table => print_report(&files),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "mod_test.rs"]
+mod tests;
@@ -0,0 +1,98 @@
+use crate::cli::OutputMode;
+use crate::walk::{ExcludeFilter, WalkConfig};
+use git2::Repository;
+use std::fs;
+use std::path::Path;
+
+fn commit(repo: &Repository, path: &str, content: &str, message: &str) {
+    let sig =
+        git2::Signature::new("Test", "test@test.com", &git2::Time::new(1_700_000_000, 0)).unwrap();
+    let root = repo.workdir().unwrap();
+    fs::write(root.join(path), content).unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new(path)).unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+    repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+        .unwrap();
+}
+
+/// `a.rs`: 3 commits, 2 fixes. `b.rs`: 1 fix. `c.rs`: no fixes.
+fn sample_repo() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    let repo = Repository::init(dir.path()).unwrap();
+    commit(&repo, "a.rs", "fn a() {\n    if x { y(); }\n}\n", "Add a");
+    commit(
+        &repo,
+        "a.rs",
+        "fn a() {\n    if x { z(); }\n}\n",
+        "Fix a crash",
+    );
+    commit(
+        &repo,
+        "a.rs",
+        "fn a() {\n    if x { w(); }\n}\n",
+        "APP-12: wrong w",
+    );
+    commit(&repo, "b.rs", "fn b() {}\n", "bugfix b");
+    commit(&repo, "c.rs", "fn c() {}\n", "Add c fixtures");
+    dir
+}
+
+#[test]
+fn counts_fix_commits_per_file() {
+    let dir = sample_repo();
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(dir.path(), false, &filter);
+    let files = super::analyze_project(&cfg, "fixes", None).unwrap();
+    let rows: Vec<_> = files
+        .iter()
+        .map(|f| (f.path.to_str().unwrap(), f.commits, f.fixes, f.code_lines))
+        .collect();
+    assert_eq!(rows, [("a.rs", 3, 2, 3), ("b.rs", 1, 1, 1)]);
+    assert_eq!(files[0].complexity, Some(1));
+
+    let by_ratio = super::analyze_project(&cfg, "ratio", None).unwrap();
+    assert_eq!(by_ratio[0].path, Path::new("b.rs"));
+}
+
+#[test]
+fn configured_patterns_decide_what_is_a_fix() {
+    let dir = sample_repo();
+    let filter = ExcludeFilter::default();
+    let patterns = crate::git::FixPatterns::resolve(&crate::config::DefectsConfig {
+        patterns: Some(vec!["^Add".to_string()]),
+    })
+    .unwrap();
+    let cfg = WalkConfig::new(dir.path(), false, &filter).with_fix_patterns(Some(&patterns));
+    let files = super::analyze_project(&cfg, "file", None).unwrap();
+    let paths: Vec<_> = files.iter().map(|f| f.path.to_str().unwrap()).collect();
+    assert_eq!(paths, ["a.rs", "c.rs"]);
+}
+
+#[test]
+fn run_formats() {
+    let dir = sample_repo();
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(dir.path(), false, &filter);
+    for output in [
+        OutputMode::Table,
+        OutputMode::Json,
+        OutputMode::Short,
+        OutputMode::Terse,
+    ] {
+        super::run(&cfg, output, 10, "density", None).unwrap();
+    }
+    assert!(super::run(&cfg, OutputMode::Github, 10, "fixes", None).is_err());
+}
+
+#[test]
+fn run_on_non_git_dir() {
+    let dir = tempfile::tempdir().unwrap();
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(dir.path(), false, &filter);
+    let err = super::run(&cfg, OutputMode::Table, 20, "fixes", None).unwrap_err();
+    assert!(err.to_string().contains("not a git repository"), "{err}");
+}
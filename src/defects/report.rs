@@ -0,0 +1,120 @@
+/// Report formatters for defect analysis.
+///
+/// Provides table and JSON output showing per-file bug-fix commits, the
+/// share of commits that were fixes, fix density (fixes per 1,000 lines of
+/// code), and cognitive complexity.
+use serde::Serialize;
+
+use super::FileDefects;
+use crate::report_helpers;
+
+const COL_LANG: usize = 10;
+const COL_COMMITS: usize = 7; // "Commits"
+const COL_FIXES: usize = 5; // "Fixes"
+const COL_RATIO: usize = 6; // "Fix %"
+const COL_DENSITY: usize = 10; // "Fixes/KLOC"
+const COL_COGNITIVE: usize = 9; // "Cognitive"
+// 1 (lead) + 2 (after path) + 1 × 5 (between remaining cols)
+const COL_SPACING: usize = 8;
+const FIXED_WIDTH: usize =
+    COL_SPACING + COL_LANG + COL_COMMITS + COL_FIXES + COL_RATIO + COL_DENSITY + COL_COGNITIVE;
+
+/// Print a table of files with their fix counts and a legend.
+pub fn print_report(files: &[FileDefects]) {
+    if files.is_empty() {
+        println!("No bug-fix commits found in git history.");
+        return;
+    }
+
+    let col_path = report_helpers::max_path_width(files.iter().map(|f| f.path.as_path()), 4);
+    let separator = report_helpers::separator(col_path + FIXED_WIDTH);
+
+    println!("Defects — Bug-Fix Commits by File");
+    println!("{separator}");
+    println!(
+        " {:<col_path$}  {:>COL_LANG$} {:>COL_COMMITS$} {:>COL_FIXES$} {:>COL_RATIO$} {:>COL_DENSITY$} {:>COL_COGNITIVE$}",
+        "File", "Language", "Commits", "Fixes", "Fix %", "Fixes/KLOC", "Cognitive",
+    );
+    println!("{separator}");
+
+    for f in files {
+        let complexity = f
+            .complexity
+            .map_or_else(|| "-".to_string(), |c| c.to_string());
+        println!(
+            " {:<col_path$}  {:>COL_LANG$} {:>COL_COMMITS$} {:>COL_FIXES$} {:>COL_RATIO$.1} {:>COL_DENSITY$.1} {:>COL_COGNITIVE$}",
+            f.path.display(),
+            f.language,
+            f.commits,
+            f.fixes,
+            f.fix_ratio() * 100.0,
+            f.density(),
+            complexity,
+        );
+    }
+
+    println!("{separator}");
+    println!();
+    println!("Fix % = share of the file's commits that were bug fixes.");
+    println!("Fixes/KLOC = bug-fix commits per 1,000 lines of code.");
+    println!("Frequently fixed, complex files are where defects are most likely next.");
+}
+
+/// JSON-serializable representation of a single file's defect data.
+#[derive(Serialize)]
+struct JsonEntry {
+    path: String,
+    language: String,
+    commits: usize,
+    fixes: usize,
+    fix_ratio: f64,
+    code_lines: usize,
+    fixes_per_kloc: f64,
+    cognitive_complexity: Option<usize>,
+}
+
+/// Serialize defect data as a pretty-printed JSON string.
+pub fn format_json(files: &[FileDefects]) -> Result<String, Box<dyn std::error::Error>> {
+    let entries: Vec<JsonEntry> = files
+        .iter()
+        .map(|f| JsonEntry {
+            path: f.path.display().to_string(),
+            language: f.language.clone(),
+            commits: f.commits,
+            fixes: f.fixes,
+            fix_ratio: (f.fix_ratio() * 100.0).round() / 100.0,
+            code_lines: f.code_lines,
+            fixes_per_kloc: (f.density() * 100.0).round() / 100.0,
+            cognitive_complexity: f.complexity,
+        })
+        .collect();
+
+    report_helpers::json_string(&entries)
+}
+
+/// Serialize defect data as pretty-printed JSON to stdout.
+pub fn print_json(files: &[FileDefects]) {
+    println!("{}", format_json(files).unwrap());
+}
+
+/// Print defects as a single compact line.
+pub fn print_short(files: &[FileDefects]) {
+    let fixes: usize = files.iter().map(|f| f.fixes).sum();
+    let top = files
+        .first()
+        .map(|f| f.path.display().to_string())
+        .unwrap_or_else(|| "-".into());
+    println!("defects files:{} fixes:{fixes} top:{top}", files.len());
+}
+
+/// Print only the most frequently fixed file path.
+pub fn print_terse(files: &[FileDefects]) {
+    match files.first() {
+        Some(f) => println!("{}", f.path.display()),
+        None => println!("-"),
+    }
+}
+
+#[cfg(test)]
+#[path = "report_test.rs"]
+mod tests;
@@ -0,0 +1,68 @@
+use super::*;
+use std::path::PathBuf;
+
+fn sample() -> Vec<FileDefects> {
+    vec![
+        FileDefects {
+            path: PathBuf::from("src/parser.rs"),
+            language: "Rust".to_string(),
+            commits: 20,
+            fixes: 8,
+            code_lines: 400,
+            complexity: Some(57),
+        },
+        FileDefects {
+            path: PathBuf::from("config/app.yaml"),
+            language: "YAML".to_string(),
+            commits: 4,
+            fixes: 1,
+            code_lines: 0,
+            complexity: None,
+        },
+    ]
+}
+
+#[test]
+fn ratio_and_density() {
+    let files = sample();
+    assert!((files[0].fix_ratio() - 0.4).abs() < 1e-9);
+    assert!((files[0].density() - 20.0).abs() < 1e-9);
+    assert!(
+        (files[1].density() - 1000.0).abs() < 1e-9,
+        "an empty file counts as one line"
+    );
+}
+
+#[test]
+fn json_has_one_entry_per_file() {
+    let json: serde_json::Value = serde_json::from_str(&format_json(&sample()).unwrap()).unwrap();
+    let arr = json.as_array().unwrap();
+    assert_eq!(arr.len(), 2);
+    assert_eq!(arr[0]["fixes"], 8);
+    assert_eq!(arr[0]["fix_ratio"], 0.4);
+    assert_eq!(arr[0]["fixes_per_kloc"], 20.0);
+    assert_eq!(arr[0]["cognitive_complexity"], 57);
+    assert!(arr[1]["cognitive_complexity"].is_null());
+}
+
+#[test]
+fn separator_matches_row_width() {
+    let files = sample();
+    let col_path = report_helpers::max_path_width(files.iter().map(|f| f.path.as_path()), 4);
+    let row = format!(
+        " {:<col_path$}  {:>COL_LANG$} {:>COL_COMMITS$} {:>COL_FIXES$} {:>COL_RATIO$} {:>COL_DENSITY$} {:>COL_COGNITIVE$}",
+        "File", "Language", "Commits", "Fixes", "Fix %", "Fixes/KLOC", "Cognitive",
+    );
+    assert_eq!(row.len(), col_path + FIXED_WIDTH);
+}
+
+#[test]
+fn printers_do_not_panic() {
+    print_report(&sample());
+    print_report(&[]);
+    print_json(&sample());
+    print_short(&sample());
+    print_short(&[]);
+    print_terse(&sample());
+    print_terse(&[]);
+}
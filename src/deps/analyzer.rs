@@ -353,12 +353,7 @@ mod tests {
     fn edges_map(pairs: &[(&str, &[&str])]) -> HashMap<PathBuf, Vec<PathBuf>> {
         pairs
             .iter()
-            .map(|(k, vs)| {
-                (
-                    PathBuf::from(k),
-                    vs.iter().map(|v| PathBuf::from(v)).collect(),
-                )
-            })
+            .map(|(k, vs)| (PathBuf::from(k), vs.iter().map(PathBuf::from).collect()))
             .collect()
     }
 
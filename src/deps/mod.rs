@@ -7,21 +7,24 @@
 
 mod analyzer;
 mod extractor;
-mod report;
+pub(crate) mod report;
 
 use std::cmp::Reverse;
 use std::collections::{HashMap, HashSet};
 use std::error::Error;
 use std::path::{Path, PathBuf};
 
+use crate::rev;
 use crate::walk::{self, WalkConfig};
 
-use analyzer::{DepEntry, DepResult, build_graph, resolve_import};
+use analyzer::{build_graph, resolve_import};
 use extractor::extract_imports;
 
+pub use analyzer::{DepEntry, DepResult};
+
 /// Try to read the Go module name from `go.mod` in the project root.
 fn detect_go_module(root: &Path) -> Option<String> {
-    let content = std::fs::read_to_string(root.join("go.mod")).ok()?;
+    let content = rev::read_to_string(&root.join("go.mod")).ok()?;
     for line in content.lines() {
         if let Some(rest) = line.strip_prefix("module ") {
             return Some(rest.trim().to_string());
@@ -30,14 +33,10 @@ fn detect_go_module(root: &Path) -> Option<String> {
     None
 }
 
-/// Run dependency graph analysis: walk files, extract imports, build graph, output.
-pub fn run(
-    cfg: &WalkConfig<'_>,
-    output: crate::cli::OutputMode,
-    cycles_only: bool,
-    sort_by: &str,
-    top: usize,
-) -> Result<(), Box<dyn Error>> {
+/// Walk files, extract and resolve imports, and build the dependency graph.
+/// Entries are sorted by `sort_by` (`fan-in`, `fan-out`, or the default
+/// fan-out then fan-in ordering).
+pub fn analyze_project(cfg: &WalkConfig<'_>, sort_by: &str) -> DepResult {
     let go_module = detect_go_module(cfg.path);
 
     // Collect all source files with their language
@@ -53,21 +52,16 @@ pub fn run(
     // Build a set of known project-relative paths for fast lookup during resolution
     let file_set: HashSet<PathBuf> = all_files.iter().map(|(p, _)| p.clone()).collect();
 
-    // For each file, read content and extract + resolve imports
-    let mut edges: HashMap<PathBuf, Vec<PathBuf>> = HashMap::new();
-
-    for (rel_path, language) in &all_files {
+    // For each file, read content and extract + resolve imports.
+    // Unreadable files get an empty import list.
+    let resolved_imports = cfg.par_map(&all_files, |(rel_path, language)| {
         let abs_path = cfg.path.join(rel_path);
-        let source = match std::fs::read_to_string(&abs_path) {
-            Ok(s) => s,
-            Err(_) => {
-                edges.entry(rel_path.clone()).or_default();
-                continue;
-            }
+        let Ok(source) = rev::read_to_string(&abs_path) else {
+            return Vec::new();
         };
 
         let raw_imports = extract_imports(rel_path, language, &source);
-        let resolved: Vec<PathBuf> = raw_imports
+        let mut resolved: Vec<PathBuf> = raw_imports
             .iter()
             .filter_map(|imp| {
                 resolve_import(rel_path, imp, language, &file_set, go_module.as_deref())
@@ -75,17 +69,17 @@ pub fn run(
             .collect();
 
         // Dedup (same file can be imported multiple times)
-        let mut deduped = resolved;
-        deduped.sort();
-        deduped.dedup();
-
-        edges.insert(rel_path.clone(), deduped);
-    }
-
-    // Ensure every file has an entry (even with no imports)
-    for (path, _) in &all_files {
-        edges.entry(path.clone()).or_default();
-    }
+        resolved.sort();
+        resolved.dedup();
+        resolved
+    });
+
+    // Every file gets an entry (even with no imports)
+    let edges: HashMap<PathBuf, Vec<PathBuf>> = all_files
+        .iter()
+        .map(|(path, _)| path.clone())
+        .zip(resolved_imports)
+        .collect();
 
     let mut result = build_graph(&all_files, &edges);
 
@@ -103,6 +97,19 @@ pub fn run(
         }
     }
 
+    result
+}
+
+/// Run dependency graph analysis: walk files, extract imports, build graph, output.
+pub fn run(
+    cfg: &WalkConfig<'_>,
+    output: crate::cli::OutputMode,
+    cycles_only: bool,
+    sort_by: &str,
+    top: usize,
+) -> Result<(), Box<dyn Error>> {
+    let result = analyze_project(cfg, sort_by);
+
     // Filter to cycles-only if requested
     let entries: Vec<&DepEntry> = if cycles_only {
         result.entries.iter().filter(|e| e.in_cycle).collect()
@@ -126,8 +133,15 @@ pub fn run(
             report::print_terse(&result);
             Ok(())
         }
-        crate::cli::OutputMode::Github | crate::cli::OutputMode::Codeclimate => {
-            Err(crate::cli::ERR_CI_FORMAT_ONLY.into())
+        // CI formats flag every file in a cycle, regardless of --top.
+        crate::cli::OutputMode::Github => {
+            report::print_github(&result, cfg.path);
+            Ok(())
+        }
+        crate::cli::OutputMode::Codeclimate => report::print_codeclimate(&result, cfg.path),
+        crate::cli::OutputMode::Sarif => report::print_sarif(&result, cfg.path),
+        crate::cli::OutputMode::Html | crate::cli::OutputMode::Markdown => {
+            Err(crate::cli::ERR_REPORT_FORMAT_ONLY.into())
         }
         crate::cli::OutputMode::Table => {
             let entries_vec: Vec<DepEntry> = entries.into_iter().cloned().collect();
@@ -3,7 +3,10 @@
 /// Provides table and JSON output showing per-file fan-in, fan-out,
 /// coupling classification, and cycle membership. Cycles are printed
 /// separately after the main table.
+use std::path::Path;
+
 use crate::report_helpers;
+use crate::sarif;
 
 use super::analyzer::{DepEntry, DepResult, JsonDepResult};
 
@@ -66,10 +69,16 @@ pub fn print_report(entries: &[DepEntry], result: &DepResult) {
     }
 }
 
+/// Serialize dependency analysis as a pretty-printed JSON string.
+pub fn format_json(result: &DepResult) -> Result<String, Box<dyn std::error::Error>> {
+    let out = JsonDepResult::from(result);
+    report_helpers::json_string(&out)
+}
+
 /// Serialize dependency analysis as pretty-printed JSON to stdout.
 pub fn print_json(result: &DepResult) -> Result<(), Box<dyn std::error::Error>> {
-    let out = JsonDepResult::from(result);
-    report_helpers::print_json_stdout(&out)
+    println!("{}", format_json(result)?);
+    Ok(())
 }
 
 /// Print dependency analysis as a single compact line.
@@ -88,6 +97,67 @@ pub fn print_terse(result: &DepResult) {
     println!("{}", result.cycles.len());
 }
 
+/// CI annotations: one per file participating in a dependency cycle,
+/// naming the other files of its cycle. Cycle paths are relative to
+/// `root`, which is prefixed so annotations match the other commands.
+pub fn annotations(result: &DepResult, root: &Path) -> Vec<report_helpers::Annotation> {
+    let mut out = Vec::new();
+    for cycle in &result.cycles {
+        for (i, path) in cycle.iter().enumerate() {
+            let others: Vec<String> = cycle
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .map(|(_, p)| root.join(p).display().to_string())
+                .collect();
+            out.push(report_helpers::Annotation {
+                level: "warning",
+                severity: "major",
+                file: root.join(path).display().to_string(),
+                line: 1,
+                title: "Dependency Cycle",
+                message: format!(
+                    "file is part of a {}-file dependency cycle with {}",
+                    cycle.len(),
+                    others.join(", ")
+                ),
+            });
+        }
+    }
+    out
+}
+
+/// Emit one GitHub Actions warning annotation per file in a cycle.
+pub fn print_github(result: &DepResult, root: &Path) {
+    report_helpers::print_github_annotations(&annotations(result, root));
+}
+
+/// Emit a CodeClimate JSON array with one entry per file in a cycle.
+pub fn print_codeclimate(
+    result: &DepResult,
+    root: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    report_helpers::print_codeclimate_annotations(&annotations(result, root))
+}
+
+/// The SARIF rule every file in a cycle is filed under.
+fn sarif_rule() -> sarif::Rule {
+    sarif::Rule {
+        id: "deps/dependency-cycle".to_string(),
+        name: "DependencyCycle".to_string(),
+        short_description: "File in a dependency cycle".to_string(),
+        full_description: "The file imports, directly or indirectly, a file that imports it; \
+                           cycles keep modules from being understood, tested or changed alone."
+            .to_string(),
+        level: "warning",
+    }
+}
+
+/// Emit a SARIF 2.1.0 log with one result per file in a cycle.
+pub fn print_sarif(result: &DepResult, root: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    report_helpers::print_sarif_annotations(&sarif_rule(), &annotations(result, root))
+}
+
 #[cfg(test)]
 #[path = "report_test.rs"]
 mod tests;
@@ -136,3 +136,41 @@ fn print_json_with_cycles() {
     let result = make_result(entries, vec![cycle]);
     print_json(&result).unwrap();
 }
+
+// ── CI annotations ──────────────────────────────────────────────────────────
+
+#[test]
+fn annotations_one_per_cycle_member() {
+    let result = make_result(
+        vec![
+            make_entry("a.rs", "Rust", 1, 1, true),
+            make_entry("b.rs", "Rust", 1, 1, true),
+            make_entry("c.rs", "Rust", 0, 0, false),
+        ],
+        vec![vec![PathBuf::from("a.rs"), PathBuf::from("b.rs")]],
+    );
+    let anns = annotations(&result, Path::new("src"));
+    assert_eq!(anns.len(), 2);
+    assert_eq!(
+        anns[0].file,
+        Path::new("src").join("a.rs").display().to_string()
+    );
+    assert_eq!(anns[0].line, 1);
+    assert_eq!(anns[0].level, "warning");
+    assert_eq!(
+        anns[0].message,
+        format!(
+            "file is part of a 2-file dependency cycle with {}",
+            Path::new("src").join("b.rs").display()
+        )
+    );
+    print_github(&result, Path::new("src"));
+    print_codeclimate(&result, Path::new("src")).unwrap();
+    print_sarif(&result, Path::new("src")).unwrap();
+}
+
+#[test]
+fn annotations_empty_without_cycles() {
+    let result = make_result(vec![make_entry("a.rs", "Rust", 0, 0, false)], vec![]);
+    assert!(annotations(&result, Path::new(".")).is_empty());
+}
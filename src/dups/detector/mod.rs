@@ -26,7 +26,7 @@ use std::path::PathBuf;
 use extension::{extend_backward, extend_forward, verify_extended_block};
 use groups::build_group;
 use hashing::{hash_location_set, hash_window};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use validation::validate_hashes;
 
 /// Severity classification based on the Rule of Three.
@@ -39,6 +39,7 @@ pub enum DuplicationSeverity {
 }
 
 /// A normalized code line with its original position and content.
+#[derive(Serialize, Deserialize)]
 pub struct NormalizedLine {
     pub original_line_number: usize, // 1-based
     pub content: String,             // trimmed code text
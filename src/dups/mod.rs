@@ -5,26 +5,29 @@
 //! optionally excluding inline `#[cfg(test)]` blocks. The detection algorithm
 //! uses a sliding-window fingerprint to identify identical code sequences,
 //! then groups them by severity (Critical for 3+ occurrences, Tolerable for 2).
-pub(crate) mod detector;
+pub mod detector;
 pub(crate) mod report;
 
 use std::collections::HashSet;
 use std::error::Error;
 use std::path::Path;
 
-use crate::git::GitRepo;
+use crate::cache::{self, AnalysisCache};
 use crate::loc::counter::LineKind;
 use crate::loc::language::LanguageSpec;
+use crate::rev::RevTree;
 use crate::util::{find_test_block_start, read_and_classify};
 use crate::walk::WalkConfig;
-use detector::{NormalizedFile, NormalizedLine, detect_duplicates};
+use detector::{DuplicateGroup, NormalizedFile, NormalizedLine, detect_duplicates};
 use report::{
-    DuplicationMetrics, display_limit, print_detailed, print_json, print_short, print_summary,
-    print_terse,
+    display_limit, print_codeclimate, print_detailed, print_github, print_json, print_sarif,
+    print_short, print_summary, print_terse,
 };
 
+pub use report::DuplicationMetrics;
+
 /// Normalize pre-read content (avoids re-reading the file).
-pub(crate) fn normalize_content(lines: &[String], kinds: &[LineKind]) -> Vec<NormalizedLine> {
+pub fn normalize_content(lines: &[String], kinds: &[LineKind]) -> Vec<NormalizedLine> {
     lines
         .iter()
         .zip(kinds.iter())
@@ -39,49 +42,83 @@ pub(crate) fn normalize_content(lines: &[String], kinds: &[LineKind]) -> Vec<Nor
 
 /// Read a file, classify its lines, and normalize code lines for duplication.
 /// Returns `None` for binary files. Strips test blocks when `exclude_tests`.
-pub(crate) fn normalize_file(
+pub fn normalize_file(
     path: &Path,
     spec: &LanguageSpec,
     exclude_tests: bool,
 ) -> Result<Option<NormalizedFile>, Box<dyn Error>> {
-    let (lines, kinds) = match read_and_classify(path, spec)? {
-        Some(v) => v,
-        None => return Ok(None),
-    };
+    normalize_file_cached(path, spec, exclude_tests, None)
+}
 
-    // Strip inline #[cfg(test)] blocks when excluding tests (Rust-specific)
-    let end = if exclude_tests {
-        find_test_block_start(&lines)
-    } else {
-        lines.len()
-    };
+/// Like [`normalize_file`], but reuses the normalized lines stored in
+/// `cache` when the file content has not changed.
+pub fn normalize_file_cached(
+    path: &Path,
+    spec: &LanguageSpec,
+    exclude_tests: bool,
+    cache: Option<&AnalysisCache>,
+) -> Result<Option<NormalizedFile>, Box<dyn Error>> {
+    let analyzer = if exclude_tests { "dups" } else { "dups-tests" };
+    let lines = cache::cached(cache, analyzer, path, spec, || {
+        let Some((lines, kinds)) = read_and_classify(path, spec)? else {
+            return Ok(None);
+        };
+
+        // Strip inline #[cfg(test)] blocks when excluding tests (Rust-specific)
+        let end = if exclude_tests {
+            find_test_block_start(&lines)
+        } else {
+            lines.len()
+        };
+        Ok(Some(normalize_content(&lines[..end], &kinds[..end])))
+    })?;
 
-    Ok(Some(NormalizedFile {
+    Ok(lines.map(|lines| NormalizedFile {
         path: path.to_path_buf(),
-        lines: normalize_content(&lines[..end], &kinds[..end]),
+        lines,
     }))
 }
 
-/// Compute duplication metrics for a given walk config without printing anything.
-pub(crate) fn compute_metrics(cfg: &WalkConfig<'_>, min_lines: usize) -> DuplicationMetrics {
+/// Result of a project-wide duplication analysis: summary metrics plus
+/// every duplicate group, sorted Critical first then by duplicated lines.
+pub struct DupsAnalysis {
+    /// Number of source files that were normalized (binary files excluded).
+    pub files_analyzed: usize,
+    pub metrics: DuplicationMetrics,
+    pub groups: Vec<DuplicateGroup>,
+}
+
+/// Walk source files, normalize them, and detect duplicate blocks of at
+/// least `min_lines` lines. `quiet` suppresses the boilerplate-skip notice.
+pub fn analyze_project(cfg: &WalkConfig<'_>, min_lines: usize, quiet: bool) -> DupsAnalysis {
     let exclude_tests = cfg.exclude_tests();
     let mut files: Vec<NormalizedFile> = Vec::new();
     let mut total_code_lines: usize = 0;
 
-    for (file_path, spec) in cfg.source_files() {
-        match normalize_file(&file_path, spec, exclude_tests) {
+    let sources = cfg.source_files();
+    let normalized = cfg.par_map(&sources, |(file_path, spec)| {
+        normalize_file_cached(file_path, spec, exclude_tests, cfg.cache)
+            .map_err(|err| err.to_string())
+    });
+
+    for ((file_path, _), outcome) in sources.iter().zip(normalized) {
+        match outcome {
             Ok(Some(nf)) => {
                 total_code_lines += nf.lines.len();
                 files.push(nf);
             }
-            Ok(None) => {}
+            Ok(None) => {} // binary, skip
             Err(err) => {
                 eprintln!("warning: {}: {err}", file_path.display());
             }
         }
     }
 
-    let groups = detect_duplicates(&files, min_lines, false);
+    let groups = if files.is_empty() {
+        Vec::new()
+    } else {
+        detect_duplicates(&files, min_lines, quiet)
+    };
     let duplicated_lines: usize = groups.iter().map(|g| g.duplicated_lines()).sum();
     let largest_block = groups.iter().map(|g| g.line_count).max().unwrap_or(0);
     let files_with_dups: HashSet<&Path> = groups
@@ -89,15 +126,26 @@ pub(crate) fn compute_metrics(cfg: &WalkConfig<'_>, min_lines: usize) -> Duplica
         .flat_map(|g| g.locations.iter().map(|l| l.file_path.as_path()))
         .collect();
 
-    DuplicationMetrics {
+    let metrics = DuplicationMetrics {
         total_code_lines,
         duplicated_lines,
         duplicate_groups: groups.len(),
         files_with_duplicates: files_with_dups.len(),
         largest_block,
+    };
+
+    DupsAnalysis {
+        files_analyzed: files.len(),
+        metrics,
+        groups,
     }
 }
 
+/// Compute duplication metrics for a given walk config without printing anything.
+pub fn compute_metrics(cfg: &WalkConfig<'_>, min_lines: usize) -> DuplicationMetrics {
+    analyze_project(cfg, min_lines, false).metrics
+}
+
 /// Quality gate options for `km dups`.
 /// All conditions are independent and checked after the report is printed.
 #[derive(Debug, Clone, Default)]
@@ -149,18 +197,10 @@ fn check_quality_gates(
             0.0
         };
 
-        let repo = GitRepo::open(cfg.path)?;
-        let tmpdir = tempfile::tempdir()?;
-        repo.extract_tree_to_dir(git_ref, tmpdir.path())?;
-
-        let (_, prefix) = repo.walk_prefix(cfg.path)?;
-        let tmp_path = if prefix.as_os_str().is_empty() {
-            tmpdir.path().to_path_buf()
-        } else {
-            tmpdir.path().join(&prefix)
-        };
-
-        let ref_cfg = WalkConfig::new(&tmp_path, cfg.include_tests, cfg.filter);
+        let tree = RevTree::load(cfg.path, git_ref)?;
+        let ref_cfg = WalkConfig::new(tree.path(), cfg.include_tests, cfg.filter)
+            .with_jobs(cfg.jobs)
+            .with_tree(Some(&tree));
         let ref_metrics = compute_metrics(&ref_cfg, min_lines);
 
         let before_ratio = if ref_metrics.total_code_lines > 0 {
@@ -201,34 +241,29 @@ pub fn run(
     output: crate::cli::OutputMode,
     gate: DupsGate,
 ) -> Result<(), Box<dyn Error>> {
-    let exclude_tests = cfg.exclude_tests();
-    let mut files: Vec<NormalizedFile> = Vec::new();
-    let mut total_code_lines: usize = 0;
+    let DupsAnalysis {
+        files_analyzed,
+        metrics,
+        groups,
+    } = analyze_project(
+        cfg,
+        min_lines,
+        matches!(
+            output,
+            crate::cli::OutputMode::Json
+                | crate::cli::OutputMode::Sarif
+                | crate::cli::OutputMode::Codeclimate
+        ),
+    );
 
-    for (file_path, spec) in cfg.source_files() {
-        match normalize_file(&file_path, spec, exclude_tests) {
-            Ok(Some(nf)) => {
-                total_code_lines += nf.lines.len();
-                files.push(nf);
-            }
-            Ok(None) => {} // binary, skip
-            Err(err) => {
-                eprintln!("warning: {}: {err}", file_path.display());
-            }
-        }
-    }
-
-    if files.is_empty() {
+    if files_analyzed == 0 {
         match output {
-            crate::cli::OutputMode::Json => {
-                let metrics = DuplicationMetrics {
-                    total_code_lines: 0,
-                    duplicated_lines: 0,
-                    duplicate_groups: 0,
-                    files_with_duplicates: 0,
-                    largest_block: 0,
-                };
-                print_json(&metrics, &[])?;
+            crate::cli::OutputMode::Json => print_json(&metrics, &[])?,
+            crate::cli::OutputMode::Sarif => print_sarif(&[])?,
+            crate::cli::OutputMode::Codeclimate => print_codeclimate(&[])?,
+            crate::cli::OutputMode::Github => {}
+            crate::cli::OutputMode::Html | crate::cli::OutputMode::Markdown => {
+                return Err(crate::cli::ERR_REPORT_FORMAT_ONLY.into());
             }
             _ => {
                 println!("No recognized source files found.");
@@ -237,24 +272,6 @@ pub fn run(
         return Ok(());
     }
 
-    let groups = detect_duplicates(&files, min_lines, output == crate::cli::OutputMode::Json);
-
-    let duplicated_lines: usize = groups.iter().map(|g| g.duplicated_lines()).sum();
-    let largest_block = groups.iter().map(|g| g.line_count).max().unwrap_or(0);
-
-    let files_with_dups: HashSet<&Path> = groups
-        .iter()
-        .flat_map(|g| g.locations.iter().map(|l| l.file_path.as_path()))
-        .collect();
-
-    let metrics = DuplicationMetrics {
-        total_code_lines,
-        duplicated_lines,
-        duplicate_groups: groups.len(),
-        files_with_duplicates: files_with_dups.len(),
-        largest_block,
-    };
-
     // Always print first so CI logs show the full report before any gate error.
     match output {
         crate::cli::OutputMode::Json => {
@@ -263,8 +280,12 @@ pub fn run(
         }
         crate::cli::OutputMode::Short => print_short(&metrics),
         crate::cli::OutputMode::Terse => print_terse(&metrics),
-        crate::cli::OutputMode::Github | crate::cli::OutputMode::Codeclimate => {
-            return Err(crate::cli::ERR_CI_FORMAT_ONLY.into());
+        // CI formats carry every group: reviewers and code scanning need the full set.
+        crate::cli::OutputMode::Sarif => print_sarif(&groups)?,
+        crate::cli::OutputMode::Github => print_github(&groups),
+        crate::cli::OutputMode::Codeclimate => print_codeclimate(&groups)?,
+        crate::cli::OutputMode::Html | crate::cli::OutputMode::Markdown => {
+            return Err(crate::cli::ERR_REPORT_FORMAT_ONLY.into());
         }
         crate::cli::OutputMode::Table => {
             if show_report {
@@ -658,3 +658,43 @@ fn run_terse_format() {
     let cfg = WalkConfig::new(dir.path(), false, &filter);
     run(&cfg, 6, false, false, OutputMode::Terse, gate()).unwrap();
 }
+
+#[test]
+fn analyze_project_returns_groups_and_metrics() {
+    let dir = tempfile::tempdir().unwrap();
+    let code = "fn process() {\n    let x = read();\n    let y = transform(x);\n    write(y);\n    log(\"done\");\n    cleanup();\n}\n";
+    fs::write(dir.path().join("a.rs"), code).unwrap();
+    fs::write(dir.path().join("b.rs"), code).unwrap();
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(dir.path(), false, &filter);
+
+    let analysis = analyze_project(&cfg, 6, true);
+    assert_eq!(analysis.files_analyzed, 2);
+    assert_eq!(analysis.groups.len(), 1);
+    assert_eq!(analysis.groups[0].locations.len(), 2);
+    assert_eq!(analysis.metrics.duplicate_groups, 1);
+    assert_eq!(analysis.metrics.files_with_duplicates, 2);
+}
+
+#[test]
+fn analyze_project_on_empty_dir() {
+    let dir = tempfile::tempdir().unwrap();
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(dir.path(), false, &filter);
+    let analysis = analyze_project(&cfg, 6, true);
+    assert_eq!(analysis.files_analyzed, 0);
+    assert!(analysis.groups.is_empty());
+    assert_eq!(analysis.metrics.total_code_lines, 0);
+}
+
+#[test]
+fn run_supports_ci_formats() {
+    let dir = tempfile::tempdir().unwrap();
+    let code = "fn process() {\n    let x = read();\n    let y = transform(x);\n    write(y);\n    log(\"done\");\n    cleanup();\n}\n";
+    fs::write(dir.path().join("a.rs"), code).unwrap();
+    fs::write(dir.path().join("b.rs"), code).unwrap();
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(dir.path(), false, &filter);
+    run(&cfg, 6, false, false, OutputMode::Github, gate()).unwrap();
+    run(&cfg, 6, false, false, OutputMode::Codeclimate, gate()).unwrap();
+}
@@ -6,6 +6,8 @@
 //! - **Detailed**: summary plus a listing of each duplicate group with
 //!   file locations, severity label, and a code sample (up to 5 lines).
 //! - **JSON**: machine-readable output combining metrics and group data.
+//! - **SARIF**: one result per group, located at its first occurrence with
+//!   the other occurrences as related locations.
 //!
 //! The Rule of Three analysis classifies duplicates as **Critical** (3+
 //! occurrences, indicating a pattern that should be extracted into a shared
@@ -15,6 +17,7 @@ use serde::Serialize;
 
 use super::detector::{DuplicateGroup, DuplicationSeverity};
 use crate::report_helpers;
+use crate::sarif;
 
 /// Counts of groups and duplicated lines split by severity level.
 /// Computed in a single pass over the duplicate groups.
@@ -275,6 +278,135 @@ pub fn print_json(
     Ok(())
 }
 
+/// SARIF rule id for a group's Rule of Three severity.
+fn sarif_rule_id(severity: DuplicationSeverity) -> &'static str {
+    match severity {
+        DuplicationSeverity::Critical => "dups/critical",
+        DuplicationSeverity::Tolerable => "dups/tolerable",
+    }
+}
+
+/// SARIF rules: one per Rule of Three severity.
+fn sarif_rules() -> Vec<sarif::Rule> {
+    vec![
+        sarif::Rule {
+            id: sarif_rule_id(DuplicationSeverity::Critical).to_string(),
+            name: "CriticalDuplication".to_string(),
+            short_description: "Code block duplicated in 3 or more places".to_string(),
+            full_description: "The same block appears three or more times (Rule of Three); \
+                               extract it into a shared function or module."
+                .to_string(),
+            level: "warning",
+        },
+        sarif::Rule {
+            id: sarif_rule_id(DuplicationSeverity::Tolerable).to_string(),
+            name: "TolerableDuplication".to_string(),
+            short_description: "Code block duplicated in 2 places".to_string(),
+            full_description: "The same block appears twice. Often acceptable, \
+                               but a third copy should trigger a refactor."
+                .to_string(),
+            level: "note",
+        },
+    ]
+}
+
+/// Build the SARIF finding for one duplicate group.
+fn sarif_finding(group: &DuplicateGroup) -> sarif::Finding {
+    let region = |loc: &super::detector::DuplicateLocation| sarif::Region {
+        path: loc.file_path.display().to_string(),
+        start_line: loc.start_line,
+        end_line: Some(loc.end_line),
+    };
+    let first = &group.locations[0];
+    let others: Vec<String> = group.locations[1..]
+        .iter()
+        .map(|l| {
+            let uri = sarif::artifact_uri(&l.file_path.display().to_string());
+            format!("{uri}:{}-{}", l.start_line, l.end_line)
+        })
+        .collect();
+    let total = group.locations.len();
+    sarif::Finding {
+        rule_id: sarif_rule_id(group.severity).to_string(),
+        message: format!(
+            "{}-line block duplicated in {total} locations; also at {}",
+            group.line_count,
+            others.join(", ")
+        ),
+        location: region(first),
+        related: group.locations[1..]
+            .iter()
+            .enumerate()
+            .map(|(i, loc)| (region(loc), format!("duplicate {} of {total}", i + 2)))
+            .collect(),
+        fingerprint: format!(
+            "dups:{}:{}",
+            first.file_path.display(),
+            group.sample.join("\n")
+        ),
+        level: None,
+    }
+}
+
+/// Build a SARIF 2.1.0 log with one result per duplicate group.
+pub fn format_sarif(groups: &[DuplicateGroup]) -> Result<String, Box<dyn std::error::Error>> {
+    let findings: Vec<sarif::Finding> = groups.iter().map(sarif_finding).collect();
+    sarif::format_log(&sarif_rules(), &findings)
+}
+
+/// Print the SARIF log for `format_sarif` to stdout.
+pub fn print_sarif(groups: &[DuplicateGroup]) -> Result<(), Box<dyn std::error::Error>> {
+    println!("{}", format_sarif(groups)?);
+    Ok(())
+}
+
+/// CI annotations: one per duplicate location, each naming its siblings.
+/// Critical groups are warnings, tolerable ones notices.
+pub fn annotations(groups: &[DuplicateGroup]) -> Vec<report_helpers::Annotation> {
+    let mut out = Vec::new();
+    for group in groups {
+        let (level, severity) = match group.severity {
+            DuplicationSeverity::Critical => ("warning", "major"),
+            DuplicationSeverity::Tolerable => ("notice", "minor"),
+        };
+        let total = group.locations.len();
+        for (i, loc) in group.locations.iter().enumerate() {
+            let siblings: Vec<String> = group
+                .locations
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .map(|(_, l)| format!("{}:{}-{}", l.file_path.display(), l.start_line, l.end_line))
+                .collect();
+            out.push(report_helpers::Annotation {
+                level,
+                severity,
+                file: loc.file_path.display().to_string(),
+                line: loc.start_line,
+                title: "Duplicate Code",
+                message: format!(
+                    "{}-line block (lines {}-{}) duplicated in {total} locations; also at {}",
+                    group.line_count,
+                    loc.start_line,
+                    loc.end_line,
+                    siblings.join(", ")
+                ),
+            });
+        }
+    }
+    out
+}
+
+/// Emit one GitHub Actions annotation per duplicate location.
+pub fn print_github(groups: &[DuplicateGroup]) {
+    report_helpers::print_github_annotations(&annotations(groups));
+}
+
+/// Emit a CodeClimate JSON array with one entry per duplicate location.
+pub fn print_codeclimate(groups: &[DuplicateGroup]) -> Result<(), Box<dyn std::error::Error>> {
+    report_helpers::print_codeclimate_annotations(&annotations(groups))
+}
+
 /// Print duplication as a single compact line.
 pub fn print_short(metrics: &DuplicationMetrics) {
     println!(
@@ -291,5 +423,6 @@ pub fn print_terse(metrics: &DuplicationMetrics) {
 }
 
 #[cfg(test)]
+#[allow(clippy::len_zero)]
 #[path = "report_test.rs"]
 mod tests;
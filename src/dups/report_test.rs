@@ -198,3 +198,71 @@ fn print_detailed_shows_truncation_message() {
     // Show only 1 of 2 groups
     print_detailed(&sample_metrics(), &groups[..1], total_groups);
 }
+
+#[test]
+fn format_sarif_reports_groups_with_related_locations() {
+    let log: serde_json::Value =
+        serde_json::from_str(&format_sarif(&sample_groups()).unwrap()).unwrap();
+    let run = &log["runs"][0];
+    let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+    assert_eq!(rules[0]["id"], "dups/critical");
+    assert_eq!(rules[1]["id"], "dups/tolerable");
+
+    let results = run["results"].as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    let critical = &results[0];
+    assert_eq!(critical["ruleId"], "dups/critical");
+    let region = &critical["locations"][0]["physicalLocation"]["region"];
+    assert_eq!(region["startLine"], 1);
+    assert_eq!(region["endLine"], 6);
+    let related = critical["relatedLocations"].as_array().unwrap();
+    assert_eq!(related.len(), 2);
+    assert_eq!(
+        related[0]["physicalLocation"]["artifactLocation"]["uri"],
+        "src/b.rs"
+    );
+    assert_eq!(related[1]["physicalLocation"]["region"]["startLine"], 20);
+    assert_eq!(related[1]["message"]["text"], "duplicate 3 of 3");
+    assert!(
+        critical["message"]["text"]
+            .as_str()
+            .unwrap()
+            .contains("src/c.rs:20-25")
+    );
+    assert_eq!(results[1]["level"], "note");
+}
+
+#[test]
+fn format_sarif_empty_has_no_results() {
+    let log: serde_json::Value = serde_json::from_str(&format_sarif(&[]).unwrap()).unwrap();
+    assert_eq!(log["runs"][0]["results"].as_array().unwrap().len(), 0);
+}
+
+#[test]
+fn annotations_one_per_location_with_siblings() {
+    let anns = annotations(&sample_groups());
+    assert_eq!(anns.len(), 5);
+
+    let first = &anns[0];
+    assert_eq!(first.file, "src/a.rs");
+    assert_eq!(first.line, 1);
+    assert_eq!(first.level, "warning");
+    assert_eq!(first.severity, "major");
+    assert_eq!(
+        first.message,
+        "6-line block (lines 1-6) duplicated in 3 locations; also at src/b.rs:5-10, src/c.rs:20-25"
+    );
+    assert!(!anns[1].message.contains("src/b.rs:"));
+
+    let tolerable = &anns[4];
+    assert_eq!(tolerable.file, "src/bar.rs");
+    assert_eq!(tolerable.level, "notice");
+    assert!(tolerable.message.ends_with("also at src/foo.rs:10-21"));
+}
+
+#[test]
+fn print_github_and_codeclimate_do_not_panic() {
+    print_github(&sample_groups());
+    print_codeclimate(&sample_groups()).unwrap();
+    print_codeclimate(&[]).unwrap();
+}
@@ -0,0 +1,87 @@
+//! Author aliases from `.kimun.toml` (`[authors.aliases]`).
+//!
+//! Each entry maps an identity as it appears in commits to the canonical
+//! identity it belongs to, in `.mailmap` notation:
+//!
+//! ```toml
+//! [authors.aliases]
+//! "jane@old-company.com"           = "Jane Doe <jane@example.com>"
+//! "Jane D <jane@users.noreply.com>" = "Jane Doe <jane@example.com>"
+//! ```
+//!
+//! Aliases are added on top of the repository's `.mailmap`, so they take
+//! precedence over its entries for the same identity.
+
+use std::error::Error;
+
+use git2::Mailmap;
+
+use crate::config::AuthorsConfig;
+
+/// A name and/or email in `.mailmap` notation.
+#[derive(Debug, Clone, PartialEq)]
+struct Identity {
+    name: Option<String>,
+    email: Option<String>,
+}
+
+impl Identity {
+    /// Parse `Name <email>`, `<email>`, a bare `email` (anything with an
+    /// `@`), or a bare `Name`.
+    fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        let non_empty = |s: &str| Some(s.trim()).filter(|s| !s.is_empty()).map(String::from);
+        let (name, email) = match text.split_once('<') {
+            Some((name, rest)) => (non_empty(name), non_empty(rest.strip_suffix('>')?)),
+            None if text.contains('@') => (None, non_empty(text)),
+            None => (non_empty(text), None),
+        };
+        if name.is_none() && email.is_none() {
+            return None;
+        }
+        Some(Self { name, email })
+    }
+}
+
+/// Alias entries from `[authors.aliases]`: `(alias, canonical)` pairs.
+#[derive(Debug, Clone, Default)]
+pub struct AuthorAliases {
+    entries: Vec<(Identity, Identity)>,
+}
+
+impl AuthorAliases {
+    /// Parse the `[authors.aliases]` table. Every alias must name an email,
+    /// as `.mailmap` matches on it.
+    pub fn resolve(config: &AuthorsConfig) -> Result<Self, Box<dyn Error>> {
+        let mut entries = Vec::new();
+        for (alias, canonical) in &config.aliases {
+            let from = Identity::parse(alias)
+                .filter(|id| id.email.is_some())
+                .ok_or_else(|| {
+                    format!("[authors.aliases]: '{alias}' must be 'email' or 'Name <email>'")
+                })?;
+            let to = Identity::parse(canonical).ok_or_else(|| {
+                format!("[authors.aliases]: '{canonical}' for '{alias}' is not a name or email")
+            })?;
+            entries.push((from, to));
+        }
+        Ok(Self { entries })
+    }
+
+    /// Add the aliases to `mailmap`, replacing its entries for the same identity.
+    pub(crate) fn apply(&self, mailmap: &mut Mailmap) -> Result<(), git2::Error> {
+        for (from, to) in &self.entries {
+            mailmap.add_entry(
+                to.name.as_deref(),
+                to.email.as_deref(),
+                from.name.as_deref(),
+                from.email.as_deref().unwrap_or_default(),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[path = "aliases_test.rs"]
+mod tests;
@@ -0,0 +1,91 @@
+use super::*;
+use std::collections::BTreeMap;
+
+fn config(entries: &[(&str, &str)]) -> AuthorsConfig {
+    AuthorsConfig {
+        aliases: entries
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect::<BTreeMap<_, _>>(),
+    }
+}
+
+fn resolve(aliases: &AuthorAliases, name: &str, email: &str) -> (String, String) {
+    let mut mailmap = Mailmap::new().unwrap();
+    aliases.apply(&mut mailmap).unwrap();
+    let sig = git2::Signature::now(name, email).unwrap();
+    let sig = mailmap.resolve_signature(&sig).unwrap();
+    (
+        sig.name().unwrap().to_string(),
+        sig.email().unwrap().to_string(),
+    )
+}
+
+#[test]
+fn parses_mailmap_notation() {
+    let id = |name: Option<&str>, email: Option<&str>| Identity {
+        name: name.map(String::from),
+        email: email.map(String::from),
+    };
+    assert_eq!(
+        Identity::parse(" Jane Doe <jane@x.com> "),
+        Some(id(Some("Jane Doe"), Some("jane@x.com")))
+    );
+    assert_eq!(
+        Identity::parse("<jane@x.com>"),
+        Some(id(None, Some("jane@x.com")))
+    );
+    assert_eq!(
+        Identity::parse("jane@x.com"),
+        Some(id(None, Some("jane@x.com")))
+    );
+    assert_eq!(Identity::parse("Jane"), Some(id(Some("Jane"), None)));
+    assert_eq!(Identity::parse("  "), None);
+    assert_eq!(Identity::parse("Jane <jane@x.com"), None);
+}
+
+#[test]
+fn an_email_alias_maps_every_name_using_it() {
+    let aliases =
+        AuthorAliases::resolve(&config(&[("jane@old.com", "Jane Doe <jane@new.com>")])).unwrap();
+    assert_eq!(
+        resolve(&aliases, "jdoe", "jane@old.com"),
+        ("Jane Doe".to_string(), "jane@new.com".to_string())
+    );
+    assert_eq!(
+        resolve(&aliases, "Bob", "bob@x.com"),
+        ("Bob".to_string(), "bob@x.com".to_string())
+    );
+}
+
+#[test]
+fn a_named_alias_only_maps_that_name() {
+    let aliases = AuthorAliases::resolve(&config(&[(
+        "CI Bot <ci@x.com>",
+        "Release Team <release@x.com>",
+    )]))
+    .unwrap();
+    assert_eq!(resolve(&aliases, "CI Bot", "ci@x.com").1, "release@x.com");
+    assert_eq!(resolve(&aliases, "Someone", "ci@x.com").1, "ci@x.com");
+}
+
+#[test]
+fn a_canonical_name_alone_keeps_the_email() {
+    let aliases = AuthorAliases::resolve(&config(&[("jd@x.com", "Jane Doe")])).unwrap();
+    assert_eq!(
+        resolve(&aliases, "jd", "jd@x.com"),
+        ("Jane Doe".to_string(), "jd@x.com".to_string())
+    );
+}
+
+#[test]
+fn an_alias_without_email_is_rejected() {
+    let err = AuthorAliases::resolve(&config(&[("Jane", "Jane Doe <jane@x.com>")]))
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("'Jane' must be"), "{err}");
+    let err = AuthorAliases::resolve(&config(&[("jane@x.com", " ")]))
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("is not a name or email"), "{err}");
+}
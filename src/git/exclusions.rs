@@ -0,0 +1,102 @@
+//! Noise commits left out of history analyses (`[git]` in `.kimun.toml`):
+//! bots, listed mass changes, and commits that touch too many files.
+//!
+//! ```toml
+//! [git]
+//! ignore_authors = ['\[bot\]', '^Renovate ']
+//! ignore_commits = ".git-blame-ignore-revs"
+//! max_changeset_size = 100
+//! ```
+//!
+//! The commit list uses the format of `.git-blame-ignore-revs`: one full
+//! SHA per line, with blank lines and `#` comments ignored.
+
+use std::collections::HashSet;
+use std::error::Error;
+use std::path::Path;
+
+use git2::Oid;
+use regex::Regex;
+
+use crate::config::GitConfig;
+
+/// Commits to leave out of history walks and blame. The default excludes
+/// nothing.
+#[derive(Debug, Clone, Default)]
+pub struct CommitExclusions {
+    /// Matched against the author's `Name <email>`.
+    authors: Vec<Regex>,
+    commits: HashSet<Oid>,
+    max_changeset_size: Option<usize>,
+}
+
+impl CommitExclusions {
+    /// Compile `[git] ignore_authors` and read the `[git] ignore_commits`
+    /// file, resolved against `root` when relative.
+    pub fn resolve(config: &GitConfig, root: &Path) -> Result<Self, Box<dyn Error>> {
+        let authors = config
+            .ignore_authors
+            .iter()
+            .map(|p| Regex::new(p).map_err(|e| format!("[git] ignore_authors: '{p}': {e}")))
+            .collect::<Result<_, _>>()?;
+        let commits = match &config.ignore_commits {
+            Some(file) => {
+                let path = root.join(file);
+                let text = std::fs::read_to_string(&path)
+                    .map_err(|e| format!("[git] ignore_commits: {}: {e}", path.display()))?;
+                parse_revs(&text).map_err(|e| format!("[git] ignore_commits: {file}: {e}"))?
+            }
+            None => HashSet::new(),
+        };
+        Ok(Self {
+            authors,
+            commits,
+            max_changeset_size: config.max_changeset_size,
+        })
+    }
+
+    /// Whether commits by this author are left out.
+    pub fn ignores_author(&self, name: &str, email: &str) -> bool {
+        if self.authors.is_empty() {
+            return false;
+        }
+        let identity = format!("{name} <{email}>");
+        self.authors.iter().any(|p| p.is_match(&identity))
+    }
+
+    /// Whether the commit `id` is listed in `ignore_commits`.
+    pub fn ignores_commit(&self, id: Oid) -> bool {
+        self.commits.contains(&id)
+    }
+
+    /// Whether a commit changing `files` files exceeds `max_changeset_size`.
+    pub fn exceeds_changeset(&self, files: usize) -> bool {
+        self.max_changeset_size.is_some_and(|max| files > max)
+    }
+
+    /// Whether commits are capped by size, which takes a diff to check.
+    pub fn caps_changesets(&self) -> bool {
+        self.max_changeset_size.is_some()
+    }
+}
+
+/// Parse a `.git-blame-ignore-revs` style list of full commit SHAs.
+fn parse_revs(text: &str) -> Result<HashSet<Oid>, Box<dyn Error>> {
+    let mut revs = HashSet::new();
+    for (n, line) in text.lines().enumerate() {
+        let rev = line.split('#').next().unwrap_or("").trim();
+        if rev.is_empty() {
+            continue;
+        }
+        if rev.len() != 40 {
+            return Err(format!("line {}: '{rev}' is not a full commit SHA", n + 1).into());
+        }
+        let id = Oid::from_str(rev).map_err(|e| format!("line {}: '{rev}': {e}", n + 1))?;
+        revs.insert(id);
+    }
+    Ok(revs)
+}
+
+#[cfg(test)]
+#[path = "exclusions_test.rs"]
+mod tests;
@@ -0,0 +1,70 @@
+use super::*;
+
+const SHA: &str = "0123456789abcdef0123456789abcdef01234567";
+
+fn config(authors: &[&str], commits: Option<&str>, max: Option<usize>) -> GitConfig {
+    GitConfig {
+        ignore_authors: authors.iter().map(|s| s.to_string()).collect(),
+        ignore_commits: commits.map(str::to_string),
+        max_changeset_size: max,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn default_excludes_nothing() {
+    let exclusions = CommitExclusions::default();
+    assert!(!exclusions.ignores_author("dependabot[bot]", "bot@github.com"));
+    assert!(!exclusions.ignores_commit(Oid::from_str(SHA).unwrap()));
+    assert!(!exclusions.exceeds_changeset(10_000));
+    assert!(!exclusions.caps_changesets());
+}
+
+#[test]
+fn authors_match_name_and_email() {
+    let dir = tempfile::tempdir().unwrap();
+    let exclusions = CommitExclusions::resolve(
+        &config(&[r"\[bot\]", "@ci\\.example"], None, None),
+        dir.path(),
+    )
+    .unwrap();
+    assert!(exclusions.ignores_author(
+        "dependabot[bot]",
+        "49699333+dependabot[bot]@users.noreply.github.com"
+    ));
+    assert!(exclusions.ignores_author("Build", "build@ci.example"));
+    assert!(!exclusions.ignores_author("Jane", "jane@example.com"));
+}
+
+#[test]
+fn commits_are_read_from_a_blame_ignore_revs_file() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join(".git-blame-ignore-revs"),
+        format!("# Reformat with rustfmt\n{SHA}\n\n  # license headers\n"),
+    )
+    .unwrap();
+    let exclusions = CommitExclusions::resolve(
+        &config(&[], Some(".git-blame-ignore-revs"), Some(50)),
+        dir.path(),
+    )
+    .unwrap();
+    assert!(exclusions.ignores_commit(Oid::from_str(SHA).unwrap()));
+    assert!(exclusions.exceeds_changeset(51));
+    assert!(!exclusions.exceeds_changeset(50));
+}
+
+#[test]
+fn invalid_settings_are_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let err = CommitExclusions::resolve(&config(&["[bot"], None, None), dir.path()).unwrap_err();
+    assert!(err.to_string().contains("ignore_authors: '[bot'"), "{err}");
+
+    let err =
+        CommitExclusions::resolve(&config(&[], Some("missing"), None), dir.path()).unwrap_err();
+    assert!(err.to_string().contains("ignore_commits"), "{err}");
+
+    std::fs::write(dir.path().join("revs"), "0123abc\n").unwrap();
+    let err = CommitExclusions::resolve(&config(&[], Some("revs"), None), dir.path()).unwrap_err();
+    assert!(err.to_string().contains("line 1"), "{err}");
+}
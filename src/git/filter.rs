@@ -0,0 +1,107 @@
+//! Commit filters for git-history analyses: a revision range, a date
+//! cutoff, authors, and paths.
+//!
+//! History walks read only the commits that pass the filter, and only
+//! their changes to matching paths. Blame-based analyses attribute lines
+//! as of the end of the range (or the `--until` date).
+
+use std::error::Error;
+use std::path::Path;
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+
+/// Restricts which commits git-history analyses read. The default filter
+/// keeps every commit.
+#[derive(Debug, Clone, Default)]
+pub struct CommitFilter {
+    /// Leave out commits reachable from this revision.
+    pub(super) from: Option<String>,
+    /// Read history up to this revision instead of HEAD.
+    pub(super) to: Option<String>,
+    /// Leave out commits made after this Unix timestamp.
+    pub(super) until: Option<i64>,
+    /// Lowercased substrings of author names or emails; empty keeps all.
+    authors: Vec<String>,
+    /// Repository-relative files, directories or globs; `None` keeps all.
+    paths: Option<GlobSet>,
+}
+
+impl CommitFilter {
+    /// Leave out commits reachable from `from`, and read history up to `to`
+    /// instead of HEAD — `git log FROM..TO`.
+    pub fn with_revisions(mut self, from: Option<&str>, to: Option<&str>) -> Self {
+        self.from = from.map(str::to_string);
+        self.to = to.map(str::to_string);
+        self
+    }
+
+    /// Restrict to a `FROM..TO` range. Either side may be left out:
+    /// `v1.2..` reads up to HEAD, `..v1.3` from the start of history.
+    pub fn with_range(self, range: &str) -> Result<Self, Box<dyn Error>> {
+        let parts = range
+            .split_once("..")
+            .filter(|(_, to)| !to.starts_with('.'))
+            .ok_or_else(|| format!("invalid --range {range:?} (expected FROM..TO)"))?;
+        let (from, to) = (parts.0.trim(), parts.1.trim());
+        Ok(self.with_revisions(
+            (!from.is_empty()).then_some(from),
+            (!to.is_empty()).then_some(to),
+        ))
+    }
+
+    /// Leave out commits made after the Unix timestamp `until`.
+    pub fn with_until(mut self, until: Option<i64>) -> Self {
+        self.until = until;
+        self
+    }
+
+    /// Keep only commits whose author name or email contains one of
+    /// `authors` (case-insensitive).
+    pub fn with_authors(mut self, authors: &[String]) -> Self {
+        self.authors = authors.iter().map(|a| a.to_lowercase()).collect();
+        self
+    }
+
+    /// Keep only changes to `paths`: repository-relative files, directories
+    /// (everything below them), or glob patterns such as `src/**/*.rs`.
+    pub fn with_paths(mut self, paths: &[String]) -> Result<Self, Box<dyn Error>> {
+        if paths.is_empty() {
+            self.paths = None;
+            return Ok(self);
+        }
+        let mut builder = GlobSetBuilder::new();
+        for path in paths {
+            let path = path.trim_end_matches('/');
+            let invalid = |e: globset::Error| format!("invalid --path {path:?}: {e}");
+            builder.add(Glob::new(path).map_err(invalid)?);
+            builder.add(Glob::new(&format!("{path}/**")).map_err(invalid)?);
+        }
+        self.paths = Some(builder.build()?);
+        Ok(self)
+    }
+
+    /// Whether the filter restricts paths.
+    pub fn has_paths(&self) -> bool {
+        self.paths.is_some()
+    }
+
+    /// Whether an author with this name and email passes the filter.
+    pub fn matches_author(&self, name: &str, email: &str) -> bool {
+        if self.authors.is_empty() {
+            return true;
+        }
+        let (name, email) = (name.to_lowercase(), email.to_lowercase());
+        self.authors
+            .iter()
+            .any(|a| name.contains(a.as_str()) || email.contains(a.as_str()))
+    }
+
+    /// Whether a repository-relative path passes the filter.
+    pub fn matches_path(&self, path: &Path) -> bool {
+        self.paths.as_ref().is_none_or(|set| set.is_match(path))
+    }
+}
+
+#[cfg(test)]
+#[path = "filter_test.rs"]
+mod tests;
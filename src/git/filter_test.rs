@@ -0,0 +1,48 @@
+use super::*;
+
+#[test]
+fn range_sets_both_ends() {
+    let filter = CommitFilter::default().with_range("v1.2..v1.3").unwrap();
+    assert_eq!(filter.from.as_deref(), Some("v1.2"));
+    assert_eq!(filter.to.as_deref(), Some("v1.3"));
+
+    let open_end = CommitFilter::default().with_range("v1.2..").unwrap();
+    assert_eq!(open_end.from.as_deref(), Some("v1.2"));
+    assert_eq!(open_end.to, None);
+
+    for invalid in ["v1.2", "v1.2...v1.3"] {
+        let err = CommitFilter::default().with_range(invalid).unwrap_err();
+        assert!(err.to_string().contains("FROM..TO"), "{invalid}: {err}");
+    }
+}
+
+#[test]
+fn authors_match_name_or_email_substrings() {
+    let filter =
+        CommitFilter::default().with_authors(&["ALICE".to_string(), "@team.io".to_string()]);
+    assert!(filter.matches_author("Alice Smith", "a@example.com"));
+    assert!(filter.matches_author("Bob", "bob@team.io"));
+    assert!(!filter.matches_author("Carol", "carol@example.com"));
+    assert!(CommitFilter::default().matches_author("Carol", "carol@example.com"));
+}
+
+#[test]
+fn paths_match_files_directories_and_globs() {
+    let filter = CommitFilter::default()
+        .with_paths(&["src/git/".to_string(), "*.toml".to_string()])
+        .unwrap();
+    assert!(filter.has_paths());
+    assert!(filter.matches_path(Path::new("src/git/mod.rs")));
+    assert!(filter.matches_path(Path::new("Cargo.toml")));
+    assert!(!filter.matches_path(Path::new("src/main.rs")));
+    assert!(!filter.matches_path(Path::new("src/gitignore.rs")));
+
+    let none = CommitFilter::default().with_paths(&[]).unwrap();
+    assert!(!none.has_paths());
+    assert!(none.matches_path(Path::new("src/main.rs")));
+    assert!(
+        CommitFilter::default()
+            .with_paths(&["src/[".to_string()])
+            .is_err()
+    );
+}
@@ -0,0 +1,68 @@
+//! Bug-fix commit classification from `.kimun.toml` (`[defects]`).
+//!
+//! A commit is a fix when any pattern matches its message. Patterns are
+//! regular expressions; the defaults catch the usual wording and an issue
+//! key opening the message:
+//!
+//! ```toml
+//! [defects]
+//! patterns = ['(?i)\b(hot)?fix(e[sd]|ing)?\b', '(?i)\bbug', '^\[?[A-Z]{2,}[A-Z0-9]*-\d{2,}(?:[\]:\s]|$)']
+//! ```
+
+use std::error::Error;
+
+use regex::Regex;
+
+use crate::config::DefectsConfig;
+
+/// Patterns used when `[defects] patterns` is not set: "fix"/"fixes"/
+/// "fixed"/"fixing", words starting with "bug" (also "bugfix"), "hotfix",
+/// and an issue key such as `PROJ-123` or `[PROJ-123]` opening the
+/// message. Keys need two letters and two digits and must open the
+/// message, so names like `UTF-8`, `SHA-256`, `ISO-8601` or
+/// `CVE-2024-1234` in the text are not taken for one.
+pub const DEFAULT_FIX_PATTERNS: &[&str] = &[
+    r"(?i)\b(hot)?fix(e[sd]|ing)?\b",
+    r"(?i)\bbug",
+    r"^\[?[A-Z]{2,}[A-Z0-9]*-\d{2,}(?:[\]:\s]|$)",
+];
+
+/// Compiled fix-commit patterns.
+#[derive(Debug, Clone)]
+pub struct FixPatterns {
+    patterns: Vec<Regex>,
+}
+
+impl Default for FixPatterns {
+    fn default() -> Self {
+        Self {
+            patterns: DEFAULT_FIX_PATTERNS
+                .iter()
+                .map(|p| Regex::new(p).expect("default fix pattern is valid"))
+                .collect(),
+        }
+    }
+}
+
+impl FixPatterns {
+    /// Compile `[defects] patterns`, or the defaults when unset.
+    pub fn resolve(config: &DefectsConfig) -> Result<Self, Box<dyn Error>> {
+        let Some(patterns) = &config.patterns else {
+            return Ok(Self::default());
+        };
+        let patterns = patterns
+            .iter()
+            .map(|p| Regex::new(p).map_err(|e| format!("[defects] patterns: '{p}': {e}")))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { patterns })
+    }
+
+    /// Whether a commit with this message is a bug fix.
+    pub fn is_fix(&self, message: &str) -> bool {
+        self.patterns.iter().any(|p| p.is_match(message))
+    }
+}
+
+#[cfg(test)]
+#[path = "fixes_test.rs"]
+mod tests;
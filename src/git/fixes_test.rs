@@ -0,0 +1,69 @@
+use super::*;
+
+fn config(patterns: Option<&[&str]>) -> DefectsConfig {
+    DefectsConfig {
+        patterns: patterns.map(|p| p.iter().map(|s| s.to_string()).collect()),
+    }
+}
+
+#[test]
+fn default_patterns_catch_fix_wording_and_issue_keys() {
+    let fixes = FixPatterns::default();
+    for message in [
+        "Fix crash on empty input",
+        "fixed off-by-one in pager",
+        "Fixes #12",
+        "hotfix: null deref",
+        "Bugfix for login",
+        "PROJ-482 handle timeouts",
+        "[AB-12] retry on reset",
+        "OPS-99: restore backups",
+    ] {
+        assert!(fixes.is_fix(message), "{message}");
+    }
+    for message in [
+        "Add test fixtures",
+        "Refactor prefix handling",
+        "Update debugger docs",
+        "Bump version to 1.2",
+    ] {
+        assert!(!fixes.is_fix(message), "{message}");
+    }
+}
+
+#[test]
+fn default_patterns_ignore_standard_names_that_look_like_issue_keys() {
+    let fixes = FixPatterns::default();
+    for message in [
+        "UTF-8 output for reports",
+        "Switch checksums to SHA-256",
+        "Parse ISO-8601 timestamps",
+        "CVE-2024-3094: pin xz",
+        "Bump openssl for CVE-2024-12797",
+        "Support A-12 paper size",
+    ] {
+        assert!(!fixes.is_fix(message), "{message}");
+    }
+}
+
+#[test]
+fn configured_patterns_replace_the_defaults() {
+    let fixes = FixPatterns::resolve(&config(Some(&["^defect:"]))).unwrap();
+    assert!(fixes.is_fix("defect: wrong total"));
+    assert!(!fixes.is_fix("Fix crash"));
+    let none = FixPatterns::resolve(&config(Some(&[]))).unwrap();
+    assert!(
+        !none.is_fix("Fix crash"),
+        "an empty list classifies nothing"
+    );
+    let default = FixPatterns::resolve(&config(None)).unwrap();
+    assert!(default.is_fix("Fix crash"));
+}
+
+#[test]
+fn an_invalid_pattern_is_rejected() {
+    let err = FixPatterns::resolve(&config(Some(&["fix("])))
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("'fix('"), "{err}");
+}
@@ -1,25 +1,101 @@
 //! Git repository access via libgit2.
 //!
-//! Provides file change frequencies, co-changing commit analysis,
+//! Provides file change frequencies and line churn, co-changing commit analysis,
 //! git blame for ownership, and recent author detection — all used
 //! by the hotspots, knowledge, and temporal coupling modules.
 //! The `GitRepo` wrapper encapsulates `git2::Repository` and its
 //! resolved working directory root, providing a safe API for walking
 //! commits, diffing trees, and resolving paths between the filesystem
-//! walk and git's path namespace.
+//! walk and git's path namespace. History walks follow renamed files, so
+//! a file's commits, age, and coupling survive a move, and can be limited
+//! to a range, date, authors and paths with a [`CommitFilter`]. Noise
+//! commits configured in `[git]` are left out with [`CommitExclusions`].
+use std::cell::RefCell;
 use std::cmp::Reverse;
 use std::collections::{HashMap, HashSet};
 use std::error::Error;
-use std::fs;
 use std::ops::ControlFlow;
 use std::path::{Path, PathBuf};
 
-use git2::{BlameOptions, Delta, DiffOptions, ObjectType, Repository, Sort, Tree};
+use git2::{
+    BlameOptions, Delta, DiffFindOptions, DiffOptions, Mailmap, ObjectType, Oid, Patch, Repository,
+    Sort, TreeWalkMode, TreeWalkResult,
+};
+
+use crate::rev;
+
+mod aliases;
+mod exclusions;
+mod filter;
+mod fixes;
+
+pub use aliases::AuthorAliases;
+pub use exclusions::CommitExclusions;
+pub use filter::CommitFilter;
+pub use fixes::{DEFAULT_FIX_PATTERNS, FixPatterns};
 
 /// Wrapper around a `git2::Repository` with its resolved root path.
 pub struct GitRepo {
     repo: Repository,
+    /// Working directory, or the git directory of a bare repository.
     root: PathBuf,
+    /// Commit history is read from: the commit of the tree mounted over
+    /// the opened path, or `None` for HEAD.
+    tip: Option<Oid>,
+    /// Commits reachable from here are left out (the start of a range).
+    hide: Option<Oid>,
+    /// Range, date, author and path restrictions on history walks.
+    commits: CommitFilter,
+    /// Bot and mass-change commits left out of walks and blame.
+    exclusions: CommitExclusions,
+    /// Commits already checked against the changeset cap during blame.
+    oversized: RefCell<HashMap<Oid, bool>>,
+    /// Identities merged on top of `.mailmap` when reading signatures.
+    aliases: AuthorAliases,
+    /// Commit messages that mark a bug fix.
+    fix_patterns: FixPatterns,
+    /// Minimum similarity (percent) for a deleted and an added file to be
+    /// followed as a rename; 0 turns rename tracking off.
+    rename_threshold: u16,
+}
+
+/// Default similarity for rename tracking, as in `git diff -M`.
+pub const DEFAULT_RENAME_THRESHOLD: u16 = 50;
+
+/// A file touched by a commit, as found by `GitRepo::changed_files`.
+struct FileChange {
+    /// Repository-relative path after the commit.
+    path: PathBuf,
+    /// Path before the commit, when the file was renamed.
+    renamed_from: Option<PathBuf>,
+    /// Whether the content is unchanged, as in a pure rename.
+    unchanged: bool,
+    /// Lines added and deleted; zero unless line counts were requested.
+    added: usize,
+    deleted: usize,
+}
+
+/// A file's change history with its line volume, as found by
+/// [`GitRepo::line_churn`].
+pub struct FileLineChurn {
+    /// Commit and fix counts, first/last timestamps.
+    pub frequency: FileFrequency,
+    /// Lines added across those commits.
+    pub added: usize,
+    /// Lines deleted across those commits.
+    pub deleted: usize,
+    /// Each author's share of the added and deleted lines, most lines first.
+    pub authors: Vec<AuthorLines>,
+}
+
+/// Lines one author added to and deleted from a file.
+pub struct AuthorLines {
+    /// Author display name, resolved through `.mailmap` and the aliases.
+    pub author: String,
+    /// Author email, resolved through `.mailmap` and the aliases.
+    pub email: String,
+    pub added: usize,
+    pub deleted: usize,
 }
 
 /// How often a file was changed in git history.
@@ -32,6 +108,28 @@ pub struct FileFrequency {
     pub first_commit: i64,
     /// Unix timestamp of the most recent commit touching this file.
     pub last_commit: i64,
+    /// Number of those commits classified as bug fixes by their message.
+    pub fixes: usize,
+}
+
+impl FileFrequency {
+    fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            commits: 0,
+            first_commit: i64::MAX,
+            last_commit: i64::MIN,
+            fixes: 0,
+        }
+    }
+
+    /// Count a commit made at `time`.
+    fn record(&mut self, time: i64, fix: bool) {
+        self.commits += 1;
+        self.fixes += usize::from(fix);
+        self.first_commit = self.first_commit.min(time);
+        self.last_commit = self.last_commit.max(time);
+    }
 }
 
 /// Per-hunk blame data with line range, used to join against FSM line classification.
@@ -46,6 +144,35 @@ pub struct BlameHunkInfo {
     pub lines: usize,
 }
 
+/// Lines added or modified in one file, as found by
+/// [`GitRepo::lines_changed_since`].
+pub struct ChangedLines {
+    /// Repository-relative file path.
+    pub path: PathBuf,
+    /// 1-based line numbers in the current file, ascending.
+    pub lines: Vec<usize>,
+}
+
+/// A commit on the first-parent line of HEAD, as found by
+/// [`GitRepo::first_parent_history`].
+pub struct HistoryCommit {
+    /// Full hex commit id.
+    pub id: String,
+    /// Unix timestamp of the commit.
+    pub time: i64,
+}
+
+/// A regular file in a commit's tree, as listed by [`GitRepo::tree_files`].
+pub struct TreeFile {
+    /// Repository-relative file path.
+    pub path: PathBuf,
+    /// Blob id of the file content.
+    pub id: Oid,
+}
+
+/// Git file mode of a symbolic link; its blob holds the link target.
+const SYMLINK_MODE: i32 = 0o120000;
+
 /// Per-author blame contribution for a single file.
 pub struct BlameInfo {
     /// Author display name from git signature.
@@ -59,28 +186,192 @@ pub struct BlameInfo {
 }
 
 impl GitRepo {
-    /// Open the git repository that contains `path`.
+    /// Open the git repository that contains `path`: a working tree, a
+    /// linked worktree, or a bare repository. When `path` lies in a tree
+    /// mounted by [`RevTree`](crate::rev::RevTree), history is read from
+    /// that tree's commit instead of HEAD.
     pub fn open(path: &Path) -> Result<Self, Box<dyn Error>> {
-        let repo = Repository::discover(path)?;
-        let root = repo
-            .workdir()
-            .ok_or("bare repositories are not supported")?
-            .to_path_buf();
-        Ok(Self { repo, root })
+        let (repo, tip) = match rev::mounted_commit(path) {
+            Some((git_dir, commit)) => (Repository::open(git_dir)?, Some(commit)),
+            None => (Repository::discover(path)?, None),
+        };
+        let root = repo.workdir().unwrap_or(repo.path()).to_path_buf();
+        Ok(Self {
+            repo,
+            root,
+            tip,
+            hide: None,
+            commits: CommitFilter::default(),
+            exclusions: CommitExclusions::default(),
+            oversized: RefCell::default(),
+            aliases: AuthorAliases::default(),
+            fix_patterns: FixPatterns::default(),
+            rename_threshold: DEFAULT_RENAME_THRESHOLD,
+        })
+    }
+
+    /// Follow renames whose similarity reaches `threshold` percent
+    /// (0 turns rename tracking off).
+    pub fn with_rename_threshold(mut self, threshold: u16) -> Self {
+        self.rename_threshold = threshold.min(100);
+        self
+    }
+
+    /// Merge author identities with `aliases` as well as `.mailmap`.
+    pub fn with_aliases(mut self, aliases: Option<&AuthorAliases>) -> Self {
+        self.aliases = aliases.cloned().unwrap_or_default();
+        self
+    }
+
+    /// Classify bug-fix commits with `patterns` instead of the defaults.
+    pub fn with_fix_patterns(mut self, patterns: Option<&FixPatterns>) -> Self {
+        self.fix_patterns = patterns.cloned().unwrap_or_default();
+        self
+    }
+
+    /// Leave the commits matched by `exclusions` out of history walks and
+    /// blame.
+    pub fn with_exclusions(mut self, exclusions: Option<&CommitExclusions>) -> Self {
+        self.exclusions = exclusions.cloned().unwrap_or_default();
+        self
+    }
+
+    /// Restrict history walks to the commits that pass `filter`. The end of
+    /// its range, moved back to the last commit before `--until`, becomes
+    /// the history tip, so blame attributes lines as of that commit.
+    pub fn with_commit_filter(
+        mut self,
+        filter: Option<&CommitFilter>,
+    ) -> Result<Self, Box<dyn Error>> {
+        let Some(filter) = filter else {
+            return Ok(self);
+        };
+        if let Some(to) = &filter.to {
+            self.tip = Some(self.resolve_commit(to)?);
+        }
+        self.hide = filter
+            .from
+            .as_deref()
+            .map(|from| self.resolve_commit(from))
+            .transpose()?;
+        if let Some(until) = filter.until {
+            self.tip = Some(self.last_commit_until(until)?);
+        }
+        self.commits = filter.clone();
+        Ok(self)
+    }
+
+    /// Resolve a revision to the id of the commit it names.
+    fn resolve_commit(&self, rev: &str) -> Result<Oid, Box<dyn Error>> {
+        let commit = self
+            .repo
+            .revparse_single(rev)
+            .map_err(|e| format!("cannot resolve ref '{rev}': {e}"))?
+            .peel_to_commit()
+            .map_err(|e| format!("'{rev}' is not a commit: {e}"))?;
+        Ok(commit.id())
     }
 
-    /// Iterate non-merge commits in reverse chronological order, optionally
-    /// filtered by a `since` timestamp. Calls `f` for each qualifying commit.
+    /// The newest first-parent ancestor of the tip committed at or before
+    /// `until`.
+    fn last_commit_until(&self, until: i64) -> Result<Oid, Box<dyn Error>> {
+        let mut revwalk = self.repo.revwalk()?;
+        self.push_tip(&mut revwalk)?;
+        revwalk.simplify_first_parent()?;
+        for oid in revwalk {
+            let oid = oid?;
+            if self.repo.find_commit(oid)?.time().seconds() <= until {
+                return Ok(oid);
+            }
+        }
+        Err("no commits before the --until date".into())
+    }
+
+    /// Whether `path` passes the path filter of
+    /// [`with_commit_filter`](Self::with_commit_filter).
+    pub fn includes_path(&self, path: &Path) -> bool {
+        self.commits.matches_path(path)
+    }
+
+    /// Whether `rel_path` exists in the tree of the history tip (HEAD
+    /// unless moved by a mounted tree or a commit filter).
+    pub fn exists_at_tip(&self, rel_path: &Path) -> bool {
+        let commit = match self.tip {
+            Some(id) => self.repo.find_commit(id),
+            None => self.repo.head().and_then(|h| h.peel_to_commit()),
+        };
+        commit
+            .and_then(|c| c.tree())
+            .is_ok_and(|tree| tree.get_path(rel_path).is_ok())
+    }
+
+    /// Whether `commit` is a bug fix, judged by its message.
+    fn is_fix(&self, commit: &git2::Commit) -> bool {
+        let message = String::from_utf8_lossy(commit.message_bytes());
+        self.fix_patterns.is_fix(&message)
+    }
+
+    /// The repository's `.mailmap` extended with the configured aliases.
+    fn mailmap(&self) -> Result<Mailmap, git2::Error> {
+        let mut mailmap = self.repo.mailmap()?;
+        self.aliases.apply(&mut mailmap)?;
+        Ok(mailmap)
+    }
+
+    /// Whether the repository has no working directory.
+    pub fn is_bare(&self) -> bool {
+        self.repo.is_bare()
+    }
+
+    /// The repository's git directory (`.git`, or the bare repository itself).
+    pub fn git_dir(&self) -> &Path {
+        self.repo.path()
+    }
+
+    /// Start `revwalk` at the history tip: the mounted commit or HEAD.
+    fn push_tip(&self, revwalk: &mut git2::Revwalk<'_>) -> Result<(), git2::Error> {
+        match self.tip {
+            Some(id) => revwalk.push(id),
+            None => revwalk.push_head(),
+        }
+    }
+
+    /// Iterate non-merge commits newest first, children before parents, optionally
+    /// filtered by a `since` timestamp and by the range, date and authors of
+    /// the commit filter. Ignored commits and authors are skipped; the
+    /// changeset cap is applied by [`walk_changes`](Self::walk_changes),
+    /// which has the diff. Calls `f` for each qualifying commit.
     /// The callback returns `ControlFlow::Continue(())` to keep walking or
     /// `ControlFlow::Break(())` to stop early.
     fn walk_commits(
         &self,
         since: Option<i64>,
         mut f: impl FnMut(&git2::Commit) -> Result<ControlFlow<()>, Box<dyn Error>>,
+    ) -> Result<(), Box<dyn Error>> {
+        let mailmap = self.mailmap()?;
+        self.walk_history(since, |commit| {
+            if self.filters_out(commit, &mailmap)? {
+                return Ok(ControlFlow::Continue(()));
+            }
+            f(commit)
+        })
+    }
+
+    /// Iterate every non-merge commit of the range, children before parents
+    /// and otherwise newest first, leaving out commits older than `since`.
+    fn walk_history(
+        &self,
+        since: Option<i64>,
+        mut f: impl FnMut(&git2::Commit) -> Result<ControlFlow<()>, Box<dyn Error>>,
     ) -> Result<(), Box<dyn Error>> {
         let mut revwalk = self.repo.revwalk()?;
-        revwalk.push_head()?;
-        revwalk.set_sorting(Sort::TIME)?;
+        self.push_tip(&mut revwalk)?;
+        if let Some(id) = self.hide {
+            revwalk.hide(id)?;
+        }
+        // Topological order keeps a rename ahead of the older commits it
+        // maps, even when their timestamps tie.
+        revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
 
         for oid in revwalk {
             let commit = self.repo.find_commit(oid?)?;
@@ -88,10 +379,11 @@ impl GitRepo {
             if commit.parent_count() > 1 {
                 continue;
             }
-            // Commits are in reverse chronological order; once we hit one
-            // older than the cutoff, all remaining commits are older too.
+            // Topological order does not keep dates in order: a commit
+            // merged from a side branch may predate the cutoff while later
+            // ones in the walk are still inside it, so skip rather than stop.
             if since.is_some_and(|ts| commit.time().seconds() < ts) {
-                break;
+                continue;
             }
             if f(&commit)?.is_break() {
                 break;
@@ -100,33 +392,101 @@ impl GitRepo {
         Ok(())
     }
 
-    /// Count how many commits touched each file, with first/last timestamps.
+    /// Whether `commit` is left out by the `--until` date, the `--author`
+    /// filter, or the ignored commits and authors.
+    fn filters_out(
+        &self,
+        commit: &git2::Commit,
+        mailmap: &Mailmap,
+    ) -> Result<bool, Box<dyn Error>> {
+        if self
+            .commits
+            .until
+            .is_some_and(|ts| commit.time().seconds() > ts)
+            || self.exclusions.ignores_commit(commit.id())
+        {
+            return Ok(true);
+        }
+        let author = commit.author_with_mailmap(mailmap)?;
+        let (name, email) = (
+            author.name().unwrap_or("unknown"),
+            author.email().unwrap_or(""),
+        );
+        Ok(
+            self.exclusions.ignores_author(name, email)
+                || !self.commits.matches_author(name, email),
+        )
+    }
+
+    /// Like [`walk_commits`](Self::walk_commits), also passing the files each
+    /// commit changed, with line counts when `lines` is set. Renamed files
+    /// are followed: changes made before a rename are reported under the
+    /// file's path at the tip, and a rename that leaves the content
+    /// unchanged is not a change. Renames are followed through every
+    /// commit, including those the filters, the ignore lists and the
+    /// changeset cap leave out. With a path filter, only changes to
+    /// matching paths are passed, and commits without any are skipped.
+    fn walk_changes(
+        &self,
+        since: Option<i64>,
+        lines: bool,
+        mut f: impl FnMut(&git2::Commit, Vec<FileChange>) -> Result<ControlFlow<()>, Box<dyn Error>>,
+    ) -> Result<(), Box<dyn Error>> {
+        let mailmap = self.mailmap()?;
+        // Path before a rename → the file's path at the tip.
+        let mut renamed: HashMap<PathBuf, PathBuf> = HashMap::new();
+        self.walk_history(since, |commit| {
+            let mut changes = self.changed_files(commit, lines)?;
+            let current: Vec<PathBuf> = changes
+                .iter()
+                .map(|c| renamed.get(&c.path).unwrap_or(&c.path).clone())
+                .collect();
+            for (change, path) in changes.iter_mut().zip(current) {
+                if let Some(old) = change.renamed_from.take() {
+                    renamed.insert(old, path.clone());
+                }
+                change.path = path;
+            }
+            if self.exclusions.exceeds_changeset(changes.len())
+                || self.filters_out(commit, &mailmap)?
+            {
+                return Ok(ControlFlow::Continue(()));
+            }
+            changes.retain(|c| !c.unchanged && self.commits.matches_path(&c.path));
+            if changes.is_empty() && self.commits.has_paths() {
+                return Ok(ControlFlow::Continue(()));
+            }
+            f(commit, changes)
+        })
+    }
+
+    /// Like [`walk_changes`](Self::walk_changes) without line counts,
+    /// passing only the changed paths.
+    fn walk_changed_paths(
+        &self,
+        since: Option<i64>,
+        mut f: impl FnMut(&git2::Commit, Vec<PathBuf>) -> Result<ControlFlow<()>, Box<dyn Error>>,
+    ) -> Result<(), Box<dyn Error>> {
+        self.walk_changes(since, false, |commit, changes| {
+            f(commit, changes.into_iter().map(|c| c.path).collect())
+        })
+    }
+
+    /// Count how many commits touched each file, and how many of them were
+    /// bug fixes, with first/last timestamps.
     pub fn file_frequencies(
         &self,
         since: Option<i64>,
     ) -> Result<Vec<FileFrequency>, Box<dyn Error>> {
         let mut map: HashMap<PathBuf, FileFrequency> = HashMap::new();
 
-        self.walk_commits(since, |commit| {
+        self.walk_changed_paths(since, |commit, paths| {
             let time = commit.time().seconds();
-            let paths = self.changed_files(commit)?;
+            let fix = self.is_fix(commit);
             for path in paths {
                 map.entry(path.clone())
-                    .and_modify(|f| {
-                        f.commits += 1;
-                        if time < f.first_commit {
-                            f.first_commit = time;
-                        }
-                        if time > f.last_commit {
-                            f.last_commit = time;
-                        }
-                    })
-                    .or_insert(FileFrequency {
-                        path,
-                        commits: 1,
-                        first_commit: time,
-                        last_commit: time,
-                    });
+                    .or_insert_with(|| FileFrequency::new(path))
+                    .record(time, fix);
             }
             Ok(ControlFlow::Continue(()))
         })?;
@@ -136,6 +496,57 @@ impl GitRepo {
         Ok(result)
     }
 
+    /// Like [`file_frequencies`](Self::file_frequencies), also counting the
+    /// lines each commit added to and deleted from the file, in total and
+    /// per author. Sorted by churned lines (added + deleted) descending.
+    pub fn line_churn(&self, since: Option<i64>) -> Result<Vec<FileLineChurn>, Box<dyn Error>> {
+        let mailmap = self.mailmap()?;
+        let mut map: HashMap<PathBuf, FileLineChurn> = HashMap::new();
+
+        self.walk_changes(since, true, |commit, changes| {
+            let time = commit.time().seconds();
+            let fix = self.is_fix(commit);
+            let author = commit.author_with_mailmap(&mailmap)?;
+            let name = author.name().unwrap_or("unknown");
+            let email = author.email().unwrap_or("");
+            for change in changes {
+                let file = map
+                    .entry(change.path.clone())
+                    .or_insert_with(|| FileLineChurn {
+                        frequency: FileFrequency::new(change.path),
+                        added: 0,
+                        deleted: 0,
+                        authors: Vec::new(),
+                    });
+                file.frequency.record(time, fix);
+                file.added += change.added;
+                file.deleted += change.deleted;
+                let share = match file.authors.iter_mut().find(|a| a.email == email) {
+                    Some(share) => share,
+                    None => {
+                        file.authors.push(AuthorLines {
+                            author: name.to_string(),
+                            email: email.to_string(),
+                            added: 0,
+                            deleted: 0,
+                        });
+                        file.authors.last_mut().unwrap()
+                    }
+                };
+                share.added += change.added;
+                share.deleted += change.deleted;
+            }
+            Ok(ControlFlow::Continue(()))
+        })?;
+
+        let mut result: Vec<FileLineChurn> = map.into_values().collect();
+        for file in &mut result {
+            file.authors.sort_by_key(|a| Reverse(a.added + a.deleted));
+        }
+        result.sort_by_key(|r| Reverse(r.added + r.deleted));
+        Ok(result)
+    }
+
     /// Return the most recent commit timestamp for each file in `targets`.
     ///
     /// Walks commits newest-first; the first time a target file appears is its
@@ -148,12 +559,12 @@ impl GitRepo {
         let mut remaining: HashSet<&PathBuf> = targets.iter().collect();
         let mut result: HashMap<PathBuf, i64> = HashMap::new();
 
-        self.walk_commits(None, |commit| {
+        self.walk_changed_paths(None, |commit, paths| {
             if remaining.is_empty() {
                 return Ok(ControlFlow::Break(()));
             }
             let time = commit.time().seconds();
-            for path in self.changed_files(commit)? {
+            for path in paths {
                 if remaining.remove(&path) {
                     result.insert(path, time);
                 }
@@ -164,6 +575,33 @@ impl GitRepo {
         Ok(result)
     }
 
+    /// List the commits on the first-parent line of HEAD, newest first —
+    /// the states the branch itself went through, merges included. With
+    /// `since`, the walk stops after the first commit older than the cutoff,
+    /// so the state of the tree at the cutoff is still covered.
+    pub fn first_parent_history(
+        &self,
+        since: Option<i64>,
+    ) -> Result<Vec<HistoryCommit>, Box<dyn Error>> {
+        let mut revwalk = self.repo.revwalk()?;
+        self.push_tip(&mut revwalk)?;
+        revwalk.simplify_first_parent()?;
+
+        let mut commits = Vec::new();
+        for oid in revwalk {
+            let commit = self.repo.find_commit(oid?)?;
+            let time = commit.time().seconds();
+            commits.push(HistoryCommit {
+                id: commit.id().to_string(),
+                time,
+            });
+            if since.is_some_and(|ts| time < ts) {
+                break;
+            }
+        }
+        Ok(commits)
+    }
+
     /// Collect groups of files that changed together in each commit.
     /// Only includes commits that touch 2+ files.
     pub fn co_changing_commits(
@@ -172,8 +610,7 @@ impl GitRepo {
     ) -> Result<Vec<Vec<PathBuf>>, Box<dyn Error>> {
         let mut result = Vec::new();
 
-        self.walk_commits(since, |commit| {
-            let paths = self.changed_files(commit)?;
+        self.walk_changed_paths(since, |_, paths| {
             if paths.len() >= 2 {
                 result.push(paths);
             }
@@ -183,18 +620,60 @@ impl GitRepo {
         Ok(result)
     }
 
-    /// Run git blame on a file and return per-author contributions.
+    /// Blame options that attribute lines as of the history tip.
+    fn blame_options(&self) -> BlameOptions {
+        let mut opts = BlameOptions::new();
+        if let Some(id) = self.tip {
+            opts.newest_commit(id);
+        }
+        opts
+    }
+
+    /// Whether lines last changed by commit `id`, by this author, are left
+    /// out of blame: an ignored commit or author, or a commit over the
+    /// changeset cap. libgit2 cannot pass such lines on to the previous
+    /// commit as `git blame --ignore-revs-file` does, so they are dropped.
+    fn excluded_from_blame(
+        &self,
+        id: Oid,
+        name: &str,
+        email: &str,
+    ) -> Result<bool, Box<dyn Error>> {
+        if self.exclusions.ignores_commit(id) || self.exclusions.ignores_author(name, email) {
+            return Ok(true);
+        }
+        if !self.exclusions.caps_changesets() || id.is_zero() {
+            return Ok(false);
+        }
+        if let Some(&oversized) = self.oversized.borrow().get(&id) {
+            return Ok(oversized);
+        }
+        let commit = self.repo.find_commit(id)?;
+        let oversized = self
+            .exclusions
+            .exceeds_changeset(self.changed_files(&commit, false)?.len());
+        self.oversized.borrow_mut().insert(id, oversized);
+        Ok(oversized)
+    }
+
+    /// Run git blame on a file and return per-author contributions, with
+    /// identities resolved through `.mailmap` and the aliases. Lines last
+    /// changed by an excluded commit are left out.
     /// `rel_path` is relative to the git root.
     pub fn blame_file(&self, rel_path: &Path) -> Result<Vec<BlameInfo>, Box<dyn Error>> {
-        let mut opts = BlameOptions::new();
+        let mut opts = self.blame_options();
         let blame = self.repo.blame_file(rel_path, Some(&mut opts))?;
+        let mailmap = self.mailmap()?;
 
         let mut map: HashMap<String, BlameInfo> = HashMap::new();
 
         for hunk in blame.iter() {
-            let sig = hunk.final_signature();
+            let sig = mailmap.resolve_signature(&hunk.final_signature())?;
             let email = sig.email().unwrap_or("unknown").to_string();
             let author = sig.name().unwrap_or("unknown").to_string();
+            if self.excluded_from_blame(hunk.final_commit_id(), &author, &email)? {
+                continue;
+            }
             // Use the signature timestamp directly — avoids an O(1) git
             // object lookup per hunk that would otherwise be O(N) total.
             let commit_time = sig.when().seconds();
@@ -225,32 +704,40 @@ impl GitRepo {
 
     /// Run git blame on a file and return per-hunk data preserving line ranges.
     /// Used to join blame attribution with per-line FSM classification.
+    /// Hunks last changed by an excluded commit are left out, as in
+    /// [`blame_file`](Self::blame_file).
     pub fn blame_hunks(&self, rel_path: &Path) -> Result<Vec<BlameHunkInfo>, Box<dyn Error>> {
-        let mut opts = BlameOptions::new();
+        let mut opts = self.blame_options();
         let blame = self.repo.blame_file(rel_path, Some(&mut opts))?;
+        let mailmap = self.mailmap()?;
 
-        let result = blame
-            .iter()
-            .map(|hunk| {
-                let sig = hunk.final_signature();
-                BlameHunkInfo {
-                    author: sig.name().unwrap_or("unknown").to_string(),
-                    email: sig.email().unwrap_or("unknown").to_string(),
-                    start_line: hunk.final_start_line(),
-                    lines: hunk.lines_in_hunk(),
-                }
-            })
-            .collect();
+        let mut result = Vec::new();
+        for hunk in blame.iter() {
+            let sig = mailmap.resolve_signature(&hunk.final_signature())?;
+            let author = sig.name().unwrap_or("unknown").to_string();
+            let email = sig.email().unwrap_or("unknown").to_string();
+            if self.excluded_from_blame(hunk.final_commit_id(), &author, &email)? {
+                continue;
+            }
+            result.push(BlameHunkInfo {
+                author,
+                email,
+                start_line: hunk.final_start_line(),
+                lines: hunk.lines_in_hunk(),
+            });
+        }
 
         Ok(result)
     }
 
-    /// Collect authors who have commits since the given timestamp.
+    /// Collect the emails of authors who have commits since the given
+    /// timestamp, resolved through `.mailmap` and the aliases.
     pub fn recent_authors(&self, since: Option<i64>) -> Result<HashSet<String>, Box<dyn Error>> {
         let mut authors = HashSet::new();
+        let mailmap = self.mailmap()?;
 
         self.walk_commits(since, |commit| {
-            if let Some(email) = commit.author().email() {
+            if let Some(email) = commit.author_with_mailmap(&mailmap)?.email() {
                 authors.insert(email.to_string());
             }
             Ok(ControlFlow::Continue(()))
@@ -265,7 +752,21 @@ impl GitRepo {
     /// Returns `(canonical_walk_root, prefix)`. For example:
     ///   - `git_root=/a/b`, `walk_root=/a/b/src` → prefix = `"src"`
     ///   - `git_root=/a/b`, `walk_root=/a/b`     → prefix = `""`
+    ///
+    /// A `walk_root` inside a mounted tree is returned as is, with the
+    /// repository path it stands for. A bare repository has no files to
+    /// walk until a tree is mounted over it.
     pub fn walk_prefix(&self, walk_root: &Path) -> Result<(PathBuf, PathBuf), Box<dyn Error>> {
+        if let Some(prefix) = rev::mounted_path(walk_root) {
+            return Ok((walk_root.to_path_buf(), prefix));
+        }
+        if self.is_bare() {
+            return Err(format!(
+                "{} is a bare repository: analyze a revision with --rev",
+                self.root.display()
+            )
+            .into());
+        }
         let git_root = self
             .root
             .canonicalize()
@@ -291,10 +792,16 @@ impl GitRepo {
         }
     }
 
-    /// Extract the file tree at a given git ref (e.g. "HEAD", "main~3") into
-    /// a destination directory. Writes blobs as files and recurses into subtrees.
-    /// Skips submodules and symlinks.
-    pub fn extract_tree_to_dir(&self, refspec: &str, dest: &Path) -> Result<(), Box<dyn Error>> {
+    /// The root of the working directory, or the git directory of a bare
+    /// repository.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// List the regular files in the tree at a given git ref (e.g. "HEAD",
+    /// "main~3"), in tree order, together with the full commit id. Skips
+    /// submodules, symlinks, and entries with non-UTF-8 names.
+    pub fn tree_files(&self, refspec: &str) -> Result<(String, Vec<TreeFile>), Box<dyn Error>> {
         let obj = self
             .repo
             .revparse_single(refspec)
@@ -302,32 +809,25 @@ impl GitRepo {
         let commit = obj
             .peel_to_commit()
             .map_err(|e| format!("'{refspec}' is not a commit: {e}"))?;
-        let tree = commit.tree()?;
-        self.write_tree_recursive(&tree, dest)
+        let mut files = Vec::new();
+        commit.tree()?.walk(TreeWalkMode::PreOrder, |dir, entry| {
+            if entry.kind() == Some(ObjectType::Blob)
+                && entry.filemode() != SYMLINK_MODE
+                && let Some(name) = entry.name()
+            {
+                files.push(TreeFile {
+                    path: Path::new(dir).join(name),
+                    id: entry.id(),
+                });
+            }
+            TreeWalkResult::Ok
+        })?;
+        Ok((commit.id().to_string(), files))
     }
 
-    /// Recursively write a git tree to a filesystem directory.
-    fn write_tree_recursive(&self, tree: &Tree, dest: &Path) -> Result<(), Box<dyn Error>> {
-        for entry in tree.iter() {
-            let name = entry
-                .name()
-                .ok_or_else(|| format!("non-UTF-8 entry in tree: {:?}", entry.id()))?;
-            let path = dest.join(name);
-
-            match entry.kind() {
-                Some(ObjectType::Blob) => {
-                    let blob = self.repo.find_blob(entry.id())?;
-                    fs::write(&path, blob.content())?;
-                }
-                Some(ObjectType::Tree) => {
-                    let subtree = self.repo.find_tree(entry.id())?;
-                    fs::create_dir_all(&path)?;
-                    self.write_tree_recursive(&subtree, &path)?;
-                }
-                _ => {} // skip submodules, symlinks, etc.
-            }
-        }
-        Ok(())
+    /// Read the content of a blob listed by [`tree_files`](Self::tree_files).
+    pub fn blob_content(&self, id: Oid) -> Result<Vec<u8>, Box<dyn Error>> {
+        Ok(self.repo.find_blob(id)?.content().to_vec())
     }
 
     /// Diff a commit against its parent to get the list of changed file paths.
@@ -365,7 +865,74 @@ impl GitRepo {
         Ok(paths)
     }
 
-    fn changed_files(&self, commit: &git2::Commit) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    /// Return the lines added or modified in the working directory since the
+    /// merge base of `since_ref` and HEAD, per file. Comparing against the
+    /// merge base leaves out changes made on `since_ref` after the branch
+    /// point; comparing with the working directory includes uncommitted and
+    /// untracked files, so line numbers match the files on disk.
+    pub fn lines_changed_since(
+        &self,
+        since_ref: &str,
+    ) -> Result<Vec<ChangedLines>, Box<dyn Error>> {
+        let since = self
+            .repo
+            .revparse_single(since_ref)
+            .map_err(|e| format!("cannot resolve ref '{since_ref}': {e}"))?
+            .peel_to_commit()
+            .map_err(|e| format!("'{since_ref}' is not a commit: {e}"))?;
+        let base = self
+            .repo
+            .head()
+            .and_then(|h| h.peel_to_commit())
+            .and_then(|head| self.repo.merge_base(since.id(), head.id()))
+            .and_then(|oid| self.repo.find_commit(oid))
+            .unwrap_or(since);
+
+        let mut opts = DiffOptions::new();
+        opts.context_lines(0)
+            .include_untracked(true)
+            .recurse_untracked_dirs(true)
+            .show_untracked_content(true);
+        let mut diff = self
+            .repo
+            .diff_tree_to_workdir_with_index(Some(&base.tree()?), Some(&mut opts))?;
+        diff.find_similar(None)?;
+
+        let mut map: HashMap<PathBuf, Vec<usize>> = HashMap::new();
+        diff.foreach(
+            &mut |_, _| true,
+            None,
+            None,
+            Some(&mut |delta, _, line| {
+                if line.origin() == '+'
+                    && let (Some(path), Some(n)) = (delta.new_file().path(), line.new_lineno())
+                {
+                    map.entry(path.to_path_buf()).or_default().push(n as usize);
+                }
+                true
+            }),
+        )?;
+
+        let mut changed: Vec<ChangedLines> = map
+            .into_iter()
+            .map(|(path, mut lines)| {
+                lines.sort_unstable();
+                lines.dedup();
+                ChangedLines { path, lines }
+            })
+            .collect();
+        changed.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(changed)
+    }
+
+    /// Diff a commit against its first parent, detecting renames. Copies
+    /// are left as additions so a copy does not take over the original's
+    /// history.
+    fn changed_files(
+        &self,
+        commit: &git2::Commit,
+        lines: bool,
+    ) -> Result<Vec<FileChange>, Box<dyn Error>> {
         let tree = commit.tree()?;
         let parent_tree = if commit.parent_count() > 0 {
             Some(commit.parent(0)?.tree()?)
@@ -374,17 +941,43 @@ impl GitRepo {
         };
 
         let mut opts = DiffOptions::new();
-        let diff =
+        let mut diff =
             self.repo
                 .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut opts))?;
+        if self.rename_threshold > 0 {
+            let mut find = DiffFindOptions::new();
+            find.renames(true).rename_threshold(self.rename_threshold);
+            diff.find_similar(Some(&mut find))?;
+        }
 
-        let mut paths = Vec::new();
-        for delta in diff.deltas() {
-            if let Some(path) = delta.new_file().path() {
-                paths.push(path.to_path_buf());
-            }
+        let mut changes = Vec::new();
+        for (idx, delta) in diff.deltas().enumerate() {
+            let Some(path) = delta.new_file().path() else {
+                continue;
+            };
+            let renamed = delta.status() == Delta::Renamed;
+            let unchanged = renamed && delta.old_file().id() == delta.new_file().id();
+            // Binary files have no patch and count no lines.
+            let patch = if lines && !unchanged {
+                Patch::from_diff(&diff, idx)?
+            } else {
+                None
+            };
+            let (_, added, deleted) = match patch {
+                Some(patch) => patch.line_stats()?,
+                None => (0, 0, 0),
+            };
+            changes.push(FileChange {
+                path: path.to_path_buf(),
+                renamed_from: renamed
+                    .then(|| delta.old_file().path().map(Path::to_path_buf))
+                    .flatten(),
+                unchanged,
+                added,
+                deleted,
+            });
         }
-        Ok(paths)
+        Ok(changes)
     }
 }
 
@@ -223,3 +223,563 @@ fn test_empty_repo() {
     let co = git_repo.co_changing_commits(None);
     assert!(co.is_err() || co.unwrap().is_empty());
 }
+
+#[test]
+fn test_lines_changed_since() {
+    let (dir, repo) = create_test_repo();
+    let base = make_commit(&repo, &[("a.txt", "one\ntwo\nthree\n")], "base");
+    make_commit(&repo, &[("a.txt", "one\nTWO\nthree\nfour\n")], "edit");
+    fs::write(dir.path().join("new.txt"), "x\ny\n").unwrap();
+
+    let git_repo = GitRepo::open(dir.path()).unwrap();
+    let changed = git_repo.lines_changed_since(&base.to_string()).unwrap();
+    let by_path: HashMap<PathBuf, Vec<usize>> =
+        changed.into_iter().map(|c| (c.path, c.lines)).collect();
+    assert_eq!(by_path[Path::new("a.txt")], vec![2, 4]);
+    assert_eq!(
+        by_path[Path::new("new.txt")],
+        vec![1, 2],
+        "untracked files count"
+    );
+    assert!(
+        git_repo.lines_changed_since("HEAD").unwrap().len() == 1,
+        "only the untracked file differs from HEAD"
+    );
+}
+
+#[test]
+fn test_first_parent_history_skips_merged_branches() {
+    let (dir, repo) = create_test_repo();
+    let first = make_commit_at(&repo, &[("a.txt", "1")], "first", 1_000);
+    let second = make_commit_at(&repo, &[("a.txt", "2")], "second", 2_000);
+
+    // A side-branch commit off `first`, merged into the main line.
+    let sig = git2::Signature::new("Test", "test@test.com", &git2::Time::new(2_500, 0)).unwrap();
+    let first_commit = repo.find_commit(first).unwrap();
+    let side = repo
+        .commit(
+            None,
+            &sig,
+            &sig,
+            "side",
+            &first_commit.tree().unwrap(),
+            &[&first_commit],
+        )
+        .unwrap();
+    let sig = git2::Signature::new("Test", "test@test.com", &git2::Time::new(3_000, 0)).unwrap();
+    let second_commit = repo.find_commit(second).unwrap();
+    let side_commit = repo.find_commit(side).unwrap();
+    let merge = repo
+        .commit(
+            Some("HEAD"),
+            &sig,
+            &sig,
+            "merge",
+            &second_commit.tree().unwrap(),
+            &[&second_commit, &side_commit],
+        )
+        .unwrap();
+
+    let git_repo = GitRepo::open(dir.path()).unwrap();
+    let ids = |since| -> Vec<String> {
+        git_repo
+            .first_parent_history(since)
+            .unwrap()
+            .into_iter()
+            .map(|c| c.id)
+            .collect()
+    };
+    assert_eq!(
+        ids(None),
+        [merge, second, first].map(|o| o.to_string()),
+        "the merged side commit is not on the first-parent line"
+    );
+    assert_eq!(
+        ids(Some(2_500)),
+        [merge, second].map(|o| o.to_string()),
+        "stops after the first commit older than the cutoff"
+    );
+}
+
+#[test]
+fn test_since_keeps_commits_behind_an_older_side_branch_commit() {
+    let (dir, repo) = create_test_repo();
+    let base = make_commit_at(&repo, &[("base.rs", "1")], "base", 1_000);
+    let commit = |file: &str, epoch: i64, parents: &[git2::Oid]| {
+        let sig =
+            git2::Signature::new("Test", "test@test.com", &git2::Time::new(epoch, 0)).unwrap();
+        let parent = repo.find_commit(parents[0]).unwrap();
+        let mut builder = repo.treebuilder(Some(&parent.tree().unwrap())).unwrap();
+        let blob = repo.blob(file.as_bytes()).unwrap();
+        builder.insert(file, blob, 0o100644).unwrap();
+        let tree = repo.find_tree(builder.write().unwrap()).unwrap();
+        let parents: Vec<_> = parents
+            .iter()
+            .map(|&id| repo.find_commit(id).unwrap())
+            .collect();
+        let parents: Vec<&git2::Commit> = parents.iter().collect();
+        repo.commit(None, &sig, &sig, file, &tree, &parents)
+            .unwrap()
+    };
+    // A side branch whose second commit is dated before its first.
+    let recent = commit("p2.rs", 6_000, &[base]);
+    let backdated = commit("old.rs", 500, &[recent]);
+    let main = make_commit_at(&repo, &[("p1.rs", "1")], "main", 5_000);
+    let merge = commit("merge.rs", 7_000, &[main, backdated]);
+    repo.reference("refs/heads/master", merge, true, "merge")
+        .unwrap();
+    repo.set_head("refs/heads/master").unwrap();
+
+    let git_repo = GitRepo::open(dir.path()).unwrap();
+    let mut paths: Vec<_> = git_repo
+        .file_frequencies(Some(4_000))
+        .unwrap()
+        .into_iter()
+        .map(|f| f.path.to_str().unwrap().to_string())
+        .collect();
+    paths.sort();
+    assert_eq!(paths, ["p1.rs", "p2.rs"]);
+}
+
+#[test]
+fn test_tree_files_lists_regular_files_of_a_ref() {
+    let (dir, repo) = create_test_repo();
+    make_commit(
+        &repo,
+        &[("a.rs", "fn a() {}"), ("src/b.rs", "fn b() {}")],
+        "one",
+    );
+    make_commit(&repo, &[("src/c.rs", "fn c() {}")], "two");
+    let git_repo = GitRepo::open(dir.path()).unwrap();
+
+    let (commit, files) = git_repo.tree_files("HEAD~1").unwrap();
+    let paths: Vec<&Path> = files.iter().map(|f| f.path.as_path()).collect();
+    assert_eq!(paths, [Path::new("a.rs"), Path::new("src/b.rs")]);
+    assert_eq!(commit.len(), 40);
+    assert_eq!(git_repo.blob_content(files[1].id).unwrap(), b"fn b() {}");
+
+    let (_, files) = git_repo.tree_files("HEAD").unwrap();
+    assert_eq!(files.len(), 3);
+    assert!(git_repo.tree_files("missing").is_err());
+}
+
+#[test]
+fn test_open_bare_repository() {
+    let (dir, repo) = create_test_repo();
+    make_commit(&repo, &[("a.rs", "line1\nline2\n")], "add a");
+    make_commit(&repo, &[("a.rs", "line1\nline2\nline3\n")], "edit a");
+    let bare_dir = tempfile::tempdir().unwrap();
+    let bare_path = bare_dir.path().join("mirror.git");
+    git2::build::RepoBuilder::new()
+        .bare(true)
+        .clone(dir.path().to_str().unwrap(), &bare_path)
+        .unwrap();
+
+    let git_repo = GitRepo::open(&bare_path).unwrap();
+    assert!(git_repo.is_bare());
+    assert_eq!(git_repo.root(), git_repo.git_dir());
+    let freqs = git_repo.file_frequencies(None).unwrap();
+    assert_eq!(freqs.len(), 1);
+    assert_eq!(freqs[0].commits, 2);
+    let blames = git_repo.blame_file(Path::new("a.rs")).unwrap();
+    assert_eq!(blames[0].lines, 3);
+
+    let err = git_repo.walk_prefix(&bare_path).err().unwrap();
+    assert!(err.to_string().contains("bare repository"), "{err}");
+}
+
+#[test]
+fn test_open_linked_worktree() {
+    let (dir, repo) = create_test_repo();
+    make_commit(&repo, &[("src/a.rs", "fn a() {}\n")], "add a");
+    let wt_dir = tempfile::tempdir().unwrap();
+    let wt_path = wt_dir.path().join("wt");
+    repo.worktree("wt", &wt_path, None).unwrap();
+
+    let git_repo = GitRepo::open(&wt_path.join("src")).unwrap();
+    assert!(!git_repo.is_bare());
+    let (walk_root, prefix) = git_repo.walk_prefix(&wt_path.join("src")).unwrap();
+    assert_eq!(walk_root, wt_path.join("src").canonicalize().unwrap());
+    assert_eq!(prefix, Path::new("src"));
+    assert_eq!(git_repo.file_frequencies(None).unwrap().len(), 1);
+    drop(dir);
+}
+
+fn commit_as(repo: &Repository, name: &str, email: &str, content: &str) {
+    let sig = git2::Signature::new(name, email, &git2::Time::new(1_700_000_000, 0)).unwrap();
+    let mut index = repo.index().unwrap();
+    fs::write(repo.workdir().unwrap().join("a.rs"), content).unwrap();
+    index.add_path(Path::new("a.rs")).unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+    repo.commit(Some("HEAD"), &sig, &sig, "change", &tree, &parents)
+        .unwrap();
+}
+
+#[test]
+fn test_identities_resolve_through_mailmap_and_aliases() {
+    let (dir, repo) = create_test_repo();
+    commit_as(&repo, "Jane", "jane@old.com", "a\n");
+    commit_as(&repo, "jdoe", "jane@new.com", "a\nb\n");
+    commit_as(&repo, "J", "j@laptop", "a\nb\nc\n");
+    fs::write(
+        dir.path().join(".mailmap"),
+        "Jane Doe <jane@new.com> <jane@old.com>\nJane Doe <jane@new.com>\n",
+    )
+    .unwrap();
+
+    let git_repo = GitRepo::open(dir.path()).unwrap();
+    let blames = git_repo.blame_file(Path::new("a.rs")).unwrap();
+    assert_eq!(blames.len(), 2, "the laptop identity is not in .mailmap");
+
+    let config = crate::config::AuthorsConfig {
+        aliases: [(
+            "j@laptop".to_string(),
+            "Jane Doe <jane@new.com>".to_string(),
+        )]
+        .into(),
+    };
+    let aliases = AuthorAliases::resolve(&config).unwrap();
+    let git_repo = git_repo.with_aliases(Some(&aliases));
+    let blames = git_repo.blame_file(Path::new("a.rs")).unwrap();
+    assert_eq!(blames.len(), 1);
+    assert_eq!(
+        (
+            blames[0].author.as_str(),
+            blames[0].email.as_str(),
+            blames[0].lines
+        ),
+        ("Jane Doe", "jane@new.com", 3)
+    );
+    let hunks = git_repo.blame_hunks(Path::new("a.rs")).unwrap();
+    assert!(hunks.iter().all(|h| h.email == "jane@new.com"));
+    let recent = git_repo.recent_authors(None).unwrap();
+    assert_eq!(recent, HashSet::from(["jane@new.com".to_string()]));
+}
+
+fn rename_at(repo: &Repository, renames: &[(&str, &str)], epoch: i64) -> git2::Oid {
+    rename_as(repo, "Test", "test@test.com", renames, epoch)
+}
+
+fn rename_as(
+    repo: &Repository,
+    name: &str,
+    email: &str,
+    renames: &[(&str, &str)],
+    epoch: i64,
+) -> git2::Oid {
+    let sig = git2::Signature::new(name, email, &git2::Time::new(epoch, 0)).unwrap();
+    let workdir = repo.workdir().unwrap();
+    let mut index = repo.index().unwrap();
+    for (from, to) in renames {
+        fs::create_dir_all(workdir.join(to).parent().unwrap()).unwrap();
+        fs::rename(workdir.join(from), workdir.join(to)).unwrap();
+        index.remove_path(Path::new(from)).unwrap();
+        index.add_path(Path::new(to)).unwrap();
+    }
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let parent = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.commit(Some("HEAD"), &sig, &sig, "move", &tree, &[&parent])
+        .unwrap()
+}
+
+const LONG_FILE: &str = "fn a() {\n    let x = 1;\n    let y = 2;\n    x + y\n}\n";
+
+#[test]
+fn test_history_follows_renames() {
+    let (dir, repo) = create_test_repo();
+    make_commit_at(&repo, &[("a.rs", LONG_FILE)], "add", 1_000);
+    make_commit_at(
+        &repo,
+        &[("a.rs", &format!("{LONG_FILE}// 2\n"))],
+        "edit",
+        2_000,
+    );
+    rename_at(&repo, &[("a.rs", "src/b.rs")], 3_000);
+    make_commit_at(
+        &repo,
+        &[("src/b.rs", &format!("{LONG_FILE}// 3\n"))],
+        "edit",
+        4_000,
+    );
+
+    let git_repo = GitRepo::open(dir.path()).unwrap();
+    let freqs = git_repo.file_frequencies(None).unwrap();
+    assert_eq!(freqs.len(), 1, "a.rs is stitched onto src/b.rs");
+    assert_eq!(freqs[0].path, Path::new("src/b.rs"));
+    assert_eq!(freqs[0].commits, 3, "the pure move is not a change");
+    assert_eq!(freqs[0].first_commit, 1_000);
+
+    let untracked = GitRepo::open(dir.path()).unwrap().with_rename_threshold(0);
+    let freqs = untracked.file_frequencies(None).unwrap();
+    let commits = |p: &str| {
+        freqs
+            .iter()
+            .find(|f| f.path == Path::new(p))
+            .unwrap()
+            .commits
+    };
+    assert_eq!((commits("a.rs"), commits("src/b.rs")), (3, 2));
+}
+
+#[test]
+fn test_renames_are_followed_when_commit_times_tie() {
+    let (dir, repo) = create_test_repo();
+    make_commit(&repo, &[("old.rs", LONG_FILE)], "add");
+    make_commit(&repo, &[("old.rs", &format!("{LONG_FILE}// 2\n"))], "edit");
+    rename_at(&repo, &[("old.rs", "new.rs")], 1_700_000_000);
+    make_commit(&repo, &[("new.rs", &format!("{LONG_FILE}// 4\n"))], "edit");
+
+    let git_repo = GitRepo::open(dir.path()).unwrap();
+    let churn = git_repo.line_churn(None).unwrap();
+    assert_eq!(churn.len(), 1, "old.rs is stitched onto new.rs");
+    assert_eq!(churn[0].frequency.path, Path::new("new.rs"));
+    assert_eq!(churn[0].frequency.commits, 3);
+}
+
+#[test]
+fn test_renames_are_followed_through_filtered_commits() {
+    let (dir, repo) = create_test_repo();
+    commit_as(&repo, "Alice", "alice@x.com", LONG_FILE);
+    commit_as(&repo, "Alice", "alice@x.com", &format!("{LONG_FILE}// 2\n"));
+    commit_as(&repo, "Alice", "alice@x.com", &format!("{LONG_FILE}// 3\n"));
+    let mv = rename_as(
+        &repo,
+        "Bob",
+        "bob@x.com",
+        &[("a.rs", "new.rs")],
+        1_700_000_100,
+    );
+    fs::write(dir.path().join("ignored-revs"), format!("{mv}\n")).unwrap();
+
+    let commits = |git_repo: GitRepo| {
+        git_repo
+            .file_frequencies(None)
+            .unwrap()
+            .into_iter()
+            .map(|f| (f.path.to_str().unwrap().to_string(), f.commits))
+            .collect::<Vec<_>>()
+    };
+    let expected = vec![("new.rs".to_string(), 3)];
+
+    let alice = CommitFilter::default().with_authors(&["alice".to_string()]);
+    let by_author = GitRepo::open(dir.path())
+        .unwrap()
+        .with_commit_filter(Some(&alice))
+        .unwrap();
+    assert_eq!(commits(by_author), expected);
+
+    for config in [
+        crate::config::GitConfig {
+            ignore_authors: vec!["Bob".to_string()],
+            ..Default::default()
+        },
+        crate::config::GitConfig {
+            ignore_commits: Some("ignored-revs".to_string()),
+            ..Default::default()
+        },
+    ] {
+        let exclusions = CommitExclusions::resolve(&config, dir.path()).unwrap();
+        let git_repo = GitRepo::open(dir.path())
+            .unwrap()
+            .with_exclusions(Some(&exclusions));
+        assert_eq!(commits(git_repo), expected);
+    }
+}
+
+#[test]
+fn test_a_move_keeps_age_and_adds_no_coupling() {
+    let (dir, repo) = create_test_repo();
+    make_commit_at(
+        &repo,
+        &[("a.rs", LONG_FILE), ("b.rs", &LONG_FILE.replace('a', "b"))],
+        "add",
+        1_000,
+    );
+    rename_at(&repo, &[("a.rs", "lib/a.rs"), ("b.rs", "lib/b.rs")], 2_000);
+
+    let git_repo = GitRepo::open(dir.path()).unwrap();
+    let targets = [PathBuf::from("lib/a.rs"), PathBuf::from("lib/b.rs")];
+    let modified = git_repo.last_modified_per_file(&targets).unwrap();
+    assert_eq!(modified[&targets[0]], 1_000);
+    assert_eq!(modified[&targets[1]], 1_000);
+    let co = git_repo.co_changing_commits(None).unwrap();
+    assert_eq!(co.len(), 1, "only the commit that added both files");
+}
+
+#[test]
+fn test_a_path_reused_after_a_rename_keeps_its_own_history() {
+    let (dir, repo) = create_test_repo();
+    make_commit_at(&repo, &[("a.rs", LONG_FILE)], "add", 1_000);
+    rename_at(&repo, &[("a.rs", "b.rs")], 2_000);
+    make_commit_at(&repo, &[("a.rs", "// new file\n")], "new a", 3_000);
+
+    let git_repo = GitRepo::open(dir.path()).unwrap();
+    let freqs = git_repo.file_frequencies(None).unwrap();
+    let first = |p: &str| {
+        freqs
+            .iter()
+            .find(|f| f.path == Path::new(p))
+            .unwrap()
+            .first_commit
+    };
+    assert_eq!((first("a.rs"), first("b.rs")), (3_000, 1_000));
+}
+
+#[test]
+fn test_line_churn_counts_lines_per_author_across_renames() {
+    let (dir, repo) = create_test_repo();
+    commit_as(&repo, "Jane", "jane@x.com", "a\nb\nc\n");
+    commit_as(&repo, "Bob", "bob@x.com", "a\nB\nc\nd\ne\n");
+    rename_at(&repo, &[("a.rs", "src/a.rs")], 1_700_000_100);
+    make_commit(&repo, &[("src/a.rs", "a\nB\nc\n")], "trim");
+
+    let git_repo = GitRepo::open(dir.path()).unwrap();
+    let churn = git_repo.line_churn(None).unwrap();
+    assert_eq!(churn.len(), 1, "a.rs is stitched onto src/a.rs");
+    let file = &churn[0];
+    assert_eq!(file.frequency.path, Path::new("src/a.rs"));
+    assert_eq!(file.frequency.commits, 3, "the pure move is not a change");
+    assert_eq!((file.added, file.deleted), (6, 3));
+    let shares: Vec<_> = file
+        .authors
+        .iter()
+        .map(|a| (a.email.as_str(), a.added, a.deleted))
+        .collect();
+    assert_eq!(
+        shares,
+        [
+            ("bob@x.com", 3, 1),
+            ("jane@x.com", 3, 0),
+            ("test@test.com", 0, 2)
+        ]
+    );
+}
+
+#[test]
+fn test_commit_filter_restricts_range_date_author_and_paths() {
+    let (dir, repo) = create_test_repo();
+    make_commit_at(&repo, &[("a.rs", "1"), ("b.rs", "1")], "one", 1_000);
+    let v1 = make_commit_at(&repo, &[("a.rs", "2")], "two", 2_000);
+    repo.tag_lightweight("v1", &repo.find_object(v1, None).unwrap(), false)
+        .unwrap();
+    make_commit_at(&repo, &[("a.rs", "3"), ("lib/c.rs", "3")], "three", 3_000);
+    let sig = git2::Signature::new("Ann", "ann@x.com", &git2::Time::new(4_000, 0)).unwrap();
+    fs::write(dir.path().join("b.rs"), "4").unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new("b.rs")).unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.commit(Some("HEAD"), &sig, &sig, "four", &tree, &[&head])
+        .unwrap();
+
+    let commits = |filter: CommitFilter| {
+        let git_repo = GitRepo::open(dir.path())
+            .unwrap()
+            .with_commit_filter(Some(&filter))
+            .unwrap();
+        let mut freqs: Vec<_> = git_repo
+            .file_frequencies(None)
+            .unwrap()
+            .into_iter()
+            .map(|f| (f.path.to_str().unwrap().to_string(), f.commits))
+            .collect();
+        freqs.sort();
+        freqs
+    };
+    let files = |list: &[(&str, usize)]| -> Vec<(String, usize)> {
+        list.iter().map(|(p, n)| (p.to_string(), *n)).collect()
+    };
+
+    let after_v1 = CommitFilter::default().with_range("v1..").unwrap();
+    assert_eq!(
+        commits(after_v1),
+        files(&[("a.rs", 1), ("b.rs", 1), ("lib/c.rs", 1)])
+    );
+    let up_to_v1 = CommitFilter::default().with_range("..v1").unwrap();
+    assert_eq!(commits(up_to_v1), files(&[("a.rs", 2), ("b.rs", 1)]));
+    let until = CommitFilter::default().with_until(Some(2_500));
+    assert_eq!(commits(until), files(&[("a.rs", 2), ("b.rs", 1)]));
+    let ann = CommitFilter::default().with_authors(&["ANN@".to_string()]);
+    assert_eq!(commits(ann), files(&[("b.rs", 1)]));
+    let lib = CommitFilter::default()
+        .with_paths(&["lib".to_string()])
+        .unwrap();
+    assert_eq!(commits(lib), files(&[("lib/c.rs", 1)]));
+
+    // Blame reads the tree at the end of the range.
+    let git_repo = GitRepo::open(dir.path())
+        .unwrap()
+        .with_commit_filter(Some(&CommitFilter::default().with_until(Some(1_500))))
+        .unwrap();
+    assert!(git_repo.blame_file(Path::new("lib/c.rs")).is_err());
+    assert_eq!(
+        git_repo.blame_file(Path::new("b.rs")).unwrap()[0].email,
+        "test@test.com"
+    );
+
+    let unknown = CommitFilter::default().with_range("v9..").unwrap();
+    let err = GitRepo::open(dir.path())
+        .unwrap()
+        .with_commit_filter(Some(&unknown))
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("cannot resolve ref 'v9'"), "{err}");
+}
+
+#[test]
+fn test_exclusions_drop_bots_listed_commits_and_mass_changes() {
+    let (dir, repo) = create_test_repo();
+    make_commit_at(&repo, &[("a.rs", "1\n"), ("b.rs", "1\n")], "one", 1_000);
+    commit_as(&repo, "dependabot[bot]", "bot@github.com", "1\n2\n");
+    make_commit_at(
+        &repo,
+        &[("c.rs", "1"), ("d.rs", "1"), ("e.rs", "1")],
+        "vendor",
+        3_000,
+    );
+    let reformat = make_commit_at(&repo, &[("a.rs", "1\n2\n"), ("b.rs", "2\n")], "fmt", 4_000);
+    fs::write(
+        dir.path().join(".git-blame-ignore-revs"),
+        format!("# rustfmt\n{reformat}\n"),
+    )
+    .unwrap();
+
+    let config = crate::config::GitConfig {
+        ignore_authors: vec![r"\[bot\]".to_string()],
+        ignore_commits: Some(".git-blame-ignore-revs".to_string()),
+        max_changeset_size: Some(2),
+        ..Default::default()
+    };
+    let exclusions = CommitExclusions::resolve(&config, dir.path()).unwrap();
+    let git_repo = GitRepo::open(dir.path())
+        .unwrap()
+        .with_exclusions(Some(&exclusions));
+
+    let mut freqs: Vec<_> = git_repo
+        .file_frequencies(None)
+        .unwrap()
+        .into_iter()
+        .map(|f| (f.path.to_str().unwrap().to_string(), f.commits))
+        .collect();
+    freqs.sort();
+    assert_eq!(
+        freqs,
+        vec![("a.rs".to_string(), 1), ("b.rs".to_string(), 1)]
+    );
+    let co = git_repo.co_changing_commits(None).unwrap();
+    assert_eq!(co.len(), 1);
+    assert_eq!(co[0].len(), 2);
+
+    // Blame drops the lines last touched by an ignored commit.
+    let blame = git_repo.blame_file(Path::new("a.rs")).unwrap();
+    assert_eq!(blame.len(), 1);
+    assert_eq!(
+        (blame[0].email.as_str(), blame[0].lines),
+        ("test@test.com", 1)
+    );
+}
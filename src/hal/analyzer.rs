@@ -1,7 +1,9 @@
+use serde::{Deserialize, Serialize};
+
 use super::tokenizer::TokenCounts;
 
 /// Halstead complexity metrics computed from token counts.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HalsteadMetrics {
     pub distinct_operators: usize, // η₁
     pub distinct_operands: usize,  // η₂
@@ -13,18 +13,22 @@ mod tokenizer;
 use std::error::Error;
 use std::path::Path;
 
+use crate::cache::{self, AnalysisCache};
 use crate::loc::counter::LineKind;
 use crate::loc::language::LanguageSpec;
 use crate::report_helpers;
 use crate::util::read_and_classify;
 use crate::walk::WalkConfig;
 use analyzer::compute;
-use report::{FileHalsteadMetrics, print_json, print_report, print_short, print_terse};
+use report::{print_json, print_report, print_short, print_terse};
 use string_mask::multi_line_string_mask;
 use tokenizer::{count_tokens, rules_for};
 
+pub use analyzer::HalsteadMetrics;
+pub use report::FileHalsteadMetrics;
+
 /// Analyze pre-read content (avoids re-reading the file).
-pub(crate) fn analyze_content(
+pub fn analyze_content(
     lines: &[String],
     kinds: &[LineKind],
     spec: &LanguageSpec,
@@ -50,38 +54,30 @@ pub(crate) fn analyze_content(
 
 /// Read a file from disk, classify lines, tokenize code, and compute
 /// Halstead metrics. Returns `None` for binary or unsupported files.
-pub(crate) fn analyze_file(
+pub fn analyze_file(
     path: &Path,
     spec: &LanguageSpec,
 ) -> Result<Option<FileHalsteadMetrics>, Box<dyn Error>> {
-    let rules = match rules_for(spec.name) {
-        Some(r) => r,
-        None => return Ok(None),
-    };
-
-    let (lines, kinds) = match read_and_classify(path, spec)? {
-        Some(v) => v,
-        None => return Ok(None),
-    };
-    let string_mask = multi_line_string_mask(&lines, spec);
-
-    // Collect only code lines that are not inside multi-line strings
-    let code_lines: Vec<&str> = lines
-        .iter()
-        .zip(&kinds)
-        .zip(&string_mask)
-        .filter(|((_, k), in_string)| **k == LineKind::Code && !*in_string)
-        .map(|((line, _), _)| line.as_str())
-        .collect();
+    analyze_file_cached(path, spec, None)
+}
 
-    if code_lines.is_empty() {
+/// Like [`analyze_file`], but reuses the Halstead counts stored in `cache`
+/// when the file content has not changed.
+pub fn analyze_file_cached(
+    path: &Path,
+    spec: &LanguageSpec,
+    cache: Option<&AnalysisCache>,
+) -> Result<Option<FileHalsteadMetrics>, Box<dyn Error>> {
+    if rules_for(spec.name).is_none() {
         return Ok(None);
     }
 
-    let counts = count_tokens(&code_lines, rules, spec.line_comments);
-    let metrics = match compute(&counts) {
-        Some(m) => m,
-        None => return Ok(None),
+    let metrics = cache::cached(cache, "hal", path, spec, || {
+        Ok(read_and_classify(path, spec)?
+            .and_then(|(lines, kinds)| analyze_content(&lines, &kinds, spec)))
+    })?;
+    let Some(metrics) = metrics else {
+        return Ok(None);
     };
 
     Ok(Some(FileHalsteadMetrics {
@@ -91,15 +87,10 @@ pub(crate) fn analyze_file(
     }))
 }
 
-/// Walk source files, compute Halstead metrics for each, sort by the
-/// chosen metric (effort, volume, or bugs), and print results.
-pub fn run(
-    cfg: &WalkConfig<'_>,
-    output: crate::cli::OutputMode,
-    top: usize,
-    sort_by: &str,
-) -> Result<(), Box<dyn Error>> {
-    let mut results = cfg.collect_analysis(analyze_file);
+/// Walk source files and compute Halstead metrics for each, sorted
+/// descending by the chosen metric (`effort`, `volume`, or `bugs`).
+pub fn analyze_project(cfg: &WalkConfig<'_>, sort_by: &str) -> Vec<FileHalsteadMetrics> {
+    let mut results = cfg.collect_analysis(|path, spec| analyze_file_cached(path, spec, cfg.cache));
 
     // Sort by chosen metric descending
     match sort_by {
@@ -123,6 +114,19 @@ pub fn run(
         }),
     }
 
+    results
+}
+
+/// Walk source files, compute Halstead metrics for each, sort by the
+/// chosen metric (effort, volume, or bugs), and print results.
+pub fn run(
+    cfg: &WalkConfig<'_>,
+    output: crate::cli::OutputMode,
+    top: usize,
+    sort_by: &str,
+) -> Result<(), Box<dyn Error>> {
+    let mut results = analyze_project(cfg, sort_by);
+
     report_helpers::output_results(
         &mut results,
         top,
@@ -138,8 +138,8 @@ struct JsonEntry {
     time: f64,
 }
 
-/// Serialize Halstead metrics as pretty-printed JSON to stdout.
-pub fn print_json(files: &[FileHalsteadMetrics]) -> Result<(), Box<dyn std::error::Error>> {
+/// Serialize Halstead metrics as a pretty-printed JSON string.
+pub fn format_json(files: &[FileHalsteadMetrics]) -> Result<String, Box<dyn std::error::Error>> {
     let entries: Vec<JsonEntry> = files
         .iter()
         .map(|f| {
@@ -162,7 +162,13 @@ pub fn print_json(files: &[FileHalsteadMetrics]) -> Result<(), Box<dyn std::erro
         })
         .collect();
 
-    report_helpers::print_json_stdout(&entries)
+    report_helpers::json_string(&entries)
+}
+
+/// Serialize Halstead metrics as pretty-printed JSON to stdout.
+pub fn print_json(files: &[FileHalsteadMetrics]) -> Result<(), Box<dyn std::error::Error>> {
+    println!("{}", format_json(files)?);
+    Ok(())
 }
 
 /// Print Halstead metrics as a single compact line.
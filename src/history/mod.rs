@@ -0,0 +1,231 @@
+/// Score history — how did code health evolve along the branch?
+///
+/// Samples commits on the first-parent line of HEAD at a fixed interval
+/// (`--every`) back to `--since`, mounts each sampled tree like
+/// `km score --trend` does, and scores it. Scores are stored in a snapshot
+/// file so later runs only score commits they have not seen.
+pub(crate) mod report;
+pub mod snapshots;
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::path::Path;
+
+use chrono::DateTime;
+use serde::Serialize;
+
+use crate::cli::HistoryFormat;
+use crate::git::{GitRepo, HistoryCommit};
+use crate::report_helpers::fnv1a_hex;
+use crate::rev::RevTree;
+use crate::score::{self, ScoreModel, analyzer::Grade, analyzer::score_to_grade};
+use crate::util::{parse_period, parse_since};
+use crate::walk::WalkConfig;
+
+pub use snapshots::{Snapshot, SnapshotStore};
+
+/// Snapshot file name inside the cache directory.
+pub const SNAPSHOT_FILE: &str = "history.json";
+
+/// The score of one sampled commit.
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoryPoint {
+    pub commit: String,
+    /// Commit date, `YYYY-MM-DD` (UTC).
+    pub date: String,
+    /// Commit time, Unix seconds.
+    pub timestamp: i64,
+    pub score: f64,
+    pub grade: Grade,
+    pub files_analyzed: usize,
+    pub total_loc: usize,
+    /// Dimension score keyed by dimension key.
+    pub dimensions: BTreeMap<String, f64>,
+}
+
+/// The sampled score series, oldest first.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScoreHistory {
+    pub every: String,
+    pub since: String,
+    pub points: Vec<HistoryPoint>,
+    /// Samples read from the snapshot file instead of being scored.
+    pub reused: usize,
+    pub model: ScoreModel,
+}
+
+/// Sampling and scoring options for [`compute_history`].
+pub struct HistoryOptions<'a> {
+    /// Interval between samples, e.g. `1w` or `1m`.
+    pub every: &'a str,
+    /// How far back to sample, e.g. `1y`.
+    pub since: &'a str,
+    pub min_lines: usize,
+    pub model: &'a ScoreModel,
+    /// Snapshot file to read and update; `None` scores every sample.
+    pub snapshots: Option<&'a Path>,
+}
+
+/// Pick one commit per `every` seconds, walking back from the newest
+/// commit in `commits` (newest first, as listed by
+/// `GitRepo::first_parent_history`) to the cutoff `since`. Each sample is
+/// the newest commit at or before its boundary; a commit covering several
+/// boundaries is sampled once. Returns the samples oldest first.
+pub fn sample_commits(commits: &[HistoryCommit], every: i64, since: i64) -> Vec<&HistoryCommit> {
+    let Some(head) = commits.first() else {
+        return Vec::new();
+    };
+    let mut samples: Vec<&HistoryCommit> = Vec::new();
+    let mut boundary = head.time;
+    let mut i = 0;
+    while boundary >= since {
+        while i < commits.len() && commits[i].time > boundary {
+            i += 1;
+        }
+        let Some(commit) = commits.get(i) else {
+            break;
+        };
+        if samples.last().is_none_or(|last| last.id != commit.id) {
+            samples.push(commit);
+        }
+        let Some(next) = boundary.checked_sub(every) else {
+            break;
+        };
+        boundary = next;
+    }
+    samples.reverse();
+    samples
+}
+
+/// Fingerprint of the settings a sample's score depends on besides the commit.
+fn settings_key(
+    cfg: &WalkConfig<'_>,
+    prefix: &Path,
+    min_lines: usize,
+    model: &ScoreModel,
+) -> Result<String, Box<dyn Error>> {
+    Ok(fnv1a_hex(&format!(
+        "{}|{}|{}|{}|{}|{}",
+        env!("CARGO_PKG_VERSION"),
+        prefix.display(),
+        cfg.include_tests,
+        cfg.filter.fingerprint(),
+        min_lines,
+        serde_json::to_string(model)?,
+    )))
+}
+
+/// Score `cfg.path` in the tree of `commit`.
+fn score_commit(
+    cfg: &WalkConfig<'_>,
+    commit: &str,
+    min_lines: usize,
+    model: &ScoreModel,
+) -> Result<Snapshot, Box<dyn Error>> {
+    let tree = RevTree::load(cfg.path, commit)?;
+    let sample_cfg = WalkConfig::new(tree.path(), cfg.include_tests, cfg.filter)
+        .with_jobs(cfg.jobs)
+        .with_cache(cfg.cache)
+        .with_tree(Some(&tree));
+    let project = score::compute_score(&sample_cfg, 0, min_lines, model)?;
+    Ok(Snapshot {
+        score: project.score,
+        files_analyzed: project.files_analyzed,
+        total_loc: project.total_loc,
+        dimensions: model
+            .dimensions
+            .iter()
+            .zip(&project.dimensions)
+            .map(|(d, s)| (d.dimension.key().to_string(), s.score))
+            .collect(),
+    })
+}
+
+/// Sample the first-parent history of the repository holding `cfg.path`
+/// and score each sample. Samples without source files under `cfg.path`
+/// (before the code existed) are left out.
+pub fn compute_history(
+    cfg: &WalkConfig<'_>,
+    opts: &HistoryOptions<'_>,
+) -> Result<ScoreHistory, Box<dyn Error>> {
+    let every = i64::try_from(parse_period(opts.every, "--every")?)
+        .map_err(|_| "--every value too large")?;
+    if every == 0 {
+        return Err("--every must be greater than zero".into());
+    }
+    let since = parse_since(opts.since)?;
+
+    let repo = GitRepo::open(cfg.path)
+        .map_err(|e| format!("not a git repository (or any parent): {e}"))?;
+    let (_, prefix) = repo.walk_prefix(cfg.path)?;
+    let settings = settings_key(cfg, &prefix, opts.min_lines, opts.model)?;
+    let mut store = opts.snapshots.map(SnapshotStore::load).unwrap_or_default();
+
+    let commits = repo.first_parent_history(Some(since))?;
+    let mut points = Vec::new();
+    let mut reused = 0;
+    let mut scored = 0;
+    for commit in sample_commits(&commits, every, since) {
+        let snapshot = match store.get(&settings, &commit.id) {
+            Some(s) => {
+                reused += 1;
+                s.clone()
+            }
+            None => {
+                let s = score_commit(cfg, &commit.id, opts.min_lines, opts.model)?;
+                store.insert(&settings, &commit.id, s.clone());
+                scored += 1;
+                s
+            }
+        };
+        if snapshot.files_analyzed == 0 {
+            continue;
+        }
+        points.push(HistoryPoint {
+            commit: commit.id.clone(),
+            date: DateTime::from_timestamp(commit.time, 0)
+                .map(|d| d.format("%Y-%m-%d").to_string())
+                .unwrap_or_default(),
+            timestamp: commit.time,
+            score: snapshot.score,
+            grade: score_to_grade(snapshot.score),
+            files_analyzed: snapshot.files_analyzed,
+            total_loc: snapshot.total_loc,
+            dimensions: snapshot.dimensions,
+        });
+    }
+
+    if let Some(path) = opts.snapshots
+        && scored > 0
+        && let Err(e) = store.save(path)
+    {
+        eprintln!("warning: cannot save snapshots to {}: {e}", path.display());
+    }
+
+    Ok(ScoreHistory {
+        every: opts.every.trim().to_string(),
+        since: opts.since.trim().to_string(),
+        points,
+        reused,
+        model: opts.model.clone(),
+    })
+}
+
+/// Entry point for `km history`: sample, score, and print the series.
+pub fn run(
+    cfg: &WalkConfig<'_>,
+    format: HistoryFormat,
+    opts: &HistoryOptions<'_>,
+) -> Result<(), Box<dyn Error>> {
+    let history = compute_history(cfg, opts)?;
+    match format {
+        HistoryFormat::Table => report::print_report(&history),
+        HistoryFormat::Json => report::print_json(&history)?,
+        HistoryFormat::Csv => report::print_csv(&history),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "mod_test.rs"]
+mod tests;
@@ -0,0 +1,251 @@
+use super::*;
+use crate::walk::ExcludeFilter;
+use git2::Repository;
+use std::fs;
+
+const DAY: i64 = 86_400;
+
+const SIMPLE: &str = "fn simple() -> i32 {\n    1\n}\n";
+
+const NESTED: &str = "\
+fn nested(v: &[i32]) -> i32 {
+    let mut n = 0;
+    for x in v {
+        if *x > 0 {
+            while n < 10 {
+                if n % 2 == 0 {
+                    n += 1;
+                }
+            }
+        }
+    }
+    n
+}
+";
+
+fn commit(id: &str, time: i64) -> HistoryCommit {
+    HistoryCommit {
+        id: id.to_string(),
+        time,
+    }
+}
+
+fn make_commit(repo: &Repository, files: &[(&str, &str)], days_ago: i64) {
+    let time = chrono::Utc::now().timestamp() - days_ago * DAY;
+    let sig = git2::Signature::new("Test", "test@test.com", &git2::Time::new(time, 0)).unwrap();
+    let mut index = repo.index().unwrap();
+    for (path, content) in files {
+        let full_path = repo.workdir().unwrap().join(path);
+        fs::create_dir_all(full_path.parent().unwrap()).unwrap();
+        fs::write(&full_path, content).unwrap();
+        index.add_path(Path::new(path)).unwrap();
+    }
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+    repo.commit(Some("HEAD"), &sig, &sig, "change", &tree, &parents)
+        .unwrap();
+}
+
+/// Three commits 70, 35, and 1 days ago; `src/` only appears in the second.
+fn sample_repo() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    let repo = Repository::init(dir.path()).unwrap();
+    make_commit(&repo, &[("a.rs", SIMPLE)], 70);
+    make_commit(&repo, &[("src/b.rs", NESTED)], 35);
+    make_commit(&repo, &[("src/c.rs", NESTED)], 1);
+    dir
+}
+
+fn history(root: &Path, snapshots: Option<&Path>, min_lines: usize) -> ScoreHistory {
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(root, false, &filter);
+    let model = ScoreModel::default();
+    let opts = HistoryOptions {
+        every: "1m",
+        since: "1y",
+        min_lines,
+        model: &model,
+        snapshots,
+    };
+    compute_history(&cfg, &opts).unwrap()
+}
+
+#[test]
+fn sample_commits_takes_newest_commit_per_interval() {
+    let commits = [
+        commit("d", 100),
+        commit("c", 90),
+        commit("b", 50),
+        commit("a", 10),
+    ];
+    let ids: Vec<&str> = sample_commits(&commits, 20, 0)
+        .iter()
+        .map(|c| c.id.as_str())
+        .collect();
+    assert_eq!(
+        ids,
+        ["a", "b", "d"],
+        "c is skipped; a and b cover two boundaries each"
+    );
+
+    let ids: Vec<&str> = sample_commits(&commits, 20, 70)
+        .iter()
+        .map(|c| c.id.as_str())
+        .collect();
+    assert_eq!(ids, ["b", "d"], "boundaries stop at the cutoff");
+    assert!(sample_commits(&[], 20, 0).is_empty());
+
+    let ids: Vec<&str> = sample_commits(&commits, i64::MAX, i64::MIN)
+        .iter()
+        .map(|c| c.id.as_str())
+        .collect();
+    assert_eq!(ids, ["d"], "an interval past the cutoff stops the walk");
+}
+
+#[test]
+fn scores_each_sample_oldest_first() {
+    let dir = sample_repo();
+    let result = history(dir.path(), None, 6);
+    assert_eq!(result.points.len(), 3);
+    assert!(
+        result
+            .points
+            .windows(2)
+            .all(|w| w[0].timestamp < w[1].timestamp)
+    );
+    let files: Vec<usize> = result.points.iter().map(|p| p.files_analyzed).collect();
+    assert_eq!(files, [1, 2, 3]);
+    assert!(result.points[0].dimensions.contains_key("cognitive"));
+    assert_eq!(result.points[0].date.len(), "2026-01-01".len());
+    assert_eq!(result.reused, 0);
+}
+
+#[test]
+fn subdirectory_history_skips_samples_without_files() {
+    let dir = sample_repo();
+    let result = history(&dir.path().join("src"), None, 6);
+    let files: Vec<usize> = result.points.iter().map(|p| p.files_analyzed).collect();
+    assert_eq!(files, [1, 2], "src/ did not exist 70 days ago");
+}
+
+#[test]
+fn snapshots_are_reused_for_the_same_settings() {
+    let dir = sample_repo();
+    let store_dir = tempfile::tempdir().unwrap();
+    let path = store_dir.path().join("cache").join(SNAPSHOT_FILE);
+
+    let first = history(dir.path(), Some(&path), 6);
+    assert_eq!(first.reused, 0);
+    assert!(path.exists());
+    assert!(path.with_file_name(".gitignore").exists());
+    assert_eq!(SnapshotStore::load(&path).len(), 3);
+
+    let second = history(dir.path(), Some(&path), 6);
+    assert_eq!(second.reused, 3);
+    let scores = |h: &ScoreHistory| h.points.iter().map(|p| p.score).collect::<Vec<_>>();
+    assert_eq!(scores(&first), scores(&second));
+
+    let other = history(dir.path(), Some(&path), 8);
+    assert_eq!(other.reused, 0, "different settings score afresh");
+    assert_eq!(SnapshotStore::load(&path).len(), 6);
+}
+
+#[test]
+fn corrupt_snapshot_file_starts_empty() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(SNAPSHOT_FILE);
+    fs::write(&path, "not json").unwrap();
+    assert!(SnapshotStore::load(&path).is_empty());
+}
+
+#[test]
+fn rejects_bad_options_and_non_git_dirs() {
+    let dir = sample_repo();
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(dir.path(), false, &filter);
+    let model = ScoreModel::default();
+    for (every, expected) in [
+        ("0d", "greater than zero"),
+        ("1x", "unknown unit in --every"),
+        ("500000000000y", "--every value too large"),
+    ] {
+        let opts = HistoryOptions {
+            every,
+            since: "1y",
+            min_lines: 6,
+            model: &model,
+            snapshots: None,
+        };
+        let err = compute_history(&cfg, &opts).unwrap_err();
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+
+    let plain = tempfile::tempdir().unwrap();
+    let cfg = WalkConfig::new(plain.path(), false, &filter);
+    let opts = HistoryOptions {
+        every: "1m",
+        since: "1y",
+        min_lines: 6,
+        model: &model,
+        snapshots: None,
+    };
+    let err = compute_history(&cfg, &opts).unwrap_err();
+    assert!(err.to_string().contains("not a git repository"), "{err}");
+}
+
+#[test]
+fn run_all_formats() {
+    let dir = sample_repo();
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(dir.path(), false, &filter);
+    let model = ScoreModel::default();
+    let opts = HistoryOptions {
+        every: "1w",
+        since: "3m",
+        min_lines: 6,
+        model: &model,
+        snapshots: None,
+    };
+    for format in [
+        HistoryFormat::Table,
+        HistoryFormat::Json,
+        HistoryFormat::Csv,
+    ] {
+        run(&cfg, format, &opts).unwrap();
+    }
+}
+
+#[test]
+fn csv_has_one_column_per_dimension() {
+    let dir = sample_repo();
+    let result = history(dir.path(), None, 6);
+    let csv = report::format_csv(&result);
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(
+        lines[0],
+        "date,commit,score,grade,files,loc,cognitive,duplication,indent,halstead,size"
+    );
+    assert_eq!(lines.len(), 4);
+    assert!(lines.iter().all(|l| l.split(',').count() == 11));
+}
+
+#[test]
+fn json_lists_points_and_model() {
+    let dir = sample_repo();
+    let result = history(dir.path(), None, 6);
+    let json: serde_json::Value =
+        serde_json::from_str(&report::format_json(&result).unwrap()).unwrap();
+    assert_eq!(json["every"], "1m");
+    assert_eq!(json["points"].as_array().unwrap().len(), 3);
+    assert!(json["points"][0]["grade"].is_string());
+    assert_eq!(json["model"]["name"], "cogcom");
+}
+
+#[test]
+fn sparkline_scales_between_min_and_max() {
+    assert_eq!(report::sparkline(&[0.0, 50.0, 100.0]), "▁▅█");
+    assert_eq!(report::sparkline(&[7.0, 7.0]), "▄▄");
+    assert_eq!(report::sparkline(&[]), "");
+}
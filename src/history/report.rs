@@ -0,0 +1,160 @@
+//! Report formatters for the score history.
+//!
+//! The table opens with one sparkline per metric (score, each dimension,
+//! LOC), scaled between the series' own minimum and maximum, followed by
+//! one row per sample.
+
+use super::ScoreHistory;
+use crate::report_helpers;
+
+/// Eight block heights, lowest first.
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Render `values` as a sparkline; a flat series sits mid-height.
+pub(crate) fn sparkline(values: &[f64]) -> String {
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    values
+        .iter()
+        .map(|&v| {
+            if max - min < 1e-9 {
+                BARS[BARS.len() / 2 - 1]
+            } else {
+                let level = ((v - min) / (max - min) * (BARS.len() - 1) as f64).round();
+                BARS[level as usize]
+            }
+        })
+        .collect()
+}
+
+/// Dimension keys and display names of the history's model, in report order.
+fn dimensions(history: &ScoreHistory) -> Vec<(&'static str, &'static str)> {
+    history
+        .model
+        .dimensions
+        .iter()
+        .map(|d| (d.dimension.key(), d.dimension.name()))
+        .collect()
+}
+
+/// Print sparklines for the score, each dimension, and LOC, then one row per sample.
+pub fn print_report(history: &ScoreHistory) {
+    let points = &history.points;
+    let (Some(first), Some(last)) = (points.first(), points.last()) else {
+        println!(
+            "No commits with source files in the last {} (sampling every {}).",
+            history.since, history.every
+        );
+        return;
+    };
+    let separator = report_helpers::separator(78);
+
+    println!(
+        "Score History: {} → {} ({} sample{}, every {})",
+        first.date,
+        last.date,
+        points.len(),
+        if points.len() == 1 { "" } else { "s" },
+        history.every
+    );
+    println!("{separator}");
+    let trend = |name: &str, values: Vec<f64>| {
+        let (from, to) = (values[0], values[values.len() - 1]);
+        println!(
+            " {:<23} {}  {from:.1} → {to:.1} ({:+.1})",
+            name,
+            sparkline(&values),
+            to - from
+        );
+    };
+    trend("Score", points.iter().map(|p| p.score).collect());
+    for (key, name) in dimensions(history) {
+        trend(
+            name,
+            points
+                .iter()
+                .map(|p| p.dimensions.get(key).copied().unwrap_or(0.0))
+                .collect(),
+        );
+    }
+    let loc: Vec<f64> = points.iter().map(|p| p.total_loc as f64).collect();
+    println!(
+        " {:<23} {}  {} → {}",
+        "Lines of Code",
+        sparkline(&loc),
+        first.total_loc,
+        last.total_loc
+    );
+    println!("{separator}");
+
+    println!(
+        " {:<10}  {:<8}  {:>5}  {:<5}  {:>6}  {:>8}",
+        "Date", "Commit", "Score", "Grade", "Files", "LOC"
+    );
+    println!("{separator}");
+    for p in points {
+        println!(
+            " {:<10}  {:<8}  {:>5.1}  {:<5}  {:>6}  {:>8}",
+            p.date,
+            &p.commit[..p.commit.len().min(8)],
+            p.score,
+            p.grade.as_str(),
+            p.files_analyzed,
+            p.total_loc,
+        );
+    }
+    println!("{separator}");
+    if history.reused > 0 {
+        println!(
+            " {} of {} samples from snapshots",
+            history.reused,
+            points.len()
+        );
+    }
+}
+
+/// Format the history as CSV: one row per sample, one column per dimension.
+pub fn format_csv(history: &ScoreHistory) -> String {
+    let dims = dimensions(history);
+    let mut out = String::from("date,commit,score,grade,files,loc");
+    for (key, _) in &dims {
+        out.push(',');
+        out.push_str(key);
+    }
+    out.push('\n');
+    for p in &history.points {
+        out.push_str(&format!(
+            "{},{},{:.2},{},{},{}",
+            p.date,
+            p.commit,
+            p.score,
+            p.grade.as_str(),
+            p.files_analyzed,
+            p.total_loc
+        ));
+        for (key, _) in &dims {
+            match p.dimensions.get(*key) {
+                Some(score) => out.push_str(&format!(",{score:.2}")),
+                None => out.push(','),
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Print the history as CSV to stdout.
+pub fn print_csv(history: &ScoreHistory) {
+    print!("{}", format_csv(history));
+}
+
+/// Serialize the history as a pretty-printed JSON string.
+pub fn format_json(history: &ScoreHistory) -> Result<String, Box<dyn std::error::Error>> {
+    report_helpers::json_string(history)
+}
+
+/// Serialize the history as pretty-printed JSON to stdout.
+pub fn print_json(history: &ScoreHistory) -> Result<(), Box<dyn std::error::Error>> {
+    println!("{}", format_json(history)?);
+    Ok(())
+}
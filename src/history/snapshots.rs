@@ -0,0 +1,73 @@
+//! Stored `km history` samples, so repeated runs only score new commits.
+//!
+//! One JSON file maps `<settings>:<commit>` to the score of that commit's
+//! tree. `settings` fingerprints everything besides the commit that the
+//! score depends on (km version, subdirectory, test inclusion, exclusion
+//! filter, duplicate block size, and scoring model), so changing any of
+//! them scores the commits afresh instead of mixing incomparable samples.
+//!
+//! Like the analysis cache, the file is best-effort: a missing or corrupt
+//! file starts empty, and the caller may ignore write failures.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// Score of one commit's tree.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub score: f64,
+    pub files_analyzed: usize,
+    pub total_loc: usize,
+    /// Dimension score keyed by dimension key (`cognitive`, `size`, ...).
+    pub dimensions: BTreeMap<String, f64>,
+}
+
+/// All stored snapshots.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SnapshotStore {
+    #[serde(default)]
+    snapshots: BTreeMap<String, Snapshot>,
+}
+
+impl SnapshotStore {
+    /// Read the store at `path`; missing or undecodable files yield an empty store.
+    pub fn load(path: &Path) -> Self {
+        fs::read(path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write the store to `path` atomically, creating its directory with a
+    /// `.gitignore` when needed.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let dir = path.parent().unwrap_or(Path::new("."));
+        crate::cache::create_dir(dir)?;
+        let mut tmp = tempfile::NamedTempFile::new_in(dir)?;
+        serde_json::to_writer(&mut tmp, self)?;
+        tmp.flush()?;
+        tmp.persist(path).map_err(|e| e.error)?;
+        Ok(())
+    }
+
+    pub fn get(&self, settings: &str, commit: &str) -> Option<&Snapshot> {
+        self.snapshots.get(&format!("{settings}:{commit}"))
+    }
+
+    pub fn insert(&mut self, settings: &str, commit: &str, snapshot: Snapshot) {
+        self.snapshots
+            .insert(format!("{settings}:{commit}"), snapshot);
+    }
+
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+}
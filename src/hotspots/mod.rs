@@ -2,9 +2,11 @@
 //!
 //! Combines git change frequency with cyclomatic or indentation complexity
 //! to produce a hotspot score (commits x complexity). Files with high scores
-//! are the most impactful refactoring targets.
+//! are the most impactful refactoring targets. Change frequency can also be
+//! measured in churned lines (added + deleted) or in bug-fix commits
+//! instead of commits.
 
-mod report;
+pub(crate) mod report;
 
 use std::cmp::Reverse;
 use std::collections::HashMap;
@@ -13,33 +15,51 @@ use std::path::{Path, PathBuf};
 
 use crate::cli::OutputMode;
 use crate::git::GitRepo;
+use crate::report_helpers::empty_history_note;
 use crate::util::parse_since;
 use crate::walk::{self, WalkConfig};
-use report::{print_json, print_report, print_short, print_terse};
+use report::{
+    print_codeclimate, print_github, print_json, print_report, print_sarif, print_short,
+    print_terse,
+};
 
 /// A file's hotspot data: how often it changes (commits) and how complex
-/// it is, combined into a score = commits × complexity.
+/// it is, combined into a score = commits × complexity, or churned lines
+/// or fix commits × complexity when `lines` or `fixes` is set.
 pub struct FileHotspot {
     pub path: PathBuf,
     pub language: String,
     pub commits: usize,
+    /// Lines added + deleted, when the score measures change in lines.
+    pub lines: Option<usize>,
+    /// Bug-fix commits, when the score measures change in fixes.
+    pub fixes: Option<usize>,
     pub complexity: usize,
     pub score: usize,
 }
 
+impl FileHotspot {
+    /// The change measure the score multiplies: churned lines or fix
+    /// commits when known, commits otherwise.
+    pub fn change(&self) -> usize {
+        self.lines.or(self.fixes).unwrap_or(self.commits)
+    }
+}
+
 /// Compute complexity for a file using the chosen metric.
 /// Returns None if the file cannot be analyzed.
 fn compute_complexity(
     file_path: &Path,
     spec: &crate::loc::language::LanguageSpec,
     metric: &str,
+    cache: Option<&crate::cache::AnalysisCache>,
 ) -> Result<Option<usize>, Box<dyn Error>> {
     match metric {
-        "cycom" => match crate::cycom::analyze_file(file_path, spec)? {
+        "cycom" => match crate::cycom::analyze_file_cached(file_path, spec, cache)? {
             Some(c) => Ok(Some(c.total_complexity)),
             None => Ok(None),
         },
-        "cogcom" => match crate::cogcom::analyze_file(file_path, spec)? {
+        "cogcom" => match crate::cogcom::analyze_file_cached(file_path, spec, cache)? {
             Some(c) => Ok(Some(c.total_complexity)),
             None => Ok(None),
         },
@@ -53,47 +73,76 @@ fn compute_complexity(
 /// Identify hotspot files by combining git change frequency with code
 /// complexity. Opens the git repo, walks source files, computes complexity
 /// per file, and sorts by the chosen metric (score, commits, or complexity).
-pub fn run(
+/// `changes` is the change measure: "commits", "lines" (added + deleted),
+/// or "fixes" (bug-fix commits); files without any fix are left out of the
+/// latter. Returns an empty list when the repository has no commits in range.
+pub fn analyze_project(
     cfg: &WalkConfig<'_>,
-    output: OutputMode,
-    top: usize,
     sort_by: &str,
     since: Option<&str>,
     complexity_metric: &str,
-) -> Result<(), Box<dyn Error>> {
+    changes: &str,
+) -> Result<Vec<FileHotspot>, Box<dyn Error>> {
     let git_repo = GitRepo::open(cfg.path)
-        .map_err(|e| format!("not a git repository (or any parent): {e}"))?;
+        .map_err(|e| format!("not a git repository (or any parent): {e}"))?
+        .with_fix_patterns(cfg.fix_patterns)
+        .with_rename_threshold(cfg.rename_threshold)
+        .with_exclusions(cfg.exclusions)
+        .with_commit_filter(cfg.commits)?;
 
     let since_ts = since.map(parse_since).transpose()?;
 
-    // Build a HashMap of relative path → commits
-    let freqs = git_repo.file_frequencies(since_ts)?;
-    if freqs.is_empty() {
-        if since.is_some() {
-            eprintln!("No commits found in the specified time range.");
-        } else {
-            eprintln!("No commits found in the repository.");
-        }
-        return Ok(());
+    // Build a HashMap of relative path → (commits, churned lines, fixes)
+    let freq_map: HashMap<PathBuf, (usize, Option<usize>, Option<usize>)> = match changes {
+        "lines" => git_repo
+            .line_churn(since_ts)?
+            .into_iter()
+            .map(|c| {
+                let lines = c.added + c.deleted;
+                (c.frequency.path, (c.frequency.commits, Some(lines), None))
+            })
+            .collect(),
+        "fixes" => git_repo
+            .file_frequencies(since_ts)?
+            .into_iter()
+            .filter(|f| f.fixes > 0)
+            .map(|f| (f.path, (f.commits, None, Some(f.fixes))))
+            .collect(),
+        _ => git_repo
+            .file_frequencies(since_ts)?
+            .into_iter()
+            .map(|f| (f.path, (f.commits, None, None)))
+            .collect(),
+    };
+    if freq_map.is_empty() {
+        return Ok(Vec::new());
     }
-    let freq_map: HashMap<PathBuf, usize> =
-        freqs.into_iter().map(|f| (f.path, f.commits)).collect();
 
     let (walk_root, walk_prefix) = git_repo.walk_prefix(cfg.path)?;
 
-    let mut results: Vec<FileHotspot> = Vec::new();
-
-    for (file_path, spec) in walk::source_files(&walk_root, cfg.exclude_tests(), cfg.filter) {
-        let rel_path = GitRepo::to_git_path(&walk_root, &walk_prefix, &file_path);
+    // Look up commits from git history before the expensive analysis,
+    // keeping only files that have history.
+    let candidates: Vec<_> = walk::source_files(&walk_root, cfg.exclude_tests(), cfg.filter)
+        .into_iter()
+        .filter_map(|(file_path, spec)| {
+            let rel_path = GitRepo::to_git_path(&walk_root, &walk_prefix, &file_path);
+            let (commits, lines, fixes) = *freq_map.get(&rel_path)?;
+            Some((file_path, rel_path, spec, commits, lines, fixes))
+        })
+        .collect();
+
+    // Compute complexity (only for files with git history) in parallel.
+    let complexities = cfg.par_map(&candidates, |(file_path, _, spec, ..)| {
+        compute_complexity(file_path, spec, complexity_metric, cfg.cache)
+            .map_err(|err| err.to_string())
+    });
 
-        // Look up commits from git history (before expensive analysis)
-        let commits = match freq_map.get(&rel_path) {
-            Some(&c) => c,
-            None => continue,
-        };
+    let mut results: Vec<FileHotspot> = Vec::new();
 
-        // Compute complexity (only for files with git history)
-        let complexity = match compute_complexity(&file_path, spec, complexity_metric) {
+    for ((file_path, rel_path, spec, commits, lines, fixes), complexity) in
+        candidates.into_iter().zip(complexities)
+    {
+        let complexity = match complexity {
             Ok(Some(c)) => c,
             Ok(None) => continue,
             Err(err) => {
@@ -102,12 +151,14 @@ pub fn run(
             }
         };
 
-        let score = commits * complexity;
+        let score = lines.or(fixes).unwrap_or(commits) * complexity;
 
         results.push(FileHotspot {
             path: rel_path,
             language: spec.name.to_string(),
             commits,
+            lines,
+            fixes,
             complexity,
             score,
         });
@@ -120,14 +171,37 @@ pub fn run(
         _ => results.sort_by_key(|r| Reverse(r.score)),
     }
 
+    Ok(results)
+}
+
+/// Run hotspot analysis and print the top results in the chosen format.
+pub fn run(
+    cfg: &WalkConfig<'_>,
+    output: OutputMode,
+    top: usize,
+    sort_by: &str,
+    since: Option<&str>,
+    complexity_metric: &str,
+    changes: &str,
+) -> Result<(), Box<dyn Error>> {
+    let mut results = analyze_project(cfg, sort_by, since, complexity_metric, changes)?;
+    if results.is_empty()
+        && let Some(note) = empty_history_note(since.is_some(), cfg.commits.is_some())
+    {
+        eprintln!("{note}");
+        return Ok(());
+    }
     results.truncate(top);
 
     match output {
         OutputMode::Json => print_json(&results, complexity_metric)?,
         OutputMode::Short => print_short(&results),
         OutputMode::Terse => print_terse(&results),
-        OutputMode::Github | OutputMode::Codeclimate => {
-            return Err(crate::cli::ERR_CI_FORMAT_ONLY.into());
+        OutputMode::Github => print_github(&results, complexity_metric),
+        OutputMode::Codeclimate => print_codeclimate(&results, complexity_metric)?,
+        OutputMode::Sarif => print_sarif(&results, complexity_metric)?,
+        OutputMode::Html | OutputMode::Markdown => {
+            return Err(crate::cli::ERR_REPORT_FORMAT_ONLY.into());
         }
         OutputMode::Table => print_report(&results, complexity_metric),
     }
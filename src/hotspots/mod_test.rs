@@ -140,7 +140,16 @@ fn run_on_non_git_dir() {
     fs::create_dir_all(&sub).unwrap();
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(&sub, false, &filter);
-    let err = run(&cfg, OutputMode::Table, 20, "score", None, "indent").unwrap_err();
+    let err = run(
+        &cfg,
+        OutputMode::Table,
+        20,
+        "score",
+        None,
+        "indent",
+        "commits",
+    )
+    .unwrap_err();
     assert!(
         err.to_string().contains("not a git repository"),
         "should mention not a git repository, got: {err}"
@@ -151,7 +160,15 @@ fn run_on_non_git_dir() {
 fn run_json_output_indent() {
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(StdPath::new("."), false, &filter);
-    let result = run(&cfg, OutputMode::Json, 5, "score", None, "indent");
+    let result = run(
+        &cfg,
+        OutputMode::Json,
+        5,
+        "score",
+        None,
+        "indent",
+        "commits",
+    );
     assert!(
         result.is_ok(),
         "hotspots (indent) should succeed on a git repo"
@@ -162,7 +179,7 @@ fn run_json_output_indent() {
 fn run_json_output_cycom() {
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(StdPath::new("."), false, &filter);
-    let result = run(&cfg, OutputMode::Json, 5, "score", None, "cycom");
+    let result = run(&cfg, OutputMode::Json, 5, "score", None, "cycom", "commits");
     assert!(
         result.is_ok(),
         "hotspots (cycom) should succeed on a git repo"
@@ -223,7 +240,15 @@ fn integration_indent_scores() {
 
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(dir.path(), false, &filter);
-    let result = run(&cfg, OutputMode::Table, 20, "score", None, "indent");
+    let result = run(
+        &cfg,
+        OutputMode::Table,
+        20,
+        "score",
+        None,
+        "indent",
+        "commits",
+    );
     assert!(result.is_ok(), "indent hotspots should succeed");
 }
 
@@ -241,7 +266,15 @@ fn integration_cycom_scores() {
 
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(dir.path(), false, &filter);
-    let result = run(&cfg, OutputMode::Table, 20, "score", None, "cycom");
+    let result = run(
+        &cfg,
+        OutputMode::Table,
+        20,
+        "score",
+        None,
+        "cycom",
+        "commits",
+    );
     assert!(result.is_ok(), "cycom hotspots should succeed");
 }
 
@@ -266,7 +299,15 @@ fn integration_sort_by_commits() {
 
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(dir.path(), false, &filter);
-    let result = run(&cfg, OutputMode::Table, 20, "commits", None, "indent");
+    let result = run(
+        &cfg,
+        OutputMode::Table,
+        20,
+        "commits",
+        None,
+        "indent",
+        "commits",
+    );
     assert!(result.is_ok(), "sort by commits should work");
 }
 
@@ -282,7 +323,15 @@ fn integration_since_filters_commits() {
     // Commits at epoch 2023 → --since 1d from 2026 excludes all
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(dir.path(), false, &filter);
-    let result = run(&cfg, OutputMode::Table, 20, "score", Some("1d"), "indent");
+    let result = run(
+        &cfg,
+        OutputMode::Table,
+        20,
+        "score",
+        Some("1d"),
+        "indent",
+        "commits",
+    );
     assert!(result.is_ok(), "since filter should not crash");
 }
 
@@ -291,7 +340,15 @@ fn integration_empty_repo() {
     let (dir, _repo) = create_test_repo();
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(dir.path(), false, &filter);
-    let result = run(&cfg, OutputMode::Table, 20, "score", None, "indent");
+    let result = run(
+        &cfg,
+        OutputMode::Table,
+        20,
+        "score",
+        None,
+        "indent",
+        "commits",
+    );
     // Empty repo: file_frequencies fails, which is ok
     assert!(
         result.is_ok() || result.is_err(),
@@ -312,7 +369,15 @@ fn integration_json_structure() {
     );
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(dir.path(), false, &filter);
-    let result = run(&cfg, OutputMode::Json, 20, "score", None, "indent");
+    let result = run(
+        &cfg,
+        OutputMode::Json,
+        20,
+        "score",
+        None,
+        "indent",
+        "commits",
+    );
     assert!(result.is_ok(), "JSON output should succeed");
 }
 
@@ -330,7 +395,15 @@ fn integration_cogcom_scores() {
 
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(dir.path(), false, &filter);
-    let result = run(&cfg, OutputMode::Table, 20, "score", None, "cogcom");
+    let result = run(
+        &cfg,
+        OutputMode::Table,
+        20,
+        "score",
+        None,
+        "cogcom",
+        "commits",
+    );
     assert!(result.is_ok(), "cogcom hotspots should succeed");
 }
 
@@ -348,7 +421,15 @@ fn integration_sort_by_complexity() {
 
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(dir.path(), false, &filter);
-    let result = run(&cfg, OutputMode::Table, 20, "complexity", None, "indent");
+    let result = run(
+        &cfg,
+        OutputMode::Table,
+        20,
+        "complexity",
+        None,
+        "indent",
+        "commits",
+    );
     assert!(result.is_ok(), "sort by complexity should work");
 }
 
@@ -362,7 +443,16 @@ fn run_short_format() {
     );
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(dir.path(), false, &filter);
-    run(&cfg, OutputMode::Short, 20, "score", None, "indent").unwrap();
+    run(
+        &cfg,
+        OutputMode::Short,
+        20,
+        "score",
+        None,
+        "indent",
+        "commits",
+    )
+    .unwrap();
 }
 
 #[test]
@@ -375,5 +465,81 @@ fn run_terse_format() {
     );
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(dir.path(), false, &filter);
-    run(&cfg, OutputMode::Terse, 20, "score", None, "indent").unwrap();
+    run(
+        &cfg,
+        OutputMode::Terse,
+        20,
+        "score",
+        None,
+        "indent",
+        "commits",
+    )
+    .unwrap();
+}
+
+#[test]
+fn line_churn_scores_by_lines_changed() {
+    let (dir, repo) = create_test_repo();
+    // a.rs: two commits, 6 churned lines; b.rs: one commit, 10 churned lines.
+    make_commit(&repo, &[("a.rs", "fn a() {\n    x;\n}\n")], "c1");
+    make_commit(&repo, &[("a.rs", "fn a() {\n    y;\n}\n")], "c2");
+    make_commit(
+        &repo,
+        &[(
+            "b.rs",
+            "fn b() {\n    1;\n    2;\n    3;\n    4;\n    5;\n    6;\n    7;\n    8;\n}\n",
+        )],
+        "c3",
+    );
+
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(dir.path(), false, &filter);
+    let by_commits = analyze_project(&cfg, "score", None, "indent", "commits").unwrap();
+    assert!(
+        by_commits
+            .iter()
+            .all(|h| h.lines.is_none() && h.fixes.is_none())
+    );
+    let by_lines = analyze_project(&cfg, "score", None, "indent", "lines").unwrap();
+    let a = by_lines
+        .iter()
+        .find(|h| h.path == StdPath::new("a.rs"))
+        .unwrap();
+    let b = by_lines
+        .iter()
+        .find(|h| h.path == StdPath::new("b.rs"))
+        .unwrap();
+    assert_eq!((a.commits, a.lines), (2, Some(5)));
+    assert_eq!((b.commits, b.lines), (1, Some(10)));
+    assert_eq!(a.score, 5 * a.complexity);
+    assert_eq!(by_lines[0].path, StdPath::new("b.rs"));
+}
+
+#[test]
+fn fix_commits_score_only_files_with_fixes() {
+    let (dir, repo) = create_test_repo();
+    make_commit(&repo, &[("a.rs", "fn a() {\n    x;\n}\n")], "Add a");
+    make_commit(&repo, &[("a.rs", "fn a() {\n    y;\n}\n")], "Fix a");
+    make_commit(
+        &repo,
+        &[("a.rs", "fn a() {\n    z;\n}\n")],
+        "PROJ-72 handle z",
+    );
+    make_commit(&repo, &[("b.rs", "fn b() {\n    1;\n}\n")], "Add b");
+
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(dir.path(), false, &filter);
+    let results = analyze_project(&cfg, "score", None, "indent", "fixes").unwrap();
+    assert_eq!(results.len(), 1, "b.rs has no fixes");
+    let a = &results[0];
+    assert_eq!((a.commits, a.fixes), (3, Some(2)));
+    assert_eq!(a.score, 2 * a.complexity);
+
+    let patterns = crate::git::FixPatterns::resolve(&crate::config::DefectsConfig {
+        patterns: Some(vec!["^Add".to_string()]),
+    })
+    .unwrap();
+    let cfg = cfg.with_fix_patterns(Some(&patterns));
+    let results = analyze_project(&cfg, "score", None, "indent", "fixes").unwrap();
+    assert_eq!(results.len(), 2);
 }
@@ -1,25 +1,42 @@
 /// Report formatters for hotspot analysis.
 ///
 /// Provides table and JSON output showing files ranked by their
-/// hotspot score (commits x complexity), identifying prime refactoring targets.
+/// hotspot score (commits, churned lines or fix commits x complexity),
+/// identifying prime refactoring targets.
 use serde::Serialize;
 
 use super::FileHotspot;
 use crate::report_helpers;
+use crate::sarif;
 
 /// Map the metric flag to a human-readable column header.
 fn complexity_label(metric: &str) -> &'static str {
     match metric {
         "cycom" => "Cyclomatic",
+        "cogcom" => "Cognitive",
         _ => "Total Indent",
     }
 }
 
-/// Return a description of the scoring formula for the chosen metric.
-fn method_description(metric: &str) -> &'static str {
+/// The change measure a hotspot's score multiplies, as (title, column
+/// header, noun).
+fn change_labels(f: &FileHotspot) -> (&'static str, &'static str, &'static str) {
+    if f.lines.is_some() {
+        ("Churned Lines", "Lines", "churned lines")
+    } else if f.fixes.is_some() {
+        ("Fix Commits", "Fixes", "fix commits")
+    } else {
+        ("Commits", "Commits", "commits")
+    }
+}
+
+/// Return a description of the scoring formula for the chosen metric and
+/// change measure.
+fn method_description(metric: &str, change: &str) -> String {
     match metric {
-        "cycom" => "Score = Commits × Cyclomatic Complexity.",
-        _ => "Score = Commits × Total Indentation (Thornhill method).",
+        "cycom" => format!("Score = {change} × Cyclomatic Complexity."),
+        "cogcom" => format!("Score = {change} × Cognitive Complexity."),
+        _ => format!("Score = {change} × Total Indentation (Thornhill method)."),
     }
 }
 
@@ -32,19 +49,20 @@ pub fn print_report(files: &[FileHotspot], metric: &str) {
     }
 
     let label = complexity_label(metric);
+    let (change_title, change_col, _) = change_labels(&files[0]);
 
     let max_path_len = report_helpers::max_path_width(files.iter().map(|f| f.path.as_path()), 4);
     // 1 (leading space) + path + 2 + 10 + 1 + 7 + 1 + 12 + 1 + 10 = path + 45
     let header_width = max_path_len + 45;
     let separator = report_helpers::separator(header_width.max(78));
 
-    println!("Hotspots (Commits × {label} Complexity)");
+    println!("Hotspots ({change_title} × {label} Complexity)");
     println!("{separator}");
     println!(
         " {:<width$}  {:>10} {:>7} {:>12} {:>10}",
         "File",
         "Language",
-        "Commits",
+        change_col,
         label,
         "Score",
         width = max_path_len
@@ -56,7 +74,7 @@ pub fn print_report(files: &[FileHotspot], metric: &str) {
             " {:<width$}  {:>10} {:>7} {:>12} {:>10}",
             f.path.display(),
             f.language,
-            f.commits,
+            f.change(),
             f.complexity,
             f.score,
             width = max_path_len
@@ -65,7 +83,7 @@ pub fn print_report(files: &[FileHotspot], metric: &str) {
 
     println!("{separator}");
     println!();
-    println!("{}", method_description(metric));
+    println!("{}", method_description(metric, change_title));
     println!("High-score files are change-prone and complex — prime refactoring targets.");
 }
 
@@ -75,26 +93,41 @@ struct JsonEntry {
     path: String,
     language: String,
     commits: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    churned_lines: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fix_commits: Option<usize>,
     complexity: usize,
     complexity_metric: String,
     score: usize,
 }
 
-/// Serialize hotspot data as pretty-printed JSON to stdout.
-pub fn print_json(files: &[FileHotspot], metric: &str) -> Result<(), Box<dyn std::error::Error>> {
+/// Serialize hotspot data as a pretty-printed JSON string.
+pub fn format_json(
+    files: &[FileHotspot],
+    metric: &str,
+) -> Result<String, Box<dyn std::error::Error>> {
     let entries: Vec<JsonEntry> = files
         .iter()
         .map(|f| JsonEntry {
             path: f.path.display().to_string(),
             language: f.language.clone(),
             commits: f.commits,
+            churned_lines: f.lines,
+            fix_commits: f.fixes,
             complexity: f.complexity,
             complexity_metric: metric.to_string(),
             score: f.score,
         })
         .collect();
 
-    report_helpers::print_json_stdout(&entries)
+    report_helpers::json_string(&entries)
+}
+
+/// Serialize hotspot data as pretty-printed JSON to stdout.
+pub fn print_json(files: &[FileHotspot], metric: &str) -> Result<(), Box<dyn std::error::Error>> {
+    println!("{}", format_json(files, metric)?);
+    Ok(())
 }
 
 /// Print hotspots as a single compact line.
@@ -122,6 +155,71 @@ pub fn print_terse(files: &[FileHotspot]) {
     }
 }
 
+/// CI annotations for the ranked hotspots, one per file. The top quarter
+/// (at least the first file) are warnings, the rest notices.
+pub fn annotations(files: &[FileHotspot], metric: &str) -> Vec<report_helpers::Annotation> {
+    let label = complexity_label(metric).to_lowercase();
+    let warn_count = files.len().div_ceil(4);
+    files
+        .iter()
+        .enumerate()
+        .map(|(i, f)| {
+            let (level, severity) = if i < warn_count {
+                ("warning", "major")
+            } else {
+                ("notice", "minor")
+            };
+            report_helpers::Annotation {
+                level,
+                severity,
+                file: f.path.display().to_string(),
+                line: 1,
+                title: "Hotspot",
+                message: format!(
+                    "hotspot #{} of {}: {} {} × {} {label} complexity = score {}",
+                    i + 1,
+                    files.len(),
+                    f.change(),
+                    change_labels(f).2,
+                    f.complexity,
+                    f.score
+                ),
+            }
+        })
+        .collect()
+}
+
+/// Emit one GitHub Actions annotation per hotspot.
+pub fn print_github(files: &[FileHotspot], metric: &str) {
+    report_helpers::print_github_annotations(&annotations(files, metric));
+}
+
+/// Emit a CodeClimate JSON array with one entry per hotspot.
+pub fn print_codeclimate(
+    files: &[FileHotspot],
+    metric: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    report_helpers::print_codeclimate_annotations(&annotations(files, metric))
+}
+
+/// The SARIF rule every hotspot is filed under.
+fn sarif_rule() -> sarif::Rule {
+    sarif::Rule {
+        id: "hotspots/hotspot".to_string(),
+        name: "Hotspot".to_string(),
+        short_description: "Frequently changed, complex file".to_string(),
+        full_description: "The file is among the most frequently changed and most complex \
+                           in the project, where changes are the most likely to introduce defects."
+            .to_string(),
+        level: "warning",
+    }
+}
+
+/// Emit a SARIF 2.1.0 log with one result per hotspot.
+pub fn print_sarif(files: &[FileHotspot], metric: &str) -> Result<(), Box<dyn std::error::Error>> {
+    report_helpers::print_sarif_annotations(&sarif_rule(), &annotations(files, metric))
+}
+
 #[cfg(test)]
 #[path = "report_test.rs"]
 mod tests;
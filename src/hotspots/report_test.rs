@@ -7,6 +7,8 @@ fn sample_files() -> Vec<FileHotspot> {
             path: PathBuf::from("src/foo.rs"),
             language: "Rust".to_string(),
             commits: 42,
+            lines: None,
+            fixes: None,
             complexity: 34,
             score: 42 * 34,
         },
@@ -14,6 +16,8 @@ fn sample_files() -> Vec<FileHotspot> {
             path: PathBuf::from("src/bar.rs"),
             language: "Rust".to_string(),
             commits: 10,
+            lines: None,
+            fixes: None,
             complexity: 3,
             score: 10 * 3,
         },
@@ -85,3 +89,62 @@ fn json_structure_is_valid() {
         "second entry should be bar.rs"
     );
 }
+
+#[test]
+fn annotations_rank_top_files() {
+    let anns = annotations(&sample_files(), "cycom");
+    assert_eq!(anns.len(), 2);
+    assert_eq!(anns[0].file, "src/foo.rs");
+    assert_eq!(anns[0].level, "warning");
+    assert_eq!(
+        anns[0].message,
+        "hotspot #1 of 2: 42 commits × 34 cyclomatic complexity = score 1428"
+    );
+    assert_eq!(anns[1].level, "notice");
+    assert_eq!(anns[1].severity, "minor");
+}
+
+#[test]
+fn print_github_and_codeclimate_do_not_panic() {
+    print_github(&sample_files(), "indent");
+    print_codeclimate(&sample_files(), "cogcom").unwrap();
+    print_codeclimate(&[], "indent").unwrap();
+    print_sarif(&sample_files(), "cycom").unwrap();
+}
+
+#[test]
+fn line_churn_scores_are_labeled_as_lines() {
+    let mut files = sample_files();
+    for f in &mut files {
+        f.lines = Some(f.commits * 10);
+        f.score = f.change() * f.complexity;
+    }
+    let anns = annotations(&files, "cogcom");
+    assert_eq!(
+        anns[0].message,
+        "hotspot #1 of 2: 420 churned lines × 34 cognitive complexity = score 14280"
+    );
+    let json: serde_json::Value =
+        serde_json::from_str(&format_json(&files, "cogcom").unwrap()).unwrap();
+    assert_eq!(json[0]["churned_lines"], 420);
+    let json: serde_json::Value =
+        serde_json::from_str(&format_json(&sample_files(), "cogcom").unwrap()).unwrap();
+    assert!(json[0].get("churned_lines").is_none());
+    print_report(&files, "cogcom");
+}
+
+#[test]
+fn fix_scores_are_labeled_as_fixes() {
+    let mut files = sample_files();
+    files[0].fixes = Some(5);
+    files[0].score = 5 * files[0].complexity;
+    let anns = annotations(&files[..1], "indent");
+    assert_eq!(
+        anns[0].message,
+        "hotspot #1 of 1: 5 fix commits × 34 total indent complexity = score 170"
+    );
+    let json: serde_json::Value =
+        serde_json::from_str(&format_json(&files, "indent").unwrap()).unwrap();
+    assert_eq!(json[0]["fix_commits"], 5);
+    assert!(json[1].get("fix_commits").is_none());
+}
@@ -14,14 +14,17 @@ use crate::loc::language::LanguageSpec;
 use crate::util::read_and_classify;
 use crate::walk::WalkConfig;
 use analyzer::analyze;
-use report::{FileIndentMetrics, print_json, print_report, print_short, print_terse};
+use report::{print_json, print_report, print_short, print_terse};
+
+pub use analyzer::{ComplexityLevel, IndentMetrics};
+pub use report::FileIndentMetrics;
 
 /// Tab width used for converting tabs to spaces in indentation calculation.
 const TAB_WIDTH: usize = 4;
 
 /// Read a file, classify lines, and compute indentation metrics.
 /// Returns `None` for binary files or files with no code lines.
-pub(crate) fn analyze_file(
+pub fn analyze_file(
     path: &Path,
     spec: &LanguageSpec,
 ) -> Result<Option<FileIndentMetrics>, Box<dyn Error>> {
@@ -45,21 +48,32 @@ pub(crate) fn analyze_file(
     }))
 }
 
-/// Walk source files, compute indentation metrics, sort by stddev
-/// descending, and print as a table, JSON, or compact format.
-pub fn run(cfg: &WalkConfig<'_>, output: crate::cli::OutputMode) -> Result<(), Box<dyn Error>> {
+/// Walk source files and compute indentation metrics, sorted by stddev descending.
+pub fn analyze_project(cfg: &WalkConfig<'_>) -> Vec<FileIndentMetrics> {
     let mut results = cfg.collect_analysis(analyze_file);
 
     // Sort by stddev descending
     results.sort_by(|a, b| b.stddev.total_cmp(&a.stddev));
+    results
+}
+
+/// Walk source files, compute indentation metrics, sort by stddev
+/// descending, and print as a table, JSON, or compact format.
+pub fn run(cfg: &WalkConfig<'_>, output: crate::cli::OutputMode) -> Result<(), Box<dyn Error>> {
+    let results = analyze_project(cfg);
 
     match output {
         crate::cli::OutputMode::Json => print_json(&results)?,
         crate::cli::OutputMode::Short => print_short(&results),
         crate::cli::OutputMode::Terse => print_terse(&results),
-        crate::cli::OutputMode::Github | crate::cli::OutputMode::Codeclimate => {
+        crate::cli::OutputMode::Github
+        | crate::cli::OutputMode::Codeclimate
+        | crate::cli::OutputMode::Sarif => {
             return Err(crate::cli::ERR_CI_FORMAT_ONLY.into());
         }
+        crate::cli::OutputMode::Html | crate::cli::OutputMode::Markdown => {
+            return Err(crate::cli::ERR_REPORT_FORMAT_ONLY.into());
+        }
         crate::cli::OutputMode::Table => print_report(&results),
     }
 
@@ -69,8 +69,8 @@ struct JsonFileEntry {
     complexity: ComplexityLevel,
 }
 
-/// Serialize indentation metrics as pretty-printed JSON to stdout.
-pub fn print_json(files: &[FileIndentMetrics]) -> Result<(), Box<dyn std::error::Error>> {
+/// Serialize indentation metrics as a pretty-printed JSON string.
+pub fn format_json(files: &[FileIndentMetrics]) -> Result<String, Box<dyn std::error::Error>> {
     let entries: Vec<JsonFileEntry> = files
         .iter()
         .map(|f| JsonFileEntry {
@@ -82,7 +82,13 @@ pub fn print_json(files: &[FileIndentMetrics]) -> Result<(), Box<dyn std::error:
         })
         .collect();
 
-    report_helpers::print_json_stdout(&entries)
+    report_helpers::json_string(&entries)
+}
+
+/// Serialize indentation metrics as pretty-printed JSON to stdout.
+pub fn print_json(files: &[FileIndentMetrics]) -> Result<(), Box<dyn std::error::Error>> {
+    println!("{}", format_json(files)?);
+    Ok(())
 }
 
 /// Print indentation metrics as a single compact line.
@@ -14,7 +14,7 @@ use crate::cycom::markers::markers_for;
 use crate::detection::{FunctionDetectionMarkers as _, detect_function_bodies};
 use crate::dups;
 use crate::loc::counter::LineKind;
-use crate::score::{self, ScoringModel, analyzer::Grade};
+use crate::score::{self, ScoreModel, analyzer::Grade};
 use crate::util::read_and_classify;
 use crate::walk::{ExcludeFilter, WalkConfig};
 
@@ -168,7 +168,7 @@ fn collect_stats(cfg: &WalkConfig<'_>) -> Result<ProjectStats, Box<dyn Error>> {
     let dup_metrics = dups::compute_metrics(cfg, DupsConfig::DEFAULT_MIN_LINES);
     let dup_ratio = dup_metrics.percentage();
 
-    let score = score::compute_score(cfg, 0, 6, &ScoringModel::Cognitive)?;
+    let score = score::compute_score(cfg, 0, 6, &ScoreModel::default())?;
     let health_grade = score.grade;
 
     Ok(ProjectStats {
@@ -6,9 +6,10 @@
 //! files (lock files, minified JS) are automatically excluded.
 
 pub mod analyzer;
-mod report;
+pub(crate) mod report;
 
 use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
 use std::path::Path;
 
@@ -18,11 +19,16 @@ use crate::walk::{self, WalkConfig};
 
 use crate::cli::OutputMode;
 use crate::report_helpers;
-use analyzer::{FileOwnership, aggregate_by_author, compute_bus_factor, compute_ownership};
+use analyzer::{aggregate_by_author, compute_ownership};
+
+pub use analyzer::{
+    AuthorSummary, BusFactor, BusFactorEntry, FileOwnership, RiskLevel, compute_bus_factor,
+};
 use report::{
     print_bus_factor_json, print_bus_factor_report, print_bus_factor_short, print_bus_factor_terse,
-    print_json, print_report, print_short, print_summary_json, print_summary_report,
-    print_summary_short, print_summary_terse, print_terse,
+    print_codeclimate, print_github, print_json, print_report, print_sarif, print_short,
+    print_summary_json, print_summary_report, print_summary_short, print_summary_terse,
+    print_terse,
 };
 
 /// Check if a file is machine-generated (lock files, minified assets,
@@ -61,14 +67,15 @@ pub struct KnowledgeOptions<'a> {
     pub risk_only: bool,
     pub summary: bool,
     pub bus_factor: bool,
-    /// Filter to files owned by this author (case-insensitive substring match).
-    pub author: Option<&'a str>,
+    /// Filter to files owned by any of these authors (case-insensitive
+    /// substring match on name or email); empty keeps all files.
+    pub authors: &'a [String],
 }
 
 /// Sort file ownership results by the given sort key.
 /// "diffusion" sorts by contributor count, "risk" by risk level then ownership,
 /// and anything else (default "concentration") by highest ownership percentage first.
-fn sort_results(results: &mut [FileOwnership], sort_by: &str) {
+pub(crate) fn sort_results(results: &mut [FileOwnership], sort_by: &str) {
     match sort_by {
         "diffusion" => results.sort_by_key(|r| Reverse(r.contributors)),
         "risk" => results.sort_by(|a, b| {
@@ -89,27 +96,52 @@ fn sort_results(results: &mut [FileOwnership], sort_by: &str) {
     }
 }
 
-/// Run knowledge map analysis: walk source files, blame each one,
-/// compute ownership concentration and risk, then output results.
-pub fn run(cfg: &WalkConfig<'_>, opts: &KnowledgeOptions<'_>) -> Result<(), Box<dyn Error>> {
+/// Per-file ownership plus the raw blame line totals per author that
+/// feed the project-wide bus factor.
+pub struct KnowledgeMap {
+    pub files: Vec<FileOwnership>,
+    /// Author name → total blame lines across all analyzed files.
+    pub author_lines: HashMap<String, usize>,
+}
+
+impl KnowledgeMap {
+    /// Bus factor: minimum number of authors owning 80% of the code.
+    pub fn bus_factor(&self) -> BusFactor {
+        compute_bus_factor(&self.author_lines, 80.0)
+    }
+}
+
+/// Walk source files and blame each one to compute ownership concentration
+/// and risk. When `since` is set, owners with no commits after the cutoff
+/// are flagged as knowledge loss. Files are returned in walk order.
+///
+/// With a commit filter (`cfg.commits`), lines are blamed as of the end of
+/// its range, only files under its paths are analyzed, and only the
+/// selected commits count as recent activity.
+pub fn analyze_project(
+    cfg: &WalkConfig<'_>,
+    since: Option<&str>,
+) -> Result<KnowledgeMap, Box<dyn Error>> {
     let git_repo = GitRepo::open(cfg.path)
-        .map_err(|e| format!("not a git repository (or any parent): {e}"))?;
+        .map_err(|e| format!("not a git repository (or any parent): {e}"))?
+        .with_aliases(cfg.aliases)
+        .with_exclusions(cfg.exclusions)
+        .with_commit_filter(cfg.commits)?;
 
-    let since_ts = opts.since.map(parse_since).transpose()?;
+    let since_ts = since.map(parse_since).transpose()?;
 
     // Collect recent authors (for knowledge loss detection)
     let recent_authors = if since_ts.is_some() {
         git_repo.recent_authors(since_ts)?
     } else {
-        std::collections::HashSet::new()
+        HashSet::new()
     };
 
     let (walk_root, walk_prefix) = git_repo.walk_prefix(cfg.path)?;
 
     let mut results: Vec<FileOwnership> = Vec::new();
     // author name → total blame lines across all files (for bus factor)
-    let mut author_lines: std::collections::HashMap<String, usize> =
-        std::collections::HashMap::new();
+    let mut author_lines: HashMap<String, usize> = HashMap::new();
 
     for (file_path, spec) in walk::source_files(&walk_root, cfg.exclude_tests(), cfg.filter) {
         if is_generated(&file_path) {
@@ -117,6 +149,13 @@ pub fn run(cfg: &WalkConfig<'_>, opts: &KnowledgeOptions<'_>) -> Result<(), Box<
         }
 
         let rel_path = GitRepo::to_git_path(&walk_root, &walk_prefix, &file_path);
+        // Skip files outside --path, and files added after the end of the
+        // commit range (nothing to blame).
+        if !git_repo.includes_path(&rel_path)
+            || cfg.commits.is_some() && !git_repo.exists_at_tip(&rel_path)
+        {
+            continue;
+        }
 
         // Run blame
         let blames = match git_repo.blame_file(&rel_path) {
@@ -127,6 +166,11 @@ pub fn run(cfg: &WalkConfig<'_>, opts: &KnowledgeOptions<'_>) -> Result<(), Box<
             }
         };
 
+        // Nothing left to attribute once excluded commits are dropped.
+        if blames.is_empty() {
+            continue;
+        }
+
         // Accumulate raw blame lines for bus factor computation.
         for b in &blames {
             *author_lines.entry(b.author.clone()).or_insert(0) += b.lines;
@@ -136,24 +180,60 @@ pub fn run(cfg: &WalkConfig<'_>, opts: &KnowledgeOptions<'_>) -> Result<(), Box<
         results.push(ownership);
     }
 
+    Ok(KnowledgeMap {
+        files: results,
+        author_lines,
+    })
+}
+
+/// Apply the `authors` and `risk_only` filters from `opts` to `files`,
+/// then sort by `opts.sort_by`.
+pub fn select_files(
+    mut files: Vec<FileOwnership>,
+    opts: &KnowledgeOptions<'_>,
+) -> Vec<FileOwnership> {
     // Filter by author if requested (case-insensitive substring match on name or email)
-    if let Some(author_filter) = opts.author {
-        let lower = author_filter.to_lowercase();
-        results.retain(|f| {
-            f.primary_owner.to_lowercase().contains(&lower)
-                || f.primary_email.to_lowercase().contains(&lower)
+    if !opts.authors.is_empty() {
+        let lower: Vec<String> = opts.authors.iter().map(|a| a.to_lowercase()).collect();
+        files.retain(|f| {
+            let owner = f.primary_owner.to_lowercase();
+            let email = f.primary_email.to_lowercase();
+            lower
+                .iter()
+                .any(|a| owner.contains(a.as_str()) || email.contains(a.as_str()))
         });
     }
 
     // Filter risk-only if requested
     if opts.risk_only {
-        results.retain(|f| f.knowledge_loss);
+        files.retain(|f| f.knowledge_loss);
     }
 
-    sort_results(&mut results, opts.sort_by);
+    sort_results(&mut files, opts.sort_by);
+    files
+}
+
+/// Aggregate `files` by primary owner for `--summary`. In summary mode
+/// `sort_by` maps: concentration → files owned, diffusion → lines,
+/// risk → worst risk.
+pub fn summarize(files: &[FileOwnership], sort_by: &str) -> Vec<AuthorSummary> {
+    let mut authors = aggregate_by_author(files);
+    match sort_by {
+        "diffusion" => authors.sort_by_key(|a| Reverse(a.total_lines)),
+        "risk" => authors.sort_by_key(|a| a.worst_risk.sort_key()),
+        _ => authors.sort_by_key(|a| Reverse(a.files_owned)),
+    }
+    authors
+}
+
+/// Run knowledge map analysis: walk source files, blame each one,
+/// compute ownership concentration and risk, then output results.
+pub fn run(cfg: &WalkConfig<'_>, opts: &KnowledgeOptions<'_>) -> Result<(), Box<dyn Error>> {
+    let map = analyze_project(cfg, opts.since)?;
+    let mut results = select_files(map.files, opts);
 
     if opts.bus_factor {
-        let bf = compute_bus_factor(&author_lines, 80.0);
+        let bf = compute_bus_factor(&map.author_lines, 80.0);
         return match opts.output {
             OutputMode::Json => print_bus_factor_json(&bf),
             OutputMode::Short => {
@@ -164,9 +244,12 @@ pub fn run(cfg: &WalkConfig<'_>, opts: &KnowledgeOptions<'_>) -> Result<(), Box<
                 print_bus_factor_terse(&bf);
                 Ok(())
             }
-            OutputMode::Github | OutputMode::Codeclimate => {
+            OutputMode::Github | OutputMode::Codeclimate | OutputMode::Sarif => {
                 Err(crate::cli::ERR_CI_FORMAT_ONLY.into())
             }
+            OutputMode::Html | OutputMode::Markdown => {
+                Err(crate::cli::ERR_REPORT_FORMAT_ONLY.into())
+            }
             OutputMode::Table => {
                 print_bus_factor_report(&bf);
                 Ok(())
@@ -175,34 +258,47 @@ pub fn run(cfg: &WalkConfig<'_>, opts: &KnowledgeOptions<'_>) -> Result<(), Box<
     }
 
     if opts.summary {
-        let mut authors = aggregate_by_author(&results);
-        // In summary mode sort_by maps: concentration→files owned, diffusion→lines, risk→worst risk
-        match opts.sort_by {
-            "diffusion" => authors.sort_by_key(|a| Reverse(a.total_lines)),
-            "risk" => authors.sort_by_key(|a| a.worst_risk.sort_key()),
-            _ => authors.sort_by_key(|a| Reverse(a.files_owned)),
-        }
-        let limit = opts.top.min(authors.len());
-        let authors = &authors[..limit];
+        let mut authors = summarize(&results, opts.sort_by);
+        authors.truncate(opts.top);
         match opts.output {
-            OutputMode::Json => print_summary_json(authors),
+            OutputMode::Json => print_summary_json(&authors),
             OutputMode::Short => {
-                print_summary_short(authors);
+                print_summary_short(&authors);
                 Ok(())
             }
             OutputMode::Terse => {
-                print_summary_terse(authors);
+                print_summary_terse(&authors);
                 Ok(())
             }
-            OutputMode::Github | OutputMode::Codeclimate => {
+            OutputMode::Github | OutputMode::Codeclimate | OutputMode::Sarif => {
                 Err(crate::cli::ERR_CI_FORMAT_ONLY.into())
             }
+            OutputMode::Html | OutputMode::Markdown => {
+                Err(crate::cli::ERR_REPORT_FORMAT_ONLY.into())
+            }
             OutputMode::Table => {
-                print_summary_report(authors);
+                print_summary_report(&authors);
                 Ok(())
             }
         }
     } else {
+        // CI formats annotate the at-risk files among the top results.
+        match opts.output {
+            OutputMode::Github => {
+                results.truncate(opts.top);
+                print_github(&results);
+                return Ok(());
+            }
+            OutputMode::Codeclimate => {
+                results.truncate(opts.top);
+                return print_codeclimate(&results);
+            }
+            OutputMode::Sarif => {
+                results.truncate(opts.top);
+                return print_sarif(&results);
+            }
+            _ => {}
+        }
         report_helpers::output_results(
             &mut results,
             opts.top,
@@ -22,7 +22,7 @@ fn opts<'a>(
         risk_only,
         summary,
         bus_factor: false,
-        author: None,
+        authors: &[],
     }
 }
 
@@ -306,6 +306,26 @@ fn integration_with_generated_file_skipped() {
     assert!(result.is_ok(), "generated files should be skipped");
 }
 
+#[test]
+fn files_with_only_excluded_lines_are_skipped() {
+    let (dir, repo) = create_test_repo();
+    make_commit(&repo, &[("main.rs", "fn main() {}\n")], "add main");
+    make_commit(&repo, &[("vendored.rs", "fn v() {}\n")], "vendor");
+    let vendor = repo.head().unwrap().target().unwrap();
+    fs::write(dir.path().join("ignored-revs"), format!("{vendor}\n")).unwrap();
+    let config = crate::config::GitConfig {
+        ignore_commits: Some("ignored-revs".to_string()),
+        ..Default::default()
+    };
+    let exclusions = crate::git::CommitExclusions::resolve(&config, dir.path()).unwrap();
+
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(dir.path(), false, &filter).with_exclusions(Some(&exclusions));
+    let map = analyze_project(&cfg, None).unwrap();
+    let paths: Vec<_> = map.files.iter().map(|f| f.path.clone()).collect();
+    assert_eq!(paths, [StdPath::new("main.rs")]);
+}
+
 #[test]
 fn integration_author_filter_match() {
     let (dir, repo) = create_test_repo();
@@ -331,7 +351,7 @@ fn integration_author_filter_match() {
             risk_only: false,
             summary: false,
             bus_factor: false,
-            author: Some("Fresia"),
+            authors: &["Fresia".to_string()],
         },
     );
     assert!(result.is_ok(), "author filter should succeed");
@@ -355,7 +375,7 @@ fn integration_author_filter_no_match() {
             risk_only: false,
             summary: false,
             bus_factor: false,
-            author: Some("nonexistent"),
+            authors: &["nonexistent".to_string()],
         },
     );
     assert!(
@@ -385,7 +405,7 @@ fn integration_bus_factor_table() {
             risk_only: false,
             summary: false,
             bus_factor: true,
-            author: None,
+            authors: &[],
         },
     );
     assert!(
@@ -415,7 +435,7 @@ fn integration_bus_factor_json() {
             risk_only: false,
             summary: false,
             bus_factor: true,
-            author: None,
+            authors: &[],
         },
     );
     assert!(result.is_ok(), "bus factor JSON should succeed: {result:?}");
@@ -445,7 +465,7 @@ fn integration_summary_sort_by_diffusion() {
             risk_only: false,
             summary: true,
             bus_factor: false,
-            author: None,
+            authors: &[],
         },
     );
     assert!(
@@ -475,7 +495,7 @@ fn integration_summary_sort_by_risk() {
             risk_only: false,
             summary: true,
             bus_factor: false,
-            author: None,
+            authors: &[],
         },
     );
     assert!(result.is_ok(), "summary risk sort should work: {result:?}");
@@ -4,8 +4,9 @@
 /// ownership concentration, contributor count, and knowledge loss risk.
 use serde::Serialize;
 
-use super::analyzer::{AuthorSummary, BusFactor, FileOwnership};
+use super::analyzer::{AuthorSummary, BusFactor, FileOwnership, RiskLevel};
 use crate::report_helpers;
+use crate::sarif;
 
 const COL_LANG: usize = 10;
 const COL_LINES: usize = 7;
@@ -110,8 +111,8 @@ struct JsonEntry {
     knowledge_loss: bool,
 }
 
-/// Serialize per-file ownership data as pretty-printed JSON to stdout.
-pub fn print_json(files: &[FileOwnership]) -> Result<(), Box<dyn std::error::Error>> {
+/// Serialize per-file ownership data as a pretty-printed JSON string.
+pub fn format_json(files: &[FileOwnership]) -> Result<String, Box<dyn std::error::Error>> {
     let entries: Vec<JsonEntry> = files
         .iter()
         .map(|f| JsonEntry {
@@ -126,7 +127,76 @@ pub fn print_json(files: &[FileOwnership]) -> Result<(), Box<dyn std::error::Err
         })
         .collect();
 
-    report_helpers::print_json_stdout(&entries)
+    report_helpers::json_string(&entries)
+}
+
+/// Serialize per-file ownership data as pretty-printed JSON to stdout.
+pub fn print_json(files: &[FileOwnership]) -> Result<(), Box<dyn std::error::Error>> {
+    println!("{}", format_json(files)?);
+    Ok(())
+}
+
+/// CI annotations for files at knowledge risk: critical or high ownership
+/// concentration, or an inactive primary owner. Critical and knowledge-loss
+/// files are warnings; high-risk files are notices.
+pub fn annotations(files: &[FileOwnership]) -> Vec<report_helpers::Annotation> {
+    files
+        .iter()
+        .filter(|f| f.knowledge_loss || matches!(f.risk, RiskLevel::Critical | RiskLevel::High))
+        .map(|f| {
+            let (level, severity) = if f.knowledge_loss || f.risk == RiskLevel::Critical {
+                ("warning", "major")
+            } else {
+                ("notice", "minor")
+            };
+            let mut message = format!(
+                "{} owns {:.0}% of {} lines ({} bus-factor risk)",
+                f.primary_owner,
+                f.ownership_pct,
+                f.total_lines,
+                f.risk.label()
+            );
+            if f.knowledge_loss {
+                message.push_str("; primary owner is inactive (knowledge loss risk)");
+            }
+            report_helpers::Annotation {
+                level,
+                severity,
+                file: f.path.display().to_string(),
+                line: 1,
+                title: "Knowledge Risk",
+                message,
+            }
+        })
+        .collect()
+}
+
+/// Emit one GitHub Actions annotation per file at knowledge risk.
+pub fn print_github(files: &[FileOwnership]) {
+    report_helpers::print_github_annotations(&annotations(files));
+}
+
+/// Emit a CodeClimate JSON array with one entry per file at knowledge risk.
+pub fn print_codeclimate(files: &[FileOwnership]) -> Result<(), Box<dyn std::error::Error>> {
+    report_helpers::print_codeclimate_annotations(&annotations(files))
+}
+
+/// The SARIF rule every file at knowledge risk is filed under.
+fn sarif_rule() -> sarif::Rule {
+    sarif::Rule {
+        id: "knowledge/knowledge-risk".to_string(),
+        name: "KnowledgeRisk".to_string(),
+        short_description: "File at knowledge risk".to_string(),
+        full_description: "Most of the file is owned by one author, or its primary owner \
+                           is no longer active, so knowledge of it could be lost."
+            .to_string(),
+        level: "warning",
+    }
+}
+
+/// Emit a SARIF 2.1.0 log with one result per file at knowledge risk.
+pub fn print_sarif(files: &[FileOwnership]) -> Result<(), Box<dyn std::error::Error>> {
+    report_helpers::print_sarif_annotations(&sarif_rule(), &annotations(files))
 }
 
 const SUM_COL_FILES: usize = 5;
@@ -222,8 +292,10 @@ struct JsonSummaryEntry {
     knowledge_loss_files: usize,
 }
 
-/// Serialize per-author ownership summary as pretty-printed JSON to stdout.
-pub fn print_summary_json(authors: &[AuthorSummary]) -> Result<(), Box<dyn std::error::Error>> {
+/// Serialize per-author ownership summary as a pretty-printed JSON string.
+pub fn format_summary_json(
+    authors: &[AuthorSummary],
+) -> Result<String, Box<dyn std::error::Error>> {
     let entries: Vec<JsonSummaryEntry> = authors
         .iter()
         .map(|a| JsonSummaryEntry {
@@ -235,7 +307,13 @@ pub fn print_summary_json(authors: &[AuthorSummary]) -> Result<(), Box<dyn std::
             knowledge_loss_files: a.knowledge_loss_files,
         })
         .collect();
-    report_helpers::print_json_stdout(&entries)
+    report_helpers::json_string(&entries)
+}
+
+/// Serialize per-author ownership summary as pretty-printed JSON to stdout.
+pub fn print_summary_json(authors: &[AuthorSummary]) -> Result<(), Box<dyn std::error::Error>> {
+    println!("{}", format_summary_json(authors)?);
+    Ok(())
 }
 
 /// Print the bus factor report as a human-readable table.
@@ -330,8 +408,8 @@ pub fn print_bus_factor_terse(bf: &BusFactor) {
     println!("{}", bf.factor);
 }
 
-/// JSON-serializable bus factor output.
-pub fn print_bus_factor_json(bf: &BusFactor) -> Result<(), Box<dyn std::error::Error>> {
+/// Serialize the bus factor breakdown as a pretty-printed JSON string.
+pub fn format_bus_factor_json(bf: &BusFactor) -> Result<String, Box<dyn std::error::Error>> {
     use serde::Serialize;
 
     #[derive(Serialize)]
@@ -368,7 +446,13 @@ pub fn print_bus_factor_json(bf: &BusFactor) -> Result<(), Box<dyn std::error::E
             .collect(),
     };
 
-    report_helpers::print_json_stdout(&out)
+    report_helpers::json_string(&out)
+}
+
+/// JSON-serializable bus factor output.
+pub fn print_bus_factor_json(bf: &BusFactor) -> Result<(), Box<dyn std::error::Error>> {
+    println!("{}", format_bus_factor_json(bf)?);
+    Ok(())
 }
 
 #[cfg(test)]
@@ -224,3 +224,32 @@ fn print_bus_factor_json_with_data() {
     );
     print_bus_factor_json(&bf).unwrap();
 }
+
+#[test]
+fn annotations_flag_risky_files() {
+    let mut files = sample_files();
+    files.push(FileOwnership {
+        path: PathBuf::from("src/ok.rs"),
+        language: "Rust".to_string(),
+        total_lines: 100,
+        primary_owner: "Galvarino".to_string(),
+        primary_email: "galvarino@ruca.mapu".to_string(),
+        ownership_pct: 40.0,
+        contributors: 4,
+        risk: RiskLevel::Low,
+        knowledge_loss: false,
+    });
+    let anns = annotations(&files);
+    assert_eq!(anns.len(), 2);
+    assert_eq!(anns[0].file, "src/foo.rs");
+    assert_eq!((anns[0].level, anns[0].severity), ("warning", "major"));
+    assert_eq!(
+        anns[0].message,
+        "Lautaro owns 94% of 731 lines (CRITICAL bus-factor risk); \
+         primary owner is inactive (knowledge loss risk)"
+    );
+    assert_eq!(anns[1].file, "src/bar.rs");
+    assert_eq!((anns[1].level, anns[1].severity), ("notice", "minor"));
+    print_github(&files);
+    print_codeclimate(&files).unwrap();
+}
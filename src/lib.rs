@@ -0,0 +1,99 @@
+//! Kimün — code metrics as a library.
+//!
+//! Every analysis behind the `km` binary is available here as a typed entry
+//! point that walks a project and returns the result structs without printing
+//! anything to stdout. The `km` binary is a thin client over this API: it
+//! parses CLI arguments, calls these functions, and formats the results.
+//!
+//! ```no_run
+//! use std::path::Path;
+//! use kimun::{ExcludeFilter, WalkConfig};
+//!
+//! let filter = ExcludeFilter::default();
+//! let cfg = WalkConfig::new(Path::new("."), false, &filter);
+//!
+//! let languages = kimun::loc::analyze_project(&cfg);
+//! let cognitive = kimun::cogcom::analyze_project(&cfg, 1, "total");
+//! let model = kimun::score::ScoreModel::default();
+//! let score = kimun::score::compute_score(&cfg, 10, 6, &model).unwrap();
+//! println!("{} files, grade {}", cognitive.len(), score.grade);
+//! # let _ = languages;
+//! ```
+//!
+//! Static analyses take a [`WalkConfig`]; git-based analyses open the
+//! repository containing the walk root through [`GitRepo`].
+
+/// Code age analysis: Active / Stale / Frozen classification by last git modification.
+pub mod age;
+/// AI-powered analysis via external LLM providers.
+pub mod ai;
+/// Author summary: per-author ownership, lines, languages, last active date.
+pub mod authors;
+/// Persistent on-disk analysis cache keyed by file content hash.
+pub mod cache;
+/// Code churn analysis: pure change frequency per file from git history.
+pub mod churn;
+/// CLI argument definitions using `clap` derive macros.
+pub mod cli;
+/// Long help text constants extracted from CLI definitions.
+mod cli_help;
+/// Cognitive complexity analysis (SonarSource, 2017).
+pub mod cogcom;
+/// Project-level configuration loaded from `.kimun.toml`.
+pub mod config;
+/// Cyclomatic complexity analysis (per-file and per-function).
+pub mod cycom;
+/// Technical debt estimate: findings priced in remediation minutes.
+pub mod debt;
+/// Defect analysis: bug-fix commits per file, classified by commit message.
+pub mod defects;
+/// Dependency graph analysis: internal module coupling via import parsing.
+pub mod deps;
+/// Shared function detection for complexity analyzers.
+mod detection;
+/// Duplicate code detection using sliding-window fingerprinting.
+pub mod dups;
+/// Git repository access via libgit2 (change frequency, blame, coupling).
+pub mod git;
+/// Halstead complexity metrics (volume, effort, bugs, time).
+pub mod hal;
+/// Score history: code health sampled along first-parent git history.
+pub mod history;
+/// Hotspot analysis: change frequency × complexity.
+pub mod hotspots;
+/// Indentation complexity (stddev and max depth).
+pub mod indent;
+/// Interactive wizard: analyze current project state and write `.kimun.toml`.
+pub mod init;
+/// Knowledge maps: code ownership via git blame.
+pub mod knowledge;
+/// Lines of code counting with FSM-based line classification.
+pub mod loc;
+/// Model Context Protocol server exposing every analysis as a tool over stdio.
+pub mod mcp;
+/// Maintainability Index (Visual Studio variant, 0–100 scale).
+pub mod mi;
+/// Maintainability Index (verifysoft variant, with comment weight).
+pub mod miv;
+/// Combined report (`km report`) aggregating all metrics.
+pub mod report;
+/// Shared report formatting utilities (separators, path widths, JSON output).
+mod report_helpers;
+/// Analysis of a git revision from the object database (`--rev`).
+pub mod rev;
+/// SARIF 2.1.0 log builder for `--format sarif`.
+mod sarif;
+/// Overall code health score (A++ to F--, 5 weighted dimensions).
+pub mod score;
+/// Code smell detection (long functions, magic numbers, etc.).
+pub mod smells;
+/// Temporal coupling analysis (co-changing files in git history).
+pub mod tc;
+/// Shared utilities (string masking, file reading, since parsing).
+pub mod util;
+/// Filesystem walking with .gitignore support and test exclusion.
+pub mod walk;
+
+pub use git::GitRepo;
+pub use loc::language::LanguageSpec;
+pub use walk::{ExcludeFilter, WalkConfig};
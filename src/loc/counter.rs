@@ -1,9 +1,9 @@
-use std::fs::File;
 use std::io::{self, BufRead, BufReader};
 use std::path::Path;
 
 use super::fsm::{State, StringKind, step_in_block_comment, step_in_string, step_normal};
 use super::language::LanguageSpec;
+use crate::rev;
 use crate::util::is_binary_reader;
 
 #[derive(Debug, Default, Clone)]
@@ -25,8 +25,7 @@ pub enum LineKind {
 /// Returns `None` if the file contains null bytes (binary detection).
 /// Opens the file, checks for binary content, then delegates to `count_reader`.
 pub fn count_lines(path: &Path, spec: &LanguageSpec) -> io::Result<Option<FileStats>> {
-    let file = File::open(path)?;
-    let mut reader = BufReader::new(file);
+    let mut reader = BufReader::new(rev::open(path)?);
 
     if is_binary_reader(&mut reader)? {
         return Ok(None);
@@ -3,34 +3,40 @@
 /// Walks the directory tree, deduplicates files by content hash,
 /// counts blank/comment/code lines per language via a character-level
 /// FSM, and aggregates results for table or JSON output.
-pub(crate) mod counter;
+pub mod counter;
 mod fsm;
 mod lang_macro;
-pub(crate) mod language;
+pub mod language;
 pub(crate) mod report;
 
 use std::collections::{HashMap, HashSet};
 use std::error::Error;
-use std::fs::File;
 use std::io::BufReader;
 use std::time::Instant;
 
 use crate::git::GitRepo;
+use crate::rev;
 use crate::util::hash_file;
 use crate::walk::{self, WalkConfig};
-use counter::{FileStats, LineKind, classify_reader, count_lines};
+use counter::{FileStats, LineKind, classify_reader};
 use report::{
-    AuthorReport, LanguageReport, VerboseStats, print_author_json, print_author_report,
-    print_author_short, print_author_terse, print_json, print_report, print_short, print_terse,
+    print_author_json, print_author_report, print_author_short, print_author_terse, print_json,
+    print_report, print_short, print_terse,
 };
 
-/// Walk source files, deduplicate by content hash, count lines per
-/// language, and print a summary table or other format per `output`.
-pub fn run(
-    cfg: &WalkConfig<'_>,
-    verbose: bool,
-    output: crate::cli::OutputMode,
-) -> Result<(), Box<dyn Error>> {
+pub use counter::count_lines;
+pub use report::{AuthorReport, LanguageReport, VerboseStats};
+
+/// Per-language line counts for a project plus the walk statistics
+/// (files read, duplicates and binaries skipped, elapsed time).
+pub struct LocSummary {
+    pub languages: Vec<LanguageReport>,
+    pub stats: VerboseStats,
+}
+
+/// Walk source files, deduplicate by content hash, and count lines per
+/// language. Languages are returned in no particular order.
+pub fn analyze_project(cfg: &WalkConfig<'_>) -> LocSummary {
     let start = Instant::now();
     let mut stats_by_lang: HashMap<&'static str, (usize, FileStats)> = HashMap::new();
     let mut seen_hashes: HashSet<u64> = HashSet::new();
@@ -39,18 +45,27 @@ pub fn run(
     let mut duplicate_files: usize = 0;
     let mut binary_files: usize = 0;
 
-    for (file_path, spec) in cfg.source_files() {
+    // Hash and count every file in parallel; deduplication happens below in
+    // walk order so the first occurrence of identical content always wins.
+    let files = cfg.source_files();
+    let counted = cfg.par_map(&files, |(file_path, spec)| {
+        let hash = hash_file(file_path);
+        let counts = count_lines(file_path, spec).map_err(|err| err.to_string());
+        (hash, counts)
+    });
+
+    for ((file_path, spec), (hash, counts)) in files.iter().zip(counted) {
         total_files += 1;
 
         // Skip duplicate files (same content)
-        if let Some(h) = hash_file(&file_path)
+        if let Some(h) = hash
             && !seen_hashes.insert(h)
         {
             duplicate_files += 1;
             continue;
         }
 
-        match count_lines(&file_path, spec) {
+        match counts {
             Ok(Some(file_stats)) => {
                 unique_files += 1;
                 let entry = stats_by_lang
@@ -70,7 +85,7 @@ pub fn run(
         }
     }
 
-    let reports: Vec<LanguageReport> = stats_by_lang
+    let languages: Vec<LanguageReport> = stats_by_lang
         .into_iter()
         .map(|(name, (files, fs))| LanguageReport {
             name: name.to_string(),
@@ -81,6 +96,29 @@ pub fn run(
         })
         .collect();
 
+    LocSummary {
+        languages,
+        stats: VerboseStats {
+            total_files,
+            unique_files,
+            duplicate_files,
+            binary_files,
+            elapsed: start.elapsed(),
+        },
+    }
+}
+
+/// Count lines per language and print a summary table or other format per `output`.
+pub fn run(
+    cfg: &WalkConfig<'_>,
+    verbose: bool,
+    output: crate::cli::OutputMode,
+) -> Result<(), Box<dyn Error>> {
+    let LocSummary {
+        languages: reports,
+        stats,
+    } = analyze_project(cfg);
+
     if reports.is_empty() {
         match output {
             crate::cli::OutputMode::Json => {
@@ -98,22 +136,16 @@ pub fn run(
             crate::cli::OutputMode::Json => print_json(reports),
             crate::cli::OutputMode::Short => print_short(&reports),
             crate::cli::OutputMode::Terse => print_terse(&reports),
-            crate::cli::OutputMode::Github | crate::cli::OutputMode::Codeclimate => {
+            crate::cli::OutputMode::Github
+            | crate::cli::OutputMode::Codeclimate
+            | crate::cli::OutputMode::Sarif => {
                 return Err(crate::cli::ERR_CI_FORMAT_ONLY.into());
             }
+            crate::cli::OutputMode::Html | crate::cli::OutputMode::Markdown => {
+                return Err(crate::cli::ERR_REPORT_FORMAT_ONLY.into());
+            }
             crate::cli::OutputMode::Table => {
-                let verbose_stats = if verbose {
-                    Some(VerboseStats {
-                        total_files,
-                        unique_files,
-                        duplicate_files,
-                        binary_files,
-                        elapsed: start.elapsed(),
-                    })
-                } else {
-                    None
-                };
-                print_report(reports, verbose_stats);
+                print_report(reports, verbose.then_some(stats));
             }
         }
     }
@@ -121,13 +153,13 @@ pub fn run(
     Ok(())
 }
 
-/// Walk source files in a git repository, attribute each line to its author
-/// via `git blame`, classify lines with the FSM, and print a per-author table.
-pub fn run_by_author(
-    cfg: &WalkConfig<'_>,
-    output: crate::cli::OutputMode,
-) -> Result<(), Box<dyn Error>> {
-    let git = GitRepo::open(cfg.path)?;
+/// Walk source files in a git repository and attribute each line to its
+/// author via `git blame`, classifying lines with the FSM. Authors are
+/// returned in no particular order.
+pub fn analyze_by_author(cfg: &WalkConfig<'_>) -> Result<Vec<AuthorReport>, Box<dyn Error>> {
+    let git = GitRepo::open(cfg.path)?
+        .with_aliases(cfg.aliases)
+        .with_exclusions(cfg.exclusions);
     let (canonical_walk, prefix) = git.walk_prefix(cfg.path)?;
 
     // author email → (name, stats, files_set)
@@ -139,7 +171,7 @@ pub fn run_by_author(
             continue;
         }
 
-        let Ok(file) = File::open(&file_path) else {
+        let Ok(file) = rev::open(&file_path) else {
             continue;
         };
         let reader = BufReader::new(file);
@@ -172,7 +204,28 @@ pub fn run_by_author(
         }
     }
 
-    if by_author.is_empty() {
+    let reports = by_author
+        .into_iter()
+        .map(|(email, (name, fs, files))| AuthorReport {
+            name,
+            email,
+            files: files.len(),
+            blank: fs.blank,
+            comment: fs.comment,
+            code: fs.code,
+        })
+        .collect();
+    Ok(reports)
+}
+
+/// Count lines per git author and print a per-author table or other format.
+pub fn run_by_author(
+    cfg: &WalkConfig<'_>,
+    output: crate::cli::OutputMode,
+) -> Result<(), Box<dyn Error>> {
+    let reports = analyze_by_author(cfg)?;
+
+    if reports.is_empty() {
         match output {
             crate::cli::OutputMode::Json => {
                 println!(
@@ -187,25 +240,18 @@ pub fn run_by_author(
         return Ok(());
     }
 
-    let reports: Vec<AuthorReport> = by_author
-        .into_iter()
-        .map(|(email, (name, fs, files))| AuthorReport {
-            name,
-            email,
-            files: files.len(),
-            blank: fs.blank,
-            comment: fs.comment,
-            code: fs.code,
-        })
-        .collect();
-
     match output {
         crate::cli::OutputMode::Json => print_author_json(reports),
         crate::cli::OutputMode::Short => print_author_short(&reports),
         crate::cli::OutputMode::Terse => print_author_terse(&reports),
-        crate::cli::OutputMode::Github | crate::cli::OutputMode::Codeclimate => {
+        crate::cli::OutputMode::Github
+        | crate::cli::OutputMode::Codeclimate
+        | crate::cli::OutputMode::Sarif => {
             return Err(crate::cli::ERR_CI_FORMAT_ONLY.into());
         }
+        crate::cli::OutputMode::Html | crate::cli::OutputMode::Markdown => {
+            return Err(crate::cli::ERR_REPORT_FORMAT_ONLY.into());
+        }
         crate::cli::OutputMode::Table => print_author_report(reports),
     }
 
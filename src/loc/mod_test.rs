@@ -219,6 +219,30 @@ fn run_by_author_multiple_files() {
     assert!(result.is_ok(), "multi-file run_by_author should succeed");
 }
 
+#[test]
+fn by_author_leaves_out_excluded_commits() {
+    let (dir, repo) = create_test_repo();
+    make_commit(&repo, &[("main.rs", "fn main() {}\n")], "add main");
+    make_commit(
+        &repo,
+        &[("vendored.rs", "fn v() {}\nfn w() {}\n")],
+        "vendor",
+    );
+    let vendor = repo.head().unwrap().target().unwrap();
+    fs::write(dir.path().join("ignored-revs"), format!("{vendor}\n")).unwrap();
+    let config = crate::config::GitConfig {
+        ignore_commits: Some("ignored-revs".to_string()),
+        ..Default::default()
+    };
+    let exclusions = crate::git::CommitExclusions::resolve(&config, dir.path()).unwrap();
+
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(dir.path(), false, &filter).with_exclusions(Some(&exclusions));
+    let reports = analyze_by_author(&cfg).unwrap();
+    assert_eq!(reports.len(), 1);
+    assert_eq!((reports[0].files, reports[0].code), (1, 1));
+}
+
 #[test]
 fn run_by_author_empty_repo() {
     // No source files — exercises the empty by_author branch
@@ -294,3 +318,28 @@ fn run_codeclimate_returns_error() {
         "error message should mention codeclimate"
     );
 }
+
+#[test]
+fn analyze_project_counts_per_language() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("main.rs"),
+        "fn main() {\n    // hello\n\n    println!(\"hi\");\n}\n",
+    )
+    .unwrap();
+    fs::write(dir.path().join("a.c"), "int x = 1;\n").unwrap();
+    fs::write(dir.path().join("b.c"), "int x = 1;\n").unwrap();
+
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(dir.path(), false, &filter);
+    let summary = analyze_project(&cfg);
+
+    assert_eq!(summary.stats.total_files, 3);
+    assert_eq!(summary.stats.duplicate_files, 1);
+    let rust = summary.languages.iter().find(|l| l.name == "Rust").unwrap();
+    assert_eq!(rust.code, 3);
+    assert_eq!(rust.comment, 1);
+    assert_eq!(rust.blank, 1);
+    let c = summary.languages.iter().find(|l| l.name == "C").unwrap();
+    assert_eq!(c.files, 1);
+}
@@ -112,8 +112,8 @@ struct JsonTotals {
     code: usize,
 }
 
-/// Serialize line counts as pretty-printed JSON to stdout.
-pub fn print_json(mut reports: Vec<LanguageReport>) {
+/// Serialize line counts as a pretty-printed JSON string.
+pub fn format_json(mut reports: Vec<LanguageReport>) -> Result<String, Box<dyn std::error::Error>> {
     reports.sort_by_key(|r| Reverse(r.code));
 
     let totals = JsonTotals {
@@ -128,7 +128,12 @@ pub fn print_json(mut reports: Vec<LanguageReport>) {
         totals,
     };
 
-    report_helpers::print_json_stdout(&output).unwrap();
+    report_helpers::json_string(&output)
+}
+
+/// Serialize line counts as pretty-printed JSON to stdout.
+pub fn print_json(reports: Vec<LanguageReport>) {
+    println!("{}", format_json(reports).unwrap());
 }
 
 /// Per-author line count summary across all files.
@@ -201,8 +206,10 @@ pub fn print_author_report(mut reports: Vec<AuthorReport>) {
     println!("{separator}");
 }
 
-/// Serialize by-author line counts as pretty-printed JSON to stdout.
-pub fn print_author_json(mut reports: Vec<AuthorReport>) {
+/// Serialize by-author line counts as a pretty-printed JSON string.
+pub fn format_author_json(
+    mut reports: Vec<AuthorReport>,
+) -> Result<String, Box<dyn std::error::Error>> {
     reports.sort_by_key(|r| Reverse(r.code));
 
     #[derive(Serialize)]
@@ -218,11 +225,15 @@ pub fn print_author_json(mut reports: Vec<AuthorReport>) {
         code: reports.iter().map(|r| r.code).sum(),
     };
 
-    report_helpers::print_json_stdout(&JsonOutput {
+    report_helpers::json_string(&JsonOutput {
         authors: reports,
         totals,
     })
-    .unwrap();
+}
+
+/// Serialize by-author line counts as pretty-printed JSON to stdout.
+pub fn print_author_json(reports: Vec<AuthorReport>) {
+    println!("{}", format_author_json(reports).unwrap());
 }
 
 /// Print LOC as a single compact line.
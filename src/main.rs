@@ -2,77 +2,25 @@
 //!
 //! Supports 12 analysis commands covering static metrics (LOC, duplication,
 //! Halstead, cyclomatic, indentation, MI, code health score) and git-based
-//! metrics (hotspots, knowledge maps, temporal coupling). Each command is
-//! a standalone module that handles its own analysis and reporting.
+//! metrics (hotspots, knowledge maps, temporal coupling). The analyses live
+//! in the `kimun` library crate; this binary is a thin client over it.
 //!
 //! The dispatch pattern is uniform: parse CLI args with `clap`, resolve
 //! the target path (defaulting to "."), and delegate to the module's `run()`
 //! function. All errors are printed to stderr and cause exit code 1.
 
-/// Code age analysis: Active / Stale / Frozen classification by last git modification.
-mod age;
-/// AI-powered analysis via external LLM providers.
-mod ai;
-/// Author summary: per-author ownership, lines, languages, last active date.
-mod authors;
-/// Code churn analysis: pure change frequency per file from git history.
-mod churn;
-/// CLI argument definitions using `clap` derive macros.
-mod cli;
-/// Long help text constants extracted from CLI definitions.
-mod cli_help;
-/// Cognitive complexity analysis (SonarSource, 2017).
-mod cogcom;
-/// Project-level configuration loaded from `.kimun.toml`.
-mod config;
-/// Cyclomatic complexity analysis (per-file and per-function).
-mod cycom;
-/// Dependency graph analysis: internal module coupling via import parsing.
-mod deps;
-/// Shared function detection for complexity analyzers.
-mod detection;
-/// Duplicate code detection using sliding-window fingerprinting.
-mod dups;
-/// Git repository access via libgit2 (change frequency, blame, coupling).
-mod git;
-/// Halstead complexity metrics (volume, effort, bugs, time).
-mod hal;
-/// Hotspot analysis: change frequency × complexity.
-mod hotspots;
-/// Indentation complexity (stddev and max depth).
-mod indent;
-/// Interactive wizard: analyze current project state and write `.kimun.toml`.
-mod init;
-/// Knowledge maps: code ownership via git blame.
-mod knowledge;
-/// Lines of code counting with FSM-based line classification.
-mod loc;
-/// Maintainability Index (Visual Studio variant, 0–100 scale).
-mod mi;
-/// Maintainability Index (verifysoft variant, with comment weight).
-mod miv;
-/// Combined report (`km report`) aggregating all metrics.
-mod report;
-/// Shared report formatting utilities (separators, path widths, JSON output).
-mod report_helpers;
-/// Overall code health score (A++ to F--, 5 weighted dimensions).
-mod score;
-/// Code smell detection (long functions, magic numbers, etc.).
-mod smells;
-/// Temporal coupling analysis (co-changing files in git history).
-mod tc;
-/// Shared utilities (string masking, file reading, since parsing).
-mod util;
-/// Filesystem walking with .gitignore support and test exclusion.
-mod walk;
-
 use std::path::PathBuf;
 
 use clap::{CommandFactory, Parser};
 use clap_complete::{Shell, generate};
 
-use cli::{AiCommands, Cli, Commands, ScoreCommands};
-use walk::{ExcludeFilter, WalkConfig};
+use kimun::cli::{self, AiCommands, Cli, Commands, ScoreCommands};
+use kimun::rev::RevTree;
+use kimun::walk::{self, ExcludeFilter, WalkConfig};
+use kimun::{
+    age, ai, authors, cache, churn, cogcom, config, cycom, debt, defects, deps, dups, git, hal,
+    history, hotspots, indent, init, knowledge, loc, mcp, mi, miv, report, score, smells, tc,
+};
 
 /// Resolve an optional path to a default of "." and run an analysis
 /// command, printing errors to stderr and exiting with code 1 on failure.
@@ -111,52 +59,153 @@ fn maybe_list_excluded(
 /// Saves ~7 lines of identical boilerplate per subcommand arm.
 ///
 /// The `$output` identifier is explicit in the call site pattern so that Rust's
-/// macro hygiene allows it to be referenced inside `$body`.
+/// macro hygiene allows it to be referenced inside `$body`. Git-history
+/// commands also pass their `CommitFilterArgs`; they and commands given
+/// `git: <condition>` read the `[authors]`, `[defects]` and `[git]`
+/// settings, which other commands leave alone so a broken one cannot stop
+/// them.
 macro_rules! dispatch {
-    ($common:expr, |$cfg:ident, $output:ident| $body:expr) => {{
+    ($common:expr, |$cfg:ident, $output:ident| $body:expr) => {
+        dispatch!(@run $common, &cli::CommitFilterArgs::default(), false, |$cfg, $output| $body)
+    };
+    ($common:expr, git: $git:expr, |$cfg:ident, $output:ident| $body:expr) => {
+        dispatch!(@run $common, &cli::CommitFilterArgs::default(), $git, |$cfg, $output| $body)
+    };
+    ($common:expr, $commits:expr, |$cfg:ident, $output:ident| $body:expr) => {
+        dispatch!(@run $common, $commits, true, |$cfg, $output| $body)
+    };
+    (@run $common:expr, $commits:expr, $git:expr, |$cfg:ident, $output:ident| $body:expr) => {{
         let _c = $common;
+        let commit_filter = resolve_commit_filter($commits);
         let include_tests = _c.include_tests;
+        let kcfg = config::KimunConfig::load();
+        let jobs = kcfg.analysis.resolve_jobs(_c.jobs);
+        let cache = open_cache(&kcfg, _c.cache_args.enabled());
+        let reads_git = $git;
+        let aliases = reads_git.then(|| resolve_aliases(&kcfg));
+        let fix_patterns = reads_git.then(|| resolve_fix_patterns(&kcfg));
+        let exclusions = reads_git.then(|| resolve_exclusions(&kcfg));
+        let rename_threshold = kcfg.git.resolve_rename_threshold();
         let $output = _c.format;
         let filter = _c.exclude_filter();
         maybe_list_excluded(&_c.path, include_tests, &filter, _c.list_excluded());
+        let rev = _c.rev;
         run_command(_c.path, |t| {
-            let $cfg = WalkConfig::new(t, include_tests, &filter);
+            let tree = mount_rev(t, rev.as_deref())?;
+            let t = tree.as_ref().map_or(t, |tree| tree.path());
+            let $cfg = WalkConfig::new(t, include_tests, &filter)
+                .with_jobs(jobs)
+                .with_cache(cache.as_ref())
+                .with_aliases(aliases.as_ref())
+                .with_fix_patterns(fix_patterns.as_ref())
+                .with_commit_filter(commit_filter.as_ref())
+                .with_exclusions(exclusions.as_ref())
+                .with_rename_threshold(rename_threshold)
+                .with_tree(tree.as_ref());
             $body
         })
     }};
 }
 
+/// Mount the tree of `--rev` (or HEAD's, in a bare repository) for
+/// analyzing `path`; `None` analyzes the working tree.
+fn mount_rev(
+    path: &std::path::Path,
+    rev: Option<&str>,
+) -> Result<Option<RevTree>, Box<dyn std::error::Error>> {
+    RevTree::mount(path, rev)
+}
+
+/// Exit with an error when `--rev` is given to `what`, which compares
+/// against the working tree or reads files outside one tree.
+fn reject_rev(rev: &Option<String>, what: &str) {
+    if rev.is_some() {
+        eprintln!("error: --rev cannot be used with {what}");
+        std::process::exit(1);
+    }
+}
+
+/// Parse `[authors.aliases]` from `.kimun.toml`, exiting on invalid entries.
+fn resolve_aliases(kcfg: &config::KimunConfig) -> git::AuthorAliases {
+    git::AuthorAliases::resolve(&kcfg.authors).unwrap_or_else(|e| {
+        eprintln!("error: .kimun.toml: {e}");
+        std::process::exit(1);
+    })
+}
+
+/// Compile `[defects] patterns` from `.kimun.toml`, exiting on invalid regexes.
+fn resolve_fix_patterns(kcfg: &config::KimunConfig) -> git::FixPatterns {
+    git::FixPatterns::resolve(&kcfg.defects).unwrap_or_else(|e| {
+        eprintln!("error: .kimun.toml: {e}");
+        std::process::exit(1);
+    })
+}
+
+/// Read the `[git]` commit exclusions from `.kimun.toml`, exiting on an
+/// invalid pattern or an unreadable `ignore_commits` file.
+fn resolve_exclusions(kcfg: &config::KimunConfig) -> git::CommitExclusions {
+    git::CommitExclusions::resolve(&kcfg.git, &config::KimunConfig::project_root()).unwrap_or_else(
+        |e| {
+            eprintln!("error: .kimun.toml: {e}");
+            std::process::exit(1);
+        },
+    )
+}
+
+/// Build the commit filter from `--range`, `--until`, `--author` and
+/// `--path`, exiting on an invalid value.
+fn resolve_commit_filter(args: &cli::CommitFilterArgs) -> Option<git::CommitFilter> {
+    args.commit_filter().unwrap_or_else(|e| {
+        eprintln!("error: {e}");
+        std::process::exit(1);
+    })
+}
+
+/// Open the analysis cache when enabled by `--cache` or `.kimun.toml`.
+/// Failing to open it is not fatal: warn and analyze without a cache.
+fn open_cache(kcfg: &config::KimunConfig, cli: Option<bool>) -> Option<cache::AnalysisCache> {
+    if !kcfg.cache.resolve_enabled(cli) {
+        return None;
+    }
+    let dir = kcfg.cache.resolve_dir(&config::KimunConfig::project_root());
+    match cache::AnalysisCache::open(&dir) {
+        Ok(c) => Some(c),
+        Err(e) => {
+            eprintln!("warning: cache disabled: {}: {e}", dir.display());
+            None
+        }
+    }
+}
+
 /// Dispatch the `tc` subcommand. Temporal coupling works entirely from git
 /// history, so filesystem exclude flags have no effect — warn the user.
 fn dispatch_tc(
     common: cli::CommonArgs,
+    commits: cli::CommitFilterArgs,
     top: usize,
     sort_by: String,
     since: Option<String>,
     min_degree: Option<usize>,
     min_strength: Option<f64>,
 ) {
-    let cfg = config::KimunConfig::load();
-    let min_degree = cfg.tc.resolve_min_degree(min_degree);
-    let min_strength = cfg.tc.resolve_min_strength(min_strength);
+    let kcfg = config::KimunConfig::load();
+    let min_degree = kcfg.tc.resolve_min_degree(min_degree);
+    let min_strength = kcfg.tc.resolve_min_strength(min_strength);
     if !common.exclude_args.is_empty() {
         eprintln!(
             "warning: --exclude-ext/--exclude-dir/--exclude have no effect on `tc` \
              (temporal coupling works from git history, not the filesystem)"
         );
     }
-    run_command(common.path, |t| {
-        tc::run(
-            t,
-            common.format,
-            common.include_tests,
-            top,
-            &sort_by,
-            since.as_deref(),
-            min_degree,
-            min_strength,
-        )
-    });
+    dispatch!(common, &commits, |cfg, output| tc::run(
+        &cfg,
+        output,
+        top,
+        &sort_by,
+        since.as_deref(),
+        min_degree,
+        min_strength
+    ));
 }
 
 /// Dispatch the `smells` subcommand. Supports three modes: `--since-ref`,
@@ -172,6 +221,13 @@ fn dispatch_smells(
     let cfg = config::KimunConfig::load();
     let max_lines = cfg.smells.resolve_max_lines(max_lines);
     let max_params = cfg.smells.resolve_max_params(max_params);
+    if since_ref.is_some() {
+        reject_rev(&common.rev, "--since-ref");
+    } else if !files.is_empty() {
+        reject_rev(&common.rev, "--files");
+    }
+    let jobs = cfg.analysis.resolve_jobs(common.jobs);
+    let cache = open_cache(&cfg, common.cache_args.enabled());
     let include_tests = common.include_tests;
     let output = common.format;
     let filter = common.exclude_filter();
@@ -185,50 +241,88 @@ fn dispatch_smells(
         } else if !files.is_empty() {
             smells::run_on_files(&files, output, top, max_lines, max_params)
         } else {
-            let cfg = WalkConfig::new(t, include_tests, &filter);
+            let tree = mount_rev(t, common.rev.as_deref())?;
+            let t = tree.as_ref().map_or(t, |tree| tree.path());
+            let cfg = WalkConfig::new(t, include_tests, &filter)
+                .with_jobs(jobs)
+                .with_cache(cache.as_ref())
+                .with_tree(tree.as_ref());
             smells::run(&cfg, output, top, max_lines, max_params)
         }
     });
 }
 
-/// Dispatch `score` (no subcommand). Parses `--fail-below` early so errors
-/// surface before analysis runs, then delegates to `run_diff` or `run`.
+/// Dispatch `score` (no subcommand). Parses the `--fail-below` grades early
+/// so errors surface before analysis runs, then delegates to `run_diff`,
+/// `run_new_code`, `run_breakdown`, or `run`.
+#[allow(clippy::too_many_arguments)]
 fn dispatch_score(
     common: cli::CommonArgs,
     bottom: usize,
     min_lines: Option<usize>,
     model: Option<String>,
-    trend: Option<String>,
+    trend: Option<(String, usize)>,
     fail_if_worse: bool,
     fail_below: Option<String>,
+    breakdown: Option<score::Breakdown>,
+    new_code: Option<(String, Option<String>)>,
 ) {
     let kcfg = config::KimunConfig::load();
     let min_lines = kcfg.dups.resolve_min_lines(min_lines);
-    let model = kcfg.score.resolve_model(model);
-    let fail_below = kcfg.score.resolve_fail_below(fail_below);
-    let fail_below_grade = match fail_below {
-        Some(ref s) => match score::analyzer::Grade::parse(s) {
-            Ok(g) => Some(g),
-            Err(e) => {
-                eprintln!("error: --fail-below: {e}");
-                std::process::exit(1);
-            }
-        },
-        None => None,
-    };
+    let model = resolve_score_model(&kcfg, model);
+    let fail_below_grade =
+        parse_grade_arg("--fail-below", kcfg.score.resolve_fail_below(fail_below));
+    if new_code.is_some() {
+        reject_rev(&common.rev, "--new-code-since");
+    }
+    let new_code = new_code.map(|(git_ref, fail_below)| {
+        let grade = parse_grade_arg(
+            "--new-code-fail-below",
+            kcfg.score.resolve_new_code_fail_below(fail_below),
+        );
+        (git_ref, grade)
+    });
     dispatch!(common, |cfg, output| {
-        if let Some(ref git_ref) = trend {
+        if let Some((ref git_ref, top_changes)) = trend {
             let gate = score::ScoreGate {
                 fail_if_worse,
                 fail_below: fail_below_grade,
             };
-            score::run_diff(&cfg, git_ref, output, bottom, min_lines, &model, gate)
+            score::run_diff(&cfg, git_ref, output, top_changes, min_lines, &model, gate)
+        } else if let Some((ref git_ref, fail_below)) = new_code {
+            score::run_new_code(&cfg, git_ref, output, bottom, min_lines, &model, fail_below)
+        } else if let Some(by) = breakdown {
+            score::run_breakdown(&cfg, output, min_lines, &model, by)
         } else {
             score::run(&cfg, output, bottom, min_lines, &model)
         }
     });
 }
 
+/// Parse an optional grade given to `flag`.
+fn parse_grade_arg(flag: &str, value: Option<String>) -> Option<score::analyzer::Grade> {
+    value.map(|s| parse_grade(flag, &s))
+}
+
+/// Parse a grade given to `flag`, exiting with an error when it is not a
+/// valid grade.
+fn parse_grade(flag: &str, value: &str) -> score::analyzer::Grade {
+    score::analyzer::Grade::parse(value).unwrap_or_else(|e| {
+        eprintln!("error: {flag}: {e}");
+        std::process::exit(1);
+    })
+}
+
+/// Resolve the scoring model with the `[score]` weight and curve overrides,
+/// exiting with an error when the configuration is invalid.
+fn resolve_score_model(kcfg: &config::KimunConfig, model: Option<String>) -> score::ScoreModel {
+    let name = kcfg.score.resolve_model(model);
+    score::ScoreModel::resolve(&name, &kcfg.score).unwrap_or_else(|e| {
+        eprintln!("error: .kimun.toml: {e}");
+        std::process::exit(1);
+    })
+}
+
 /// Dispatch the `ai` subcommand and its nested commands.
 fn dispatch_ai(command: AiCommands) {
     match command {
@@ -236,10 +330,18 @@ fn dispatch_ai(command: AiCommands) {
             provider,
             path,
             model,
+            base_url,
+            api_key_env,
             output,
-        } => run_command(path, |t| {
-            ai::run(&provider, t, model.as_deref(), output.as_deref())
-        }),
+        } => {
+            let (provider, path) = ai::provider::split_target(provider, path);
+            run_command(path, |t| {
+                let kcfg = config::KimunConfig::load();
+                let provider =
+                    ai::Provider::from_config(&kcfg.ai, provider, model, base_url, api_key_env)?;
+                ai::run(&provider, t, output.as_deref())
+            })
+        }
         AiCommands::Skill {
             provider,
             with_permissions,
@@ -277,13 +379,15 @@ fn main() {
             common,
             verbose,
             by_author,
-        } => dispatch!(common, |cfg, output| {
-            if by_author {
-                loc::run_by_author(&cfg, output)
-            } else {
-                loc::run(&cfg, verbose, output)
-            }
-        }),
+        } => {
+            dispatch!(common, git: by_author, |cfg, output| {
+                if by_author {
+                    loc::run_by_author(&cfg, output)
+                } else {
+                    loc::run(&cfg, verbose, output)
+                }
+            })
+        }
         Commands::Dups {
             common,
             report,
@@ -342,61 +446,165 @@ fn main() {
             common,
             top,
             sort_by,
-        } => {
-            dispatch!(common, |cfg, output| mi::run(&cfg, output, top, &sort_by))
-        }
+            mi_threshold,
+        } => dispatch!(common, |cfg, output| mi::run(
+            &cfg,
+            output,
+            top,
+            &sort_by,
+            mi_threshold
+        )),
         Commands::Report {
             common,
             top,
             min_lines,
             full,
+            with,
         } => {
             let kcfg = config::KimunConfig::load();
             let min_lines = kcfg.dups.resolve_min_lines(min_lines);
             let effective_top = if full { usize::MAX } else { top };
-            dispatch!(common, |cfg, output| report::run(
-                &cfg,
-                output,
-                effective_top,
-                min_lines
-            ))
+            let sections = report::ReportSections::select(&with, &kcfg).unwrap_or_else(|e| {
+                eprintln!("error: {e}");
+                std::process::exit(1);
+            });
+            dispatch!(common, git: sections.reads_git(), |cfg, output| {
+                report::run(&cfg, output, effective_top, min_lines, &sections)
+            })
         }
         Commands::Miv {
             common,
             top,
             sort_by,
-        } => {
-            dispatch!(common, |cfg, output| miv::run(&cfg, output, top, &sort_by))
-        }
+            mi_threshold,
+        } => dispatch!(common, |cfg, output| miv::run(
+            &cfg,
+            output,
+            top,
+            &sort_by,
+            mi_threshold
+        )),
         Commands::Churn {
             common,
+            commits,
             top,
             sort_by,
             since,
+            by_author,
         } => {
-            dispatch!(common, |cfg, output| churn::run(
+            dispatch!(common, &commits, |cfg, output| churn::run(
                 &cfg,
                 output,
                 top,
                 &sort_by,
-                since.as_deref()
+                since.as_deref(),
+                by_author
             ))
         }
+        Commands::Defects {
+            common,
+            commits,
+            top,
+            sort_by,
+            since,
+        } => dispatch!(common, &commits, |cfg, output| defects::run(
+            &cfg,
+            output,
+            top,
+            &sort_by,
+            since.as_deref()
+        )),
         Commands::Hotspots {
             common,
+            commits,
             top,
             sort_by,
             since,
             complexity,
+            changes,
         } => {
             let kcfg = config::KimunConfig::load();
             let complexity = kcfg.hotspots.resolve_complexity(complexity);
-            dispatch!(common, |cfg, output| {
-                hotspots::run(&cfg, output, top, &sort_by, since.as_deref(), &complexity)
+            let changes = kcfg.hotspots.resolve_changes(changes);
+            dispatch!(common, &commits, |cfg, output| {
+                hotspots::run(
+                    &cfg,
+                    output,
+                    top,
+                    &sort_by,
+                    since.as_deref(),
+                    &complexity,
+                    &changes,
+                )
+            })
+        }
+        Commands::Debt { common, top } => {
+            let kcfg = config::KimunConfig::load();
+            let model = debt::DebtModel::resolve(&kcfg.debt).unwrap_or_else(|e| {
+                eprintln!("error: .kimun.toml: {e}");
+                std::process::exit(1);
+            });
+            let thresholds = debt::DebtThresholds {
+                max_lines: kcfg.smells.resolve_max_lines(None),
+                max_params: kcfg.smells.resolve_max_params(None),
+                min_dup_lines: kcfg.dups.resolve_min_lines(None),
+            };
+            dispatch!(common, |cfg, output| debt::run(
+                &cfg,
+                output,
+                top,
+                &model,
+                &thresholds
+            ))
+        }
+        Commands::History {
+            path,
+            format,
+            include_tests,
+            jobs,
+            cache_args,
+            exclude_args,
+            every,
+            since,
+            min_lines,
+            model,
+        } => {
+            let kcfg = config::KimunConfig::load();
+            let min_lines = kcfg.dups.resolve_min_lines(min_lines);
+            let model = resolve_score_model(&kcfg, model);
+            let jobs = kcfg.analysis.resolve_jobs(jobs);
+            let cache = open_cache(&kcfg, cache_args.enabled());
+            // Snapshots are kept unless --no-cache asks for a fresh run.
+            let snapshots = (cache_args.enabled() != Some(false)).then(|| {
+                kcfg.cache
+                    .resolve_dir(&config::KimunConfig::project_root())
+                    .join(history::SNAPSHOT_FILE)
+            });
+            let filter = exclude_args.exclude_filter();
+            maybe_list_excluded(&path, include_tests, &filter, exclude_args.list_excluded);
+            run_command(path, |t| {
+                let tree = mount_rev(t, None)?;
+                let t = tree.as_ref().map_or(t, |tree| tree.path());
+                let cfg = WalkConfig::new(t, include_tests, &filter)
+                    .with_jobs(jobs)
+                    .with_cache(cache.as_ref())
+                    .with_tree(tree.as_ref());
+                history::run(
+                    &cfg,
+                    format,
+                    &history::HistoryOptions {
+                        every: &every,
+                        since: &since,
+                        min_lines,
+                        model: &model,
+                        snapshots: snapshots.as_deref(),
+                    },
+                )
             })
         }
         Commands::Age {
             common,
+            commits,
             active_days,
             frozen_days,
             sort_by,
@@ -405,7 +613,7 @@ fn main() {
             let kcfg = config::KimunConfig::load();
             let active_days = kcfg.age.resolve_active_days(active_days);
             let frozen_days = kcfg.age.resolve_frozen_days(frozen_days);
-            dispatch!(common, |cfg, output| {
+            dispatch!(common, &commits, |cfg, output| {
                 age::run(
                     &cfg,
                     output,
@@ -418,15 +626,15 @@ fn main() {
         }
         Commands::Knowledge {
             common,
+            commits,
             top,
             sort_by,
             since,
             risk_only,
             summary,
             bus_factor,
-            author,
         } => {
-            dispatch!(common, |cfg, output| {
+            dispatch!(common, &commits, |cfg, output| {
                 knowledge::run(
                     &cfg,
                     &knowledge::KnowledgeOptions {
@@ -437,7 +645,7 @@ fn main() {
                         risk_only,
                         summary,
                         bus_factor,
-                        author: author.as_deref(),
+                        authors: &commits.author,
                     },
                 )
             })
@@ -456,8 +664,12 @@ fn main() {
                 top
             ))
         }
-        Commands::Authors { common, since } => {
-            dispatch!(common, |cfg, output| authors::run(
+        Commands::Authors {
+            common,
+            commits,
+            since,
+        } => {
+            dispatch!(common, &commits, |cfg, output| authors::run(
                 &cfg,
                 output,
                 since.as_deref()
@@ -465,12 +677,21 @@ fn main() {
         }
         Commands::Tc {
             common,
+            commits,
             top,
             sort_by,
             since,
             min_degree,
             min_strength,
-        } => dispatch_tc(common, top, sort_by, since, min_degree, min_strength),
+        } => dispatch_tc(
+            common,
+            commits,
+            top,
+            sort_by,
+            since,
+            min_degree,
+            min_strength,
+        ),
         Commands::Smells {
             common,
             top,
@@ -486,16 +707,25 @@ fn main() {
             min_lines,
             model,
             trend,
+            top_changes,
             fail_if_worse,
             fail_below,
+            by_dir,
+            by_package,
+            new_code_since,
+            new_code_fail_below,
         } => dispatch_score(
             common,
             bottom,
             min_lines,
             model,
-            trend,
+            trend.map(|r| (r, top_changes)),
             fail_if_worse,
             fail_below,
+            by_dir
+                .map(score::Breakdown::Dir)
+                .or(by_package.then_some(score::Breakdown::Package)),
+            new_code_since.map(|r| (r, new_code_fail_below)),
         ),
         Commands::Score {
             subcommand:
@@ -504,8 +734,12 @@ fn main() {
                     path,
                     format,
                     include_tests,
+                    jobs,
+                    cache_args,
                     exclude_args,
-                    bottom,
+                    bottom: _,
+                    top_changes,
+                    rev,
                     min_lines,
                     model,
                 }),
@@ -513,23 +747,65 @@ fn main() {
         } => {
             let kcfg = config::KimunConfig::load();
             let min_lines = kcfg.dups.resolve_min_lines(min_lines);
-            let model = kcfg.score.resolve_model(model);
+            let model = resolve_score_model(&kcfg, model);
+            let jobs = kcfg.analysis.resolve_jobs(jobs);
+            let cache = open_cache(&kcfg, cache_args.enabled());
             let filter = exclude_args.exclude_filter();
             maybe_list_excluded(&path, include_tests, &filter, exclude_args.list_excluded);
             run_command(path, |t| {
-                let cfg = WalkConfig::new(t, include_tests, &filter);
+                let tree = mount_rev(t, rev.as_deref())?;
+                let t = tree.as_ref().map_or(t, |tree| tree.path());
+                let cfg = WalkConfig::new(t, include_tests, &filter)
+                    .with_jobs(jobs)
+                    .with_cache(cache.as_ref())
+                    .with_tree(tree.as_ref());
                 score::run_diff(
                     &cfg,
                     &git_ref,
                     format,
-                    bottom,
+                    top_changes,
                     min_lines,
                     &model,
                     score::ScoreGate::default(),
                 )
             })
         }
+        Commands::Score {
+            subcommand:
+                Some(ScoreCommands::Plan {
+                    target,
+                    path,
+                    format,
+                    include_tests,
+                    jobs,
+                    cache_args,
+                    exclude_args,
+                    rev,
+                    min_lines,
+                    model,
+                }),
+            ..
+        } => {
+            let kcfg = config::KimunConfig::load();
+            let min_lines = kcfg.dups.resolve_min_lines(min_lines);
+            let model = resolve_score_model(&kcfg, model);
+            let target = parse_grade("--target", &target);
+            let jobs = kcfg.analysis.resolve_jobs(jobs);
+            let cache = open_cache(&kcfg, cache_args.enabled());
+            let filter = exclude_args.exclude_filter();
+            maybe_list_excluded(&path, include_tests, &filter, exclude_args.list_excluded);
+            run_command(path, |t| {
+                let tree = mount_rev(t, rev.as_deref())?;
+                let t = tree.as_ref().map_or(t, |tree| tree.path());
+                let cfg = WalkConfig::new(t, include_tests, &filter)
+                    .with_jobs(jobs)
+                    .with_cache(cache.as_ref())
+                    .with_tree(tree.as_ref());
+                score::run_plan(&cfg, format, min_lines, &model, target, &kcfg.score.healthy)
+            })
+        }
         Commands::Ai { command } => dispatch_ai(command),
+        Commands::Mcp { path } => run_command(path, mcp::run),
         Commands::Init { path, yes } => run_command(path, |t| init::run(t, yes)),
         Commands::Completions { shell } => {
             write_completions(shell, &mut std::io::stdout());
@@ -0,0 +1,150 @@
+//! Model Context Protocol server (`km mcp`).
+//!
+//! Speaks JSON-RPC 2.0 over stdio, one message per line, and exposes every
+//! `km` analysis as an MCP tool. Tool names, descriptions and input schemas
+//! come from `ai::schema`, and calls run in-process through the same
+//! executor `km ai analyze` uses, so a tool returns exactly the JSON that
+//! `km <subcmd> --format json` prints.
+//!
+//! Supported methods: `initialize`, `ping`, `tools/list`, `tools/call`.
+//! Notifications (messages without an `id`) are accepted and ignored.
+//! stdout carries protocol messages only; diagnostics go to stderr.
+
+use std::error::Error;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+use serde_json::{Value, json};
+
+use crate::ai::{executor, schema};
+
+/// Protocol revisions this server understands, newest first. The newest is
+/// offered when the client asks for one we do not know.
+const PROTOCOL_VERSIONS: &[&str] = &["2025-06-18", "2025-03-26", "2024-11-05"];
+
+/// JSON-RPC error codes.
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Serve MCP requests on stdin/stdout until stdin closes. Tool `path`
+/// arguments are resolved inside `path`.
+pub fn run(path: &Path) -> Result<(), Box<dyn Error>> {
+    let project = path
+        .canonicalize()
+        .map_err(|e| format!("Cannot resolve path '{}': {e}", path.display()))?;
+    eprintln!("km mcp: serving {} on stdio", project.display());
+    serve(io::stdin().lock(), io::stdout().lock(), &project)
+}
+
+/// Read newline-delimited JSON-RPC messages from `reader` and write one
+/// response line per request to `writer`.
+pub fn serve(
+    reader: impl BufRead,
+    mut writer: impl Write,
+    project: &Path,
+) -> Result<(), Box<dyn Error>> {
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<Value>(&line) {
+            Ok(message) => handle_message(&message, project),
+            Err(e) => Some(error(
+                Value::Null,
+                PARSE_ERROR,
+                &format!("parse error: {e}"),
+            )),
+        };
+        if let Some(response) = response {
+            writeln!(writer, "{response}")?;
+            writer.flush()?;
+        }
+    }
+    Ok(())
+}
+
+/// Handle one decoded message. Returns `None` for notifications and for
+/// responses sent by the client, which need no reply.
+pub fn handle_message(message: &Value, project: &Path) -> Option<Value> {
+    let Some(method) = message.get("method").and_then(Value::as_str) else {
+        // Responses to server requests (we send none) need no reply.
+        let is_response = message.get("result").is_some() || message.get("error").is_some();
+        let id = message.get("id").cloned().unwrap_or(Value::Null);
+        return (!is_response).then(|| error(id, INVALID_REQUEST, "invalid request"));
+    };
+    let id = message.get("id")?.clone();
+    let params = message.get("params").cloned().unwrap_or(Value::Null);
+    Some(match method {
+        "initialize" => success(id, initialize(&params)),
+        "ping" => success(id, json!({})),
+        "tools/list" => success(id, json!({ "tools": list_tools() })),
+        "tools/call" => match call_tool(&params, project) {
+            Ok(result) => success(id, result),
+            Err(msg) => error(id, INVALID_PARAMS, &msg),
+        },
+        _ => error(id, METHOD_NOT_FOUND, &format!("method not found: {method}")),
+    })
+}
+
+/// Negotiate the protocol version and advertise the tools capability.
+fn initialize(params: &Value) -> Value {
+    let requested = params.get("protocolVersion").and_then(Value::as_str);
+    let version = requested
+        .filter(|v| PROTOCOL_VERSIONS.contains(v))
+        .unwrap_or(PROTOCOL_VERSIONS[0]);
+    json!({
+        "protocolVersion": version,
+        "capabilities": { "tools": {} },
+        "serverInfo": { "name": "kimun", "version": env!("CARGO_PKG_VERSION") },
+        "instructions": "Code metrics for the project this server was started in. \
+            Every tool returns JSON; `path` arguments are relative to the project root."
+    })
+}
+
+/// The `ai::schema` tool definitions in MCP form (`inputSchema`).
+fn list_tools() -> Vec<Value> {
+    schema::tool_definitions()
+        .into_iter()
+        .map(|def| {
+            json!({
+                "name": def["name"],
+                "description": def["description"],
+                "inputSchema": def["input_schema"],
+            })
+        })
+        .collect()
+}
+
+/// Run a tool. Unknown tool names are protocol errors; failures inside the
+/// tool are reported as `isError` results so the client model can see them.
+fn call_tool(params: &Value, project: &Path) -> Result<Value, String> {
+    let name = params
+        .get("name")
+        .and_then(Value::as_str)
+        .ok_or("missing tool name")?;
+    let arguments = params.get("arguments").unwrap_or(&Value::Null);
+    let (text, is_error) = match executor::run_tool(name, arguments, project) {
+        Ok(Some(json)) => (json, false),
+        Ok(None) => return Err(format!("unknown tool: {name}")),
+        Err(e) => (format!("Error running {name}: {e}"), true),
+    };
+    Ok(json!({
+        "content": [{ "type": "text", "text": text }],
+        "isError": is_error,
+    }))
+}
+
+fn success(id: Value, result: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+fn error(id: Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+#[cfg(test)]
+#[path = "mod_test.rs"]
+mod tests;
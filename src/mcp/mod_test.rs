@@ -0,0 +1,146 @@
+use super::*;
+use std::fs;
+
+fn project() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("lib.rs"),
+        "fn classify(x: i32) -> i32 {\n    if x > 0 {\n        if x > 10 {\n            return 2;\n        }\n        return 1;\n    }\n    0\n}\n",
+    )
+    .unwrap();
+    dir
+}
+
+fn request(id: i64, method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+}
+
+#[test]
+fn initialize_negotiates_version_and_tools_capability() {
+    let dir = project();
+    let resp = handle_message(
+        &request(1, "initialize", json!({ "protocolVersion": "2024-11-05" })),
+        dir.path(),
+    )
+    .unwrap();
+    assert_eq!(resp["id"], 1);
+    assert_eq!(resp["result"]["protocolVersion"], "2024-11-05");
+    assert!(resp["result"]["capabilities"]["tools"].is_object());
+    assert_eq!(resp["result"]["serverInfo"]["name"], "kimun");
+
+    let resp = handle_message(
+        &request(2, "initialize", json!({ "protocolVersion": "1999-01-01" })),
+        dir.path(),
+    )
+    .unwrap();
+    assert_eq!(resp["result"]["protocolVersion"], PROTOCOL_VERSIONS[0]);
+}
+
+#[test]
+fn tools_list_mirrors_ai_schema() {
+    let dir = project();
+    let resp = handle_message(&request(1, "tools/list", json!({})), dir.path()).unwrap();
+    let tools = resp["result"]["tools"].as_array().unwrap();
+    assert_eq!(tools.len(), schema::tool_definitions().len());
+    for tool in tools {
+        assert!(tool["name"].as_str().unwrap().starts_with("km_"));
+        assert_eq!(tool["inputSchema"]["type"], "object");
+        assert!(tool.get("input_schema").is_none());
+    }
+}
+
+#[test]
+fn tools_call_returns_per_function_cogcom_json() {
+    let dir = project();
+    let resp = handle_message(
+        &request(
+            3,
+            "tools/call",
+            json!({ "name": "km_cogcom", "arguments": { "top": 5 } }),
+        ),
+        dir.path(),
+    )
+    .unwrap();
+    let result = &resp["result"];
+    assert_eq!(result["isError"], false);
+    let text = result["content"][0]["text"].as_str().unwrap();
+    let files: Value = serde_json::from_str(text).unwrap();
+    assert_eq!(files[0]["functions"][0]["name"], "classify");
+    assert!(files[0]["functions"][0]["complexity"].as_u64().unwrap() > 0);
+}
+
+#[test]
+fn tool_failures_are_error_results() {
+    let dir = project();
+    let resp = handle_message(
+        &request(
+            4,
+            "tools/call",
+            json!({ "name": "km_hal", "arguments": { "sort_by": "bogus" } }),
+        ),
+        dir.path(),
+    )
+    .unwrap();
+    assert_eq!(resp["result"]["isError"], true);
+    assert!(
+        resp["result"]["content"][0]["text"]
+            .as_str()
+            .unwrap()
+            .contains("sort_by")
+    );
+}
+
+#[test]
+fn unknown_tool_and_method_are_protocol_errors() {
+    let dir = project();
+    let resp = handle_message(
+        &request(5, "tools/call", json!({ "name": "km_nope" })),
+        dir.path(),
+    )
+    .unwrap();
+    assert_eq!(resp["error"]["code"], INVALID_PARAMS);
+
+    let resp = handle_message(&request(6, "resources/list", json!({})), dir.path()).unwrap();
+    assert_eq!(resp["id"], 6);
+    assert_eq!(resp["error"]["code"], METHOD_NOT_FOUND);
+}
+
+#[test]
+fn notifications_and_responses_get_no_reply() {
+    let dir = project();
+    let note = json!({ "jsonrpc": "2.0", "method": "notifications/initialized" });
+    assert!(handle_message(&note, dir.path()).is_none());
+    let reply = json!({ "jsonrpc": "2.0", "id": 9, "result": {} });
+    assert!(handle_message(&reply, dir.path()).is_none());
+    let garbage = json!({ "jsonrpc": "2.0", "id": 9 });
+    assert_eq!(
+        handle_message(&garbage, dir.path()).unwrap()["error"]["code"],
+        INVALID_REQUEST
+    );
+}
+
+#[test]
+fn serve_handles_a_session_over_lines() {
+    let dir = project();
+    let input = [
+        request(1, "initialize", json!({})).to_string(),
+        json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }).to_string(),
+        String::new(),
+        "{not json".to_string(),
+        request(2, "ping", json!({})).to_string(),
+    ]
+    .join("\n");
+    let mut out = Vec::new();
+    serve(input.as_bytes(), &mut out, dir.path()).unwrap();
+    let lines: Vec<Value> = String::from_utf8(out)
+        .unwrap()
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0]["id"], 1);
+    assert_eq!(lines[1]["error"]["code"], PARSE_ERROR);
+    assert!(lines[1]["id"].is_null());
+    assert_eq!(lines[2]["id"], 2);
+    assert_eq!(lines[2]["result"], json!({}));
+}
@@ -25,9 +25,20 @@ use crate::report_helpers;
 use crate::util::read_and_classify;
 use crate::walk::WalkConfig;
 use analyzer::compute_mi;
-use report::{FileMIMetrics, print_json, print_report, print_short, print_terse};
+use report::{
+    print_codeclimate, print_github, print_json, print_report, print_sarif, print_short,
+    print_terse,
+};
 
-fn analyze_file(path: &Path, spec: &LanguageSpec) -> Result<Option<FileMIMetrics>, Box<dyn Error>> {
+pub use analyzer::{MILevel, MIMetrics};
+pub use report::FileMIMetrics;
+
+/// Read a file once and compute its Maintainability Index from Halstead
+/// volume and cyclomatic complexity. Returns `None` for unsupported files.
+pub fn analyze_file(
+    path: &Path,
+    spec: &LanguageSpec,
+) -> Result<Option<FileMIMetrics>, Box<dyn Error>> {
     let (lines, kinds) = match read_and_classify(path, spec)? {
         Some(v) => v,
         None => return Ok(None),
@@ -58,12 +69,9 @@ fn analyze_file(path: &Path, spec: &LanguageSpec) -> Result<Option<FileMIMetrics
     }))
 }
 
-pub fn run(
-    cfg: &WalkConfig<'_>,
-    output: crate::cli::OutputMode,
-    top: usize,
-    sort_by: &str,
-) -> Result<(), Box<dyn Error>> {
+/// Walk source files and compute MI for each. Sorted by `sort_by`:
+/// `mi` ascending (worst first), `volume`/`complexity`/`loc` descending.
+pub fn analyze_project(cfg: &WalkConfig<'_>, sort_by: &str) -> Vec<FileMIMetrics> {
     let mut results = cfg.collect_analysis(analyze_file);
 
     // Sort: mi ascending (worst first), volume/complexity/loc descending
@@ -79,6 +87,30 @@ pub fn run(
         "loc" => results.sort_by_key(|r| Reverse(r.metrics.loc)),
         _ => results.sort_by(|a, b| a.metrics.mi_score.total_cmp(&b.metrics.mi_score)),
     }
+    results
+}
+
+/// Walk source files, compute MI, sort, and print the top `top` results.
+/// The CI formats (`github`, `codeclimate`) instead annotate every file
+/// whose MI is below `threshold`.
+pub fn run(
+    cfg: &WalkConfig<'_>,
+    output: crate::cli::OutputMode,
+    top: usize,
+    sort_by: &str,
+    threshold: f64,
+) -> Result<(), Box<dyn Error>> {
+    let mut results = analyze_project(cfg, sort_by);
+
+    match output {
+        crate::cli::OutputMode::Github => {
+            print_github(&results, threshold);
+            return Ok(());
+        }
+        crate::cli::OutputMode::Codeclimate => return print_codeclimate(&results, threshold),
+        crate::cli::OutputMode::Sarif => return print_sarif(&results, threshold),
+        _ => {}
+    }
 
     report_helpers::output_results(
         &mut results,
@@ -9,6 +9,7 @@ use serde::Serialize;
 
 use super::analyzer::{MILevel, MIMetrics};
 use crate::report_helpers;
+use crate::sarif;
 
 /// Per-file MI analysis result bundled with filesystem path and language.
 pub struct FileMIMetrics {
@@ -89,8 +90,8 @@ struct JsonEntry {
     level: MILevel,
 }
 
-/// Serialize per-file MI data as pretty-printed JSON to stdout.
-pub fn print_json(files: &[FileMIMetrics]) -> Result<(), Box<dyn std::error::Error>> {
+/// Serialize per-file MI data as a pretty-printed JSON string.
+pub fn format_json(files: &[FileMIMetrics]) -> Result<String, Box<dyn std::error::Error>> {
     let entries: Vec<JsonEntry> = files
         .iter()
         .map(|f| {
@@ -107,7 +108,13 @@ pub fn print_json(files: &[FileMIMetrics]) -> Result<(), Box<dyn std::error::Err
         })
         .collect();
 
-    report_helpers::print_json_stdout(&entries)
+    report_helpers::json_string(&entries)
+}
+
+/// Serialize per-file MI data as pretty-printed JSON to stdout.
+pub fn print_json(files: &[FileMIMetrics]) -> Result<(), Box<dyn std::error::Error>> {
+    println!("{}", format_json(files)?);
+    Ok(())
 }
 
 /// Print MI as a single compact line.
@@ -144,6 +151,68 @@ pub fn print_terse(files: &[FileMIMetrics]) {
     println!("{avg_mi:.1}");
 }
 
+/// CI annotations: one per file whose MI is below `threshold`, with the
+/// annotation level following the file's MI level.
+pub fn annotations(files: &[FileMIMetrics], threshold: f64) -> Vec<report_helpers::Annotation> {
+    files
+        .iter()
+        .filter(|f| f.metrics.mi_score < threshold)
+        .map(|f| {
+            let (level, severity) = match f.metrics.level {
+                MILevel::Red => ("error", "major"),
+                MILevel::Yellow => ("warning", "minor"),
+                MILevel::Green => ("notice", "info"),
+            };
+            report_helpers::Annotation {
+                level,
+                severity,
+                file: f.path.display().to_string(),
+                line: 1,
+                title: "Maintainability Index",
+                message: format!(
+                    "maintainability index {:.1} ({}) is below {threshold}",
+                    f.metrics.mi_score, f.metrics.level
+                ),
+            }
+        })
+        .collect()
+}
+
+/// Emit one GitHub Actions annotation per file below `threshold`.
+pub fn print_github(files: &[FileMIMetrics], threshold: f64) {
+    report_helpers::print_github_annotations(&annotations(files, threshold));
+}
+
+/// Emit a CodeClimate JSON array with one entry per file below `threshold`.
+pub fn print_codeclimate(
+    files: &[FileMIMetrics],
+    threshold: f64,
+) -> Result<(), Box<dyn std::error::Error>> {
+    report_helpers::print_codeclimate_annotations(&annotations(files, threshold))
+}
+
+/// The SARIF rule every file below the threshold is filed under.
+fn sarif_rule() -> sarif::Rule {
+    sarif::Rule {
+        id: "mi/low-maintainability".to_string(),
+        name: "LowMaintainabilityIndex".to_string(),
+        short_description: "File below the maintainability index threshold".to_string(),
+        full_description:
+            "The file's Maintainability Index (Visual Studio variant, 0-100) is below \
+                           the threshold; low values mark code that is costly to change."
+                .to_string(),
+        level: "warning",
+    }
+}
+
+/// Emit a SARIF 2.1.0 log with one result per file below `threshold`.
+pub fn print_sarif(
+    files: &[FileMIMetrics],
+    threshold: f64,
+) -> Result<(), Box<dyn std::error::Error>> {
+    report_helpers::print_sarif_annotations(&sarif_rule(), &annotations(files, threshold))
+}
+
 #[cfg(test)]
 #[path = "report_test.rs"]
 mod tests;
@@ -67,3 +67,32 @@ fn json_structure_is_valid() {
     assert_eq!(arr.len(), 2);
     assert!(arr[0]["mi_score"].as_f64().unwrap() > 0.0);
 }
+
+#[test]
+fn annotations_only_below_threshold() {
+    assert!(annotations(&sample_files(), 20.0).is_empty());
+
+    let anns = annotations(&sample_files(), 40.0);
+    assert_eq!(anns.len(), 1);
+    assert_eq!(anns[0].file, "src/foo.rs");
+    assert_eq!(anns[0].level, "notice");
+    assert_eq!(
+        anns[0].message,
+        "maintainability index 35.2 (green) is below 40"
+    );
+}
+
+#[test]
+fn annotations_level_follows_mi_level() {
+    let mut files = sample_files();
+    files[0].metrics.mi_score = 5.0;
+    files[0].metrics.level = MILevel::Red;
+    files[1].metrics.mi_score = 15.0;
+    files[1].metrics.level = MILevel::Yellow;
+    let anns = annotations(&files, 20.0);
+    assert_eq!(anns.len(), 2);
+    assert_eq!((anns[0].level, anns[0].severity), ("error", "major"));
+    assert_eq!((anns[1].level, anns[1].severity), ("warning", "minor"));
+    print_github(&files, 20.0);
+    print_codeclimate(&files, 20.0).unwrap();
+}
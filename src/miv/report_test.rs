@@ -75,3 +75,18 @@ fn json_structure_is_valid() {
     assert_eq!(arr.len(), 2);
     assert!(arr[0]["mi_score"].as_f64().unwrap() > 0.0);
 }
+
+#[test]
+fn annotations_only_below_threshold() {
+    let anns = annotations(&sample_files(), 85.0);
+    assert_eq!(anns.len(), 1);
+    assert_eq!(anns[0].file, "src/foo.rs");
+    assert_eq!((anns[0].level, anns[0].severity), ("notice", "minor"));
+    assert_eq!(
+        anns[0].message,
+        "maintainability index 76.7 (moderate) is below 85"
+    );
+    assert!(annotations(&sample_files(), 65.0).is_empty());
+    print_github(&sample_files(), 85.0);
+    print_codeclimate(&sample_files(), 85.0).unwrap();
+}
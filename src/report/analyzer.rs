@@ -1,11 +1,15 @@
 //! Per-file analysis for the combined report.
 //!
 //! Reads, classifies, and runs all metric analyzers (indentation,
-//! Halstead, cyclomatic, MI) on a single file, producing a
+//! Halstead, cyclomatic, cognitive, MI) on a single file, producing a
 //! `FileReportData` bundle for aggregation. Called once per source
 //! file during the report builder's filesystem walk.
 use std::path::Path;
 
+use serde::{Deserialize, Serialize};
+
+use crate::cache::{self, AnalysisCache};
+use crate::cogcom;
 use crate::cycom;
 use crate::hal;
 use crate::indent;
@@ -14,12 +18,15 @@ use crate::mi;
 use crate::miv;
 use crate::util::read_and_classify;
 
-use super::data::{CycomEntry, HalsteadEntry, IndentEntry, MiVerifysoftEntry, MiVisualStudioEntry};
+use super::data::{
+    CogcomEntry, CycomEntry, HalsteadEntry, IndentEntry, MiVerifysoftEntry, MiVisualStudioEntry,
+};
 
 /// Per-file analysis results collected during report building.
 ///
 /// Each field is `Option` because not all analyzers succeed on every file
 /// (e.g., empty files produce no Halstead or cyclomatic metrics).
+#[derive(Serialize, Deserialize)]
 pub struct FileReportData {
     /// Number of blank lines in the file.
     pub blank: usize,
@@ -35,34 +42,78 @@ pub struct FileReportData {
     pub halstead: Option<HalsteadEntry>,
     /// Cyclomatic complexity result (total, max, average).
     pub cycom: Option<CycomEntry>,
+    /// Cognitive complexity result (total, max, average).
+    pub cogcom: Option<CogcomEntry>,
     /// Visual Studio MI result (0–100 normalized scale).
     pub mi_vs: Option<MiVisualStudioEntry>,
     /// Verifysoft MI result (unbounded scale with comment weight).
     pub mi_vf: Option<MiVerifysoftEntry>,
 }
 
+impl FileReportData {
+    /// Point every per-file entry at `path` (entries served from the cache
+    /// carry the path of whichever file first had this content).
+    fn set_path(&mut self, path: &str) {
+        let path = path.to_string();
+        if let Some(e) = &mut self.indent {
+            e.path.clone_from(&path);
+        }
+        if let Some(e) = &mut self.halstead {
+            e.path.clone_from(&path);
+        }
+        if let Some(e) = &mut self.cycom {
+            e.path.clone_from(&path);
+        }
+        if let Some(e) = &mut self.cogcom {
+            e.path.clone_from(&path);
+        }
+        if let Some(e) = &mut self.mi_vs {
+            e.path.clone_from(&path);
+        }
+        if let Some(e) = &mut self.mi_vf {
+            e.path = path;
+        }
+    }
+}
+
 /// Read, classify, and run all analyzers on a single file.
-/// Returns `None` for binary files or on I/O errors.
+/// Returns `None` for binary files or on I/O errors. Results for unchanged
+/// content are served from `cache` when one is given.
 pub fn analyze_file_for_report(
     file_path: &Path,
     spec: &crate::loc::language::LanguageSpec,
+    cache: Option<&AnalysisCache>,
 ) -> Option<FileReportData> {
-    let (lines, kinds) = match read_and_classify(file_path, spec) {
-        Ok(Some(v)) => v,
-        Ok(None) => return None,
+    let cached = cache::cached(cache, "report", file_path, spec, || {
+        Ok(read_and_classify(file_path, spec)?
+            .map(|(lines, kinds)| analyze_lines(file_path, &lines, &kinds, spec)))
+    });
+    match cached {
+        Ok(data) => data.map(|mut d| {
+            d.set_path(&file_path.display().to_string());
+            d
+        }),
         Err(e) => {
             eprintln!("warning: {}: {e}", file_path.display());
-            return None;
+            None
         }
-    };
+    }
+}
 
+/// Run all analyzers on already-classified content.
+fn analyze_lines(
+    file_path: &Path,
+    lines: &[String],
+    kinds: &[LineKind],
+    spec: &crate::loc::language::LanguageSpec,
+) -> FileReportData {
     let blank = kinds.iter().filter(|k| **k == LineKind::Blank).count();
     let comment_lines = kinds.iter().filter(|k| **k == LineKind::Comment).count();
     let code_lines = kinds.iter().filter(|k| **k == LineKind::Code).count();
 
-    let dup_normalized = crate::dups::normalize_content(&lines, &kinds);
+    let dup_normalized = crate::dups::normalize_content(lines, kinds);
 
-    let indent = indent::analyzer::analyze(&lines, &kinds, 4).map(|m| IndentEntry {
+    let indent = indent::analyzer::analyze(lines, kinds, 4).map(|m| IndentEntry {
         path: file_path.display().to_string(),
         code_lines: m.code_lines,
         stddev: m.stddev,
@@ -71,7 +122,7 @@ pub fn analyze_file_for_report(
     });
 
     let path_str = file_path.display().to_string();
-    let (halstead, volume_opt) = if let Some(h) = hal::analyze_content(&lines, &kinds, spec) {
+    let (halstead, volume_opt) = if let Some(h) = hal::analyze_content(lines, kinds, spec) {
         let vol = h.volume;
         (
             Some(HalsteadEntry {
@@ -87,7 +138,7 @@ pub fn analyze_file_for_report(
         (None, None)
     };
 
-    let (cycom, complexity_opt) = if let Some(c) = cycom::analyze_content(&lines, &kinds, spec) {
+    let (cycom, complexity_opt) = if let Some(c) = cycom::analyze_content(lines, kinds, spec) {
         (
             Some(CycomEntry {
                 path: path_str.clone(),
@@ -103,6 +154,15 @@ pub fn analyze_file_for_report(
         (None, None)
     };
 
+    let cogcom = cogcom::analyze_content(lines, kinds, spec).map(|c| CogcomEntry {
+        path: path_str.clone(),
+        functions: c.functions.len(),
+        total: c.total_complexity,
+        max: c.max_complexity,
+        avg: c.avg_complexity,
+        level: c.level.as_str().to_string(),
+    });
+
     let (mi_vs, mi_vf) = if let (Some(volume), Some(complexity)) = (volume_opt, complexity_opt) {
         let vs =
             mi::analyzer::compute_mi(volume, complexity, code_lines).map(|m| MiVisualStudioEntry {
@@ -123,7 +183,7 @@ pub fn analyze_file_for_report(
         (None, None)
     };
 
-    Some(FileReportData {
+    FileReportData {
         blank,
         comment_lines,
         code_lines,
@@ -131,7 +191,8 @@ pub fn analyze_file_for_report(
         indent,
         halstead,
         cycom,
+        cogcom,
         mi_vs,
         mi_vf,
-    })
+    }
 }
@@ -1,9 +1,11 @@
 //! Report builder: walks the file tree once and constructs a `ProjectReport`.
 //!
 //! Reads each source file once via the analyzer, collects per-file metrics
-//! for every dimension (LOC, indentation, Halstead, cyclomatic, MI), and
-//! runs project-level duplication detection after the walk completes.
-//! Sections are sorted (worst first) and truncated to `top` entries.
+//! for every dimension (LOC, indentation, Halstead, cyclomatic, cognitive,
+//! MI), and runs project-level duplication detection after the walk
+//! completes. Sections are sorted (worst first) and truncated to `top`
+//! entries. Optional sections selected with `--with` are added last by
+//! the `sections` submodule.
 
 use std::cmp::Reverse;
 use std::collections::{HashMap, HashSet};
@@ -18,6 +20,7 @@ use crate::walk::WalkConfig;
 
 use super::analyzer::analyze_file_for_report;
 use super::data::*;
+use super::sections::{self, ReportSections};
 
 // --- Section descriptions (used in both markdown and JSON output) ---
 // These short paragraphs appear in the report to explain each metric
@@ -47,6 +50,12 @@ pub const DESC_CYCOM: &str = "Cyclomatic complexity counts linearly independent
     Levels: simple (1-10), moderate (11-20), complex (21-50), highly complex (>50). \
     Reference: McCabe, T.J. (1976) A Complexity Measure.";
 
+/// Section description for cognitive complexity.
+pub const DESC_COGCOM: &str = "Cognitive complexity (SonarSource method) measures how hard code is to understand. \
+    Breaks in linear flow add 1, plus 1 per level of nesting, so nested logic weighs more than flat branching. \
+    Levels: simple (0-4), moderate (5-9), complex (10-14), very complex (15-24), extreme (25+). \
+    Reference: Campbell, G.A. (2018) Cognitive Complexity.";
+
 /// Section description for MI (Visual Studio variant, 0–100 scale).
 pub const DESC_MI_VS: &str = "Maintainability Index (Visual Studio variant). \
     Formula: MI = max(0, (171 - 5.2*ln(V) - 0.23*G - 16.2*ln(LOC)) * 100/171). \
@@ -79,6 +88,17 @@ pub fn build_report(
     cfg: &WalkConfig<'_>,
     top: usize,
     min_lines: usize,
+) -> Result<ProjectReport, Box<dyn Error>> {
+    build_report_with(cfg, top, min_lines, &ReportSections::default())
+}
+
+/// Build the project report plus the optional sections in `extra`.
+/// Git sections that cannot be computed are recorded in `skipped`.
+pub fn build_report_with(
+    cfg: &WalkConfig<'_>,
+    top: usize,
+    min_lines: usize,
+    extra: &ReportSections,
 ) -> Result<ProjectReport, Box<dyn Error>> {
     // Accumulators for each metric dimension. Populated during the file walk
     // and consumed when building the final ProjectReport struct.
@@ -95,19 +115,28 @@ pub fn build_report(
     let mut indent_results: Vec<IndentEntry> = Vec::new();
     let mut hal_results: Vec<HalsteadEntry> = Vec::new();
     let mut cycom_results: Vec<CycomEntry> = Vec::new();
+    let mut cogcom_results: Vec<CogcomEntry> = Vec::new();
     let mut mi_vs_results: Vec<MiVisualStudioEntry> = Vec::new();
     let mut mi_vf_results: Vec<MiVerifysoftEntry> = Vec::new();
 
     // Walk source files, skipping test directories/files when requested.
-    // Each file is analyzed once and its results distributed to all accumulators.
-    for (file_path, spec) in cfg.source_files() {
+    // Each file is analyzed once (in parallel) and its results distributed
+    // to all accumulators in walk order.
+    let files = cfg.source_files();
+    let analyzed = cfg.par_map(&files, |(file_path, spec)| {
+        (
+            hash_file(file_path),
+            analyze_file_for_report(file_path, spec, cfg.cache),
+        )
+    });
+    for ((file_path, spec), (hash, result)) in files.iter().zip(analyzed) {
         // Skip duplicate files (same content), matching km loc behavior.
-        if let Some(h) = hash_file(&file_path)
+        if let Some(h) = hash
             && !seen_hashes.insert(h)
         {
             continue;
         }
-        let result = match analyze_file_for_report(&file_path, spec) {
+        let result = match result {
             Some(r) => r,
             None => continue,
         };
@@ -139,6 +168,9 @@ pub fn build_report(
         if let Some(e) = result.cycom {
             cycom_results.push(e);
         }
+        if let Some(e) = result.cogcom {
+            cogcom_results.push(e);
+        }
         if let Some(e) = result.mi_vs {
             mi_vs_results.push(e);
         }
@@ -149,7 +181,7 @@ pub fn build_report(
 
     // Duplication detection runs at project level (cross-file), not per-file.
     // This happens after the walk so all normalized files are available.
-    let dup_summary = build_dup_summary(&dup_files, total_code_lines, min_lines);
+    let dup_summary = build_dup_summary(&dup_files, total_code_lines, min_lines, top);
 
     // Build LOC reports sorted by code lines descending.
     let loc_reports = build_loc_reports(stats_by_lang);
@@ -160,6 +192,7 @@ pub fn build_report(
     });
     let hal_total = sort_truncate(&mut hal_results, top, |a, b| b.effort.total_cmp(&a.effort));
     let cycom_total = sort_truncate(&mut cycom_results, top, |a, b| b.total.cmp(&a.total));
+    let cogcom_total = sort_truncate(&mut cogcom_results, top, |a, b| b.total.cmp(&a.total));
     let mi_vs_total = sort_truncate(&mut mi_vs_results, top, |a, b| {
         a.mi_score.total_cmp(&b.mi_score)
     });
@@ -167,7 +200,7 @@ pub fn build_report(
         a.mi_score.total_cmp(&b.mi_score)
     });
 
-    Ok(ProjectReport {
+    let mut report = ProjectReport {
         path: cfg.path.display().to_string(),
         top,
         include_tests: cfg.include_tests,
@@ -189,6 +222,11 @@ pub fn build_report(
             total_count: cycom_total,
             entries: cycom_results,
         },
+        cognitive: SectionResult {
+            description: DESC_COGCOM,
+            total_count: cogcom_total,
+            entries: cogcom_results,
+        },
         mi_visual_studio: SectionResult {
             description: DESC_MI_VS,
             total_count: mi_vs_total,
@@ -199,21 +237,32 @@ pub fn build_report(
             total_count: mi_vf_total,
             entries: mi_vf_results,
         },
-    })
+        smells: None,
+        dependencies: None,
+        hotspots: None,
+        knowledge: None,
+        temporal_coupling: None,
+        age: None,
+        skipped: Vec::new(),
+    };
+    sections::add_sections(&mut report, cfg, extra, top);
+    Ok(report)
 }
 
 /// Run project-level duplication detection and build the summary.
 ///
 /// Cross-file detection runs after all files have been normalized,
 /// since duplicates can span different source files. The `min_lines`
-/// parameter controls the minimum block size for detection (default 6).
+/// parameter controls the minimum block size for detection (default 6);
+/// the first `top` groups are kept for the HTML report's samples.
 fn build_dup_summary(
     dup_files: &[dups::detector::NormalizedFile],
     total_code_lines: usize,
     min_lines: usize,
+    top: usize,
 ) -> DupsSummary {
     // Detect duplicates across all normalized files (cross-file matching).
-    let dup_groups = if dup_files.is_empty() {
+    let mut dup_groups = if dup_files.is_empty() {
         Vec::new()
     } else {
         dups::detector::detect_duplicates(dup_files, min_lines, true)
@@ -221,10 +270,13 @@ fn build_dup_summary(
     // Aggregate group-level stats into project-level summary.
     let duplicated_lines: usize = dup_groups.iter().map(|g| g.duplicated_lines()).sum();
     let largest_block = dup_groups.iter().map(|g| g.line_count).max().unwrap_or(0);
-    let files_with_dups: HashSet<&Path> = dup_groups
+    let files_with_dups = dup_groups
         .iter()
         .flat_map(|g| g.locations.iter().map(|l| l.file_path.as_path()))
-        .collect();
+        .collect::<HashSet<&Path>>()
+        .len();
+    let duplicate_groups = dup_groups.len();
+    dup_groups.truncate(top);
     let dup_percentage = if total_code_lines == 0 {
         0.0
     } else {
@@ -236,9 +288,10 @@ fn build_dup_summary(
         total_code_lines,
         duplicated_lines,
         duplication_percentage: dup_percentage,
-        duplicate_groups: dup_groups.len(),
-        files_with_duplicates: files_with_dups.len(),
+        duplicate_groups,
+        files_with_duplicates: files_with_dups,
         largest_block,
+        groups: dup_groups,
     }
 }
 
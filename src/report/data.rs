@@ -2,16 +2,21 @@
 ///
 /// These structs are populated by the report builder and consumed
 /// by both the markdown and JSON formatters.
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
+use crate::dups::detector::DuplicateGroup;
 use crate::loc::report::LanguageReport;
 
 /// Comprehensive project report combining all code metrics.
 ///
 /// Contains LOC breakdown, duplication summary, and per-file entries
-/// for indentation, Halstead, cyclomatic, and MI (both variants).
+/// for indentation, Halstead, cyclomatic, cognitive, and MI (both variants).
 /// Each per-file section includes `total_count` (before truncation)
 /// and up to `top` entries sorted by the relevant metric.
+///
+/// The sections selected with `--with` (smells, dependencies, and the git
+/// history sections) are `None` when not requested and omitted from JSON.
+/// Git sections that could not be computed are listed in `skipped`.
 #[derive(Debug, Serialize)]
 pub struct ProjectReport {
     pub path: String,
@@ -23,8 +28,25 @@ pub struct ProjectReport {
     pub indent: SectionResult<IndentEntry>,
     pub halstead: SectionResult<HalsteadEntry>,
     pub cyclomatic: SectionResult<CycomEntry>,
+    pub cognitive: SectionResult<CogcomEntry>,
     pub mi_visual_studio: SectionResult<MiVisualStudioEntry>,
     pub mi_verifysoft: SectionResult<MiVerifysoftEntry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smells: Option<SectionResult<SmellsEntry>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dependencies: Option<DepsSection>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hotspots: Option<HotspotsSection>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub knowledge: Option<KnowledgeSection>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temporal_coupling: Option<SectionResult<CouplingEntry>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub age: Option<SectionResult<AgeEntry>>,
+    /// Requested sections that could not be computed, with the reason
+    /// (e.g. git sections outside a repository).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<String>,
 }
 
 /// A section of per-file results with total count before truncation.
@@ -45,10 +67,14 @@ pub struct DupsSummary {
     pub duplicate_groups: usize,
     pub files_with_duplicates: usize,
     pub largest_block: usize,
+    /// The top duplicate groups (worst first), with their code samples.
+    /// Rendered by the HTML report; omitted from JSON.
+    #[serde(skip)]
+    pub groups: Vec<DuplicateGroup>,
 }
 
 /// Per-file indentation complexity entry for the combined report.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct IndentEntry {
     pub path: String,
     pub code_lines: usize,
@@ -58,7 +84,7 @@ pub struct IndentEntry {
 }
 
 /// Per-file Halstead metrics entry for the combined report.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct HalsteadEntry {
     pub path: String,
     pub volume: f64,
@@ -68,7 +94,7 @@ pub struct HalsteadEntry {
 }
 
 /// Per-file cyclomatic complexity entry for the combined report.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct CycomEntry {
     pub path: String,
     pub functions: usize,
@@ -78,8 +104,19 @@ pub struct CycomEntry {
     pub level: String,
 }
 
+/// Per-file cognitive complexity entry for the combined report.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CogcomEntry {
+    pub path: String,
+    pub functions: usize,
+    pub total: usize,
+    pub max: usize,
+    pub avg: f64,
+    pub level: String,
+}
+
 /// Per-file Visual Studio MI entry for the combined report.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct MiVisualStudioEntry {
     pub path: String,
     pub mi_score: f64,
@@ -87,9 +124,95 @@ pub struct MiVisualStudioEntry {
 }
 
 /// Per-file verifysoft MI entry for the combined report.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct MiVerifysoftEntry {
     pub path: String,
     pub mi_score: f64,
     pub level: String,
 }
+
+/// Per-file code smell counts, by kind, for the combined report.
+#[derive(Debug, Serialize)]
+pub struct SmellsEntry {
+    pub path: String,
+    pub total: usize,
+    pub long_functions: usize,
+    pub long_params: usize,
+    pub todo_debt: usize,
+    pub magic_numbers: usize,
+    pub commented_code: usize,
+}
+
+/// Per-file dependency entry for the combined report.
+#[derive(Debug, Serialize)]
+pub struct DepsEntry {
+    pub path: String,
+    pub fan_in: usize,
+    pub fan_out: usize,
+    pub in_cycle: bool,
+}
+
+/// Dependency section: per-file fan-in/fan-out plus every cycle found.
+#[derive(Debug, Serialize)]
+pub struct DepsSection {
+    #[serde(flatten)]
+    pub files: SectionResult<DepsEntry>,
+    /// Files forming each dependency cycle.
+    pub cycles: Vec<Vec<String>>,
+}
+
+/// Per-file hotspot entry (change frequency × complexity).
+#[derive(Debug, Serialize)]
+pub struct HotspotEntry {
+    pub path: String,
+    pub commits: usize,
+    pub complexity: usize,
+    pub score: usize,
+}
+
+/// Hotspot section, with the complexity metric used for the score.
+#[derive(Debug, Serialize)]
+pub struct HotspotsSection {
+    #[serde(flatten)]
+    pub files: SectionResult<HotspotEntry>,
+    /// `indent`, `cycom`, or `cogcom`.
+    pub complexity_metric: String,
+}
+
+/// Per-file ownership entry for the combined report.
+#[derive(Debug, Serialize)]
+pub struct KnowledgeEntry {
+    pub path: String,
+    pub owner: String,
+    pub ownership_pct: f64,
+    pub contributors: usize,
+    pub risk: String,
+    pub knowledge_loss: bool,
+}
+
+/// Knowledge section: per-file ownership plus the project bus factor.
+#[derive(Debug, Serialize)]
+pub struct KnowledgeSection {
+    #[serde(flatten)]
+    pub files: SectionResult<KnowledgeEntry>,
+    /// Minimum number of contributors covering 80% of the code.
+    pub bus_factor: usize,
+}
+
+/// A pair of files that change together, for the combined report.
+#[derive(Debug, Serialize)]
+pub struct CouplingEntry {
+    pub file_a: String,
+    pub file_b: String,
+    pub shared_commits: usize,
+    pub strength: f64,
+    pub level: String,
+}
+
+/// Per-file code age entry for the combined report.
+#[derive(Debug, Serialize)]
+pub struct AgeEntry {
+    pub path: String,
+    pub age_days: u64,
+    pub status: String,
+}
@@ -0,0 +1,492 @@
+//! Self-contained HTML formatter for the combined `km report` command.
+//!
+//! Renders one offline page with embedded CSS and JavaScript (no external
+//! resources): a language breakdown chart and table, the duplication summary
+//! with collapsible group samples, and a sortable table for every metric
+//! section. Section tables are built from the serialized fields of each
+//! entry, so a new `SectionResult` only needs a title and an anchor here.
+
+use std::error::Error;
+use std::fmt::{self, Write};
+
+use serde::de::{MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+use super::ProjectReport;
+use super::data::SectionResult;
+use super::markdown::top_of;
+use crate::dups::detector::DuplicationSeverity;
+
+const STYLE: &str = r#"
+:root { --fg: #1f2328; --muted: #59636e; --border: #d1d9e0; --accent: #0969da; --bg-alt: #f6f8fa; }
+* { box-sizing: border-box; }
+body { font: 14px/1.5 -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; color: var(--fg); margin: 0 auto; max-width: 1100px; padding: 24px; }
+h1 { margin-bottom: 4px; }
+h2 { border-bottom: 1px solid var(--border); padding-bottom: 4px; margin-top: 40px; }
+.meta, .desc, .note { color: var(--muted); }
+nav a { margin-right: 12px; color: var(--accent); text-decoration: none; }
+table { border-collapse: collapse; width: 100%; margin: 12px 0; }
+th, td { border: 1px solid var(--border); padding: 4px 8px; text-align: left; }
+th { background: var(--bg-alt); }
+td.num, th.num { text-align: right; font-variant-numeric: tabular-nums; }
+table.sortable th { cursor: pointer; user-select: none; }
+table.sortable th::after { content: " \2195"; color: var(--muted); }
+table.sortable th.asc::after { content: " \2191"; }
+table.sortable th.desc::after { content: " \2193"; }
+tfoot td { font-weight: bold; }
+.chart { margin: 12px 0; }
+.bar-row { display: grid; grid-template-columns: 160px 1fr 90px; gap: 8px; align-items: center; margin: 3px 0; }
+.bar { background: var(--accent); height: 14px; border-radius: 3px; min-width: 2px; }
+.bar-value { text-align: right; font-variant-numeric: tabular-nums; }
+details { border: 1px solid var(--border); border-radius: 6px; margin: 6px 0; padding: 4px 10px; }
+details[open] { background: var(--bg-alt); }
+summary { cursor: pointer; }
+.critical { color: #cf222e; font-weight: bold; }
+.tolerable { color: #9a6700; font-weight: bold; }
+pre { background: #fff; border: 1px solid var(--border); padding: 8px; overflow-x: auto; }
+"#;
+
+const SCRIPT: &str = r#"
+document.querySelectorAll("table.sortable").forEach(function (table) {
+  table.querySelectorAll("thead th").forEach(function (th, col) {
+    th.addEventListener("click", function () {
+      var asc = !th.classList.contains("asc");
+      table.querySelectorAll("thead th").forEach(function (h) { h.classList.remove("asc", "desc"); });
+      th.classList.add(asc ? "asc" : "desc");
+      var numeric = th.classList.contains("num");
+      var body = table.tBodies[0];
+      var rows = Array.prototype.slice.call(body.rows);
+      rows.sort(function (a, b) {
+        var x = a.cells[col].getAttribute("data-v") || a.cells[col].textContent;
+        var y = b.cells[col].getAttribute("data-v") || b.cells[col].textContent;
+        var cmp = numeric ? parseFloat(x) - parseFloat(y) : x.localeCompare(y);
+        return asc ? cmp : -cmp;
+      });
+      rows.forEach(function (r) { body.appendChild(r); });
+    });
+  });
+});
+"#;
+
+/// A serialized report entry with its fields in declaration order
+/// (`serde_json::Map` would sort them alphabetically).
+struct Row(Vec<(String, Value)>);
+
+impl<'de> Deserialize<'de> for Row {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct RowVisitor;
+
+        impl<'de> Visitor<'de> for RowVisitor {
+            type Value = Row;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a report entry object")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Row, A::Error> {
+                let mut fields = Vec::new();
+                while let Some(field) = map.next_entry::<String, Value>()? {
+                    fields.push(field);
+                }
+                Ok(Row(fields))
+            }
+        }
+
+        deserializer.deserialize_map(RowVisitor)
+    }
+}
+
+/// Escape text for use in HTML element content and attribute values.
+pub(crate) fn escape_html(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Column header for a serialized entry field: `"mi_score"` → `"MI"`,
+/// `"max_depth"` → `"Max Depth"`.
+fn column_label(key: &str) -> String {
+    match key {
+        "path" => "File".to_string(),
+        "mi_score" => "MI".to_string(),
+        "stddev" => "StdDev".to_string(),
+        "code_lines" => "Lines".to_string(),
+        "time" => "Time (s)".to_string(),
+        "ownership_pct" => "Ownership %".to_string(),
+        "age_days" => "Age (days)".to_string(),
+        _ => key
+            .split('_')
+            .map(|w| {
+                let mut chars = w.chars();
+                chars
+                    .next()
+                    .map(|c| c.to_uppercase().chain(chars).collect::<String>())
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>()
+            .join(" "),
+    }
+}
+
+/// Render one serialized field as a table cell. Numbers are right-aligned
+/// and carry their raw value for sorting; floats are shown with 2 decimals.
+fn cell(value: &Value) -> String {
+    match value {
+        Value::Number(n) if n.is_f64() => {
+            let v = n.as_f64().unwrap_or(0.0);
+            format!("<td class=\"num\" data-v=\"{v}\">{v:.2}</td>")
+        }
+        Value::Number(n) => format!("<td class=\"num\" data-v=\"{n}\">{n}</td>"),
+        Value::String(s) => format!("<td>{}</td>", escape_html(s)),
+        Value::Bool(b) => format!("<td>{}</td>", if *b { "yes" } else { "no" }),
+        Value::Null => "<td></td>".to_string(),
+        other => format!("<td>{}</td>", escape_html(&other.to_string())),
+    }
+}
+
+/// Write a metric section: heading, description, and a sortable table
+/// whose columns are the fields of the serialized entries.
+fn write_section<T: Serialize>(
+    out: &mut String,
+    id: &str,
+    title: &str,
+    section: &SectionResult<T>,
+) -> Result<(), Box<dyn Error>> {
+    write_section_with(out, id, title, section, "")
+}
+
+/// Like `write_section`, with `extra` HTML (already escaped) placed between
+/// the description and the table.
+fn write_section_with<T: Serialize>(
+    out: &mut String,
+    id: &str,
+    title: &str,
+    section: &SectionResult<T>,
+    extra: &str,
+) -> Result<(), Box<dyn Error>> {
+    writeln!(
+        out,
+        "<section id=\"{id}\"><h2>{} ({})</h2>",
+        escape_html(title),
+        top_of(section.entries.len(), section.total_count)
+    )?;
+    writeln!(
+        out,
+        "<p class=\"desc\">{}</p>",
+        escape_html(section.description)
+    )?;
+    out.push_str(extra);
+    let rows: Vec<Row> = section
+        .entries
+        .iter()
+        .map(|e| serde_json::from_str(&serde_json::to_string(e)?))
+        .collect::<Result<_, _>>()?;
+    let Some(first) = rows.first() else {
+        writeln!(out, "<p class=\"note\">No data.</p></section>")?;
+        return Ok(());
+    };
+
+    out.push_str("<table class=\"sortable\"><thead><tr>");
+    for (key, value) in &first.0 {
+        let class = if value.is_number() {
+            " class=\"num\""
+        } else {
+            ""
+        };
+        write!(out, "<th{class}>{}</th>", escape_html(&column_label(key)))?;
+    }
+    out.push_str("</tr></thead><tbody>\n");
+    for row in &rows {
+        out.push_str("<tr>");
+        for (_, value) in &row.0 {
+            out.push_str(&cell(value));
+        }
+        out.push_str("</tr>\n");
+    }
+    out.push_str("</tbody></table></section>\n");
+    Ok(())
+}
+
+/// Write the language breakdown: a bar chart of code lines and a table.
+fn write_loc(out: &mut String, report: &ProjectReport) -> Result<(), Box<dyn Error>> {
+    out.push_str("<section id=\"loc\"><h2>Lines of Code</h2>\n");
+    if report.loc.is_empty() {
+        out.push_str("<p class=\"note\">No recognized source files found.</p></section>\n");
+        return Ok(());
+    }
+    let max_code = report.loc.iter().map(|l| l.code).max().unwrap_or(0).max(1);
+    out.push_str("<div class=\"chart\">\n");
+    for l in &report.loc {
+        let pct = l.code as f64 / max_code as f64 * 100.0;
+        writeln!(
+            out,
+            "<div class=\"bar-row\"><span>{}</span><div class=\"bar\" style=\"width:{pct:.1}%\"></div>\
+             <span class=\"bar-value\">{}</span></div>",
+            escape_html(&l.name),
+            l.code
+        )?;
+    }
+    out.push_str("</div>\n");
+
+    out.push_str(
+        "<table class=\"sortable\"><thead><tr><th>Language</th><th class=\"num\">Files</th>\
+         <th class=\"num\">Blank</th><th class=\"num\">Comment</th><th class=\"num\">Code</th>\
+         </tr></thead><tbody>\n",
+    );
+    let (mut files, mut blank, mut comment, mut code) = (0, 0, 0, 0);
+    for l in &report.loc {
+        writeln!(
+            out,
+            "<tr><td>{}</td>{}{}{}{}</tr>",
+            escape_html(&l.name),
+            cell(&Value::from(l.files)),
+            cell(&Value::from(l.blank)),
+            cell(&Value::from(l.comment)),
+            cell(&Value::from(l.code)),
+        )?;
+        files += l.files;
+        blank += l.blank;
+        comment += l.comment;
+        code += l.code;
+    }
+    writeln!(
+        out,
+        "</tbody><tfoot><tr><td>Total</td><td class=\"num\">{files}</td>\
+         <td class=\"num\">{blank}</td><td class=\"num\">{comment}</td>\
+         <td class=\"num\">{code}</td></tr></tfoot></table></section>"
+    )?;
+    Ok(())
+}
+
+/// Write the duplication summary and one collapsible block per group,
+/// showing its locations and normalized code sample.
+fn write_duplication(out: &mut String, report: &ProjectReport) -> Result<(), Box<dyn Error>> {
+    let d = &report.duplication;
+    out.push_str("<section id=\"duplication\"><h2>Code Duplication</h2>\n");
+    writeln!(out, "<p class=\"desc\">{}</p>", escape_html(d.description))?;
+    writeln!(
+        out,
+        "<table><tbody>\
+         <tr><th>Total code lines</th><td class=\"num\">{}</td></tr>\
+         <tr><th>Duplicated lines</th><td class=\"num\">{} ({:.1}%)</td></tr>\
+         <tr><th>Duplicate groups</th><td class=\"num\">{}</td></tr>\
+         <tr><th>Files with duplicates</th><td class=\"num\">{}</td></tr>\
+         <tr><th>Largest block</th><td class=\"num\">{} lines</td></tr>\
+         </tbody></table>",
+        d.total_code_lines,
+        d.duplicated_lines,
+        d.duplication_percentage,
+        d.duplicate_groups,
+        d.files_with_duplicates,
+        d.largest_block,
+    )?;
+    if !d.groups.is_empty() {
+        writeln!(
+            out,
+            "<h3>Duplicate groups ({})</h3>",
+            top_of(d.groups.len(), d.duplicate_groups)
+        )?;
+    }
+    for group in &d.groups {
+        let (class, label) = match group.severity {
+            DuplicationSeverity::Critical => ("critical", "CRITICAL"),
+            DuplicationSeverity::Tolerable => ("tolerable", "TOLERABLE"),
+        };
+        writeln!(
+            out,
+            "<details><summary><span class=\"{class}\">{label}</span> \
+             {} lines &times; {} copies</summary><ul>",
+            group.line_count,
+            group.locations.len()
+        )?;
+        for loc in &group.locations {
+            writeln!(
+                out,
+                "<li><code>{}</code> lines {}-{}</li>",
+                escape_html(&loc.file_path.display().to_string()),
+                loc.start_line,
+                loc.end_line
+            )?;
+        }
+        writeln!(
+            out,
+            "</ul><pre><code>{}</code></pre></details>",
+            escape_html(&group.sample.join("\n"))
+        )?;
+    }
+    out.push_str("</section>\n");
+    Ok(())
+}
+
+/// Write the sections selected with `--with` and the skipped-section notes.
+fn write_optional_sections(out: &mut String, report: &ProjectReport) -> Result<(), Box<dyn Error>> {
+    if let Some(smells) = &report.smells {
+        write_section(out, "smells", "Code Smells", smells)?;
+    }
+    if let Some(deps) = &report.dependencies {
+        let mut cycles = String::new();
+        if deps.cycles.is_empty() {
+            cycles.push_str("<p class=\"note\">No dependency cycles.</p>\n");
+        } else {
+            writeln!(
+                cycles,
+                "<h3>Dependency cycles ({})</h3><ul>",
+                deps.cycles.len()
+            )?;
+            for cycle in &deps.cycles {
+                let files: Vec<String> = cycle
+                    .iter()
+                    .map(|p| format!("<code>{}</code>", escape_html(p)))
+                    .collect();
+                writeln!(cycles, "<li>{}</li>", files.join(", "))?;
+            }
+            cycles.push_str("</ul>\n");
+        }
+        write_section_with(out, "deps", "Dependencies", &deps.files, &cycles)?;
+    }
+    if let Some(hot) = &report.hotspots {
+        let metric = format!(
+            "<p class=\"meta\">Complexity metric: <code>{}</code></p>\n",
+            escape_html(&hot.complexity_metric)
+        );
+        write_section_with(out, "hotspots", "Hotspots", &hot.files, &metric)?;
+    }
+    if let Some(k) = &report.knowledge {
+        let bus = format!(
+            "<p class=\"meta\">Bus factor: <strong>{}</strong></p>\n",
+            k.bus_factor
+        );
+        write_section_with(out, "knowledge", "Knowledge Map", &k.files, &bus)?;
+    }
+    if let Some(tc) = &report.temporal_coupling {
+        write_section(out, "tc", "Temporal Coupling", tc)?;
+    }
+    if let Some(age) = &report.age {
+        write_section(out, "age", "Code Age", age)?;
+    }
+    if !report.skipped.is_empty() {
+        out.push_str("<section id=\"skipped\"><h2>Skipped Sections</h2><ul>\n");
+        for note in &report.skipped {
+            writeln!(out, "<li>{}</li>", escape_html(note))?;
+        }
+        out.push_str("</ul></section>\n");
+    }
+    Ok(())
+}
+
+/// Render the full project report as a single self-contained HTML page.
+pub fn format_html(report: &ProjectReport) -> Result<String, Box<dyn Error>> {
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html lang=\"en\"><head><meta charset=\"utf-8\">\n");
+    out.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+    writeln!(
+        out,
+        "<title>Code Metrics Report — {}</title>",
+        escape_html(&report.path)
+    )?;
+    writeln!(out, "<style>{STYLE}</style></head><body>")?;
+    out.push_str("<h1>Code Metrics Report</h1>\n");
+    writeln!(
+        out,
+        "<p class=\"meta\">Path <code>{}</code> &middot; top {} files per section &middot; \
+         tests {} &middot; min duplicate block {} lines &middot; kimun {}</p>",
+        escape_html(&report.path),
+        report.top,
+        if report.include_tests {
+            "included"
+        } else {
+            "excluded"
+        },
+        report.min_lines,
+        env!("CARGO_PKG_VERSION"),
+    )?;
+
+    let mut sections: Vec<(&str, &str)> = vec![
+        ("loc", "Lines of Code"),
+        ("duplication", "Duplication"),
+        ("indent", "Indentation"),
+        ("halstead", "Halstead"),
+        ("cyclomatic", "Cyclomatic"),
+        ("cognitive", "Cognitive"),
+        ("mi-vs", "MI (Visual Studio)"),
+        ("mi-vf", "MI (verifysoft)"),
+    ];
+    let optional = [
+        (report.smells.is_some(), "smells", "Smells"),
+        (report.dependencies.is_some(), "deps", "Dependencies"),
+        (report.hotspots.is_some(), "hotspots", "Hotspots"),
+        (report.knowledge.is_some(), "knowledge", "Knowledge"),
+        (report.temporal_coupling.is_some(), "tc", "Coupling"),
+        (report.age.is_some(), "age", "Age"),
+        (!report.skipped.is_empty(), "skipped", "Skipped"),
+    ];
+    sections.extend(
+        optional
+            .into_iter()
+            .filter(|(present, _, _)| *present)
+            .map(|(_, id, label)| (id, label)),
+    );
+    out.push_str("<nav>");
+    for (id, label) in sections {
+        write!(out, "<a href=\"#{id}\">{label}</a>")?;
+    }
+    out.push_str("</nav>\n");
+
+    write_loc(&mut out, report)?;
+    write_duplication(&mut out, report)?;
+    write_section(&mut out, "indent", "Indentation Complexity", &report.indent)?;
+    write_section(
+        &mut out,
+        "halstead",
+        "Halstead Complexity",
+        &report.halstead,
+    )?;
+    write_section(
+        &mut out,
+        "cyclomatic",
+        "Cyclomatic Complexity",
+        &report.cyclomatic,
+    )?;
+    write_section(
+        &mut out,
+        "cognitive",
+        "Cognitive Complexity",
+        &report.cognitive,
+    )?;
+    write_section(
+        &mut out,
+        "mi-vs",
+        "Maintainability Index \u{2014} Visual Studio",
+        &report.mi_visual_studio,
+    )?;
+    write_section(
+        &mut out,
+        "mi-vf",
+        "Maintainability Index \u{2014} Verifysoft",
+        &report.mi_verifysoft,
+    )?;
+    write_optional_sections(&mut out, report)?;
+
+    writeln!(out, "<script>{SCRIPT}</script>\n</body></html>")?;
+    Ok(out)
+}
+
+/// Print the self-contained HTML report to stdout.
+pub fn print_html(report: &ProjectReport) -> Result<(), Box<dyn Error>> {
+    print!("{}", format_html(report)?);
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "html_test.rs"]
+mod tests;
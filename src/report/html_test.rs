@@ -0,0 +1,146 @@
+use super::*;
+use crate::report::{DepsEntry, DepsSection, build_report};
+use crate::walk::{ExcludeFilter, WalkConfig};
+use std::fs;
+
+/// A report over two files sharing a 7-line block, so every section
+/// and one duplicate group are populated.
+fn sample_report(dir: &std::path::Path) -> ProjectReport {
+    let shared = "    let x = read();\n    let y = transform(x);\n    write(y);\n    log(\"<done>\");\n    cleanup();\n    validate();\n    if x > 0 { finish(); }\n";
+    fs::write(dir.join("a.rs"), format!("fn process_a() {{\n{shared}}}\n")).unwrap();
+    fs::write(dir.join("b.rs"), format!("fn process_b() {{\n{shared}}}\n")).unwrap();
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(dir, false, &filter);
+    build_report(&cfg, 20, 6).unwrap()
+}
+
+#[test]
+fn escape_html_special_chars() {
+    assert_eq!(
+        escape_html("<a href=\"x\">&'</a>"),
+        "&lt;a href=&quot;x&quot;&gt;&amp;&#39;&lt;/a&gt;"
+    );
+    assert_eq!(escape_html("src/main.rs"), "src/main.rs");
+}
+
+#[test]
+fn column_label_known_and_generic_keys() {
+    assert_eq!(column_label("path"), "File");
+    assert_eq!(column_label("mi_score"), "MI");
+    assert_eq!(column_label("max_depth"), "Max Depth");
+    assert_eq!(column_label("functions"), "Functions");
+}
+
+#[test]
+fn cell_formats_numbers_and_text() {
+    assert_eq!(
+        cell(&serde_json::json!(3)),
+        "<td class=\"num\" data-v=\"3\">3</td>"
+    );
+    assert_eq!(
+        cell(&serde_json::json!(1.23456)),
+        "<td class=\"num\" data-v=\"1.23456\">1.23</td>"
+    );
+    assert_eq!(cell(&serde_json::json!("a<b")), "<td>a&lt;b</td>");
+}
+
+#[test]
+fn format_html_is_self_contained() {
+    let dir = tempfile::tempdir().unwrap();
+    let html = format_html(&sample_report(dir.path())).unwrap();
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.trim_end().ends_with("</html>"));
+    assert!(html.contains("<style>"));
+    assert!(html.contains("<script>"));
+    assert!(!html.contains("<script src"));
+    assert!(!html.contains("<link"));
+}
+
+#[test]
+fn format_html_renders_every_section() {
+    let dir = tempfile::tempdir().unwrap();
+    let html = format_html(&sample_report(dir.path())).unwrap();
+    for id in [
+        "loc",
+        "duplication",
+        "indent",
+        "halstead",
+        "cyclomatic",
+        "cognitive",
+        "mi-vs",
+        "mi-vf",
+    ] {
+        assert!(
+            html.contains(&format!("<section id=\"{id}\">")),
+            "missing {id}"
+        );
+        assert!(
+            html.contains(&format!("href=\"#{id}\"")),
+            "missing nav {id}"
+        );
+    }
+    // Language chart and sortable tables.
+    assert!(html.contains("class=\"bar\""));
+    assert!(html.contains("<table class=\"sortable\">"));
+    // Columns follow the entry's field order, path first.
+    assert!(
+        html.contains("<th>File</th><th class=\"num\">Functions</th><th class=\"num\">Total</th>")
+    );
+}
+
+#[test]
+fn format_html_shows_collapsible_duplicate_samples() {
+    let dir = tempfile::tempdir().unwrap();
+    let report = sample_report(dir.path());
+    assert_eq!(report.duplication.groups.len(), 1);
+    let html = format_html(&report).unwrap();
+    assert!(html.contains("<details><summary><span class=\"tolerable\">TOLERABLE</span>"));
+    assert!(html.contains("a.rs</code> lines"));
+    assert!(html.contains("b.rs</code> lines"));
+    // Sample code is escaped.
+    assert!(html.contains("log(&quot;&lt;done&gt;&quot;);"));
+}
+
+#[test]
+fn format_html_empty_report() {
+    let dir = tempfile::tempdir().unwrap();
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(dir.path(), false, &filter);
+    let html = format_html(&build_report(&cfg, 20, 6).unwrap()).unwrap();
+    assert!(html.contains("No recognized source files found."));
+    assert!(html.contains("No data."));
+    assert!(!html.contains("<details>"));
+}
+
+#[test]
+fn format_html_renders_optional_sections_and_skips() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut report = sample_report(dir.path());
+    // Without --with, optional sections are absent from page and nav.
+    let html = format_html(&report).unwrap();
+    assert!(!html.contains("href=\"#smells\""));
+    assert!(!html.contains("id=\"skipped\""));
+
+    report.dependencies = Some(DepsSection {
+        files: SectionResult {
+            description: "deps",
+            total_count: 2,
+            entries: vec![DepsEntry {
+                path: "a.rs".to_string(),
+                fan_in: 1,
+                fan_out: 1,
+                in_cycle: true,
+            }],
+        },
+        cycles: vec![vec!["a.rs".to_string(), "<b>.rs".to_string()]],
+    });
+    report
+        .skipped
+        .push("git sections skipped: not a git repository".to_string());
+    let html = format_html(&report).unwrap();
+    assert!(html.contains("<section id=\"deps\"><h2>Dependencies (top 1 of 2)</h2>"));
+    assert!(html.contains("Dependency cycles (1)"));
+    assert!(html.contains("<code>&lt;b&gt;.rs</code>"));
+    assert!(html.contains("href=\"#skipped\""));
+    assert!(html.contains("<li>git sections skipped: not a git repository</li>"));
+}
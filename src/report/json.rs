@@ -3,6 +3,12 @@ use std::error::Error;
 use super::ProjectReport;
 use crate::report_helpers;
 
+/// Serialize the full project report as a pretty-printed JSON string.
+pub fn format_json(report: &ProjectReport) -> Result<String, Box<dyn Error>> {
+    report_helpers::json_string(report)
+}
+
 pub fn print_json(report: &ProjectReport) -> Result<(), Box<dyn Error>> {
-    report_helpers::print_json_stdout(report)
+    println!("{}", format_json(report)?);
+    Ok(())
 }
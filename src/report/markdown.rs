@@ -1,27 +1,24 @@
 //! Markdown report formatter for the combined `km report` command.
 //!
-//! Generates a single markdown document with seven sections: lines of code
+//! Generates a single markdown document with eight sections: lines of code
 //! (language breakdown), duplication (project-wide stats), indentation
-//! complexity, Halstead complexity, cyclomatic complexity, and two MI
-//! variants (Visual Studio and verifysoft). Each section includes a
-//! description, a markdown table of the top N files sorted by the most
-//! relevant metric (worst first), and a note when entries are truncated.
+//! complexity, Halstead complexity, cyclomatic and cognitive complexity, and
+//! two MI variants (Visual Studio and verifysoft), followed by any optional
+//! sections (smells, dependencies, hotspots, knowledge, temporal coupling,
+//! age) selected with `--with`. Each section includes a description, a
+//! markdown table of the top N files sorted by the most relevant metric
+//! (worst first), and a note when entries are truncated.
 //!
 //! Paths and special characters are escaped for correct markdown rendering.
 
 use super::ProjectReport;
 use super::data::SectionResult;
 use crate::hal::report::format_time;
-
-/// Escape backslashes and pipe characters in file paths so markdown tables
-/// render correctly. Backslashes must be escaped first to avoid double-escaping.
-fn escape_md(s: &str) -> String {
-    s.replace('\\', "\\\\").replace('|', "\\|")
-}
+use crate::report_helpers::escape_md;
 
 /// Format a count as "top N of M" when truncated, or just "N" when
 /// all entries are shown.
-fn top_of(shown: usize, total: usize) -> String {
+pub(super) fn top_of(shown: usize, total: usize) -> String {
     if shown < total {
         format!("top {shown} of {total}")
     } else {
@@ -189,6 +186,35 @@ pub fn print_markdown(report: &ProjectReport) {
         }
     }
 
+    // --- Cognitive ---
+    // Worst first: highest total cognitive complexity is hardest to read.
+    let cog = &report.cognitive;
+    println!();
+    println!(
+        "## Cognitive Complexity ({}, by total desc)",
+        top_of(cog.entries.len(), cog.total_count)
+    );
+    println!();
+    println!("{}", cog.description);
+    println!();
+    if cog.entries.is_empty() {
+        println!("No data.");
+    } else {
+        println!("| File | Functions | Total | Max | Avg | Level |");
+        println!("|------|----------:|------:|----:|----:|-------|");
+        for f in &cog.entries {
+            println!(
+                "| {} | {} | {} | {} | {:.1} | {} |",
+                escape_md(&f.path),
+                f.functions,
+                f.total,
+                f.max,
+                f.avg,
+                f.level
+            );
+        }
+    }
+
     // --- MI sections ---
     // Both variants share the same File | MI | Level table format via
     // the generic print_mi_section() helper.
@@ -198,6 +224,172 @@ pub fn print_markdown(report: &ProjectReport) {
     print_mi_section("Verifysoft", &report.mi_verifysoft, |e| {
         (&e.path, e.mi_score, &e.level)
     });
+
+    print_optional_sections(report);
+}
+
+/// Print the sections selected with `--with`, then any skipped-section notes.
+fn print_optional_sections(report: &ProjectReport) {
+    if let Some(smells) = &report.smells {
+        print_heading("Code Smells", "by total desc", smells);
+        print_table(
+            smells,
+            "| File | Total | Long Fn | Params | TODO | Magic | Commented |",
+            "|------|------:|--------:|-------:|-----:|------:|----------:|",
+            |f| {
+                format!(
+                    "| {} | {} | {} | {} | {} | {} | {} |",
+                    escape_md(&f.path),
+                    f.total,
+                    f.long_functions,
+                    f.long_params,
+                    f.todo_debt,
+                    f.magic_numbers,
+                    f.commented_code
+                )
+            },
+        );
+    }
+
+    if let Some(deps) = &report.dependencies {
+        print_heading("Dependencies", "by fan-out desc", &deps.files);
+        print_table(
+            &deps.files,
+            "| File | Fan-in | Fan-out | In Cycle |",
+            "|------|-------:|--------:|----------|",
+            |f| {
+                format!(
+                    "| {} | {} | {} | {} |",
+                    escape_md(&f.path),
+                    f.fan_in,
+                    f.fan_out,
+                    if f.in_cycle { "yes" } else { "no" }
+                )
+            },
+        );
+        println!();
+        if deps.cycles.is_empty() {
+            println!("No dependency cycles.");
+        } else {
+            println!("**Dependency cycles ({}):**", deps.cycles.len());
+            println!();
+            for cycle in &deps.cycles {
+                let files: Vec<String> = cycle.iter().map(|p| format!("`{p}`")).collect();
+                println!("- {}", files.join(", "));
+            }
+        }
+    }
+
+    if let Some(hot) = &report.hotspots {
+        print_heading("Hotspots", "by score desc", &hot.files);
+        print_table(
+            &hot.files,
+            &format!(
+                "| File | Commits | Complexity ({}) | Score |",
+                hot.complexity_metric
+            ),
+            "|------|--------:|-----------:|------:|",
+            |f| {
+                format!(
+                    "| {} | {} | {} | {} |",
+                    escape_md(&f.path),
+                    f.commits,
+                    f.complexity,
+                    f.score
+                )
+            },
+        );
+    }
+
+    if let Some(k) = &report.knowledge {
+        print_heading("Knowledge Map", "by risk", &k.files);
+        println!("**Bus factor:** {}", k.bus_factor);
+        println!();
+        print_table(
+            &k.files,
+            "| File | Owner | Ownership | Contributors | Risk | Knowledge Loss |",
+            "|------|-------|----------:|-------------:|------|----------------|",
+            |f| {
+                format!(
+                    "| {} | {} | {:.0}% | {} | {} | {} |",
+                    escape_md(&f.path),
+                    escape_md(&f.owner),
+                    f.ownership_pct,
+                    f.contributors,
+                    f.risk,
+                    if f.knowledge_loss { "yes" } else { "no" }
+                )
+            },
+        );
+    }
+
+    if let Some(tc) = &report.temporal_coupling {
+        print_heading("Temporal Coupling", "by strength desc", tc);
+        print_table(
+            tc,
+            "| File A | File B | Shared | Strength | Level |",
+            "|--------|--------|-------:|---------:|-------|",
+            |p| {
+                format!(
+                    "| {} | {} | {} | {:.2} | {} |",
+                    escape_md(&p.file_a),
+                    escape_md(&p.file_b),
+                    p.shared_commits,
+                    p.strength,
+                    p.level
+                )
+            },
+        );
+    }
+
+    if let Some(age) = &report.age {
+        print_heading("Code Age", "oldest first", age);
+        print_table(
+            age,
+            "| File | Age (days) | Status |",
+            "|------|-----------:|--------|",
+            |f| format!("| {} | {} | {} |", escape_md(&f.path), f.age_days, f.status),
+        );
+    }
+
+    if !report.skipped.is_empty() {
+        println!();
+        println!("## Skipped Sections");
+        println!();
+        for note in &report.skipped {
+            println!("- {note}");
+        }
+    }
+}
+
+/// Print a section heading with its "top N of M" count and description.
+fn print_heading<T>(title: &str, order: &str, section: &SectionResult<T>) {
+    println!();
+    println!(
+        "## {title} ({}, {order})",
+        top_of(section.entries.len(), section.total_count)
+    );
+    println!();
+    println!("{}", section.description);
+    println!();
+}
+
+/// Print a section's entries as a markdown table, or "No data." when empty.
+fn print_table<T>(
+    section: &SectionResult<T>,
+    header: &str,
+    align: &str,
+    row: impl Fn(&T) -> String,
+) {
+    if section.entries.is_empty() {
+        println!("No data.");
+        return;
+    }
+    println!("{header}");
+    println!("{align}");
+    for entry in &section.entries {
+        println!("{}", row(entry));
+    }
 }
 
 /// Print a Maintainability Index section (File | MI | Level table).
@@ -1,8 +1,10 @@
 //! Combined report module (`km report` command).
 //!
 //! Walks all source files once, runs every analyzer (LOC, duplication,
-//! indentation, Halstead, cyclomatic, MI), and produces a unified
-//! markdown or JSON report with all metrics.
+//! indentation, Halstead, cyclomatic, cognitive, MI), and produces a unified
+//! markdown, JSON, or self-contained HTML report with all metrics. Smells,
+//! dependencies, and the git history sections (hotspots, knowledge,
+//! temporal coupling, age) are added on request with `--with`.
 //!
 //! The single-walk design avoids reading files multiple times — each file
 //! is read and classified once by the analyzer, then all metric computations
@@ -15,10 +17,14 @@ mod analyzer;
 mod builder;
 /// Data structures for the combined report (sections, entries, summaries).
 pub(crate) mod data;
+/// Self-contained HTML rendering of the combined report.
+pub(crate) mod html;
 /// JSON serialization of the combined report.
-mod json;
+pub(crate) mod json;
 /// Markdown (table) formatting of the combined report.
 mod markdown;
+/// Optional sections selected with `--with`.
+mod sections;
 
 use std::error::Error;
 
@@ -27,25 +33,28 @@ use crate::walk::WalkConfig;
 
 pub use data::*;
 
-pub use builder::build_report;
+pub use builder::{build_report, build_report_with};
+pub use sections::{ReportSections, SECTION_NAMES};
 
-/// Entry point: build the combined report and print it as markdown or JSON.
+/// Entry point: build the combined report and print it as markdown, JSON, or HTML.
 pub fn run(
     cfg: &WalkConfig<'_>,
     output: OutputMode,
     top: usize,
     min_lines: usize,
+    sections: &ReportSections,
 ) -> Result<(), Box<dyn Error>> {
-    let report = build_report(cfg, top, min_lines)?;
+    let report = build_report_with(cfg, top, min_lines, sections)?;
 
     match output {
         OutputMode::Json => json::print_json(&report)?,
         OutputMode::Short => print_short(&report),
         OutputMode::Terse => print_terse(&report),
-        OutputMode::Github | OutputMode::Codeclimate => {
+        OutputMode::Github | OutputMode::Codeclimate | OutputMode::Sarif => {
             return Err(crate::cli::ERR_CI_FORMAT_ONLY.into());
         }
-        OutputMode::Table => markdown::print_markdown(&report),
+        OutputMode::Html => html::print_html(&report)?,
+        OutputMode::Table | OutputMode::Markdown => markdown::print_markdown(&report),
     }
 
     Ok(())
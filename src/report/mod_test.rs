@@ -8,7 +8,7 @@ fn run_on_empty_dir() {
     let dir = tempfile::tempdir().unwrap();
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(dir.path(), false, &filter);
-    run(&cfg, OutputMode::Table, 20, 6).unwrap();
+    run(&cfg, OutputMode::Table, 20, 6, &ReportSections::default()).unwrap();
 }
 
 #[test]
@@ -16,7 +16,7 @@ fn run_on_empty_dir_json() {
     let dir = tempfile::tempdir().unwrap();
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(dir.path(), false, &filter);
-    run(&cfg, OutputMode::Json, 20, 6).unwrap();
+    run(&cfg, OutputMode::Json, 20, 6, &ReportSections::default()).unwrap();
 }
 
 #[test]
@@ -29,7 +29,7 @@ fn run_on_rust_file() {
     .unwrap();
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(dir.path(), false, &filter);
-    run(&cfg, OutputMode::Table, 20, 6).unwrap();
+    run(&cfg, OutputMode::Table, 20, 6, &ReportSections::default()).unwrap();
 }
 
 #[test]
@@ -42,7 +42,7 @@ fn run_json_output() {
     .unwrap();
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(dir.path(), false, &filter);
-    run(&cfg, OutputMode::Json, 20, 6).unwrap();
+    run(&cfg, OutputMode::Json, 20, 6, &ReportSections::default()).unwrap();
 }
 
 #[test]
@@ -51,7 +51,7 @@ fn run_skips_binary() {
     fs::write(dir.path().join("data.c"), b"hello\x00world").unwrap();
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(dir.path(), false, &filter);
-    run(&cfg, OutputMode::Table, 20, 6).unwrap();
+    run(&cfg, OutputMode::Table, 20, 6, &ReportSections::default()).unwrap();
 }
 
 // --- Tests that verify actual report structure ---
@@ -255,8 +255,21 @@ fn build_report_json_structure() {
     assert!(value["indent"]["entries"].is_array());
     assert!(value["halstead"]["total_count"].is_number());
     assert!(value["cyclomatic"]["total_count"].is_number());
+    assert!(value["cognitive"]["total_count"].is_number());
     assert!(value["mi_visual_studio"]["total_count"].is_number());
     assert!(value["mi_verifysoft"]["total_count"].is_number());
+    // Optional sections are omitted unless requested.
+    for key in [
+        "smells",
+        "dependencies",
+        "hotspots",
+        "knowledge",
+        "temporal_coupling",
+        "age",
+        "skipped",
+    ] {
+        assert!(value.get(key).is_none(), "unexpected {key}");
+    }
 }
 
 #[test]
@@ -291,7 +304,7 @@ fn run_short_format() {
     .unwrap();
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(dir.path(), false, &filter);
-    run(&cfg, OutputMode::Short, 20, 6).unwrap();
+    run(&cfg, OutputMode::Short, 20, 6, &ReportSections::default()).unwrap();
 }
 
 #[test]
@@ -304,5 +317,156 @@ fn run_terse_format() {
     .unwrap();
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(dir.path(), false, &filter);
-    run(&cfg, OutputMode::Terse, 20, 6).unwrap();
+    run(&cfg, OutputMode::Terse, 20, 6, &ReportSections::default()).unwrap();
+}
+
+#[test]
+fn run_html_output() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("main.rs"),
+        "fn main() {\n    let x = 1;\n}\n",
+    )
+    .unwrap();
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(dir.path(), false, &filter);
+    run(&cfg, OutputMode::Html, 20, 6, &ReportSections::default()).unwrap();
+}
+
+#[test]
+fn build_report_keeps_top_duplicate_groups() {
+    let dir = tempfile::tempdir().unwrap();
+    let shared = "    let x = read();\n    let y = transform(x);\n    write(y);\n    log(\"done\");\n    cleanup();\n    validate();\n    finish();\n";
+    fs::write(dir.path().join("a.rs"), format!("fn a() {{\n{shared}}}\n")).unwrap();
+    fs::write(dir.path().join("b.rs"), format!("fn b() {{\n{shared}}}\n")).unwrap();
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(dir.path(), false, &filter);
+
+    let report = build_report(&cfg, 20, 6).unwrap();
+    assert_eq!(report.duplication.groups.len(), 1);
+    assert_eq!(report.duplication.groups[0].locations.len(), 2);
+
+    let report = build_report(&cfg, 0, 6).unwrap();
+    assert!(report.duplication.groups.is_empty());
+    assert_eq!(report.duplication.duplicate_groups, 1);
+}
+
+#[test]
+fn build_report_cognitive_computed() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("main.rs"),
+        "fn f(x: i32) -> i32 {\n    if x > 0 {\n        if x > 1 {\n            return 2;\n        }\n    }\n    0\n}\n",
+    )
+    .unwrap();
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(dir.path(), false, &filter);
+    let report = build_report(&cfg, 20, 6).unwrap();
+
+    assert_eq!(report.cognitive.total_count, 1);
+    let cog = &report.cognitive.entries[0];
+    assert!(cog.path.ends_with("main.rs"));
+    assert_eq!(cog.functions, 1);
+    // if (+1) and nested if (+1, +1 nesting).
+    assert_eq!(cog.total, 3);
+    assert_eq!(cog.level, "simple");
+}
+
+#[test]
+fn report_sections_select() {
+    let kcfg = crate::config::KimunConfig::default();
+    let names = |n: &[&str]| n.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+    let none = ReportSections::select(&[], &kcfg).unwrap();
+    assert!(none.smells.is_none() && !none.deps && none.hotspots.is_none());
+    assert!(!none.reads_git());
+
+    let git = ReportSections::select(&names(&["git"]), &kcfg).unwrap();
+    assert!(git.smells.is_none() && !git.deps);
+    assert!(git.hotspots.is_some() && git.knowledge && git.tc.is_some() && git.age.is_some());
+    assert!(git.reads_git());
+    assert!(
+        !ReportSections::select(&names(&["smells", "deps"]), &kcfg)
+            .unwrap()
+            .reads_git()
+    );
+
+    let all = ReportSections::select(&names(&["all"]), &kcfg).unwrap();
+    assert_eq!(all.smells, Some((50, 4)));
+    assert!(all.deps && all.knowledge);
+    assert_eq!(all.hotspots.as_deref(), Some("indent"));
+
+    assert!(ReportSections::select(&names(&["bogus"]), &kcfg).is_err());
+}
+
+#[test]
+fn build_report_with_skips_git_sections_outside_repo() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("main.rs"),
+        "fn main() {\n    let x = 42;\n    // TODO: remove\n}\n",
+    )
+    .unwrap();
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(dir.path(), false, &filter);
+    let kcfg = crate::config::KimunConfig::default();
+    let sections = ReportSections::select(&["all".to_string()], &kcfg).unwrap();
+    let report = build_report_with(&cfg, 20, 6, &sections).unwrap();
+
+    let smells = report.smells.as_ref().unwrap();
+    assert_eq!(smells.total_count, 1);
+    assert_eq!(smells.entries[0].todo_debt, 1);
+    let deps = report.dependencies.as_ref().unwrap();
+    assert!(deps.cycles.is_empty());
+
+    assert!(report.hotspots.is_none());
+    assert!(report.knowledge.is_none());
+    assert!(report.temporal_coupling.is_none());
+    assert!(report.age.is_none());
+    assert_eq!(report.skipped.len(), 1);
+    assert!(report.skipped[0].contains("not a git repository"));
+
+    let value = serde_json::to_value(&report).unwrap();
+    assert!(value["smells"]["entries"].is_array());
+    assert!(value["dependencies"]["cycles"].is_array());
+    assert!(value.get("hotspots").is_none());
+    assert_eq!(value["skipped"].as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn build_report_with_git_sections_in_repo() {
+    let dir = tempfile::tempdir().unwrap();
+    let repo = git2::Repository::init(dir.path()).unwrap();
+    let sig =
+        git2::Signature::new("Test", "test@test.com", &git2::Time::new(1_700_000_000, 0)).unwrap();
+    fs::write(
+        dir.path().join("main.rs"),
+        "fn main() {\n    let x = 1;\n    if x > 0 {\n        println!(\"{}\", x);\n    }\n}\n",
+    )
+    .unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(std::path::Path::new("main.rs")).unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    repo.commit(Some("HEAD"), &sig, &sig, "initial", &tree, &[])
+        .unwrap();
+
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(dir.path(), false, &filter);
+    let kcfg = crate::config::KimunConfig::default();
+    let sections = ReportSections::select(&["git".to_string()], &kcfg).unwrap();
+    let report = build_report_with(&cfg, 20, 6, &sections).unwrap();
+
+    assert!(report.skipped.is_empty(), "{:?}", report.skipped);
+    let hot = report.hotspots.as_ref().unwrap();
+    assert_eq!(hot.complexity_metric, "indent");
+    assert_eq!(hot.files.entries.len(), 1);
+    assert_eq!(hot.files.entries[0].commits, 1);
+    let k = report.knowledge.as_ref().unwrap();
+    assert_eq!(k.bus_factor, 1);
+    assert_eq!(k.files.entries[0].owner, "Test");
+    assert!(report.temporal_coupling.is_some());
+    let age = report.age.as_ref().unwrap();
+    assert_eq!(age.entries.len(), 1);
+    assert_eq!(age.entries[0].status, "FROZEN");
 }
@@ -0,0 +1,318 @@
+//! Optional sections of the combined report, selected with `--with`.
+//!
+//! Smells and dependencies are static analyses and always succeed. The git
+//! history sections (hotspots, knowledge, temporal coupling, age) need a
+//! repository: outside one they are skipped with a single note, and any
+//! other git failure skips only the affected section. Skips are recorded in
+//! `ProjectReport::skipped` and echoed to stderr, never turned into errors.
+
+use std::error::Error;
+
+use crate::age::analyzer::AgeThresholds;
+use crate::config::KimunConfig;
+use crate::git::GitRepo;
+use crate::smells::SmellKind;
+use crate::walk::WalkConfig;
+use crate::{age, deps, hotspots, knowledge, smells, tc};
+
+use super::data::*;
+
+/// Values accepted by `--with`: one per section, plus `git` (all history
+/// sections) and `all`.
+pub const SECTION_NAMES: &[&str] = &[
+    "smells",
+    "deps",
+    "hotspots",
+    "knowledge",
+    "tc",
+    "age",
+    "git",
+    "all",
+];
+
+/// Section description for code smells.
+pub const DESC_SMELLS: &str = "Code smells per file: long functions, long parameter lists, \
+    TODO/FIXME debt, magic numbers, and commented-out code. \
+    Each smell is a local, fixable signal of code that is harder to maintain than it needs to be.";
+
+/// Section description for internal dependencies.
+pub const DESC_DEPS: &str = "Internal module dependencies. Fan-in counts the project files that \
+    import a file; fan-out counts the project files it imports. \
+    Files in a dependency cycle cannot be changed, tested, or reused in isolation.";
+
+/// Section description for hotspots.
+pub const DESC_HOTSPOTS: &str = "Hotspots combine change frequency with complexity: \
+    score = commits \u{d7} complexity. Files that are both complex and frequently changed \
+    are where refactoring pays off most. Reference: Tornhill, A. (2015) Your Code as a Crime Scene.";
+
+/// Section description for knowledge maps.
+pub const DESC_KNOWLEDGE: &str = "Code ownership from git blame. \
+    Risk: critical (one person owns >80%), high (60-80%), medium (2-3 people own >80%), low. \
+    Knowledge loss marks files whose primary owner has no recent commits. \
+    The bus factor is the minimum number of contributors owning 80% of the code.";
+
+/// Section description for temporal coupling.
+pub const DESC_TC: &str = "Temporal coupling: pairs of files that change in the same commits. \
+    Strength = shared commits / min(commits of either file). \
+    Strong coupling (>= 0.5) between unrelated modules suggests a hidden dependency.";
+
+/// Section description for code age.
+pub const DESC_AGE: &str = "Code age: days since each file was last changed. \
+    Active files changed within the active threshold (default 90 days), frozen ones not for longer \
+    than the frozen threshold (default 365 days), and stale ones in between. Oldest first.";
+
+/// Optional report sections and the settings each one runs with.
+/// `None`/`false` leaves the section out of the report.
+#[derive(Default)]
+pub struct ReportSections {
+    /// Code smells, with (max function lines, max parameters).
+    pub smells: Option<(usize, usize)>,
+    pub deps: bool,
+    /// Hotspots, with the complexity metric (`indent`, `cycom`, `cogcom`).
+    pub hotspots: Option<String>,
+    pub knowledge: bool,
+    /// Temporal coupling, with the minimum commits per file.
+    pub tc: Option<usize>,
+    pub age: Option<AgeThresholds>,
+}
+
+impl ReportSections {
+    /// Whether any selected section reads git history or blame.
+    pub fn reads_git(&self) -> bool {
+        self.hotspots.is_some() || self.knowledge || self.tc.is_some() || self.age.is_some()
+    }
+
+    /// Select sections by `--with` name, taking their settings from `kcfg`.
+    pub fn select(names: &[String], kcfg: &KimunConfig) -> Result<Self, Box<dyn Error>> {
+        let mut sections = Self::default();
+        for name in names {
+            let (all, git) = (name == "all", name == "all" || name == "git");
+            if !SECTION_NAMES.contains(&name.as_str()) {
+                return Err(format!(
+                    "unknown report section '{name}' (expected one of: {})",
+                    SECTION_NAMES.join(", ")
+                )
+                .into());
+            }
+            if all || name == "smells" {
+                sections.smells = Some((
+                    kcfg.smells.resolve_max_lines(None),
+                    kcfg.smells.resolve_max_params(None),
+                ));
+            }
+            if all || name == "deps" {
+                sections.deps = true;
+            }
+            if git || name == "hotspots" {
+                sections.hotspots = Some(kcfg.hotspots.resolve_complexity(None));
+            }
+            if git || name == "knowledge" {
+                sections.knowledge = true;
+            }
+            if git || name == "tc" {
+                sections.tc = Some(kcfg.tc.resolve_min_degree(None));
+            }
+            if git || name == "age" {
+                sections.age = Some(AgeThresholds {
+                    active_days: kcfg.age.resolve_active_days(None),
+                    frozen_days: kcfg.age.resolve_frozen_days(None),
+                });
+            }
+        }
+        Ok(sections)
+    }
+
+    /// True when any section that reads git history is selected.
+    fn wants_git(&self) -> bool {
+        self.hotspots.is_some() || self.knowledge || self.tc.is_some() || self.age.is_some()
+    }
+}
+
+/// Compute the selected sections and store them in `report`.
+pub(super) fn add_sections(
+    report: &mut ProjectReport,
+    cfg: &WalkConfig<'_>,
+    sections: &ReportSections,
+    top: usize,
+) {
+    if let Some((max_lines, max_params)) = sections.smells {
+        report.smells = Some(smells_section(cfg, max_lines, max_params, top));
+    }
+    if sections.deps {
+        report.dependencies = Some(deps_section(cfg, top));
+    }
+    if !sections.wants_git() {
+        return;
+    }
+    if let Err(e) = GitRepo::open(cfg.path) {
+        skip(
+            report,
+            format!("git sections skipped: not a git repository (or any parent): {e}"),
+        );
+        return;
+    }
+    if let Some(metric) = &sections.hotspots {
+        match hotspots_section(cfg, metric, top) {
+            Ok(s) => report.hotspots = Some(s),
+            Err(e) => skip(report, format!("hotspots skipped: {e}")),
+        }
+    }
+    if sections.knowledge {
+        match knowledge_section(cfg, top) {
+            Ok(s) => report.knowledge = Some(s),
+            Err(e) => skip(report, format!("knowledge skipped: {e}")),
+        }
+    }
+    if let Some(min_degree) = sections.tc {
+        match tc_section(cfg, min_degree, top) {
+            Ok(s) => report.temporal_coupling = Some(s),
+            Err(e) => skip(report, format!("temporal coupling skipped: {e}")),
+        }
+    }
+    if let Some(thresholds) = &sections.age {
+        match age_section(cfg, thresholds, top) {
+            Ok(s) => report.age = Some(s),
+            Err(e) => skip(report, format!("age skipped: {e}")),
+        }
+    }
+}
+
+/// Record a skipped section and tell the user on stderr.
+fn skip(report: &mut ProjectReport, note: String) {
+    eprintln!("warning: {note}");
+    report.skipped.push(note);
+}
+
+/// Build a section from already-sorted entries, truncated to `top`.
+fn section<T>(description: &'static str, mut entries: Vec<T>, top: usize) -> SectionResult<T> {
+    let total_count = entries.len();
+    entries.truncate(top);
+    SectionResult {
+        description,
+        total_count,
+        entries,
+    }
+}
+
+fn smells_section(
+    cfg: &WalkConfig<'_>,
+    max_lines: usize,
+    max_params: usize,
+    top: usize,
+) -> SectionResult<SmellsEntry> {
+    let entries = smells::analyze_project(cfg, max_lines, max_params)
+        .into_iter()
+        .filter(|f| f.total > 0)
+        .map(|f| {
+            let count = |kind| f.smells.smells.iter().filter(|s| s.kind == kind).count();
+            SmellsEntry {
+                path: f.path.display().to_string(),
+                total: f.total,
+                long_functions: count(SmellKind::LongFunction),
+                long_params: count(SmellKind::LongParameterList),
+                todo_debt: count(SmellKind::TodoDebt),
+                magic_numbers: count(SmellKind::MagicNumber),
+                commented_code: count(SmellKind::CommentedOutCode),
+            }
+        })
+        .collect();
+    section(DESC_SMELLS, entries, top)
+}
+
+fn deps_section(cfg: &WalkConfig<'_>, top: usize) -> DepsSection {
+    let result = deps::analyze_project(cfg, "fan-out");
+    let entries = result
+        .entries
+        .into_iter()
+        .map(|e| DepsEntry {
+            path: e.path.display().to_string(),
+            fan_in: e.fan_in,
+            fan_out: e.fan_out,
+            in_cycle: e.in_cycle,
+        })
+        .collect();
+    DepsSection {
+        files: section(DESC_DEPS, entries, top),
+        cycles: result
+            .cycles
+            .iter()
+            .map(|c| c.iter().map(|p| p.display().to_string()).collect())
+            .collect(),
+    }
+}
+
+fn hotspots_section(
+    cfg: &WalkConfig<'_>,
+    metric: &str,
+    top: usize,
+) -> Result<HotspotsSection, Box<dyn Error>> {
+    let entries = hotspots::analyze_project(cfg, "score", None, metric, "commits")?
+        .into_iter()
+        .map(|h| HotspotEntry {
+            path: h.path.display().to_string(),
+            commits: h.commits,
+            complexity: h.complexity,
+            score: h.score,
+        })
+        .collect();
+    Ok(HotspotsSection {
+        files: section(DESC_HOTSPOTS, entries, top),
+        complexity_metric: metric.to_string(),
+    })
+}
+
+fn knowledge_section(cfg: &WalkConfig<'_>, top: usize) -> Result<KnowledgeSection, Box<dyn Error>> {
+    let map = knowledge::analyze_project(cfg, None)?;
+    let bus_factor = map.bus_factor().factor;
+    let mut files = map.files;
+    knowledge::sort_results(&mut files, "risk");
+    let entries = files
+        .into_iter()
+        .map(|f| KnowledgeEntry {
+            path: f.path.display().to_string(),
+            owner: f.primary_owner,
+            ownership_pct: f.ownership_pct,
+            contributors: f.contributors,
+            risk: f.risk.label().to_string(),
+            knowledge_loss: f.knowledge_loss,
+        })
+        .collect();
+    Ok(KnowledgeSection {
+        files: section(DESC_KNOWLEDGE, entries, top),
+        bus_factor,
+    })
+}
+
+fn tc_section(
+    cfg: &WalkConfig<'_>,
+    min_degree: usize,
+    top: usize,
+) -> Result<SectionResult<CouplingEntry>, Box<dyn Error>> {
+    let entries = tc::analyze_project(cfg, "strength", None, min_degree, None)?
+        .into_iter()
+        .map(|c| CouplingEntry {
+            file_a: c.file_a.display().to_string(),
+            file_b: c.file_b.display().to_string(),
+            shared_commits: c.shared_commits,
+            strength: c.strength,
+            level: c.level.label().to_string(),
+        })
+        .collect();
+    Ok(section(DESC_TC, entries, top))
+}
+
+fn age_section(
+    cfg: &WalkConfig<'_>,
+    thresholds: &AgeThresholds,
+    top: usize,
+) -> Result<SectionResult<AgeEntry>, Box<dyn Error>> {
+    let entries = age::analyze_project(cfg, thresholds, "date", None)?
+        .into_iter()
+        .map(|f| AgeEntry {
+            path: f.path.display().to_string(),
+            age_days: f.age_days,
+            status: f.status.label().to_string(),
+        })
+        .collect();
+    Ok(section(DESC_AGE, entries, top))
+}
@@ -102,8 +102,53 @@ pub fn codeclimate_entry(
     })
 }
 
-/// FNV-1a 64-bit hash, hex-encoded. Used for CodeClimate fingerprints.
-fn fnv1a_hex(s: &str) -> String {
+/// A single CI finding, renderable as a GitHub Actions annotation, a
+/// CodeClimate entry, or a SARIF result.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annotation {
+    /// GitHub level: `"notice"`, `"warning"`, or `"error"`.
+    pub level: &'static str,
+    /// CodeClimate severity: `"info"`, `"minor"`, `"major"`, `"critical"`, or `"blocker"`.
+    pub severity: &'static str,
+    pub file: String,
+    pub line: usize,
+    pub title: &'static str,
+    pub message: String,
+}
+
+/// Emit one GitHub Actions annotation per entry.
+pub fn print_github_annotations(annotations: &[Annotation]) {
+    for a in annotations {
+        github_annotation(a.level, &a.file, a.line, a.title, &a.message);
+    }
+}
+
+/// Build the CodeClimate JSON array for `annotations`.
+pub fn codeclimate_entries(annotations: &[Annotation]) -> Vec<serde_json::Value> {
+    annotations
+        .iter()
+        .map(|a| codeclimate_entry(a.severity, &a.file, a.line, a.title, &a.message))
+        .collect()
+}
+
+/// Emit a CodeClimate JSON array (GitLab Code Quality format) for `annotations`.
+pub fn print_codeclimate_annotations(
+    annotations: &[Annotation],
+) -> Result<(), Box<dyn std::error::Error>> {
+    print_json_stdout(&codeclimate_entries(annotations))
+}
+
+/// Emit a SARIF 2.1.0 log for `annotations`, all filed under `rule`.
+pub fn print_sarif_annotations(
+    rule: &crate::sarif::Rule,
+    annotations: &[Annotation],
+) -> Result<(), Box<dyn std::error::Error>> {
+    println!("{}", crate::sarif::format_annotations(rule, annotations)?);
+    Ok(())
+}
+
+/// FNV-1a 64-bit hash, hex-encoded. Used for CodeClimate and SARIF fingerprints.
+pub(crate) fn fnv1a_hex(s: &str) -> String {
     let mut hash: u64 = 0xcbf29ce484222325;
     for byte in s.bytes() {
         hash ^= byte as u64;
@@ -190,23 +235,46 @@ pub fn max_path_width<'a>(paths: impl Iterator<Item = &'a Path>, min: usize) ->
         .max(min)
 }
 
+/// Escape backslashes and pipe characters in file paths so markdown tables
+/// render correctly. Backslashes must be escaped first to avoid double-escaping.
+pub fn escape_md(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('|', "\\|")
+}
+
 /// Print a horizontal separator of box-drawing chars.
 pub fn separator(width: usize) -> String {
     "\u{2500}".repeat(width)
 }
 
+/// Serialize to a pretty JSON string.
+pub fn json_string(value: &impl Serialize) -> Result<String, Box<dyn std::error::Error>> {
+    Ok(serde_json::to_string_pretty(value)?)
+}
+
 /// Serialize to pretty JSON and print to stdout.
 pub fn print_json_stdout(value: &impl Serialize) -> Result<(), Box<dyn std::error::Error>> {
-    println!("{}", serde_json::to_string_pretty(value)?);
+    println!("{}", json_string(value)?);
     Ok(())
 }
 
+/// Note for a git-history analysis that came back empty because `--since`
+/// or the `--range`/`--until`/`--author`/`--path` filters left nothing to
+/// analyze. `None` leaves it to the report to say the result is empty.
+pub fn empty_history_note(since: bool, filtered: bool) -> Option<&'static str> {
+    if since {
+        Some("Nothing found in the specified time range.")
+    } else if filtered {
+        Some("Nothing matches the --range/--until/--author/--path filters.")
+    } else {
+        None
+    }
+}
+
 /// Truncate results to `top` and dispatch to the appropriate output function
 /// based on `OutputMode`.
 ///
-/// For `OutputMode::Github` and `OutputMode::Codeclimate`, returns an error —
-/// modules that support CI formats (cycom, cogcom, smells) handle them before
-/// calling this helper.
+/// For the CI formats (`Github`, `Codeclimate`, `Sarif`), returns an error —
+/// modules that support them handle them before calling this helper.
 pub fn output_results<T>(
     results: &mut Vec<T>,
     top: usize,
@@ -227,8 +295,11 @@ pub fn output_results<T>(
             Ok(())
         }
         crate::cli::OutputMode::Json => print_json_fn(results),
-        crate::cli::OutputMode::Github | crate::cli::OutputMode::Codeclimate => {
-            Err(crate::cli::ERR_CI_FORMAT_ONLY.into())
+        crate::cli::OutputMode::Github
+        | crate::cli::OutputMode::Codeclimate
+        | crate::cli::OutputMode::Sarif => Err(crate::cli::ERR_CI_FORMAT_ONLY.into()),
+        crate::cli::OutputMode::Html | crate::cli::OutputMode::Markdown => {
+            Err(crate::cli::ERR_REPORT_FORMAT_ONLY.into())
         }
         crate::cli::OutputMode::Table => {
             print_report_fn(results);
@@ -238,5 +309,6 @@ pub fn output_results<T>(
 }
 
 #[cfg(test)]
+#[allow(clippy::useless_vec)]
 #[path = "report_helpers_test.rs"]
 mod tests;
@@ -37,3 +37,42 @@ fn print_json_stdout_works() {
     let data = vec![1, 2, 3];
     print_json_stdout(&data).unwrap();
 }
+
+#[test]
+fn codeclimate_entries_maps_annotations() {
+    let annotations = vec![Annotation {
+        level: "warning",
+        severity: "major",
+        file: "src/a.rs".to_string(),
+        line: 7,
+        title: "Dependency Cycle",
+        message: "cycle".to_string(),
+    }];
+    let entries = codeclimate_entries(&annotations);
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["severity"], "major");
+    assert_eq!(entries[0]["description"], "cycle");
+    assert_eq!(entries[0]["location"]["path"], "src/a.rs");
+    assert_eq!(entries[0]["location"]["lines"]["begin"], 7);
+    assert_eq!(
+        entries[0]["fingerprint"],
+        fnv1a_hex("src/a.rs:7:Dependency Cycle")
+    );
+    print_github_annotations(&annotations);
+}
+
+#[test]
+fn output_results_rejects_report_only_html() {
+    let mut rows = vec![1, 2, 3];
+    let err = output_results(
+        &mut rows,
+        2,
+        crate::cli::OutputMode::Html,
+        |_| Ok(()),
+        |_| {},
+        |_| {},
+        |_| {},
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), crate::cli::ERR_REPORT_FORMAT_ONLY);
+}
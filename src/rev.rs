@@ -0,0 +1,343 @@
+//! Analysis of a git revision straight from the object database (`--rev`).
+//!
+//! A [`RevTree`] lists the files of a commit's tree and mounts them over the
+//! analyzed path: while it lives, the walker lists that path from the tree
+//! instead of the filesystem, and [`open`] serves blobs for paths under it.
+//! Every static analysis thus runs unchanged on the revision, reports the
+//! same paths as for the working tree, and never extracts the tree or
+//! touches the working tree. Blobs are read on demand, and `.gitignore`
+//! files in the tree apply the way they do in a checkout.
+//!
+//! Git analyses see the revision as well: a [`GitRepo`] opened on a mounted
+//! path walks history and blames files as of the mounted commit.
+//!
+//! A bare repository has no working tree, so analyses of one run on a
+//! mounted tree too: HEAD's unless a revision is given.
+//!
+//! A tree is mounted on the thread that loads it, never process-wide, so
+//! independent callers do not see each other's trees. Worker threads see it
+//! through [`WalkConfig::with_tree`](crate::walk::WalkConfig::with_tree):
+//! [`WalkConfig::par_map`](crate::walk::WalkConfig::par_map) enters the
+//! tree on each worker, which reads blobs through its own repository handle.
+//!
+//! Mounts stack: a tree mounted over a path that is already mounted (e.g.
+//! the ref side of `km score --rev A --trend B`) shadows the earlier one
+//! until it is dropped.
+
+use std::cell::{OnceCell, RefCell};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, Cursor, Read, Seek, SeekFrom};
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, MutexGuard};
+
+use git2::{Oid, Repository};
+use ignore::Match;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+
+use crate::git::GitRepo;
+
+/// Ignore files honored inside a mounted tree, like the filesystem walker does.
+const IGNORE_FILES: &[&str] = &[".gitignore", ".ignore"];
+
+thread_local! {
+    /// Trees mounted on this thread, most recent last.
+    static MOUNTS: RefCell<Vec<Entry>> = const { RefCell::new(Vec::new()) };
+}
+
+/// A tree mounted on one thread, with that thread's handle on its repository.
+struct Entry {
+    mount: Arc<Mount>,
+    /// Opened on the first blob read, so each thread reads on its own.
+    repo: OnceCell<Repository>,
+}
+
+/// A commit's tree mounted over a path.
+struct Mount {
+    /// Path the tree is mounted over, as given for analysis.
+    base: PathBuf,
+    /// Repository-relative path that `base` stands for.
+    prefix: PathBuf,
+    /// Git directory of the repository the tree comes from.
+    git_dir: PathBuf,
+    /// The commit whose tree is mounted.
+    commit: Oid,
+    /// Blob id of every regular file, keyed by repository-relative path.
+    files: BTreeMap<PathBuf, Oid>,
+    /// Ignore rules from the tree's ignore files, deepest directory first.
+    /// Their roots are repository-relative paths under [`IGNORE_ROOT`].
+    ignores: Vec<Gitignore>,
+}
+
+/// Stand-in for the repository root when matching ignore rules, which
+/// need an absolute root.
+const IGNORE_ROOT: &str = "/";
+
+impl Mount {
+    /// Whether ignore files in the tree exclude the repository-relative `path`.
+    fn is_ignored(&self, path: &Path) -> bool {
+        let path = Path::new(IGNORE_ROOT).join(path);
+        for rules in &self.ignores {
+            if !path.starts_with(rules.path()) {
+                continue;
+            }
+            match rules.matched_path_or_any_parents(&path, false) {
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+                Match::None => {}
+            }
+        }
+        false
+    }
+
+    /// The repository-relative path of `path`, if it lies under `base`.
+    fn repo_path(&self, path: &Path) -> Option<PathBuf> {
+        let rel = path.strip_prefix(&self.base).ok()?;
+        Some(self.prefix.join(rel))
+    }
+
+    /// The analyzed path of the repository-relative `path`, under `base`.
+    fn analysis_path(&self, path: &Path) -> PathBuf {
+        match path.strip_prefix(&self.prefix) {
+            Ok(rel) if !rel.as_os_str().is_empty() => self.base.join(rel),
+            _ => self.base.clone(),
+        }
+    }
+}
+
+impl Entry {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        let id = self.mount.files.get(path).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{} is not in the tree", path.display()),
+            )
+        })?;
+        let repo = match self.repo.get() {
+            Some(repo) => repo,
+            None => {
+                let repo = Repository::open(&self.mount.git_dir)
+                    .map_err(|e| io::Error::other(e.to_string()))?;
+                self.repo.get_or_init(|| repo)
+            }
+        };
+        let blob = repo
+            .find_blob(*id)
+            .map_err(|e| io::Error::other(e.to_string()))?;
+        Ok(blob.content().to_vec())
+    }
+}
+
+/// Run `f` on the most recent mount of this thread holding `path`, with
+/// the repository-relative path of `path` in its tree.
+fn with_mount<R>(path: &Path, f: impl FnOnce(&Entry, PathBuf) -> R) -> Option<R> {
+    MOUNTS.with_borrow(|mounts| {
+        let (entry, repo_path) = mounts
+            .iter()
+            .rev()
+            .find_map(|e| Some((e, e.mount.repo_path(path)?)))?;
+        Some(f(entry, repo_path))
+    })
+}
+
+/// Mount `mount` on this thread until the returned guard is dropped.
+fn enter(mount: &Arc<Mount>) -> Entered {
+    MOUNTS.with_borrow_mut(|mounts| {
+        mounts.push(Entry {
+            mount: Arc::clone(mount),
+            repo: OnceCell::new(),
+        })
+    });
+    Entered {
+        mount: Arc::clone(mount),
+        _thread: PhantomData,
+    }
+}
+
+/// Keeps a tree mounted on the thread that entered it.
+struct Entered {
+    mount: Arc<Mount>,
+    /// Unmounting must happen on the same thread, so the guard is `!Send`.
+    _thread: PhantomData<MutexGuard<'static, ()>>,
+}
+
+impl Drop for Entered {
+    fn drop(&mut self) {
+        MOUNTS.with_borrow_mut(|mounts| {
+            if let Some(i) = mounts
+                .iter()
+                .rposition(|e| Arc::ptr_eq(&e.mount, &self.mount))
+            {
+                mounts.remove(i);
+            }
+        });
+    }
+}
+
+/// The tree of one commit, mounted over the analyzed path on the loading
+/// thread until dropped. Hand it to worker threads with
+/// [`WalkConfig::with_tree`](crate::walk::WalkConfig::with_tree).
+pub struct RevTree {
+    mount: Arc<Mount>,
+    commit: String,
+    _entered: Entered,
+}
+
+impl std::fmt::Debug for RevTree {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RevTree")
+            .field("path", &self.mount.base)
+            .field("commit", &self.commit)
+            .finish()
+    }
+}
+
+impl RevTree {
+    /// Resolve `refspec` in the repository holding `path` and mount its
+    /// tree over `path`, which may be any directory or file of the
+    /// repository, or lie inside another mounted tree.
+    pub fn load(path: &Path, refspec: &str) -> Result<Self, Box<dyn Error>> {
+        let repo = GitRepo::open(path)?;
+        let prefix = match mounted_path(path) {
+            Some(repo_path) => repo_path,
+            // A bare repository stands for its whole tree.
+            None if repo.is_bare() => PathBuf::new(),
+            None => repo.walk_prefix(path)?.1,
+        };
+        let (commit, tree_files) = repo.tree_files(refspec)?;
+
+        let mut ignores = Vec::new();
+        for file in &tree_files {
+            let is_ignore_file = file
+                .path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| IGNORE_FILES.contains(&n));
+            if !is_ignore_file {
+                continue;
+            }
+            let dir = Path::new(IGNORE_ROOT).join(file.path.parent().unwrap_or(Path::new("")));
+            let mut builder = GitignoreBuilder::new(dir);
+            let content = repo.blob_content(file.id)?;
+            for line in String::from_utf8_lossy(&content).lines() {
+                // Invalid patterns are skipped, as the filesystem walker does.
+                let _ = builder.add_line(None, line);
+            }
+            ignores.push(builder.build()?);
+        }
+        ignores.sort_by_key(|rules| std::cmp::Reverse(rules.path().components().count()));
+
+        let mount = Arc::new(Mount {
+            base: path.to_path_buf(),
+            prefix,
+            git_dir: repo.git_dir().to_path_buf(),
+            commit: Oid::from_str(&commit)?,
+            files: tree_files.into_iter().map(|f| (f.path, f.id)).collect(),
+            ignores,
+        });
+        Ok(Self {
+            _entered: enter(&mount),
+            mount,
+            commit,
+        })
+    }
+
+    /// Mount the tree `rev` names over `path` or, without `rev`, HEAD's tree
+    /// when `path` is a bare repository, which has no working tree to read.
+    /// `None` when the working tree is analyzed as is, including outside
+    /// any repository.
+    pub fn mount(path: &Path, rev: Option<&str>) -> Result<Option<Self>, Box<dyn Error>> {
+        match rev {
+            Some(rev) => Self::load(path, rev).map(Some),
+            None if GitRepo::open(path).is_ok_and(|r| r.is_bare()) => {
+                Self::load(path, "HEAD").map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// The analyzed path the tree is mounted over; use it as the walk root.
+    pub fn path(&self) -> &Path {
+        &self.mount.base
+    }
+
+    /// Full id of the mounted commit.
+    pub fn commit(&self) -> &str {
+        &self.commit
+    }
+
+    /// Run `f` with the tree mounted on the current thread as well, for
+    /// worker threads of an analysis.
+    pub(crate) fn scope<R>(&self, f: impl FnOnce() -> R) -> R {
+        let _entered = enter(&self.mount);
+        f()
+    }
+}
+
+/// The git directory and mounted commit of the tree holding `path`.
+pub(crate) fn mounted_commit(path: &Path) -> Option<(PathBuf, Oid)> {
+    with_mount(path, |e, _| (e.mount.git_dir.clone(), e.mount.commit))
+}
+
+/// The repository-relative path `path` stands for in its mounted tree.
+pub(crate) fn mounted_path(path: &Path) -> Option<PathBuf> {
+    with_mount(path, |_, repo_path| repo_path)
+}
+
+/// Paths of the files under `dir` in walk order, leaving out files ignored
+/// by the tree's ignore files. `None` when `dir` is not inside a mounted
+/// tree.
+pub(crate) fn files_under(dir: &Path) -> Option<Vec<PathBuf>> {
+    with_mount(dir, |e, repo_dir| {
+        e.mount
+            .files
+            .keys()
+            .filter(|p| p.starts_with(&repo_dir) && !e.mount.is_ignored(p))
+            .map(|p| e.mount.analysis_path(p))
+            .collect()
+    })
+}
+
+/// A readable file: a blob of a mounted tree or a file on disk.
+pub enum Source {
+    Blob(Cursor<Vec<u8>>),
+    File(File),
+}
+
+impl Read for Source {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Source::Blob(c) => c.read(buf),
+            Source::File(f) => f.read(buf),
+        }
+    }
+}
+
+impl Seek for Source {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        match self {
+            Source::Blob(c) => c.seek(pos),
+            Source::File(f) => f.seek(pos),
+        }
+    }
+}
+
+/// Open `path` for reading, from its mounted tree when it lies in one and
+/// from disk otherwise.
+pub fn open(path: &Path) -> io::Result<Source> {
+    match with_mount(path, |e, repo_path| e.read(&repo_path)) {
+        Some(blob) => Ok(Source::Blob(Cursor::new(blob?))),
+        None => Ok(Source::File(File::open(path)?)),
+    }
+}
+
+/// Read the whole file at `path` as UTF-8, like [`std::fs::read_to_string`].
+pub fn read_to_string(path: &Path) -> io::Result<String> {
+    io::read_to_string(open(path)?)
+}
+
+#[cfg(test)]
+#[path = "rev_test.rs"]
+mod tests;
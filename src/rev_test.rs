@@ -0,0 +1,231 @@
+use super::*;
+use crate::loc::language::LanguageSpec;
+use crate::walk::{self, ExcludeFilter, WalkConfig};
+use git2::Repository;
+use std::fs;
+
+fn commit_files(repo: &Repository, files: &[(&str, &str)]) {
+    let sig = git2::Signature::now("Test", "test@test.com").unwrap();
+    let mut index = repo.index().unwrap();
+    for (path, content) in files {
+        let full_path = repo.workdir().unwrap().join(path);
+        fs::create_dir_all(full_path.parent().unwrap()).unwrap();
+        fs::write(&full_path, content).unwrap();
+        index.add_path(Path::new(path)).unwrap();
+    }
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+    repo.commit(Some("HEAD"), &sig, &sig, "change", &tree, &parents)
+        .unwrap();
+}
+
+/// Two commits: the second adds `src/b.rs` and edits `src/a.rs`. The
+/// working tree then diverges from HEAD without committing.
+fn sample_repo() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    let repo = Repository::init(dir.path()).unwrap();
+    commit_files(
+        &repo,
+        &[
+            ("src/a.rs", "fn a() {}\n"),
+            (".gitignore", "gen/\n"),
+            ("gen/out.rs", "fn generated() {}\n"),
+        ],
+    );
+    commit_files(
+        &repo,
+        &[
+            ("src/a.rs", "fn a() {\n    1;\n}\n"),
+            ("src/b.rs", "fn b() {}\n"),
+            ("src/b_test.rs", "fn t() {}\n"),
+            ("vendor/v.rs", "fn v() {}\n"),
+        ],
+    );
+    fs::write(dir.path().join("src/a.rs"), "fn changed() {}\n").unwrap();
+    fs::write(dir.path().join("src/untracked.rs"), "fn u() {}\n").unwrap();
+    dir
+}
+
+fn rel_names(tree: &RevTree, files: &[(PathBuf, &LanguageSpec)]) -> Vec<String> {
+    files
+        .iter()
+        .map(|(p, _)| {
+            p.strip_prefix(tree.path())
+                .unwrap()
+                .to_string_lossy()
+                .replace('\\', "/")
+        })
+        .collect()
+}
+
+#[test]
+fn reads_committed_content_not_the_working_tree() {
+    let dir = sample_repo();
+    let tree = RevTree::load(dir.path(), "HEAD").unwrap();
+    assert_eq!(tree.commit().len(), 40);
+    assert_eq!(tree.path(), dir.path());
+    let content = read_to_string(&tree.path().join("src/a.rs")).unwrap();
+    assert_eq!(content, "fn a() {\n    1;\n}\n");
+
+    let older = RevTree::load(dir.path(), "HEAD~1").unwrap();
+    let content = read_to_string(&older.path().join("src/a.rs")).unwrap();
+    assert_eq!(content, "fn a() {}\n");
+    assert!(open(&older.path().join("src/b.rs")).is_err());
+    drop(older);
+    assert!(
+        open(&dir.path().join("src/b.rs")).is_ok(),
+        "HEAD shows again"
+    );
+}
+
+#[test]
+fn walker_lists_the_tree_with_its_rules() {
+    let dir = sample_repo();
+    let tree = RevTree::load(dir.path(), "HEAD").unwrap();
+    let filter = ExcludeFilter::default();
+    let files = walk::source_files(tree.path(), true, &filter);
+    assert_eq!(
+        rel_names(&tree, &files),
+        ["src/a.rs", "src/b.rs", "vendor/v.rs"],
+        "gitignored, test, and untracked files are left out"
+    );
+
+    let filter = ExcludeFilter::new(&[], &[], &["vendor".to_string()], &[]);
+    let files = walk::source_files(tree.path(), false, &filter);
+    assert_eq!(
+        rel_names(&tree, &files),
+        ["src/a.rs", "src/b.rs", "src/b_test.rs"]
+    );
+}
+
+#[test]
+fn subdirectory_maps_into_the_tree() {
+    let dir = sample_repo();
+    let tree = RevTree::load(&dir.path().join("src"), "HEAD").unwrap();
+    let filter = ExcludeFilter::default();
+    let files = walk::source_files(tree.path(), false, &filter);
+    assert_eq!(rel_names(&tree, &files), ["a.rs", "b.rs", "b_test.rs"]);
+}
+
+#[test]
+fn analyses_run_on_the_mounted_tree() {
+    let dir = sample_repo();
+    let tree = RevTree::load(dir.path(), "HEAD~1").unwrap();
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(tree.path(), false, &filter);
+    let summary = crate::loc::analyze_project(&cfg);
+    let code: usize = summary.languages.iter().map(|l| l.code).sum();
+    assert_eq!(code, 1, "only src/a.rs at HEAD~1; gen/ is ignored");
+}
+
+#[test]
+fn workers_read_the_tree_they_are_given() {
+    let dir = sample_repo();
+    let tree = RevTree::load(dir.path(), "HEAD").unwrap();
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(tree.path(), false, &filter)
+        .with_jobs(4)
+        .with_tree(Some(&tree));
+    let summary = crate::loc::analyze_project(&cfg);
+    let code: usize = summary.languages.iter().map(|l| l.code).sum();
+    assert_eq!(code, 5, "committed src/a.rs, src/b.rs and vendor/v.rs");
+}
+
+#[test]
+fn a_tree_is_mounted_only_on_its_thread() {
+    let dir = sample_repo();
+    let tree = RevTree::load(dir.path(), "HEAD").unwrap();
+    let path = tree.path().join("src/a.rs");
+    let elsewhere = std::thread::scope(|s| s.spawn(|| read_to_string(&path)).join().unwrap());
+    assert_eq!(elsewhere.unwrap(), "fn changed() {}\n");
+    assert_eq!(read_to_string(&path).unwrap(), "fn a() {\n    1;\n}\n");
+}
+
+#[test]
+fn a_tree_can_be_loaded_from_inside_another() {
+    let dir = sample_repo();
+    let head = RevTree::load(&dir.path().join("src"), "HEAD").unwrap();
+    let older = RevTree::load(head.path(), "HEAD~1").unwrap();
+    assert_ne!(older.commit(), head.commit());
+    let content = read_to_string(&older.path().join("a.rs")).unwrap();
+    assert_eq!(content, "fn a() {}\n");
+}
+
+#[test]
+fn dropping_the_tree_unmounts_it() {
+    let dir = sample_repo();
+    let tree = RevTree::load(dir.path(), "HEAD").unwrap();
+    let path = tree.path().join("src/a.rs");
+    assert_eq!(read_to_string(&path).unwrap(), "fn a() {\n    1;\n}\n");
+    drop(tree);
+    assert_eq!(read_to_string(&path).unwrap(), "fn changed() {}\n");
+    assert!(files_under(dir.path()).is_none());
+}
+
+#[test]
+fn a_single_file_can_be_mounted() {
+    let dir = sample_repo();
+    let path = dir.path().join("src/a.rs");
+    let tree = RevTree::load(&path, "HEAD~1").unwrap();
+    let filter = ExcludeFilter::default();
+    let files = walk::source_files(&path, false, &filter);
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].0, path);
+    assert_eq!(read_to_string(tree.path()).unwrap(), "fn a() {}\n");
+}
+
+#[test]
+fn unknown_ref_is_an_error() {
+    let dir = sample_repo();
+    let err = RevTree::load(dir.path(), "no-such-branch").err().unwrap();
+    assert!(err.to_string().contains("cannot resolve ref"), "{err}");
+}
+
+#[test]
+fn git_history_reads_up_to_the_mounted_commit() {
+    let dir = sample_repo();
+    let tree = RevTree::load(dir.path(), "HEAD~1").unwrap();
+    let repo = GitRepo::open(tree.path()).unwrap();
+    let freqs = repo.file_frequencies(None).unwrap();
+    let a = freqs
+        .iter()
+        .find(|f| f.path == Path::new("src/a.rs"))
+        .unwrap();
+    assert_eq!(a.commits, 1, "the edit in HEAD is not seen");
+    assert!(!freqs.iter().any(|f| f.path == Path::new("src/b.rs")));
+    let blames = repo.blame_file(Path::new("src/a.rs")).unwrap();
+    assert_eq!(blames[0].lines, 1);
+}
+
+#[test]
+fn a_bare_repository_mounts_head() {
+    let dir = sample_repo();
+    let bare_dir = tempfile::tempdir().unwrap();
+    let bare = bare_dir.path().join("mirror.git");
+    git2::build::RepoBuilder::new()
+        .bare(true)
+        .clone(dir.path().to_str().unwrap(), &bare)
+        .unwrap();
+
+    assert!(RevTree::mount(dir.path(), None).unwrap().is_none());
+    let tree = RevTree::mount(&bare, None).unwrap().unwrap();
+    assert_eq!(tree.path(), bare);
+    let filter = ExcludeFilter::default();
+    let files = walk::source_files(tree.path(), true, &filter);
+    assert_eq!(
+        rel_names(&tree, &files),
+        ["src/a.rs", "src/b.rs", "vendor/v.rs"]
+    );
+    let repo = GitRepo::open(tree.path()).unwrap();
+    let (walk_root, prefix) = repo.walk_prefix(tree.path()).unwrap();
+    assert_eq!(
+        (walk_root.as_path(), prefix.as_os_str()),
+        (bare.as_path(), "".as_ref())
+    );
+
+    let older = RevTree::mount(&bare, Some("HEAD~1")).unwrap().unwrap();
+    let content = read_to_string(&older.path().join("src/a.rs")).unwrap();
+    assert_eq!(content, "fn a() {}\n");
+}
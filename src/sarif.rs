@@ -0,0 +1,290 @@
+//! SARIF 2.1.0 log builder shared by findings-producing commands.
+//!
+//! Each command describes its rules (one per smell kind, complexity level,
+//! or duplication severity) and its findings; this module assembles the
+//! single-run log with rule metadata, physical locations with line regions,
+//! related locations, and stable `partialFingerprints`.
+//!
+//! Result levels are taken from the rule's `defaultConfiguration`, so a
+//! finding's severity is decided once, where its rule is declared.
+//! Commands that describe their findings as CI annotations (deps, hotspots,
+//! mi, miv, knowledge) file them under one rule and keep each annotation's
+//! own level.
+
+use std::collections::HashMap;
+
+use serde_json::{Value, json};
+
+use crate::report_helpers::{Annotation, PerFunctionFile, PerFunctionRow, fnv1a_hex};
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const INFORMATION_URI: &str = "https://github.com/lnds/kimun";
+/// Key under which kimun's own fingerprint is published.
+const FINGERPRINT_KEY: &str = "kimun/v1";
+
+/// Metadata for one reporting rule.
+pub struct Rule {
+    /// Stable identifier, e.g. `"smells/long-function"`.
+    pub id: String,
+    /// PascalCase name shown by some viewers, e.g. `"LongFunction"`.
+    pub name: String,
+    pub short_description: String,
+    pub full_description: String,
+    /// SARIF level: `"error"`, `"warning"`, or `"note"`.
+    pub level: &'static str,
+}
+
+/// A file region: 1-based inclusive line range.
+pub struct Region {
+    pub path: String,
+    pub start_line: usize,
+    pub end_line: Option<usize>,
+}
+
+/// A single reported finding.
+pub struct Finding {
+    pub rule_id: String,
+    pub message: String,
+    pub location: Region,
+    /// Other places involved in the finding, each with its own message.
+    pub related: Vec<(Region, String)>,
+    /// Line-independent identity of the finding; hashed into the
+    /// fingerprint so results survive unrelated edits above them. It does
+    /// not include the rule level, so a function that crosses a level
+    /// boundary stays the same result.
+    pub fingerprint: String,
+    /// Level of this result when it differs from its rule's default.
+    pub level: Option<&'static str>,
+}
+
+/// Turn a walk path into a SARIF artifact URI: forward slashes, no `./`.
+pub fn artifact_uri(path: &str) -> String {
+    let uri = path.replace('\\', "/");
+    uri.strip_prefix("./").unwrap_or(&uri).to_string()
+}
+
+fn physical_location(region: &Region) -> Value {
+    let uri = artifact_uri(&region.path);
+    let mut artifact = json!({ "uri": uri });
+    if !uri.starts_with('/') {
+        artifact["uriBaseId"] = json!("%SRCROOT%");
+    }
+    let mut lines = json!({ "startLine": region.start_line.max(1) });
+    if let Some(end) = region.end_line {
+        lines["endLine"] = json!(end.max(region.start_line).max(1));
+    }
+    json!({ "physicalLocation": { "artifactLocation": artifact, "region": lines } })
+}
+
+fn rule_json(rule: &Rule) -> Value {
+    json!({
+        "id": rule.id,
+        "name": rule.name,
+        "shortDescription": { "text": rule.short_description },
+        "fullDescription": { "text": rule.full_description },
+        "defaultConfiguration": { "level": rule.level },
+        "helpUri": INFORMATION_URI,
+    })
+}
+
+/// Build the SARIF log. Findings whose `rule_id` is not in `rules` are
+/// still emitted, at level `warning`.
+pub fn build_log(rules: &[Rule], findings: &[Finding]) -> Value {
+    let index: HashMap<&str, usize> = rules
+        .iter()
+        .enumerate()
+        .map(|(i, r)| (r.id.as_str(), i))
+        .collect();
+    // Identical fingerprint keys (e.g. two magic numbers in one file) are
+    // told apart by their occurrence order.
+    let mut seen: HashMap<String, usize> = HashMap::new();
+
+    let results: Vec<Value> = findings
+        .iter()
+        .map(|f| {
+            let occurrence = seen.entry(f.fingerprint.clone()).or_default();
+            *occurrence += 1;
+            let key = format!("{}#{occurrence}", f.fingerprint);
+
+            let mut result = json!({
+                "ruleId": f.rule_id,
+                "level": f.level.unwrap_or_else(|| {
+                    index.get(f.rule_id.as_str()).map_or("warning", |&i| rules[i].level)
+                }),
+                "message": { "text": f.message },
+                "locations": [physical_location(&f.location)],
+                "partialFingerprints": { FINGERPRINT_KEY: fnv1a_hex(&key) },
+            });
+            if let Some(&i) = index.get(f.rule_id.as_str()) {
+                result["ruleIndex"] = json!(i);
+            }
+            if !f.related.is_empty() {
+                let related: Vec<Value> = f
+                    .related
+                    .iter()
+                    .enumerate()
+                    .map(|(id, (region, message))| {
+                        let mut loc = physical_location(region);
+                        loc["id"] = json!(id + 1);
+                        loc["message"] = json!({ "text": message });
+                        loc
+                    })
+                    .collect();
+                result["relatedLocations"] = json!(related);
+            }
+            result
+        })
+        .collect();
+
+    json!({
+        "$schema": SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "kimun",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": INFORMATION_URI,
+                    "rules": rules.iter().map(rule_json).collect::<Vec<_>>(),
+                }
+            },
+            "results": results,
+        }]
+    })
+}
+
+/// Serialize the SARIF log to a pretty JSON string.
+pub fn format_log(
+    rules: &[Rule],
+    findings: &[Finding],
+) -> Result<String, Box<dyn std::error::Error>> {
+    crate::report_helpers::json_string(&build_log(rules, findings))
+}
+
+/// Turn a level label ("very complex") into a rule-id slug ("very-complex").
+pub fn slug(label: &str) -> String {
+    label.replace(' ', "-")
+}
+
+/// One complexity level as a SARIF rule.
+pub struct LevelRule {
+    /// Label as produced by the level's `as_str()`.
+    pub label: &'static str,
+    /// Human-readable complexity range, e.g. `"15-24"`.
+    pub range: &'static str,
+    pub level: &'static str,
+}
+
+/// "very complex" → "VeryComplex".
+fn pascal_case(label: &str) -> String {
+    label
+        .split(' ')
+        .flat_map(|w| {
+            let mut chars = w.chars();
+            chars
+                .next()
+                .map(|c| c.to_ascii_uppercase())
+                .into_iter()
+                .chain(chars)
+        })
+        .collect()
+}
+
+/// Rules for a complexity metric: one per level, with ids `{prefix}/{slug}`.
+pub fn complexity_rules(prefix: &str, metric: &str, levels: &[LevelRule]) -> Vec<Rule> {
+    levels
+        .iter()
+        .map(|l| Rule {
+            id: format!("{prefix}/{}", slug(l.label)),
+            name: format!("{}Complexity{}", pascal_case(metric), pascal_case(l.label)),
+            short_description: format!("Function with {} {metric} complexity", l.label),
+            full_description: format!(
+                "The function's {metric} complexity is in the {} range ({}). \
+                 Higher complexity makes code harder to understand, test, and change.",
+                l.label, l.range
+            ),
+            level: l.level,
+        })
+        .collect()
+}
+
+/// Findings for every function at or above `min_complexity`, each filed
+/// under the rule for its complexity level.
+pub fn complexity_findings<F: PerFunctionFile>(
+    files: &[F],
+    min_complexity: usize,
+    prefix: &str,
+    metric: &str,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for f in files {
+        let path = f.path_str();
+        for func in f.rows() {
+            if func.complexity() < min_complexity {
+                continue;
+            }
+            findings.push(Finding {
+                rule_id: format!("{prefix}/{}", slug(func.level_str())),
+                message: format!(
+                    "function '{}' has {metric} complexity {} (threshold: {min_complexity})",
+                    func.name(),
+                    func.complexity(),
+                ),
+                location: Region {
+                    path: path.clone(),
+                    start_line: func.start_line(),
+                    end_line: None,
+                },
+                related: Vec::new(),
+                fingerprint: format!("{prefix}:{path}:{}", func.name()),
+                level: None,
+            });
+        }
+    }
+    findings
+}
+
+/// Map a GitHub annotation level to the SARIF level.
+fn annotation_level(level: &str) -> &'static str {
+    match level {
+        "error" => "error",
+        "warning" => "warning",
+        _ => "note",
+    }
+}
+
+/// Findings for CI annotations, all filed under `rule_id` at their own
+/// level. Annotations are file-level, so the fingerprint is the rule and
+/// the file.
+pub fn annotation_findings(rule_id: &str, annotations: &[Annotation]) -> Vec<Finding> {
+    annotations
+        .iter()
+        .map(|a| Finding {
+            rule_id: rule_id.to_string(),
+            message: a.message.clone(),
+            location: Region {
+                path: a.file.clone(),
+                start_line: a.line,
+                end_line: None,
+            },
+            related: Vec::new(),
+            fingerprint: format!("{rule_id}:{}", artifact_uri(&a.file)),
+            level: Some(annotation_level(a.level)),
+        })
+        .collect()
+}
+
+/// Build the SARIF log for CI annotations filed under `rule`.
+pub fn format_annotations(
+    rule: &Rule,
+    annotations: &[Annotation],
+) -> Result<String, Box<dyn std::error::Error>> {
+    format_log(
+        std::slice::from_ref(rule),
+        &annotation_findings(&rule.id, annotations),
+    )
+}
+
+#[cfg(test)]
+#[path = "sarif_test.rs"]
+mod tests;
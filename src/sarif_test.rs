@@ -0,0 +1,211 @@
+use super::*;
+
+fn rules() -> Vec<Rule> {
+    vec![
+        Rule {
+            id: "demo/a".to_string(),
+            name: "DemoA".to_string(),
+            short_description: "A".to_string(),
+            full_description: "Rule A".to_string(),
+            level: "error",
+        },
+        Rule {
+            id: "demo/b".to_string(),
+            name: "DemoB".to_string(),
+            short_description: "B".to_string(),
+            full_description: "Rule B".to_string(),
+            level: "note",
+        },
+    ]
+}
+
+fn finding(rule: &str, path: &str, line: usize, key: &str) -> Finding {
+    Finding {
+        rule_id: rule.to_string(),
+        message: format!("{rule} at {line}"),
+        location: Region {
+            path: path.to_string(),
+            start_line: line,
+            end_line: None,
+        },
+        related: Vec::new(),
+        fingerprint: key.to_string(),
+        level: None,
+    }
+}
+
+#[test]
+fn log_has_sarif_envelope_and_rules() {
+    let log = build_log(&rules(), &[]);
+    assert_eq!(log["version"], "2.1.0");
+    assert!(log["$schema"].as_str().unwrap().contains("sarif-2.1.0"));
+    let driver = &log["runs"][0]["tool"]["driver"];
+    assert_eq!(driver["name"], "kimun");
+    assert_eq!(driver["version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(driver["rules"][0]["id"], "demo/a");
+    assert_eq!(driver["rules"][0]["fullDescription"]["text"], "Rule A");
+    assert_eq!(driver["rules"][1]["defaultConfiguration"]["level"], "note");
+    assert_eq!(log["runs"][0]["results"], json!([]));
+}
+
+#[test]
+fn result_takes_level_and_index_from_rule() {
+    let log = build_log(&rules(), &[finding("demo/b", "./src/x.rs", 7, "k")]);
+    let result = &log["runs"][0]["results"][0];
+    assert_eq!(result["ruleId"], "demo/b");
+    assert_eq!(result["ruleIndex"], 1);
+    assert_eq!(result["level"], "note");
+    assert_eq!(result["message"]["text"], "demo/b at 7");
+    let loc = &result["locations"][0]["physicalLocation"];
+    assert_eq!(loc["artifactLocation"]["uri"], "src/x.rs");
+    assert_eq!(loc["artifactLocation"]["uriBaseId"], "%SRCROOT%");
+    assert_eq!(loc["region"]["startLine"], 7);
+    assert!(loc["region"].get("endLine").is_none());
+}
+
+#[test]
+fn unknown_rule_defaults_to_warning() {
+    let log = build_log(&rules(), &[finding("demo/zzz", "a.rs", 1, "k")]);
+    let result = &log["runs"][0]["results"][0];
+    assert_eq!(result["level"], "warning");
+    assert!(result.get("ruleIndex").is_none());
+}
+
+#[test]
+fn absolute_paths_have_no_base_id_and_windows_separators_are_normalized() {
+    let log = build_log(
+        &rules(),
+        &[
+            finding("demo/a", "/abs/a.rs", 1, "1"),
+            finding("demo/a", "src\\win.rs", 1, "2"),
+        ],
+    );
+    let results = &log["runs"][0]["results"];
+    let abs = &results[0]["locations"][0]["physicalLocation"]["artifactLocation"];
+    assert_eq!(abs["uri"], "/abs/a.rs");
+    assert!(abs.get("uriBaseId").is_none());
+    assert_eq!(
+        results[1]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+        "src/win.rs"
+    );
+}
+
+#[test]
+fn fingerprints_ignore_lines_and_separate_repeats() {
+    let fp = |findings: &[Finding]| -> Vec<String> {
+        build_log(&rules(), findings)["runs"][0]["results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| {
+                r["partialFingerprints"][FINGERPRINT_KEY]
+                    .as_str()
+                    .unwrap()
+                    .to_string()
+            })
+            .collect()
+    };
+    let before = fp(&[finding("demo/a", "a.rs", 10, "same")]);
+    let after = fp(&[finding("demo/a", "a.rs", 42, "same")]);
+    assert_eq!(before, after);
+
+    let repeated = fp(&[
+        finding("demo/a", "a.rs", 1, "same"),
+        finding("demo/a", "a.rs", 2, "same"),
+    ]);
+    assert_ne!(repeated[0], repeated[1]);
+    assert_eq!(repeated[0], before[0]);
+}
+
+#[test]
+fn related_locations_carry_ids_messages_and_regions() {
+    let mut f = finding("demo/a", "a.rs", 3, "k");
+    f.location.end_line = Some(9);
+    f.related = vec![(
+        Region {
+            path: "b.rs".to_string(),
+            start_line: 20,
+            end_line: Some(26),
+        },
+        "duplicate 2 of 2".to_string(),
+    )];
+    let log = build_log(&rules(), &[f]);
+    let result = &log["runs"][0]["results"][0];
+    assert_eq!(
+        result["locations"][0]["physicalLocation"]["region"]["endLine"],
+        9
+    );
+    let related = &result["relatedLocations"][0];
+    assert_eq!(related["id"], 1);
+    assert_eq!(related["message"]["text"], "duplicate 2 of 2");
+    assert_eq!(
+        related["physicalLocation"]["artifactLocation"]["uri"],
+        "b.rs"
+    );
+    assert_eq!(related["physicalLocation"]["region"]["startLine"], 20);
+    assert_eq!(related["physicalLocation"]["region"]["endLine"], 26);
+}
+
+#[test]
+fn complexity_rules_cover_each_level() {
+    let levels = [
+        LevelRule {
+            label: "simple",
+            range: "0-4",
+            level: "note",
+        },
+        LevelRule {
+            label: "very complex",
+            range: "15-24",
+            level: "warning",
+        },
+    ];
+    let rules = complexity_rules("cogcom", "cognitive", &levels);
+    assert_eq!(rules.len(), 2);
+    assert_eq!(rules[1].id, "cogcom/very-complex");
+    assert_eq!(rules[1].name, "CognitiveComplexityVeryComplex");
+    assert!(rules[1].full_description.contains("15-24"));
+    assert_eq!(rules[1].level, "warning");
+}
+
+#[test]
+fn annotations_keep_their_own_level_under_one_rule() {
+    let annotation = |level, file: &str| Annotation {
+        level,
+        severity: "minor",
+        file: file.to_string(),
+        line: 1,
+        title: "Hotspot",
+        message: format!("{file} is hot"),
+    };
+    let log: Value = serde_json::from_str(
+        &format_annotations(
+            &rules()[0],
+            &[
+                annotation("warning", "./src/a.rs"),
+                annotation("notice", "src/b.rs"),
+                annotation("error", "src/c.rs"),
+            ],
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    let run = &log["runs"][0];
+    assert_eq!(run["tool"]["driver"]["rules"].as_array().unwrap().len(), 1);
+    let results = run["results"].as_array().unwrap();
+    let levels: Vec<&str> = results
+        .iter()
+        .map(|r| r["level"].as_str().unwrap())
+        .collect();
+    assert_eq!(levels, ["warning", "note", "error"]);
+    assert_eq!(results[0]["ruleId"], "demo/a");
+    assert_eq!(results[0]["message"]["text"], "./src/a.rs is hot");
+    assert_eq!(
+        results[0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+        "src/a.rs"
+    );
+    assert_ne!(
+        results[0]["partialFingerprints"],
+        results[1]["partialFingerprints"]
+    );
+}
@@ -10,6 +10,8 @@ use std::path::PathBuf;
 
 use serde::Serialize;
 
+use super::model::ScoreModel;
+
 /// Letter grade from A++ (97-100) to F-- (0-39).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 pub enum Grade {
@@ -194,6 +196,8 @@ pub struct ProjectScore {
     pub total_loc: usize,
     pub dimensions: Vec<DimensionScore>,
     pub needs_attention: Vec<FileScore>,
+    /// The model the score was computed with.
+    pub model: ScoreModel,
 }
 
 /// Weighted sum of dimension scores. Weights must sum to 1.0 when non-empty.
@@ -0,0 +1,389 @@
+//! Per-directory and per-package score breakdown (`km score --by-dir`,
+//! `--by-package`).
+//!
+//! Files are collected once, as for the project score, then grouped. A
+//! directory covers its whole subtree, so `--by-dir 2` nests `src/score`
+//! under `src`; files directly in the walk root form a `.` group. A package covers the files whose nearest manifest
+//! (`Cargo.toml` with a `[package]`, `package.json`, or `go.mod`) it is;
+//! packages nested inside another package appear as its children but do not
+//! count towards its score. Each group gets its own LOC-weighted dimension
+//! scores.
+//!
+//! Duplicate blocks are attributed to the files holding the second and later
+//! copies, so the duplicated lines of all files add up to the project total.
+
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Component, Path, PathBuf};
+
+use serde::Serialize;
+
+use super::analyzer::{DimensionScore, Grade, compute_project_score, score_to_grade};
+use super::collector::FileMetrics;
+use super::model::ScoreModel;
+use super::scoring::build_dimensions;
+use super::{Collected, collect};
+use crate::rev;
+use crate::walk::WalkConfig;
+
+/// Group name for files outside every package manifest.
+const NO_PACKAGE: &str = "(no package)";
+
+/// How `km score` groups files for a breakdown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Breakdown {
+    /// Directories down to this depth below the walk root (at least 1).
+    Dir(usize),
+    /// Cargo, npm, and Go packages.
+    Package,
+}
+
+/// Score of one directory or package, with its nested groups.
+#[derive(Debug, Clone, Serialize)]
+pub struct GroupScore {
+    /// Directory path, or package name.
+    pub name: String,
+    /// Directory relative to the walk root (`.` for the root itself).
+    pub path: String,
+    pub score: f64,
+    pub grade: Grade,
+    pub files_analyzed: usize,
+    pub total_loc: usize,
+    pub dimensions: Vec<DimensionScore>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<GroupScore>,
+}
+
+impl GroupScore {
+    /// The lowest-scoring dimension, if any.
+    pub fn weakest_dimension(&self) -> Option<&DimensionScore> {
+        self.dimensions
+            .iter()
+            .min_by(|a, b| a.score.total_cmp(&b.score))
+    }
+}
+
+/// Project score broken down into a tree of groups.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScoreBreakdown {
+    /// `dir` or `package`.
+    pub group_by: &'static str,
+    /// Directory depth for `group_by = "dir"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depth: Option<usize>,
+    pub score: f64,
+    pub grade: Grade,
+    pub files_analyzed: usize,
+    pub total_loc: usize,
+    /// Top-level groups, sorted by path.
+    pub groups: Vec<GroupScore>,
+    pub model: ScoreModel,
+}
+
+impl ScoreBreakdown {
+    /// All groups at every level, worst score first.
+    pub fn ranked(&self) -> Vec<&GroupScore> {
+        fn push<'a>(groups: &'a [GroupScore], out: &mut Vec<&'a GroupScore>) {
+            for g in groups {
+                out.push(g);
+                push(&g.children, out);
+            }
+        }
+        let mut out = Vec::new();
+        push(&self.groups, &mut out);
+        out.sort_by(|a, b| a.score.total_cmp(&b.score).then(a.path.cmp(&b.path)));
+        out
+    }
+}
+
+/// A collected file with what grouping needs to know about it.
+struct Entry<'a> {
+    metrics: &'a FileMetrics,
+    /// Path relative to the walk root.
+    rel: PathBuf,
+    normalized_lines: usize,
+    duplicated_lines: usize,
+}
+
+impl Borrow<FileMetrics> for &Entry<'_> {
+    fn borrow(&self) -> &FileMetrics {
+        self.metrics
+    }
+}
+
+/// Score the files under `cfg` and break the score down by `by`.
+pub fn compute_breakdown(
+    cfg: &WalkConfig<'_>,
+    min_lines: usize,
+    model: &ScoreModel,
+    by: Breakdown,
+) -> Result<ScoreBreakdown, Box<dyn std::error::Error>> {
+    if by == Breakdown::Dir(0) {
+        return Err("--by-dir: DEPTH must be at least 1".into());
+    }
+    let Collected {
+        file_metrics,
+        normalized_lines,
+        dup_groups,
+    } = collect(cfg, min_lines, model);
+
+    let mut duplicated: HashMap<&Path, usize> = HashMap::new();
+    for g in &dup_groups {
+        for loc in g.locations.iter().skip(1) {
+            *duplicated.entry(loc.file_path.as_path()).or_default() += g.line_count;
+        }
+    }
+    let entries: Vec<Entry<'_>> = file_metrics
+        .iter()
+        .zip(normalized_lines)
+        .map(|(metrics, normalized_lines)| Entry {
+            metrics,
+            rel: metrics
+                .path
+                .strip_prefix(cfg.path)
+                .unwrap_or(&metrics.path)
+                .to_path_buf(),
+            normalized_lines,
+            duplicated_lines: duplicated.get(metrics.path.as_path()).copied().unwrap_or(0),
+        })
+        .collect();
+    let all: Vec<&Entry<'_>> = entries.iter().collect();
+
+    let groups = match by {
+        Breakdown::Dir(depth) => dir_groups(&all, 0, depth, model),
+        Breakdown::Package => package_groups(&all, cfg.path, model),
+    };
+    let project = score_group(String::new(), String::new(), &all, model);
+    Ok(ScoreBreakdown {
+        group_by: match by {
+            Breakdown::Dir(_) => "dir",
+            Breakdown::Package => "package",
+        },
+        depth: match by {
+            Breakdown::Dir(depth) => Some(depth),
+            Breakdown::Package => None,
+        },
+        score: project.score,
+        grade: project.grade,
+        files_analyzed: project.files_analyzed,
+        total_loc: project.total_loc,
+        groups,
+        model: model.clone(),
+    })
+}
+
+/// Score a set of files the way `compute_score` scores the project.
+fn score_group(name: String, path: String, files: &[&Entry<'_>], model: &ScoreModel) -> GroupScore {
+    let total_loc: usize = files.iter().map(|e| e.metrics.code_lines).sum();
+    let normalized: usize = files.iter().map(|e| e.normalized_lines).sum();
+    let duplicated: usize = files.iter().map(|e| e.duplicated_lines).sum();
+    let dup_percent = if normalized == 0 {
+        0.0
+    } else {
+        duplicated as f64 / normalized as f64 * 100.0
+    };
+    let dimensions = build_dimensions(files, total_loc, dup_percent, model);
+    let score = if files.is_empty() {
+        0.0
+    } else {
+        compute_project_score(&dimensions)
+    };
+    GroupScore {
+        name,
+        path,
+        score,
+        grade: score_to_grade(score),
+        files_analyzed: files.len(),
+        total_loc,
+        dimensions,
+        children: Vec::new(),
+    }
+}
+
+/// Group `files` by their directory component at `level`, recursing until
+/// `depth`. Files directly in the parent directory belong to no child; at
+/// the top level, where there is no parent group, they form a `.` group.
+fn dir_groups(
+    files: &[&Entry<'_>],
+    level: usize,
+    depth: usize,
+    model: &ScoreModel,
+) -> Vec<GroupScore> {
+    let mut by_dir: BTreeMap<String, Vec<&Entry<'_>>> = BTreeMap::new();
+    for &e in files {
+        let dirs: Vec<String> = e
+            .rel
+            .parent()
+            .into_iter()
+            .flat_map(Path::components)
+            .filter_map(|c| match c {
+                Component::Normal(s) => Some(s.to_string_lossy().into_owned()),
+                _ => None,
+            })
+            .collect();
+        if dirs.len() > level {
+            by_dir.entry(dirs[..=level].join("/")).or_default().push(e);
+        } else if level == 0 {
+            by_dir.entry(".".to_string()).or_default().push(e);
+        }
+    }
+    by_dir
+        .into_iter()
+        .map(|(path, members)| {
+            let mut group = score_group(path.clone(), path, &members, model);
+            if level + 1 < depth {
+                group.children = dir_groups(&members, level + 1, depth, model);
+            }
+            group
+        })
+        .collect()
+}
+
+/// Group `files` by their nearest package manifest and nest packages inside
+/// the nearest enclosing package that owns files.
+fn package_groups(files: &[&Entry<'_>], root: &Path, model: &ScoreModel) -> Vec<GroupScore> {
+    let mut finder = PackageFinder::new(root);
+    let mut owned: BTreeMap<Option<PathBuf>, Vec<&Entry<'_>>> = BTreeMap::new();
+    for &e in files {
+        let owner = e.metrics.path.parent().and_then(|d| finder.owner(d));
+        owned.entry(owner).or_default().push(e);
+    }
+
+    // Parent of each package: the nearest enclosing package with files.
+    let mut children: BTreeMap<Option<PathBuf>, Vec<PathBuf>> = BTreeMap::new();
+    for dir in owned.keys().flatten() {
+        let mut parent = dir.parent().and_then(|d| finder.owner(d));
+        while let Some(p) = &parent
+            && !owned.contains_key(&parent)
+        {
+            parent = p.parent().and_then(|d| finder.owner(d));
+        }
+        children.entry(parent).or_default().push(dir.clone());
+    }
+
+    fn build(
+        dir: &Path,
+        finder: &PackageFinder<'_>,
+        owned: &BTreeMap<Option<PathBuf>, Vec<&Entry<'_>>>,
+        children: &BTreeMap<Option<PathBuf>, Vec<PathBuf>>,
+        model: &ScoreModel,
+    ) -> GroupScore {
+        let key = Some(dir.to_path_buf());
+        let mut group = score_group(
+            finder.names[dir].clone(),
+            finder.rel_display(dir),
+            &owned[&key],
+            model,
+        );
+        group.children = children
+            .get(&key)
+            .into_iter()
+            .flatten()
+            .map(|c| build(c, finder, owned, children, model))
+            .collect();
+        group
+    }
+
+    let mut groups: Vec<GroupScore> = children
+        .get(&None)
+        .into_iter()
+        .flatten()
+        .map(|dir| build(dir, &finder, &owned, &children, model))
+        .collect();
+    if let Some(unowned) = owned.get(&None) {
+        groups.push(score_group(
+            NO_PACKAGE.to_string(),
+            ".".to_string(),
+            unowned,
+            model,
+        ));
+    }
+    groups
+}
+
+/// Finds the nearest package manifest above a directory, within the walk root.
+struct PackageFinder<'a> {
+    root: &'a Path,
+    /// Nearest package directory for each directory looked up so far.
+    memo: HashMap<PathBuf, Option<PathBuf>>,
+    /// Package name for each package directory found.
+    names: HashMap<PathBuf, String>,
+}
+
+impl<'a> PackageFinder<'a> {
+    fn new(root: &'a Path) -> Self {
+        Self {
+            root,
+            memo: HashMap::new(),
+            names: HashMap::new(),
+        }
+    }
+
+    /// The directory of the package that owns `dir`, if any.
+    fn owner(&mut self, dir: &Path) -> Option<PathBuf> {
+        if !dir.starts_with(self.root) {
+            return None;
+        }
+        if let Some(found) = self.memo.get(dir) {
+            return found.clone();
+        }
+        let found = if let Some(name) = manifest_name(dir) {
+            self.names.insert(dir.to_path_buf(), name);
+            Some(dir.to_path_buf())
+        } else if dir == self.root {
+            None
+        } else {
+            dir.parent().and_then(|p| self.owner(p))
+        };
+        self.memo.insert(dir.to_path_buf(), found.clone());
+        found
+    }
+
+    /// `dir` relative to the walk root, `.` for the root itself.
+    fn rel_display(&self, dir: &Path) -> String {
+        match dir.strip_prefix(self.root) {
+            Ok(rel) if !rel.as_os_str().is_empty() => rel.display().to_string(),
+            _ => ".".to_string(),
+        }
+    }
+}
+
+/// Package name declared by a manifest in `dir`, if `dir` holds a package.
+/// A `Cargo.toml` without `[package]` (a virtual workspace) is not one;
+/// unnamed npm and Go packages are named after their directory.
+fn manifest_name(dir: &Path) -> Option<String> {
+    if let Ok(text) = rev::read_to_string(&dir.join("Cargo.toml"))
+        && let Some(name) = text
+            .parse::<toml::Table>()
+            .ok()
+            .and_then(|t| Some(t.get("package")?.get("name")?.as_str()?.to_string()))
+    {
+        return Some(name);
+    }
+    let dir_name = || {
+        // Canonicalize resolves `.`; directories of a `--rev` tree are not on disk.
+        dir.canonicalize()
+            .ok()
+            .and_then(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()))
+            .or_else(|| dir.file_name().map(|n| n.to_string_lossy().into_owned()))
+            .unwrap_or_else(|| dir.display().to_string())
+    };
+    if let Ok(text) = rev::read_to_string(&dir.join("package.json")) {
+        let name = serde_json::from_str::<serde_json::Value>(&text)
+            .ok()
+            .and_then(|v| v["name"].as_str().map(String::from));
+        return Some(name.unwrap_or_else(dir_name));
+    }
+    if let Ok(text) = rev::read_to_string(&dir.join("go.mod")) {
+        let module = text
+            .lines()
+            .find_map(|l| l.trim().strip_prefix("module "))
+            .map(|m| m.trim().trim_matches('"').to_string());
+        return Some(module.unwrap_or_else(dir_name));
+    }
+    None
+}
+
+#[cfg(test)]
+#[path = "breakdown_test.rs"]
+mod tests;
@@ -0,0 +1,101 @@
+//! Report formatters for the per-directory and per-package score breakdown.
+//!
+//! The table ranks every group, at every level, worst score first; the JSON
+//! document keeps the group hierarchy.
+
+use super::breakdown::{GroupScore, ScoreBreakdown};
+use super::report::format_thousands;
+use crate::report_helpers;
+
+/// Header line for the breakdown kind.
+fn title(breakdown: &ScoreBreakdown) -> String {
+    match breakdown.depth {
+        Some(depth) => format!("Code Health by Directory (depth {depth})"),
+        None => "Code Health by Package".to_string(),
+    }
+}
+
+/// Label shown for a group: the directory, or `name (path)` for packages.
+fn label(group: &GroupScore) -> String {
+    if group.name == group.path {
+        group.path.clone()
+    } else {
+        format!("{} ({})", group.name, group.path)
+    }
+}
+
+/// Print the ranked breakdown table.
+pub fn print_report(breakdown: &ScoreBreakdown) {
+    let separator = report_helpers::separator(84);
+
+    println!("{}", title(breakdown));
+    println!("{separator}");
+    println!(
+        " Project Score:  {} ({:.1})",
+        breakdown.grade, breakdown.score
+    );
+    println!(" Files Analyzed: {}", breakdown.files_analyzed);
+    println!(" Total LOC:      {}", format_thousands(breakdown.total_loc));
+    println!("{separator}");
+
+    let ranked = breakdown.ranked();
+    if ranked.is_empty() {
+        println!(" No groups found.");
+        println!("{separator}");
+        return;
+    }
+
+    println!(
+        " {:>5}  {:<5}  {:>5}  {:>7}  {:<28} Group",
+        "Score", "Grade", "Files", "LOC", "Weakest Dimension"
+    );
+    println!("{separator}");
+    for g in ranked {
+        let weakest = g
+            .weakest_dimension()
+            .map(|d| format!("{} ({:.0})", d.name, d.score))
+            .unwrap_or_default();
+        println!(
+            " {:>5.1}  {:<5}  {:>5}  {:>7}  {:<28} {}",
+            g.score,
+            g.grade.as_str(),
+            g.files_analyzed,
+            format_thousands(g.total_loc),
+            weakest,
+            label(g),
+        );
+    }
+    println!("{separator}");
+}
+
+/// Print one compact line per group, worst first.
+pub fn print_short(breakdown: &ScoreBreakdown) {
+    for g in breakdown.ranked() {
+        println!(
+            "group:{} s:{:.1} g:{} files:{} loc:{}",
+            label(g),
+            g.score,
+            g.grade,
+            g.files_analyzed,
+            g.total_loc,
+        );
+    }
+}
+
+/// Print the score and label of each group, worst first.
+pub fn print_terse(breakdown: &ScoreBreakdown) {
+    for g in breakdown.ranked() {
+        println!("{:.1} {}", g.score, label(g));
+    }
+}
+
+/// Serialize the breakdown as a pretty-printed, hierarchical JSON string.
+pub fn format_json(breakdown: &ScoreBreakdown) -> Result<String, Box<dyn std::error::Error>> {
+    report_helpers::json_string(breakdown)
+}
+
+/// Serialize the breakdown as pretty-printed JSON to stdout.
+pub fn print_json(breakdown: &ScoreBreakdown) -> Result<(), Box<dyn std::error::Error>> {
+    println!("{}", format_json(breakdown)?);
+    Ok(())
+}
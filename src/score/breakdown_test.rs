@@ -0,0 +1,212 @@
+use super::*;
+use crate::walk::ExcludeFilter;
+use std::fs;
+
+const SIMPLE: &str = "fn main() {\n    let x = 1;\n    println!(\"{}\", x);\n}\n";
+
+fn write(root: &Path, rel: &str, content: &str) {
+    let path = root.join(rel);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+fn breakdown(root: &Path, by: Breakdown) -> ScoreBreakdown {
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(root, false, &filter);
+    compute_breakdown(&cfg, 6, &ScoreModel::default(), by).unwrap()
+}
+
+fn names(groups: &[GroupScore]) -> Vec<&str> {
+    groups.iter().map(|g| g.name.as_str()).collect()
+}
+
+#[test]
+fn by_dir_nests_subtrees_to_depth() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "main.rs", SIMPLE);
+    write(dir.path(), "src/lib.rs", SIMPLE);
+    write(dir.path(), "src/a/x.rs", SIMPLE);
+    write(dir.path(), "src/a/deep/y.rs", SIMPLE);
+    write(dir.path(), "tools/z.rs", SIMPLE);
+
+    let b = breakdown(dir.path(), Breakdown::Dir(2));
+    assert_eq!(b.group_by, "dir");
+    assert_eq!(b.depth, Some(2));
+    assert_eq!(b.files_analyzed, 5);
+    assert_eq!(names(&b.groups), vec![".", "src", "tools"]);
+    let src = &b.groups[1];
+    assert_eq!(src.files_analyzed, 3, "src covers its whole subtree");
+    assert_eq!(names(&src.children), vec!["src/a"]);
+    assert_eq!(src.children[0].files_analyzed, 2);
+    assert!(
+        src.children[0].children.is_empty(),
+        "depth 2 stops at src/a"
+    );
+}
+
+#[test]
+fn by_dir_groups_root_files_under_dot() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "build.rs", SIMPLE);
+    write(dir.path(), "src/lib.rs", SIMPLE);
+    write(dir.path(), "src/a/x.rs", SIMPLE);
+
+    let b = breakdown(dir.path(), Breakdown::Dir(2));
+    assert_eq!(names(&b.groups), vec![".", "src"]);
+    let root = &b.groups[0];
+    assert_eq!(root.path, ".");
+    assert_eq!(root.files_analyzed, 1);
+    assert!(root.children.is_empty());
+    let grouped: usize = b.groups.iter().map(|g| g.files_analyzed).sum();
+    assert_eq!(
+        grouped, b.files_analyzed,
+        "every file is in a top-level group"
+    );
+}
+
+#[test]
+fn by_dir_depth_zero_is_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(dir.path(), false, &filter);
+    let err = compute_breakdown(&cfg, 6, &ScoreModel::default(), Breakdown::Dir(0)).unwrap_err();
+    assert!(err.to_string().contains("at least 1"));
+}
+
+#[test]
+fn group_scores_match_project_score_for_single_group() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "src/a.rs", SIMPLE);
+    write(dir.path(), "src/b.rs", "fn b() -> i32 {\n    42\n}\n");
+
+    let b = breakdown(dir.path(), Breakdown::Dir(1));
+    assert_eq!(b.groups.len(), 1);
+    assert_eq!(b.groups[0].score, b.score);
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(dir.path(), false, &filter);
+    let project = super::super::compute_score(&cfg, 10, 6, &ScoreModel::default()).unwrap();
+    assert_eq!(b.score, project.score);
+}
+
+#[test]
+fn duplication_is_attributed_to_later_copies() {
+    let dup: String = (0..10)
+        .map(|i| format!("let v{i} = compute({i}, \"{i}\");\n"))
+        .collect();
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "a/one.rs", &format!("fn one() {{\n{dup}}}\n"));
+    write(dir.path(), "b/two.rs", &format!("fn two() {{\n{dup}}}\n"));
+
+    let b = breakdown(dir.path(), Breakdown::Dir(1));
+    let dup_score = |g: &GroupScore| {
+        g.dimensions
+            .iter()
+            .find(|d| d.name == "Duplication")
+            .unwrap()
+            .score
+    };
+    assert_eq!(names(&b.groups), vec!["a", "b"]);
+    assert_eq!(
+        dup_score(&b.groups[0]),
+        100.0,
+        "first copy is not penalized"
+    );
+    assert!(dup_score(&b.groups[1]) < 100.0, "second copy is duplicated");
+}
+
+#[test]
+fn by_package_uses_nearest_manifest() {
+    let dir = tempfile::tempdir().unwrap();
+    write(
+        dir.path(),
+        "Cargo.toml",
+        "[workspace]\nmembers = [\"crates/*\"]\n",
+    );
+    write(dir.path(), "build.rs", SIMPLE);
+    write(
+        dir.path(),
+        "crates/core/Cargo.toml",
+        "[package]\nname = \"core\"\n",
+    );
+    write(dir.path(), "crates/core/src/lib.rs", SIMPLE);
+    write(dir.path(), "web/package.json", "{\"name\": \"@acme/web\"}");
+    write(
+        dir.path(),
+        "web/index.js",
+        "function f() {\n  return 1;\n}\n",
+    );
+    write(
+        dir.path(),
+        "svc/go.mod",
+        "module example.com/svc\n\ngo 1.22\n",
+    );
+    write(
+        dir.path(),
+        "svc/main.go",
+        "package main\n\nfunc main() {\n}\n",
+    );
+
+    let b = breakdown(dir.path(), Breakdown::Package);
+    assert_eq!(b.group_by, "package");
+    assert_eq!(b.depth, None);
+    assert_eq!(
+        names(&b.groups),
+        vec!["core", "example.com/svc", "@acme/web", NO_PACKAGE],
+        "virtual workspace root is not a package"
+    );
+    assert_eq!(b.groups[0].path, "crates/core");
+    assert_eq!(b.groups[3].files_analyzed, 1);
+}
+
+#[test]
+fn by_package_nests_packages() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "package.json", "{\"name\": \"root\"}");
+    write(dir.path(), "index.js", "function f() {\n  return 1;\n}\n");
+    write(dir.path(), "packages/ui/package.json", "{}");
+    write(
+        dir.path(),
+        "packages/ui/button.js",
+        "function g() {\n  return 2;\n}\n",
+    );
+
+    let b = breakdown(dir.path(), Breakdown::Package);
+    assert_eq!(names(&b.groups), vec!["root"]);
+    let root = &b.groups[0];
+    assert_eq!(root.files_analyzed, 1, "nested package files are excluded");
+    assert_eq!(names(&root.children), vec!["ui"], "unnamed npm package");
+    assert_eq!(root.children[0].path, "packages/ui");
+}
+
+#[test]
+fn ranked_lists_all_levels_worst_first() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "src/a/x.rs", SIMPLE);
+    write(dir.path(), "src/b/y.rs", SIMPLE);
+    let b = breakdown(dir.path(), Breakdown::Dir(2));
+    let ranked = b.ranked();
+    assert_eq!(ranked.len(), 3);
+    assert!(ranked.windows(2).all(|w| w[0].score <= w[1].score));
+}
+
+#[test]
+fn json_is_hierarchical() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "src/a/x.rs", SIMPLE);
+    let b = breakdown(dir.path(), Breakdown::Dir(2));
+    let json: serde_json::Value =
+        serde_json::from_str(&super::super::breakdown_report::format_json(&b).unwrap()).unwrap();
+    assert_eq!(json["group_by"], "dir");
+    assert_eq!(json["groups"][0]["path"], "src");
+    assert_eq!(json["groups"][0]["children"][0]["path"], "src/a");
+    assert!(json["groups"][0]["children"][0].get("children").is_none());
+    assert_eq!(json["model"]["name"], "cogcom");
+}
+
+#[test]
+fn empty_project_has_no_groups() {
+    let dir = tempfile::tempdir().unwrap();
+    let b = breakdown(dir.path(), Breakdown::Package);
+    assert!(b.groups.is_empty());
+    assert_eq!(b.score, 0.0);
+}
@@ -6,6 +6,9 @@
 /// for project-level scoring.
 use std::path::Path;
 
+use serde::{Deserialize, Serialize};
+
+use crate::cache::{self, AnalysisCache};
 use crate::cogcom;
 use crate::cycom;
 use crate::dups;
@@ -18,6 +21,7 @@ use crate::util::{find_test_block_start, read_and_classify};
 use super::ScoringModel;
 
 /// Per-file raw metrics collected during the walk.
+#[derive(Clone, Serialize, Deserialize)]
 pub struct FileMetrics {
     pub path: std::path::PathBuf,
     pub code_lines: usize,
@@ -39,39 +43,83 @@ pub struct SingleFileResult {
     pub normalized_count: usize,
 }
 
+/// Path-independent part of a `SingleFileResult`, as stored in the cache.
+#[derive(Serialize, Deserialize)]
+struct CachedFile {
+    metrics: FileMetrics,
+    normalized: Vec<dups::detector::NormalizedLine>,
+}
+
+impl CachedFile {
+    /// Attach the file path the content was read from.
+    fn into_result(mut self, file_path: &Path) -> SingleFileResult {
+        self.metrics.path = file_path.to_path_buf();
+        SingleFileResult {
+            metrics: self.metrics,
+            normalized_count: self.normalized.len(),
+            dup_file: dups::detector::NormalizedFile {
+                path: file_path.to_path_buf(),
+                lines: self.normalized,
+            },
+        }
+    }
+}
+
 /// Analyze a single source file: read, classify, compute metrics, normalize for dups.
 /// Returns `None` for binary files, non-code files, or on I/O errors.
+/// Results for unchanged content are served from `cache` when one is given.
 pub fn analyze_single_file(
     file_path: &Path,
     spec: &crate::loc::language::LanguageSpec,
     exclude_tests: bool,
     model: &ScoringModel,
+    cache: Option<&AnalysisCache>,
 ) -> Option<SingleFileResult> {
-    let (lines, kinds) = match read_and_classify(file_path, spec) {
-        Ok(Some(v)) => v,
-        Ok(None) => return None,
+    // Both the model and test stripping change what gets stored.
+    let analyzer = match (model, exclude_tests) {
+        (ScoringModel::Cognitive, true) => "score-cogcom",
+        (ScoringModel::Cognitive, false) => "score-cogcom-tests",
+        (ScoringModel::Legacy, true) => "score-legacy",
+        (ScoringModel::Legacy, false) => "score-legacy-tests",
+    };
+    let cached = cache::cached(cache, analyzer, file_path, spec, || {
+        Ok(read_and_classify(file_path, spec)?
+            .and_then(|(lines, kinds)| analyze_lines(&lines, &kinds, spec, exclude_tests, model)))
+    });
+    match cached {
+        Ok(file) => file.map(|f| f.into_result(file_path)),
         Err(e) => {
             eprintln!("warning: {}: {e}", file_path.display());
-            return None;
+            None
         }
-    };
+    }
+}
 
+/// Compute the score metrics and normalized duplication lines for
+/// already-classified content. Returns `None` for non-code files.
+fn analyze_lines(
+    lines: &[String],
+    kinds: &[LineKind],
+    spec: &crate::loc::language::LanguageSpec,
+    exclude_tests: bool,
+    model: &ScoringModel,
+) -> Option<CachedFile> {
     let code_lines = kinds.iter().filter(|k| **k == LineKind::Code).count();
     let comment_lines = kinds.iter().filter(|k| **k == LineKind::Comment).count();
 
-    let indent_stddev = indent::analyzer::analyze(&lines, &kinds, 4).map(|m| m.stddev);
+    let indent_stddev = indent::analyzer::analyze(lines, kinds, 4).map(|m| m.stddev);
 
-    let hal_metrics = hal::analyze_content(&lines, &kinds, spec);
+    let hal_metrics = hal::analyze_content(lines, kinds, spec);
     let halstead_effort = hal_metrics.as_ref().map(|h| h.effort);
 
     let (max_cognitive, mi_score, max_complexity) = match model {
         ScoringModel::Cognitive => {
-            let cogcom_result = cogcom::analyze_content(&lines, &kinds, spec);
+            let cogcom_result = cogcom::analyze_content(lines, kinds, spec);
             let max_cog = cogcom_result.as_ref().map(|c| c.max_complexity);
             (max_cog, None, None)
         }
         ScoringModel::Legacy => {
-            let cycom_result = cycom::analyze_content(&lines, &kinds, spec);
+            let cycom_result = cycom::analyze_content(lines, kinds, spec);
             let max_cycom = cycom_result.as_ref().map(|c| c.max_complexity);
 
             let mi = hal_metrics.as_ref().and_then(|h| {
@@ -99,16 +147,15 @@ pub fn analyze_single_file(
     }
 
     let dup_end = if exclude_tests {
-        find_test_block_start(&lines)
+        find_test_block_start(lines)
     } else {
         lines.len()
     };
     let normalized = dups::normalize_content(&lines[..dup_end], &kinds[..dup_end]);
-    let normalized_count = normalized.len();
 
-    Some(SingleFileResult {
+    Some(CachedFile {
         metrics: FileMetrics {
-            path: file_path.to_path_buf(),
+            path: std::path::PathBuf::new(),
             code_lines,
             max_cognitive,
             mi_score,
@@ -116,10 +163,6 @@ pub fn analyze_single_file(
             indent_stddev,
             halstead_effort,
         },
-        dup_file: dups::detector::NormalizedFile {
-            path: file_path.to_path_buf(),
-            lines: normalized,
-        },
-        normalized_count,
+        normalized,
     })
 }
@@ -1,11 +1,21 @@
 //! Diff data types and computation for comparing two `ProjectScore` snapshots.
 //!
 //! Produces a `ScoreDiff` that captures the before/after state of each
-//! dimension plus the overall project score, with signed deltas.
+//! dimension plus the overall project score, with signed deltas, and the
+//! files whose own score moved the most.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 use serde::Serialize;
 
-use super::analyzer::{Grade, ProjectScore};
+use super::analyzer::{DimensionScore, Grade, ProjectScore, score_to_grade};
+use super::collector::FileMetrics;
+use super::model::ScoreModel;
+use super::scoring::{score_file, score_file_dimensions};
+
+/// File score changes smaller than this display as `0.0` and are not listed.
+const FILE_DELTA_EPSILON: f64 = 0.05;
 
 /// Numeric delta: before, after, and signed difference.
 #[derive(Debug, Clone, Serialize)]
@@ -39,6 +49,77 @@ pub struct ScoreDiff {
     pub loc_before: usize,
     pub loc_after: usize,
     pub dimensions: Vec<DimensionDelta>,
+    /// Files whose score changed, grouped by change kind (regressed,
+    /// improved, added, removed) and ranked within each group.
+    pub files: Vec<FileDelta>,
+}
+
+/// How a file's score changed between the two snapshots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileChange {
+    Regressed,
+    Improved,
+    Added,
+    Removed,
+}
+
+impl FileChange {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FileChange::Regressed => "regressed",
+            FileChange::Improved => "improved",
+            FileChange::Added => "added",
+            FileChange::Removed => "removed",
+        }
+    }
+}
+
+/// Before/after score of one dimension of a file. A side is `None` when
+/// the file does not exist in that snapshot.
+#[derive(Debug, Clone, Serialize)]
+pub struct FileDimensionDelta {
+    pub name: String,
+    pub before: Option<f64>,
+    pub after: Option<f64>,
+    pub delta: Option<f64>,
+}
+
+/// Score change of a single file, with its per-dimension deltas.
+#[derive(Debug, Clone, Serialize)]
+pub struct FileDelta {
+    /// Path relative to the analyzed directory.
+    pub path: PathBuf,
+    pub change: FileChange,
+    pub before: Option<f64>,
+    pub before_grade: Option<Grade>,
+    pub after: Option<f64>,
+    pub after_grade: Option<Grade>,
+    /// `after - before`; `None` for added and removed files.
+    pub delta: Option<f64>,
+    pub dimensions: Vec<FileDimensionDelta>,
+}
+
+/// Score and per-dimension scores of one file in one snapshot.
+#[derive(Debug, Clone)]
+pub struct FileSnapshot {
+    /// Path relative to the analyzed directory.
+    pub path: PathBuf,
+    pub score: f64,
+    pub dimensions: Vec<DimensionScore>,
+}
+
+/// Score every file of a snapshot, with paths made relative to `root` so the
+/// working tree and the ref tree read through a `RevTree` line up.
+pub fn file_snapshots(files: &[FileMetrics], root: &Path, model: &ScoreModel) -> Vec<FileSnapshot> {
+    files
+        .iter()
+        .map(|f| FileSnapshot {
+            path: f.path.strip_prefix(root).unwrap_or(&f.path).to_path_buf(),
+            score: score_file(f, model).score,
+            dimensions: score_file_dimensions(f, model),
+        })
+        .collect()
 }
 
 /// Compare two `ProjectScore` snapshots and produce a `ScoreDiff`.
@@ -92,13 +173,123 @@ pub fn compute_diff(git_ref: &str, before: &ProjectScore, after: &ProjectScore)
         loc_before: before.total_loc,
         loc_after: after.total_loc,
         dimensions,
+        files: Vec::new(),
+    }
+}
+
+/// Match the files of two snapshots by path and list those whose score
+/// changed: regressions (largest drop first), improvements (largest gain
+/// first), added files (worst first) and removed files (worst first), at
+/// most `limit` of each.
+pub fn compute_file_deltas(
+    before: &[FileSnapshot],
+    after: &[FileSnapshot],
+    limit: usize,
+) -> Vec<FileDelta> {
+    let mut pairs: BTreeMap<&Path, (Option<&FileSnapshot>, Option<&FileSnapshot>)> =
+        BTreeMap::new();
+    for b in before {
+        pairs.entry(&b.path).or_default().0 = Some(b);
+    }
+    for a in after {
+        pairs.entry(&a.path).or_default().1 = Some(a);
+    }
+
+    let mut regressed = Vec::new();
+    let mut improved = Vec::new();
+    let mut added = Vec::new();
+    let mut removed = Vec::new();
+    for (path, (b, a)) in pairs {
+        let delta = file_delta(path, b, a);
+        match delta.change {
+            FileChange::Regressed => regressed.push(delta),
+            FileChange::Improved => improved.push(delta),
+            FileChange::Added => added.push(delta),
+            FileChange::Removed => removed.push(delta),
+        }
+    }
+    // Skip files whose score did not visibly move.
+    let moved = |d: &FileDelta| d.delta.is_some_and(|v| v.abs() >= FILE_DELTA_EPSILON);
+    regressed.retain(moved);
+    improved.retain(moved);
+
+    let by_delta = |d: &FileDelta| d.delta.unwrap_or_default();
+    regressed.sort_by(|x, y| by_delta(x).total_cmp(&by_delta(y)));
+    improved.sort_by(|x, y| by_delta(y).total_cmp(&by_delta(x)));
+    added.sort_by(|x, y| {
+        x.after
+            .unwrap_or_default()
+            .total_cmp(&y.after.unwrap_or_default())
+    });
+    removed.sort_by(|x, y| {
+        x.before
+            .unwrap_or_default()
+            .total_cmp(&y.before.unwrap_or_default())
+    });
+
+    [regressed, improved, added, removed]
+        .into_iter()
+        .flat_map(|group| group.into_iter().take(limit))
+        .collect()
+}
+
+/// Build the delta of one path present in at least one snapshot.
+fn file_delta(
+    path: &Path,
+    before: Option<&FileSnapshot>,
+    after: Option<&FileSnapshot>,
+) -> FileDelta {
+    let score_before = before.map(|b| b.score);
+    let score_after = after.map(|a| a.score);
+    let delta = score_before.zip(score_after).map(|(b, a)| a - b);
+    let change = match (score_before, delta) {
+        (None, _) => FileChange::Added,
+        (Some(_), None) => FileChange::Removed,
+        (Some(_), Some(d)) if d < 0.0 => FileChange::Regressed,
+        (Some(_), Some(_)) => FileChange::Improved,
+    };
+
+    // Both sides come from the same model, so dimensions line up by name.
+    let names = after
+        .or(before)
+        .map(|s| &s.dimensions[..])
+        .unwrap_or_default();
+    let dim_score = |s: Option<&FileSnapshot>, name: &str| {
+        s.and_then(|s| s.dimensions.iter().find(|d| d.name == name))
+            .map(|d| d.score)
+    };
+    let dimensions = names
+        .iter()
+        .map(|d| {
+            let b = dim_score(before, d.name);
+            let a = dim_score(after, d.name);
+            FileDimensionDelta {
+                name: d.name.to_string(),
+                before: b,
+                after: a,
+                delta: b.zip(a).map(|(b, a)| a - b),
+            }
+        })
+        .collect();
+
+    FileDelta {
+        path: path.to_path_buf(),
+        change,
+        before: score_before,
+        before_grade: score_before.map(score_to_grade),
+        after: score_after,
+        after_grade: score_after.map(score_to_grade),
+        delta,
+        dimensions,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::score::ScoreModel;
     use crate::score::analyzer::{DimensionScore, Grade, ProjectScore};
+    use crate::score::collector::FileMetrics;
 
     fn make_score(score: f64, files: usize, loc: usize, dim_score: f64) -> ProjectScore {
         let grade = crate::score::analyzer::score_to_grade(score);
@@ -123,6 +314,7 @@ mod tests {
                 },
             ],
             needs_attention: vec![],
+            model: ScoreModel::default(),
         }
     }
 
@@ -160,4 +352,92 @@ mod tests {
         assert!((diff.overall.delta).abs() < 0.01);
         assert_eq!(diff.before_grade, diff.after_grade);
     }
+
+    fn snapshot(path: &str, score: f64, cognitive: f64) -> FileSnapshot {
+        FileSnapshot {
+            path: path.into(),
+            score,
+            dimensions: vec![DimensionScore {
+                name: "Cognitive Complexity",
+                weight: 0.30,
+                score: cognitive,
+                grade: crate::score::analyzer::score_to_grade(cognitive),
+            }],
+        }
+    }
+
+    #[test]
+    fn compute_diff_starts_without_files() {
+        let score = make_score(85.0, 10, 1000, 85.0);
+        assert!(compute_diff("HEAD", &score, &score).files.is_empty());
+    }
+
+    #[test]
+    fn file_deltas_are_grouped_and_ranked() {
+        let before = [
+            snapshot("small_drop.rs", 90.0, 90.0),
+            snapshot("big_drop.rs", 90.0, 90.0),
+            snapshot("better.rs", 60.0, 50.0),
+            snapshot("same.rs", 80.0, 80.0),
+            snapshot("gone.rs", 40.0, 40.0),
+        ];
+        let after = [
+            snapshot("small_drop.rs", 85.0, 80.0),
+            snapshot("big_drop.rs", 50.0, 20.0),
+            snapshot("better.rs", 75.0, 80.0),
+            snapshot("same.rs", 80.01, 80.0),
+            snapshot("new.rs", 70.0, 70.0),
+        ];
+        let files = compute_file_deltas(&before, &after, 10);
+        let listed: Vec<(&str, FileChange)> = files
+            .iter()
+            .map(|f| (f.path.to_str().unwrap(), f.change))
+            .collect();
+        assert_eq!(
+            listed,
+            vec![
+                ("big_drop.rs", FileChange::Regressed),
+                ("small_drop.rs", FileChange::Regressed),
+                ("better.rs", FileChange::Improved),
+                ("new.rs", FileChange::Added),
+                ("gone.rs", FileChange::Removed),
+            ],
+            "unchanged files are left out"
+        );
+        assert!((files[0].delta.unwrap() - (-40.0)).abs() < 1e-9);
+        assert!((files[0].dimensions[0].delta.unwrap() - (-70.0)).abs() < 1e-9);
+        assert_eq!(files[3].delta, None);
+        assert_eq!(files[3].dimensions[0].before, None);
+        assert_eq!(files[4].after_grade, None);
+    }
+
+    #[test]
+    fn file_deltas_limit_each_group() {
+        let before = [snapshot("a.rs", 90.0, 90.0), snapshot("b.rs", 90.0, 90.0)];
+        let after = [
+            snapshot("a.rs", 50.0, 50.0),
+            snapshot("b.rs", 60.0, 60.0),
+            snapshot("c.rs", 70.0, 70.0),
+            snapshot("d.rs", 40.0, 40.0),
+        ];
+        let files = compute_file_deltas(&before, &after, 1);
+        let paths: Vec<&str> = files.iter().map(|f| f.path.to_str().unwrap()).collect();
+        assert_eq!(paths, vec!["a.rs", "d.rs"], "worst of each group");
+    }
+
+    #[test]
+    fn file_snapshots_are_relative_to_root() {
+        let metrics = FileMetrics {
+            path: "/tmp/tree/src/lib.rs".into(),
+            code_lines: 10,
+            max_cognitive: Some(1),
+            mi_score: None,
+            max_complexity: None,
+            indent_stddev: Some(0.5),
+            halstead_effort: Some(100.0),
+        };
+        let snaps = file_snapshots(&[metrics], Path::new("/tmp/tree"), &ScoreModel::default());
+        assert_eq!(snaps[0].path, PathBuf::from("src/lib.rs"));
+        assert_eq!(snaps[0].dimensions.len(), 4, "duplication is project-level");
+    }
 }
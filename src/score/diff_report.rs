@@ -1,13 +1,14 @@
 //! Report formatters for the score diff output.
 //!
-//! Provides table (ANSI-colored) and JSON output modes for `ScoreDiff`.
-//! Green for improvements (+), red for regressions (-), yellow for no change.
+//! Provides table (ANSI-colored), markdown and JSON output modes for
+//! `ScoreDiff`. Green for improvements (+), red for regressions (-), yellow
+//! for no change. The markdown form is meant for PR comments.
 
 use serde::Serialize;
 
 use super::analyzer::Grade;
-use super::diff::ScoreDiff;
-use crate::report_helpers;
+use super::diff::{FileDelta, FileDimensionDelta, ScoreDiff};
+use crate::report_helpers::{self, escape_md};
 
 // ANSI color codes.
 const GREEN: &str = "\x1b[32m";
@@ -16,6 +17,17 @@ const YELLOW: &str = "\x1b[33m";
 const BOLD: &str = "\x1b[1m";
 const RESET: &str = "\x1b[0m";
 
+/// ANSI color for a signed delta: green up, red down, yellow unchanged.
+fn delta_color(delta: f64) -> &'static str {
+    if delta > 0.05 {
+        GREEN
+    } else if delta < -0.05 {
+        RED
+    } else {
+        YELLOW
+    }
+}
+
 /// Format a signed delta with color and sign prefix.
 fn colored_delta(delta: f64) -> String {
     if delta > 0.05 {
@@ -38,6 +50,61 @@ fn colored_int_delta(delta: i64) -> String {
     }
 }
 
+/// Format an optional file score with its grade, or `-` when the file does
+/// not exist on that side.
+fn score_cell(score: Option<f64>, grade: Option<Grade>) -> String {
+    match score.zip(grade) {
+        Some((s, g)) => format!("{s:.1} {}", g.as_str()),
+        None => "-".to_string(),
+    }
+}
+
+/// Dimensions of a file that visibly moved, e.g. `Cognitive Complexity -30.0`.
+fn moved_dimensions(dims: &[FileDimensionDelta]) -> String {
+    dims.iter()
+        .filter_map(|d| {
+            d.delta
+                .filter(|v| v.abs() > 0.05)
+                .map(|v| format!("{} {v:+.1}", d.name))
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Print the per-file deltas below the dimension table.
+fn print_files(files: &[FileDelta], separator: &str) {
+    if files.is_empty() {
+        return;
+    }
+    println!();
+    println!(" Changed Files");
+    println!("{separator}");
+    println!(
+        " {:<10} {:>9}   {:>9}   {:>7}   File",
+        "Change", "Before", "After", "Delta"
+    );
+    println!("{separator}");
+    for f in files {
+        // Pad before coloring so the escape codes do not skew the columns.
+        let delta = match f.delta {
+            Some(d) => format!("{}{:>7}{RESET}", delta_color(d), format!("{d:+.1}")),
+            None => format!("{:>7}", "-"),
+        };
+        println!(
+            " {:<10} {:>9}   {:>9}   {delta}   {}",
+            f.change.as_str(),
+            score_cell(f.before, f.before_grade),
+            score_cell(f.after, f.after_grade),
+            f.path.display(),
+        );
+        let moved = moved_dimensions(&f.dimensions);
+        if !moved.is_empty() {
+            println!(" {:<45}{moved}", "");
+        }
+    }
+    println!("{separator}");
+}
+
 /// Print the score diff as a formatted table with ANSI colors.
 pub fn print_report(diff: &ScoreDiff) {
     let separator = report_helpers::separator(72);
@@ -45,15 +112,7 @@ pub fn print_report(diff: &ScoreDiff) {
     println!("Score Diff vs {BOLD}{}{RESET}", diff.git_ref);
     println!("{separator}");
 
-    let grade_change = if diff.before_grade == diff.after_grade {
-        diff.after_grade.as_str().to_string()
-    } else {
-        format!(
-            "{} → {}",
-            diff.before_grade.as_str(),
-            diff.after_grade.as_str()
-        )
-    };
+    let grade_change = grade_change(diff.before_grade, diff.after_grade);
 
     println!(
         " Overall Score: {:.1} → {:.1}  ({})  Grade: {grade_change}",
@@ -95,6 +154,74 @@ pub fn print_report(diff: &ScoreDiff) {
     }
 
     println!("{separator}");
+    print_files(&diff.files, &separator);
+}
+
+/// Format a grade transition, or the single grade when it did not change.
+fn grade_change(before: Grade, after: Grade) -> String {
+    if before == after {
+        after.as_str().to_string()
+    } else {
+        format!("{} → {}", before.as_str(), after.as_str())
+    }
+}
+
+/// Print the score diff as a GitHub-flavored markdown document.
+pub fn print_markdown(diff: &ScoreDiff) {
+    let files_delta = diff.files_after as i64 - diff.files_before as i64;
+    let loc_delta = diff.loc_after as i64 - diff.loc_before as i64;
+
+    println!("## Score Diff vs `{}`", diff.git_ref);
+    println!();
+    println!(
+        "**Score:** {:.1} → {:.1} ({:+.1}) · **Grade:** {}",
+        diff.overall.before,
+        diff.overall.after,
+        diff.overall.delta,
+        grade_change(diff.before_grade, diff.after_grade),
+    );
+    println!();
+    println!(
+        "**Files:** {} → {} ({files_delta:+}) · **LOC:** {} → {} ({loc_delta:+})",
+        diff.files_before, diff.files_after, diff.loc_before, diff.loc_after,
+    );
+    println!();
+    println!("| Dimension | Weight | Before | After | Delta |");
+    println!("|-----------|-------:|-------:|------:|------:|");
+    for d in &diff.dimensions {
+        println!(
+            "| {} | {:.0}% | {:.1} {} | {:.1} {} | {:+.1} |",
+            d.name,
+            d.weight * 100.0,
+            d.before_score,
+            d.before_grade.as_str(),
+            d.after_score,
+            d.after_grade.as_str(),
+            d.delta,
+        );
+    }
+
+    if diff.files.is_empty() {
+        return;
+    }
+    println!();
+    println!("### Changed Files");
+    println!();
+    println!("| File | Change | Before | After | Delta | Dimensions |");
+    println!("|------|--------|-------:|------:|------:|------------|");
+    for f in &diff.files {
+        println!(
+            "| {} | {} | {} | {} | {} | {} |",
+            escape_md(&f.path.display().to_string()),
+            f.change.as_str(),
+            score_cell(f.before, f.before_grade),
+            score_cell(f.after, f.after_grade),
+            f.delta
+                .map(|d| format!("{d:+.1}"))
+                .unwrap_or_else(|| "-".into()),
+            moved_dimensions(&f.dimensions),
+        );
+    }
 }
 
 /// Print score diff as a single compact line.
@@ -136,16 +263,17 @@ struct JsonDimensionDelta {
 }
 
 #[derive(Serialize)]
-struct JsonScoreDiff {
+struct JsonScoreDiff<'a> {
     git_ref: String,
     before: JsonScoreSnapshot,
     after: JsonScoreSnapshot,
     delta: f64,
     dimensions: Vec<JsonDimensionDelta>,
+    files: &'a [FileDelta],
 }
 
-/// Serialize the score diff as pretty-printed JSON to stdout.
-pub fn print_json(diff: &ScoreDiff) -> Result<(), Box<dyn std::error::Error>> {
+/// Serialize the score diff as a pretty-printed JSON string.
+pub fn format_json(diff: &ScoreDiff) -> Result<String, Box<dyn std::error::Error>> {
     let json = JsonScoreDiff {
         git_ref: diff.git_ref.clone(),
         before: JsonScoreSnapshot {
@@ -174,13 +302,21 @@ pub fn print_json(diff: &ScoreDiff) -> Result<(), Box<dyn std::error::Error>> {
                 delta: d.delta,
             })
             .collect(),
+        files: &diff.files,
     };
-    report_helpers::print_json_stdout(&json)
+    report_helpers::json_string(&json)
+}
+
+/// Serialize the score diff as pretty-printed JSON to stdout.
+pub fn print_json(diff: &ScoreDiff) -> Result<(), Box<dyn std::error::Error>> {
+    println!("{}", format_json(diff)?);
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::score::ScoreModel;
     use crate::score::analyzer::{DimensionScore, Grade, ProjectScore, score_to_grade};
     use crate::score::diff::compute_diff;
 
@@ -207,6 +343,7 @@ mod tests {
                 },
             ],
             needs_attention: vec![],
+            model: ScoreModel::default(),
         }
     }
 
@@ -313,4 +450,67 @@ mod tests {
         assert_eq!(diff.after_grade, Grade::B);
         print_report(&diff);
     }
+
+    fn diff_with_files() -> ScoreDiff {
+        use crate::score::diff::{FileSnapshot, compute_file_deltas};
+        let snapshot = |path: &str, score: f64| FileSnapshot {
+            path: path.into(),
+            score,
+            dimensions: vec![DimensionScore {
+                name: "Cognitive Complexity",
+                weight: 0.5,
+                score,
+                grade: score_to_grade(score),
+            }],
+        };
+        let before = make_project_score(80.0, 2, 200, 80.0);
+        let after = make_project_score(70.0, 2, 220, 70.0);
+        let mut diff = compute_diff("main", &before, &after);
+        diff.files = compute_file_deltas(
+            &[snapshot("src/a|b.rs", 90.0), snapshot("old.rs", 60.0)],
+            &[snapshot("src/a|b.rs", 55.0), snapshot("new.rs", 75.0)],
+            10,
+        );
+        diff
+    }
+
+    #[test]
+    fn print_report_and_markdown_with_files_do_not_panic() {
+        let diff = diff_with_files();
+        print_report(&diff);
+        print_markdown(&diff);
+    }
+
+    #[test]
+    fn json_includes_file_deltas() {
+        let json: serde_json::Value =
+            serde_json::from_str(&format_json(&diff_with_files()).unwrap()).unwrap();
+        let files = json["files"].as_array().unwrap();
+        assert_eq!(files.len(), 3);
+        assert_eq!(files[0]["path"], "src/a|b.rs");
+        assert_eq!(files[0]["change"], "regressed");
+        assert_eq!(files[0]["dimensions"][0]["delta"], -35.0);
+        assert_eq!(files[1]["change"], "added");
+        assert!(files[1]["before"].is_null());
+        assert_eq!(files[2]["change"], "removed");
+    }
+
+    #[test]
+    fn moved_dimensions_skips_unchanged() {
+        let dims = [
+            FileDimensionDelta {
+                name: "Cognitive Complexity".into(),
+                before: Some(90.0),
+                after: Some(60.0),
+                delta: Some(-30.0),
+            },
+            FileDimensionDelta {
+                name: "File Size".into(),
+                before: Some(100.0),
+                after: Some(100.0),
+                delta: Some(0.0),
+            },
+        ];
+        assert_eq!(moved_dimensions(&dims), "Cognitive Complexity -30.0");
+    }
 }
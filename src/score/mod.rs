@@ -10,28 +10,46 @@
 //! - **Cognitive** (default, v0.14+): 5 dimensions with cognitive complexity.
 //! - **Legacy** (`--model legacy`, v0.13): 6 dimensions with MI + cyclomatic.
 //!
+//! Weights and normalization curves of the cognitive model can be tuned in
+//! `.kimun.toml` (`[score.weights]`, `[score.curves.<dimension>]`); the
+//! resolved `ScoreModel` is reported in the JSON output.
+//!
 //! The scoring pipeline: walk → per-file analysis → project-level
 //! duplication → normalize → LOC-weighted mean → grade assignment.
 
 /// Grading system: letter grades, dimension/file/project scores.
-pub(crate) mod analyzer;
+pub mod analyzer;
+/// Per-directory and per-package score breakdown.
+pub mod breakdown;
+/// Table and JSON formatters for the score breakdown.
+pub(crate) mod breakdown_report;
 /// Single-file metric extraction (reads once, computes all dimensions).
 mod collector;
 /// Diff data types and computation for comparing two ProjectScore snapshots.
-pub(crate) mod diff;
+pub mod diff;
 /// Table and JSON formatters for score diff output.
-mod diff_report;
+pub(crate) mod diff_report;
+/// Resolved scoring model: dimensions, weights and curves.
+pub mod model;
+/// Score of the code changed since a git ref.
+pub mod new_code;
+/// Table and JSON formatters for the new-code score.
+pub(crate) mod new_code_report;
 /// Piecewise linear normalization curves mapping raw metrics to 0–100.
 mod normalize;
+/// Refactoring planner: the fewest file improvements reaching a grade.
+pub mod plan;
+/// Table and JSON formatters for the refactoring plan.
+pub(crate) mod plan_report;
 /// Table and JSON output formatters for the score report.
-mod report;
+pub(crate) mod report;
 /// Dimension scoring, per-file scoring, and LOC-weighted aggregation.
 mod scoring;
 
 use std::error::Error;
 
 use crate::dups;
-use crate::git::GitRepo;
+use crate::rev::RevTree;
 use crate::walk::WalkConfig;
 
 use crate::cli::OutputMode;
@@ -40,6 +58,9 @@ use collector::{FileMetrics, analyze_single_file};
 use report::{print_json, print_report, print_short, print_terse};
 use scoring::{build_dimensions, build_empty_dimensions, score_file};
 
+pub use breakdown::Breakdown;
+pub use model::ScoreModel;
+
 /// Quality gate options for `--trend` mode.
 /// Both conditions are independent and checked after the report is printed.
 #[derive(Debug, Clone, Copy, Default)]
@@ -79,10 +100,9 @@ pub fn run(
     output: OutputMode,
     bottom: usize,
     min_lines: usize,
-    model: &str,
+    model: &ScoreModel,
 ) -> Result<(), Box<dyn Error>> {
-    let scoring_model = ScoringModel::from_arg(model);
-    let score = compute_score(cfg, bottom, min_lines, &scoring_model)?;
+    let score = compute_score(cfg, bottom, min_lines, model)?;
 
     // Show target in header when user specified an explicit path (not ".")
     let target = cfg
@@ -95,9 +115,12 @@ pub fn run(
         OutputMode::Json => print_json(&score, target.as_deref())?,
         OutputMode::Short => print_short(&score),
         OutputMode::Terse => print_terse(&score),
-        OutputMode::Github | OutputMode::Codeclimate => {
+        OutputMode::Github | OutputMode::Codeclimate | OutputMode::Sarif => {
             return Err(crate::cli::ERR_CI_FORMAT_ONLY.into());
         }
+        OutputMode::Html | OutputMode::Markdown => {
+            return Err(crate::cli::ERR_REPORT_FORMAT_ONLY.into());
+        }
         OutputMode::Table => print_report(&score, bottom, target.as_deref()),
     }
 
@@ -120,50 +143,67 @@ pub(crate) fn format_gate_error(before: f64, after: f64, delta: f64) -> String {
     )
 }
 
+/// Score the working tree and the tree at `git_ref`, and compare them.
+/// When `cfg.path` is a subdirectory of the repository, the same
+/// subdirectory of the ref tree is scored. Up to `top_changes` files of
+/// each change kind are listed with their per-file deltas.
+pub fn analyze_diff(
+    cfg: &WalkConfig<'_>,
+    git_ref: &str,
+    top_changes: usize,
+    min_lines: usize,
+    model: &ScoreModel,
+) -> Result<diff::ScoreDiff, Box<dyn Error>> {
+    // Score the current working tree.
+    let after_files = collect(cfg, min_lines, model);
+    let after = score_collected(&after_files, top_changes, model);
+
+    // Mount the ref tree; when the user pointed at a subdir of the repo,
+    // its path is the corresponding subdir inside the tree.
+    let tree = RevTree::load(cfg.path, git_ref)?;
+
+    // Score the ref tree.
+    let ref_cfg = WalkConfig::new(tree.path(), cfg.include_tests, cfg.filter)
+        .with_jobs(cfg.jobs)
+        .with_cache(cfg.cache)
+        .with_tree(Some(&tree));
+    let before_files = collect(&ref_cfg, min_lines, model);
+    let before = score_collected(&before_files, top_changes, model);
+
+    let mut score_diff = diff::compute_diff(git_ref, &before, &after);
+    score_diff.files = diff::compute_file_deltas(
+        &diff::file_snapshots(&before_files.file_metrics, tree.path(), model),
+        &diff::file_snapshots(&after_files.file_metrics, cfg.path, model),
+        top_changes,
+    );
+    Ok(score_diff)
+}
+
 /// Entry point for `km score --trend`: compare current working tree against a git ref.
 /// Quality gates in `gate` are checked after the report is printed so CI logs are complete.
 pub fn run_diff(
     cfg: &WalkConfig<'_>,
     git_ref: &str,
     output: OutputMode,
-    bottom: usize,
+    top_changes: usize,
     min_lines: usize,
-    model: &str,
+    model: &ScoreModel,
     gate: ScoreGate,
 ) -> Result<(), Box<dyn Error>> {
-    let scoring_model = ScoringModel::from_arg(model);
-
-    // Score the current working tree.
-    let after = compute_score(cfg, bottom, min_lines, &scoring_model)?;
-
-    // Open the git repo and extract the ref tree into a temp directory.
-    let repo = GitRepo::open(cfg.path)?;
-    let tmpdir = tempfile::tempdir()?;
-    repo.extract_tree_to_dir(git_ref, tmpdir.path())?;
-
-    // Handle subdirectory case: if the user pointed at a subdir of the repo,
-    // analyze the corresponding subdir inside the extracted tree.
-    let (_, prefix) = repo.walk_prefix(cfg.path)?;
-    let tmp_path = if prefix.as_os_str().is_empty() {
-        tmpdir.path().to_path_buf()
-    } else {
-        tmpdir.path().join(&prefix)
-    };
-
-    // Score the ref tree.
-    let ref_cfg = WalkConfig::new(&tmp_path, cfg.include_tests, cfg.filter);
-    let before = compute_score(&ref_cfg, bottom, min_lines, &scoring_model)?;
-
-    let score_diff = diff::compute_diff(git_ref, &before, &after);
+    let score_diff = analyze_diff(cfg, git_ref, top_changes, min_lines, model)?;
 
     // Always print first so CI logs show the full report before any gate error.
     match output {
         OutputMode::Json => diff_report::print_json(&score_diff)?,
         OutputMode::Short => diff_report::print_short(&score_diff),
         OutputMode::Terse => diff_report::print_terse(&score_diff),
-        OutputMode::Github | OutputMode::Codeclimate => {
+        OutputMode::Github | OutputMode::Codeclimate | OutputMode::Sarif => {
             return Err(crate::cli::ERR_CI_FORMAT_ONLY.into());
         }
+        OutputMode::Html => {
+            return Err(crate::cli::ERR_REPORT_FORMAT_ONLY.into());
+        }
+        OutputMode::Markdown => diff_report::print_markdown(&score_diff),
         OutputMode::Table => diff_report::print_report(&score_diff),
     }
 
@@ -195,34 +235,163 @@ pub fn run_diff(
     Ok(())
 }
 
-/// Walk all source files, compute per-file and project-level metrics,
-/// normalize each dimension, and produce the final `ProjectScore`.
-pub(crate) fn compute_score(
+/// Entry point for `km score --by-dir` / `--by-package`: score each
+/// directory or package separately.
+pub fn run_breakdown(
     cfg: &WalkConfig<'_>,
+    output: OutputMode,
+    min_lines: usize,
+    model: &ScoreModel,
+    by: Breakdown,
+) -> Result<(), Box<dyn Error>> {
+    let result = breakdown::compute_breakdown(cfg, min_lines, model, by)?;
+    match output {
+        OutputMode::Json => breakdown_report::print_json(&result)?,
+        OutputMode::Short => breakdown_report::print_short(&result),
+        OutputMode::Terse => breakdown_report::print_terse(&result),
+        OutputMode::Github | OutputMode::Codeclimate | OutputMode::Sarif => {
+            return Err(crate::cli::ERR_CI_FORMAT_ONLY.into());
+        }
+        OutputMode::Html | OutputMode::Markdown => {
+            return Err(crate::cli::ERR_REPORT_FORMAT_ONLY.into());
+        }
+        OutputMode::Table => breakdown_report::print_report(&result),
+    }
+    Ok(())
+}
+
+/// Entry point for `km score --new-code-since`: score only the code changed
+/// since `git_ref`. The `fail_below` gate applies to the new-code grade and is
+/// checked after the report is printed; it passes when nothing changed.
+pub fn run_new_code(
+    cfg: &WalkConfig<'_>,
+    git_ref: &str,
+    output: OutputMode,
     bottom: usize,
     min_lines: usize,
-    model: &ScoringModel,
-) -> Result<ProjectScore, Box<dyn Error>> {
+    model: &ScoreModel,
+    fail_below: Option<analyzer::Grade>,
+) -> Result<(), Box<dyn Error>> {
+    let result = new_code::compute_new_code(cfg, git_ref, bottom, min_lines, model)?;
+    match output {
+        OutputMode::Json => new_code_report::print_json(&result)?,
+        OutputMode::Short => new_code_report::print_short(&result),
+        OutputMode::Terse => new_code_report::print_terse(&result),
+        OutputMode::Github | OutputMode::Codeclimate | OutputMode::Sarif => {
+            return Err(crate::cli::ERR_CI_FORMAT_ONLY.into());
+        }
+        OutputMode::Html | OutputMode::Markdown => {
+            return Err(crate::cli::ERR_REPORT_FORMAT_ONLY.into());
+        }
+        OutputMode::Table => new_code_report::print_report(&result),
+    }
+
+    let new = &result.new_code;
+    if let Some(threshold) = fail_below
+        && new.files_analyzed > 0
+        && new.grade.numeric_rank() < threshold.numeric_rank()
+    {
+        return Err(format!(
+            "quality gate failed: new code score {} is below minimum threshold {}",
+            new.grade, threshold
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Per-file metrics and project-level duplicates from one walk.
+struct Collected {
+    file_metrics: Vec<FileMetrics>,
+    /// Normalized (duplication-comparable) line count per file, parallel to
+    /// `file_metrics`.
+    normalized_lines: Vec<usize>,
+    dup_groups: Vec<dups::detector::DuplicateGroup>,
+}
+
+/// Walk all source files, compute per-file metrics, and detect duplicates
+/// across the whole walk.
+fn collect(cfg: &WalkConfig<'_>, min_lines: usize, model: &ScoreModel) -> Collected {
     let exclude_tests = cfg.exclude_tests();
     let mut file_metrics: Vec<FileMetrics> = Vec::new();
+    let mut normalized_lines: Vec<usize> = Vec::new();
     let mut dup_files: Vec<dups::detector::NormalizedFile> = Vec::new();
-    let mut total_code_lines: usize = 0;
 
-    for (file_path, spec) in cfg.source_files() {
-        if let Some(result) = analyze_single_file(&file_path, spec, exclude_tests, model) {
-            total_code_lines += result.normalized_count;
-            dup_files.push(result.dup_file);
-            file_metrics.push(result.metrics);
-        }
+    let files = cfg.source_files();
+    let analyzed = cfg.par_map(&files, |(file_path, spec)| {
+        analyze_single_file(file_path, spec, exclude_tests, &model.kind, cfg.cache)
+    });
+    for result in analyzed.into_iter().flatten() {
+        normalized_lines.push(result.normalized_count);
+        dup_files.push(result.dup_file);
+        file_metrics.push(result.metrics);
     }
 
-    // Duplication (project-level)
     let dup_groups = if dup_files.is_empty() {
         Vec::new()
     } else {
         dups::detector::detect_duplicates(&dup_files, min_lines, true)
     };
-    let duplicated_lines: usize = dup_groups.iter().map(|g| g.duplicated_lines()).sum();
+    Collected {
+        file_metrics,
+        normalized_lines,
+        dup_groups,
+    }
+}
+
+/// Entry point for `km score plan`: list the file refactorings that bring
+/// the project to `target`.
+pub fn run_plan(
+    cfg: &WalkConfig<'_>,
+    output: OutputMode,
+    min_lines: usize,
+    model: &ScoreModel,
+    target: analyzer::Grade,
+    healthy: &std::collections::BTreeMap<String, f64>,
+) -> Result<(), Box<dyn Error>> {
+    let healthy = plan::healthy_profile(model, healthy)?;
+    let result = plan::compute_plan(cfg, min_lines, model, target, healthy)?;
+    match output {
+        OutputMode::Json => plan_report::print_json(&result)?,
+        OutputMode::Short => plan_report::print_short(&result),
+        OutputMode::Terse => plan_report::print_terse(&result),
+        OutputMode::Github | OutputMode::Codeclimate | OutputMode::Sarif => {
+            return Err(crate::cli::ERR_CI_FORMAT_ONLY.into());
+        }
+        OutputMode::Html | OutputMode::Markdown => {
+            return Err(crate::cli::ERR_REPORT_FORMAT_ONLY.into());
+        }
+        OutputMode::Table => plan_report::print_report(&result),
+    }
+    Ok(())
+}
+
+/// Walk all source files, compute per-file and project-level metrics,
+/// normalize each dimension, and produce the final `ProjectScore`.
+pub fn compute_score(
+    cfg: &WalkConfig<'_>,
+    bottom: usize,
+    min_lines: usize,
+    model: &ScoreModel,
+) -> Result<ProjectScore, Box<dyn Error>> {
+    Ok(score_collected(
+        &collect(cfg, min_lines, model),
+        bottom,
+        model,
+    ))
+}
+
+/// Score the whole walk from already-collected metrics.
+fn score_collected(collected: &Collected, bottom: usize, model: &ScoreModel) -> ProjectScore {
+    let file_metrics = &collected.file_metrics;
+
+    // Duplication (project-level)
+    let total_code_lines: usize = collected.normalized_lines.iter().sum();
+    let duplicated_lines: usize = collected
+        .dup_groups
+        .iter()
+        .map(|g| g.duplicated_lines())
+        .sum();
     let dup_percent = if total_code_lines == 0 {
         0.0
     } else {
@@ -234,31 +403,36 @@ pub(crate) fn compute_score(
 
     if files_analyzed == 0 {
         let dimensions = build_empty_dimensions(model);
-        return Ok(ProjectScore {
+        return ProjectScore {
             score: 0.0,
             grade: score_to_grade(0.0),
             files_analyzed: 0,
             total_loc: 0,
             dimensions,
             needs_attention: vec![],
-        });
+            model: model.clone(),
+        };
     }
 
-    let dimensions = build_dimensions(&file_metrics, total_loc, dup_percent, model);
+    let dimensions = build_dimensions(file_metrics, total_loc, dup_percent, model);
     let project_score = compute_project_score(&dimensions);
-    let mut file_scores: Vec<FileScore> =
-        file_metrics.iter().map(|f| score_file(f, model)).collect();
-    file_scores.sort_by(|a, b| a.score.total_cmp(&b.score));
-    file_scores.truncate(bottom);
-
-    Ok(ProjectScore {
+    ProjectScore {
         score: project_score,
         grade: score_to_grade(project_score),
         files_analyzed,
         total_loc,
         dimensions,
-        needs_attention: file_scores,
-    })
+        needs_attention: worst_files(file_metrics, bottom, model),
+        model: model.clone(),
+    }
+}
+
+/// Per-file scores of `files`, worst first, truncated to `bottom`.
+fn worst_files(files: &[FileMetrics], bottom: usize, model: &ScoreModel) -> Vec<FileScore> {
+    let mut file_scores: Vec<FileScore> = files.iter().map(|f| score_file(f, model)).collect();
+    file_scores.sort_by(|a, b| a.score.total_cmp(&b.score));
+    file_scores.truncate(bottom);
+    file_scores
 }
 
 #[cfg(test)]
@@ -11,7 +11,7 @@ fn run_on_empty_dir() {
     let dir = tempfile::tempdir().unwrap();
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(dir.path(), false, &filter);
-    run(&cfg, OutputMode::Table, 10, 6, "cogcom").unwrap();
+    run(&cfg, OutputMode::Table, 10, 6, &ScoreModel::default()).unwrap();
 }
 
 #[test]
@@ -24,7 +24,7 @@ fn run_on_rust_file() {
     .unwrap();
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(dir.path(), false, &filter);
-    let model = ScoringModel::Cognitive;
+    let model = ScoreModel::default();
     let score = compute_score(&cfg, 10, 6, &model).unwrap();
     assert!(
         score.score > 50.0,
@@ -46,7 +46,7 @@ fn run_on_rust_file_legacy_model() {
     .unwrap();
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(dir.path(), false, &filter);
-    let model = ScoringModel::Legacy;
+    let model = ScoreModel::defaults(ScoringModel::Legacy);
     let score = compute_score(&cfg, 10, 6, &model).unwrap();
     assert!(
         score.score > 50.0,
@@ -60,6 +60,63 @@ fn run_on_rust_file_legacy_model() {
     assert_eq!(score.dimensions[1].name, "Cyclomatic Complexity");
 }
 
+#[test]
+fn configured_model_changes_score_and_is_reported() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("main.rs"),
+        "fn main() {\n    let x = 1;\n    println!(\"{}\", x);\n}\n",
+    )
+    .unwrap();
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(dir.path(), false, &filter);
+    let default = compute_score(&cfg, 10, 6, &ScoreModel::default()).unwrap();
+
+    // A size curve that scores every file 0 pulls the project score down.
+    let score_cfg: crate::config::ScoreConfig =
+        toml::from_str("[curves.size]\nbreakpoints = [[0, 0], [1, 0]]\n").unwrap();
+    let strict = ScoreModel::resolve("cogcom", &score_cfg).unwrap();
+    let tuned = compute_score(&cfg, 10, 6, &strict).unwrap();
+    assert!(
+        tuned.score < default.score,
+        "{} should be below {}",
+        tuned.score,
+        default.score
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_str(&report::format_json(&tuned, None).unwrap()).unwrap();
+    assert_eq!(json["model"]["name"], "cogcom");
+    assert_eq!(
+        json["model"]["curves"]["size"],
+        serde_json::json!([[0.0, 0.0], [1.0, 0.0]])
+    );
+}
+
+#[test]
+fn run_breakdown_all_formats() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir(dir.path().join("src")).unwrap();
+    fs::write(
+        dir.path().join("src/main.rs"),
+        "fn main() {\n    let x = 1;\n}\n",
+    )
+    .unwrap();
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(dir.path(), false, &filter);
+    let model = ScoreModel::default();
+    for output in [
+        OutputMode::Table,
+        OutputMode::Json,
+        OutputMode::Short,
+        OutputMode::Terse,
+    ] {
+        run_breakdown(&cfg, output, 6, &model, Breakdown::Dir(1)).unwrap();
+        run_breakdown(&cfg, output, 6, &model, Breakdown::Package).unwrap();
+    }
+    assert!(run_breakdown(&cfg, OutputMode::Sarif, 6, &model, Breakdown::Dir(1)).is_err());
+}
+
 #[test]
 fn run_json_output() {
     let dir = tempfile::tempdir().unwrap();
@@ -70,7 +127,7 @@ fn run_json_output() {
     .unwrap();
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(dir.path(), false, &filter);
-    run(&cfg, OutputMode::Json, 10, 6, "cogcom").unwrap();
+    run(&cfg, OutputMode::Json, 10, 6, &ScoreModel::default()).unwrap();
 }
 
 #[test]
@@ -84,7 +141,7 @@ fn run_includes_tests_with_flag() {
     .unwrap();
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(dir.path(), true, &filter);
-    let model = ScoringModel::Cognitive;
+    let model = ScoreModel::default();
     let score = compute_score(&cfg, 10, 6, &model).unwrap();
     assert_eq!(score.files_analyzed, 1);
 }
@@ -100,7 +157,7 @@ fn run_excludes_tests_by_default() {
     .unwrap();
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(dir.path(), false, &filter);
-    let model = ScoringModel::Cognitive;
+    let model = ScoreModel::default();
     let score = compute_score(&cfg, 10, 6, &model).unwrap();
     assert_eq!(score.files_analyzed, 0);
 }
@@ -110,7 +167,7 @@ fn run_on_current_repo() {
     // Smoke test on the actual repo
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(Path::new("."), false, &filter);
-    run(&cfg, OutputMode::Table, 5, 6, "cogcom").unwrap();
+    run(&cfg, OutputMode::Table, 5, 6, &ScoreModel::default()).unwrap();
 }
 
 #[test]
@@ -118,7 +175,14 @@ fn run_on_current_repo_legacy() {
     // Smoke test on the actual repo with legacy model
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(Path::new("."), false, &filter);
-    run(&cfg, OutputMode::Table, 5, 6, "legacy").unwrap();
+    run(
+        &cfg,
+        OutputMode::Table,
+        5,
+        6,
+        &ScoreModel::defaults(ScoringModel::Legacy),
+    )
+    .unwrap();
 }
 
 #[test]
@@ -159,7 +223,7 @@ fn excludes_markdown_files() {
     fs::write(dir.path().join("README.md"), "# Hello\n\nWorld\n").unwrap();
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(dir.path(), false, &filter);
-    let model = ScoringModel::Cognitive;
+    let model = ScoreModel::default();
     let score = compute_score(&cfg, 10, 6, &model).unwrap();
     assert_eq!(score.files_analyzed, 0, "Markdown should be excluded");
 }
@@ -174,7 +238,7 @@ fn excludes_toml_files() {
     .unwrap();
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(dir.path(), false, &filter);
-    let model = ScoringModel::Cognitive;
+    let model = ScoreModel::default();
     let score = compute_score(&cfg, 10, 6, &model).unwrap();
     assert_eq!(score.files_analyzed, 0, "TOML should be excluded");
 }
@@ -185,7 +249,7 @@ fn excludes_json_files() {
     fs::write(dir.path().join("data.json"), "{\"key\": \"value\"}\n").unwrap();
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(dir.path(), false, &filter);
-    let model = ScoringModel::Cognitive;
+    let model = ScoreModel::default();
     let score = compute_score(&cfg, 10, 6, &model).unwrap();
     assert_eq!(score.files_analyzed, 0, "JSON should be excluded");
 }
@@ -201,7 +265,7 @@ fn run_on_single_file() {
     .unwrap();
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(&file, false, &filter);
-    let model = ScoringModel::Cognitive;
+    let model = ScoreModel::default();
     let score = compute_score(&cfg, 10, 6, &model).unwrap();
     assert_eq!(score.files_analyzed, 1);
     assert!(score.total_loc > 0);
@@ -217,7 +281,7 @@ fn dimensions_sum_to_100_percent() {
     .unwrap();
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(dir.path(), false, &filter);
-    let model = ScoringModel::Cognitive;
+    let model = ScoreModel::default();
     let score = compute_score(&cfg, 10, 6, &model).unwrap();
     let total_weight: f64 = score.dimensions.iter().map(|d| d.weight).sum();
     assert!(
@@ -236,7 +300,7 @@ fn legacy_dimensions_sum_to_100_percent() {
     .unwrap();
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(dir.path(), false, &filter);
-    let model = ScoringModel::Legacy;
+    let model = ScoreModel::defaults(ScoringModel::Legacy);
     let score = compute_score(&cfg, 10, 6, &model).unwrap();
     let total_weight: f64 = score.dimensions.iter().map(|d| d.weight).sum();
     assert!(
@@ -368,7 +432,15 @@ fn deeply_nested(x: i32) -> i32 {
         fail_if_worse: true,
         fail_below: None,
     };
-    let result = run_diff(&cfg, "HEAD~1", OutputMode::Table, 10, 6, "cogcom", gate);
+    let result = run_diff(
+        &cfg,
+        "HEAD~1",
+        OutputMode::Table,
+        10,
+        6,
+        &ScoreModel::default(),
+        gate,
+    );
 
     let msg = result
         .expect_err(
@@ -469,7 +541,7 @@ fn run_diff_on_git_repo() {
         OutputMode::Table,
         5,
         6,
-        "cogcom",
+        &ScoreModel::default(),
         ScoreGate::default(),
     );
     assert!(result.is_ok(), "run_diff should succeed: {:?}", result);
@@ -486,12 +558,23 @@ fn run_diff_json_on_git_repo() {
         OutputMode::Json,
         5,
         6,
-        "cogcom",
+        &ScoreModel::default(),
         ScoreGate::default(),
     );
     assert!(result.is_ok(), "run_diff JSON should succeed: {:?}", result);
 }
 
+#[test]
+fn run_diff_markdown_on_git_repo() {
+    let (dir, _repo) = create_test_repo_with_rust_file();
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(dir.path(), true, &filter);
+    let model = ScoreModel::default();
+    let gate = ScoreGate::default();
+    run_diff(&cfg, "HEAD", OutputMode::Markdown, 5, 6, &model, gate).unwrap();
+    assert!(run_diff(&cfg, "HEAD", OutputMode::Html, 5, 6, &model, gate).is_err());
+}
+
 #[test]
 fn run_diff_legacy_model_on_git_repo() {
     let (dir, _repo) = create_test_repo_with_rust_file();
@@ -503,7 +586,7 @@ fn run_diff_legacy_model_on_git_repo() {
         OutputMode::Table,
         5,
         6,
-        "legacy",
+        &ScoreModel::defaults(ScoringModel::Legacy),
         ScoreGate::default(),
     );
     assert!(
@@ -519,7 +602,15 @@ fn score_gate_no_flags_succeeds() {
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(dir.path(), false, &filter);
     let gate = ScoreGate::default();
-    let result = run_diff(&cfg, "HEAD", OutputMode::Table, 10, 6, "cogcom", gate);
+    let result = run_diff(
+        &cfg,
+        "HEAD",
+        OutputMode::Table,
+        10,
+        6,
+        &ScoreModel::default(),
+        gate,
+    );
     assert!(result.is_ok(), "no gates should always succeed: {result:?}");
 }
 
@@ -533,7 +624,15 @@ fn score_gate_fail_below_passes_when_above_threshold() {
         fail_if_worse: false,
         fail_below: Some(analyzer::Grade::FMinusMinus),
     };
-    let result = run_diff(&cfg, "HEAD", OutputMode::Table, 10, 6, "cogcom", gate);
+    let result = run_diff(
+        &cfg,
+        "HEAD",
+        OutputMode::Table,
+        10,
+        6,
+        &ScoreModel::default(),
+        gate,
+    );
     assert!(
         result.is_ok(),
         "F-- threshold should not trigger on clean code"
@@ -559,6 +658,7 @@ fn score_gate_fail_below_unit() {
         loc_before: 10,
         loc_after: 10,
         dimensions: vec![],
+        files: vec![],
     };
     // Gate: fail if below B — current score is C, so should fail
     let threshold = analyzer::Grade::B;
@@ -586,7 +686,15 @@ fn score_gate_fail_if_worse_same_ref_passes() {
         fail_if_worse: true,
         fail_below: None,
     };
-    let result = run_diff(&cfg, "HEAD", OutputMode::Table, 10, 6, "cogcom", gate);
+    let result = run_diff(
+        &cfg,
+        "HEAD",
+        OutputMode::Table,
+        10,
+        6,
+        &ScoreModel::default(),
+        gate,
+    );
     assert!(
         result.is_ok(),
         "same ref comparison should not trigger fail-if-worse"
@@ -598,14 +706,14 @@ fn run_on_current_repo_with_target() {
     // Test with an explicit non-"." path to exercise the target display branch
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(Path::new("src"), false, &filter);
-    run(&cfg, OutputMode::Table, 5, 6, "cogcom").unwrap();
+    run(&cfg, OutputMode::Table, 5, 6, &ScoreModel::default()).unwrap();
 }
 
 #[test]
 fn run_json_with_target_path() {
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(Path::new("src"), false, &filter);
-    run(&cfg, OutputMode::Json, 5, 6, "cogcom").unwrap();
+    run(&cfg, OutputMode::Json, 5, 6, &ScoreModel::default()).unwrap();
 }
 
 #[test]
@@ -614,7 +722,7 @@ fn run_short_format() {
     fs::write(dir.path().join("main.rs"), "fn main() { let x = 1; }\n").unwrap();
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(dir.path(), false, &filter);
-    run(&cfg, OutputMode::Short, 10, 6, "cogcom").unwrap();
+    run(&cfg, OutputMode::Short, 10, 6, &ScoreModel::default()).unwrap();
 }
 
 #[test]
@@ -623,7 +731,7 @@ fn run_terse_format() {
     fs::write(dir.path().join("main.rs"), "fn main() { let x = 1; }\n").unwrap();
     let filter = ExcludeFilter::default();
     let cfg = WalkConfig::new(dir.path(), false, &filter);
-    run(&cfg, OutputMode::Terse, 10, 6, "cogcom").unwrap();
+    run(&cfg, OutputMode::Terse, 10, 6, &ScoreModel::default()).unwrap();
 }
 
 #[test]
@@ -635,7 +743,16 @@ fn run_diff_short_format() {
         fail_if_worse: false,
         fail_below: None,
     };
-    run_diff(&cfg, "HEAD", OutputMode::Short, 10, 6, "cogcom", gate).unwrap();
+    run_diff(
+        &cfg,
+        "HEAD",
+        OutputMode::Short,
+        10,
+        6,
+        &ScoreModel::default(),
+        gate,
+    )
+    .unwrap();
 }
 
 #[test]
@@ -647,5 +764,86 @@ fn run_diff_terse_format() {
         fail_if_worse: false,
         fail_below: None,
     };
-    run_diff(&cfg, "HEAD", OutputMode::Terse, 10, 6, "cogcom", gate).unwrap();
+    run_diff(
+        &cfg,
+        "HEAD",
+        OutputMode::Terse,
+        10,
+        6,
+        &ScoreModel::default(),
+        gate,
+    )
+    .unwrap();
+}
+
+#[test]
+fn run_new_code_all_formats_and_gate() {
+    let (dir, _repo) = create_test_repo_with_rust_file();
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(dir.path(), true, &filter);
+    let model = ScoreModel::default();
+    for output in [
+        OutputMode::Table,
+        OutputMode::Json,
+        OutputMode::Short,
+        OutputMode::Terse,
+    ] {
+        run_new_code(&cfg, "HEAD", output, 5, 6, &model, None).unwrap();
+    }
+    // Nothing changed since HEAD, so even the strictest gate passes.
+    let strict = analyzer::Grade::parse("A++").unwrap();
+    run_new_code(&cfg, "HEAD", OutputMode::Terse, 5, 6, &model, Some(strict)).unwrap();
+
+    std::fs::write(
+        dir.path().join("extra.rs"),
+        "fn f(a: i32) -> i32 {\n    if a > 0 {\n        if a > 1 {\n            if a > 2 {\n                return 3;\n            }\n        }\n    }\n    0\n}\n",
+    )
+    .unwrap();
+    let err =
+        run_new_code(&cfg, "HEAD", OutputMode::Terse, 5, 6, &model, Some(strict)).unwrap_err();
+    assert!(err.to_string().contains("new code score"));
+    assert!(run_new_code(&cfg, "HEAD", OutputMode::Sarif, 5, 6, &model, None).is_err());
+}
+
+#[test]
+fn run_plan_all_formats() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("main.rs"),
+        "fn main() {\n    let x = 1;\n}\n",
+    )
+    .unwrap();
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(dir.path(), false, &filter);
+    let model = ScoreModel::default();
+    let healthy = std::collections::BTreeMap::new();
+    for output in [
+        OutputMode::Table,
+        OutputMode::Json,
+        OutputMode::Short,
+        OutputMode::Terse,
+    ] {
+        run_plan(
+            &cfg,
+            output,
+            6,
+            &model,
+            analyzer::Grade::APlusPlus,
+            &healthy,
+        )
+        .unwrap();
+    }
+    assert!(
+        run_plan(
+            &cfg,
+            OutputMode::Sarif,
+            6,
+            &model,
+            analyzer::Grade::B,
+            &healthy
+        )
+        .is_err()
+    );
+    let bad = std::collections::BTreeMap::from([("nope".to_string(), 90.0)]);
+    assert!(run_plan(&cfg, OutputMode::Table, 6, &model, analyzer::Grade::B, &bad).is_err());
 }
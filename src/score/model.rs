@@ -0,0 +1,302 @@
+//! Resolved scoring model: the dimensions a model scores, their weights,
+//! and the normalization curve applied to each.
+//!
+//! Defaults are the calibrated constants in `scoring.rs` and `normalize.rs`.
+//! For the cognitive model, `[score.weights]` and `[score.curves.<dimension>]`
+//! in `.kimun.toml` replace them: weights must sum to 1.0 and each curve must
+//! be monotonic. The legacy model keeps its fixed v0.13 weights and curves
+//! so its scores stay comparable with older releases. The resolved model is
+//! part of `km score --format json`, so a result can always be reproduced.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+
+use serde::ser::{Serialize, SerializeMap, SerializeStruct, Serializer};
+
+use super::ScoringModel;
+use super::normalize::{
+    Breakpoint, COGNITIVE_CURVE, COMPLEXITY_CURVE, DUPLICATION_CURVE, FILE_SIZE_CURVE,
+    HALSTEAD_EPL_CURVE, INDENT_CURVE, MI_CURVE,
+};
+use super::scoring::{
+    W_COGCOM, W_CYCOM, W_DUP, W_DUP_LEGACY, W_HAL, W_HAL_LEGACY, W_INDENT, W_INDENT_LEGACY, W_MI,
+    W_SIZE, W_SIZE_LEGACY,
+};
+use crate::config::{CurveConfig, ScoreConfig};
+
+/// Tolerance for the weights-sum-to-one check, so `0.1 + 0.2`-style
+/// decimal weights from TOML are accepted.
+const WEIGHT_SUM_TOLERANCE: f64 = 1e-9;
+
+/// A scored quality dimension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dimension {
+    Cognitive,
+    MaintainabilityIndex,
+    Cyclomatic,
+    Duplication,
+    Indent,
+    Halstead,
+    Size,
+}
+
+impl Dimension {
+    /// Key used in `.kimun.toml` and in the JSON model description.
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Cognitive => "cognitive",
+            Self::MaintainabilityIndex => "mi",
+            Self::Cyclomatic => "cyclomatic",
+            Self::Duplication => "duplication",
+            Self::Indent => "indent",
+            Self::Halstead => "halstead",
+            Self::Size => "size",
+        }
+    }
+
+    /// Display name in score reports.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Cognitive => "Cognitive Complexity",
+            Self::MaintainabilityIndex => "Maintainability Index",
+            Self::Cyclomatic => "Cyclomatic Complexity",
+            Self::Duplication => "Duplication",
+            Self::Indent => "Indentation Complexity",
+            Self::Halstead => "Halstead Effort",
+            Self::Size => "File Size",
+        }
+    }
+
+    fn default_curve(self) -> &'static [Breakpoint] {
+        match self {
+            Self::Cognitive => COGNITIVE_CURVE,
+            Self::MaintainabilityIndex => MI_CURVE,
+            Self::Cyclomatic => COMPLEXITY_CURVE,
+            Self::Duplication => DUPLICATION_CURVE,
+            Self::Indent => INDENT_CURVE,
+            Self::Halstead => HALSTEAD_EPL_CURVE,
+            Self::Size => FILE_SIZE_CURVE,
+        }
+    }
+}
+
+/// Cognitive model dimensions with their default weights, in report order.
+const COGNITIVE_DIMENSIONS: [(Dimension, f64); 5] = [
+    (Dimension::Cognitive, W_COGCOM),
+    (Dimension::Duplication, W_DUP),
+    (Dimension::Indent, W_INDENT),
+    (Dimension::Halstead, W_HAL),
+    (Dimension::Size, W_SIZE),
+];
+
+/// Legacy model dimensions with their weights, in report order.
+const LEGACY_DIMENSIONS: [(Dimension, f64); 6] = [
+    (Dimension::MaintainabilityIndex, W_MI),
+    (Dimension::Cyclomatic, W_CYCOM),
+    (Dimension::Duplication, W_DUP_LEGACY),
+    (Dimension::Indent, W_INDENT_LEGACY),
+    (Dimension::Halstead, W_HAL_LEGACY),
+    (Dimension::Size, W_SIZE_LEGACY),
+];
+
+/// One dimension of a resolved model.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelDimension {
+    pub dimension: Dimension,
+    pub weight: f64,
+    pub curve: Vec<Breakpoint>,
+}
+
+/// A scoring model with its weights and curves fully resolved.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoreModel {
+    pub kind: ScoringModel,
+    /// Dimensions in report order; weights sum to 1.0.
+    pub dimensions: Vec<ModelDimension>,
+}
+
+impl ScoreModel {
+    /// The model with its built-in weights and curves.
+    pub fn defaults(kind: ScoringModel) -> Self {
+        let table: &[(Dimension, f64)] = match kind {
+            ScoringModel::Cognitive => &COGNITIVE_DIMENSIONS,
+            ScoringModel::Legacy => &LEGACY_DIMENSIONS,
+        };
+        Self {
+            kind,
+            dimensions: table
+                .iter()
+                .map(|&(dimension, weight)| ModelDimension {
+                    dimension,
+                    weight,
+                    curve: dimension.default_curve().to_vec(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Resolve the model named `model` (`cogcom` or `legacy`), applying the
+    /// `[score.weights]` and `[score.curves]` overrides from `cfg`.
+    pub fn resolve(model: &str, cfg: &ScoreConfig) -> Result<Self, Box<dyn Error>> {
+        let kind = ScoringModel::from_arg(model);
+        let mut resolved = Self::defaults(kind);
+        if cfg.weights.is_empty() && cfg.curves.is_empty() {
+            return Ok(resolved);
+        }
+        if kind == ScoringModel::Legacy {
+            eprintln!(
+                "warning: [score.weights] and [score.curves] apply to the cogcom model; \
+                 the legacy model uses its fixed weights and curves"
+            );
+            return Ok(resolved);
+        }
+        resolved.apply_weights(&cfg.weights)?;
+        resolved.apply_curves(&cfg.curves)?;
+        Ok(resolved)
+    }
+
+    /// Short model name as accepted by `--model`.
+    pub fn name(&self) -> &'static str {
+        match self.kind {
+            ScoringModel::Cognitive => "cogcom",
+            ScoringModel::Legacy => "legacy",
+        }
+    }
+
+    fn dimension_mut(&mut self, key: &str, table: &str) -> Result<&mut ModelDimension, String> {
+        let valid: Vec<&str> = self.dimensions.iter().map(|d| d.dimension.key()).collect();
+        let valid = valid.join(", ");
+        self.dimensions
+            .iter_mut()
+            .find(|d| d.dimension.key() == key)
+            .ok_or_else(|| {
+                format!("[{table}]: unknown dimension '{key}' (expected one of: {valid})")
+            })
+    }
+
+    fn apply_weights(&mut self, weights: &BTreeMap<String, f64>) -> Result<(), String> {
+        for (key, &weight) in weights {
+            if !(0.0..=1.0).contains(&weight) {
+                return Err(format!(
+                    "[score.weights]: {key} = {weight} is outside 0.0-1.0"
+                ));
+            }
+            self.dimension_mut(key, "score.weights")?.weight = weight;
+        }
+        let sum: f64 = self.dimensions.iter().map(|d| d.weight).sum();
+        if (sum - 1.0).abs() > WEIGHT_SUM_TOLERANCE {
+            let listed: Vec<String> = self
+                .dimensions
+                .iter()
+                .map(|d| format!("{} = {}", d.dimension.key(), d.weight))
+                .collect();
+            return Err(format!(
+                "[score.weights]: weights must sum to 1.0, got {sum} ({})",
+                listed.join(", ")
+            ));
+        }
+        let per_file: f64 = self
+            .dimensions
+            .iter()
+            .filter(|d| d.dimension != Dimension::Duplication)
+            .map(|d| d.weight)
+            .sum();
+        if per_file <= 0.0 {
+            return Err(
+                "[score.weights]: at least one dimension besides duplication needs a weight"
+                    .to_string(),
+            );
+        }
+        Ok(())
+    }
+
+    fn apply_curves(&mut self, curves: &BTreeMap<String, CurveConfig>) -> Result<(), String> {
+        for (key, curve) in curves {
+            let table = format!("score.curves.{key}");
+            let curve = parse_curve(&curve.breakpoints).map_err(|e| format!("[{table}]: {e}"))?;
+            self.dimension_mut(key, &table)?.curve = curve;
+        }
+        Ok(())
+    }
+}
+
+impl Default for ScoreModel {
+    fn default() -> Self {
+        Self::defaults(ScoringModel::Cognitive)
+    }
+}
+
+/// Validate `[input, score]` pairs and turn them into a curve: at least two
+/// finite points, inputs strictly ascending, scores within 0–100 and
+/// monotonically non-increasing (a worse metric never scores higher).
+fn parse_curve(points: &[[f64; 2]]) -> Result<Vec<Breakpoint>, String> {
+    if points.len() < 2 {
+        return Err("a curve needs at least 2 breakpoints".to_string());
+    }
+    for &[input, score] in points {
+        if !input.is_finite() || !score.is_finite() {
+            return Err(format!(
+                "breakpoint [{input}, {score}] is not a finite number"
+            ));
+        }
+        if !(0.0..=100.0).contains(&score) {
+            return Err(format!("score {score} at input {input} is outside 0-100"));
+        }
+    }
+    for w in points.windows(2) {
+        let ([a_in, a_score], [b_in, b_score]) = (w[0], w[1]);
+        if b_in <= a_in {
+            return Err(format!(
+                "inputs must be strictly ascending ({a_in} is followed by {b_in})"
+            ));
+        }
+        if b_score > a_score {
+            return Err(format!(
+                "scores must not rise as the input grows ({a_score} at {a_in}, {b_score} at {b_in})"
+            ));
+        }
+    }
+    Ok(points
+        .iter()
+        .map(|&[input, score]| Breakpoint { input, score })
+        .collect())
+}
+
+/// Serializes as `{"name", "weights": {dimension: weight}, "curves":
+/// {dimension: [[input, score], ...]}}`, dimensions in report order.
+impl Serialize for ScoreModel {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        struct Weights<'a>(&'a [ModelDimension]);
+        impl Serialize for Weights<'_> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let mut map = serializer.serialize_map(Some(self.0.len()))?;
+                for d in self.0 {
+                    map.serialize_entry(d.dimension.key(), &d.weight)?;
+                }
+                map.end()
+            }
+        }
+        struct Curves<'a>(&'a [ModelDimension]);
+        impl Serialize for Curves<'_> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let mut map = serializer.serialize_map(Some(self.0.len()))?;
+                for d in self.0 {
+                    let points: Vec<[f64; 2]> =
+                        d.curve.iter().map(|b| [b.input, b.score]).collect();
+                    map.serialize_entry(d.dimension.key(), &points)?;
+                }
+                map.end()
+            }
+        }
+
+        let mut s = serializer.serialize_struct("ScoreModel", 3)?;
+        s.serialize_field("name", self.name())?;
+        s.serialize_field("weights", &Weights(&self.dimensions))?;
+        s.serialize_field("curves", &Curves(&self.dimensions))?;
+        s.end()
+    }
+}
+
+#[cfg(test)]
+#[path = "model_test.rs"]
+mod tests;
@@ -0,0 +1,162 @@
+use super::*;
+
+fn score_cfg(toml_src: &str) -> ScoreConfig {
+    toml::from_str(toml_src).unwrap()
+}
+
+fn resolve_err(model: &str, toml_src: &str) -> String {
+    ScoreModel::resolve(model, &score_cfg(toml_src))
+        .unwrap_err()
+        .to_string()
+}
+
+#[test]
+fn defaults_weights_sum_to_one() {
+    for kind in [ScoringModel::Cognitive, ScoringModel::Legacy] {
+        let sum: f64 = ScoreModel::defaults(kind)
+            .dimensions
+            .iter()
+            .map(|d| d.weight)
+            .sum();
+        assert!((sum - 1.0).abs() < 1e-10, "{kind:?} weights sum to {sum}");
+    }
+}
+
+#[test]
+fn resolve_without_overrides_returns_defaults() {
+    let cfg = ScoreConfig::default();
+    assert_eq!(
+        ScoreModel::resolve("cogcom", &cfg).unwrap(),
+        ScoreModel::default()
+    );
+    assert_eq!(
+        ScoreModel::resolve("legacy", &cfg).unwrap(),
+        ScoreModel::defaults(ScoringModel::Legacy)
+    );
+}
+
+#[test]
+fn resolve_applies_weights() {
+    let cfg = score_cfg("[weights]\nduplication = 0.30\nsize = 0.05\n");
+    let model = ScoreModel::resolve("cogcom", &cfg).unwrap();
+    let weight = |dim| {
+        model
+            .dimensions
+            .iter()
+            .find(|d| d.dimension == dim)
+            .unwrap()
+            .weight
+    };
+    assert_eq!(weight(Dimension::Duplication), 0.30);
+    assert_eq!(weight(Dimension::Size), 0.05);
+    assert_eq!(weight(Dimension::Cognitive), W_COGCOM);
+}
+
+#[test]
+fn resolve_applies_curve() {
+    let cfg = score_cfg("[curves.duplication]\nbreakpoints = [[0, 100], [10, 0]]\n");
+    let model = ScoreModel::resolve("cogcom", &cfg).unwrap();
+    let dup = model
+        .dimensions
+        .iter()
+        .find(|d| d.dimension == Dimension::Duplication)
+        .unwrap();
+    assert_eq!(
+        dup.curve,
+        vec![
+            Breakpoint {
+                input: 0.0,
+                score: 100.0
+            },
+            Breakpoint {
+                input: 10.0,
+                score: 0.0
+            },
+        ]
+    );
+}
+
+#[test]
+fn weights_must_sum_to_one() {
+    let err = resolve_err("cogcom", "[weights]\ncognitive = 0.5\n");
+    assert!(err.contains("must sum to 1.0"), "{err}");
+    assert!(err.contains("1.2"), "sum should be reported: {err}");
+}
+
+#[test]
+fn weight_out_of_range_is_rejected() {
+    let err = resolve_err("cogcom", "[weights]\ncognitive = 1.5\n");
+    assert!(err.contains("outside 0.0-1.0"), "{err}");
+}
+
+#[test]
+fn unknown_weight_key_is_rejected() {
+    let err = resolve_err("cogcom", "[weights]\nmi = 0.3\n");
+    assert!(err.contains("unknown dimension 'mi'"), "{err}");
+}
+
+#[test]
+fn duplication_only_weights_are_rejected() {
+    let err = resolve_err(
+        "cogcom",
+        "[weights]\ncognitive = 0\nduplication = 1\nindent = 0\nhalstead = 0\nsize = 0\n",
+    );
+    assert!(err.contains("besides duplication"), "{err}");
+}
+
+#[test]
+fn curve_needs_two_points() {
+    let err = resolve_err("cogcom", "[curves.size]\nbreakpoints = [[100, 100]]\n");
+    assert!(err.contains("at least 2 breakpoints"), "{err}");
+}
+
+#[test]
+fn curve_inputs_must_ascend() {
+    let err = resolve_err(
+        "cogcom",
+        "[curves.size]\nbreakpoints = [[500, 100], [100, 50]]\n",
+    );
+    assert!(err.contains("strictly ascending"), "{err}");
+}
+
+#[test]
+fn curve_scores_must_not_rise() {
+    let err = resolve_err(
+        "cogcom",
+        "[curves.cognitive]\nbreakpoints = [[0, 50], [10, 100]]\n",
+    );
+    assert!(err.contains("must not rise"), "{err}");
+}
+
+#[test]
+fn curve_scores_must_be_in_range() {
+    let err = resolve_err(
+        "cogcom",
+        "[curves.indent]\nbreakpoints = [[0, 120], [10, 0]]\n",
+    );
+    assert!(err.contains("outside 0-100"), "{err}");
+}
+
+#[test]
+fn legacy_ignores_overrides() {
+    let cfg = score_cfg("[weights]\ncognitive = 0.5\n");
+    assert_eq!(
+        ScoreModel::resolve("legacy", &cfg).unwrap(),
+        ScoreModel::defaults(ScoringModel::Legacy)
+    );
+}
+
+#[test]
+fn serializes_weights_and_curves_in_model_order() {
+    let json = serde_json::to_string(&ScoreModel::default()).unwrap();
+    assert!(json.starts_with(r#"{"name":"cogcom","weights":{"cognitive":0.3,"duplication":0.2"#));
+    assert!(
+        json.contains(r#""curves":{"cognitive":[[4.0,100.0],"#),
+        "{json}"
+    );
+    let v: serde_json::Value =
+        serde_json::to_value(ScoreModel::defaults(ScoringModel::Legacy)).unwrap();
+    assert_eq!(v["name"], "legacy");
+    assert_eq!(v["weights"]["mi"], 0.3);
+    assert!(v["curves"]["cyclomatic"].is_array());
+}
@@ -0,0 +1,207 @@
+//! "New code" scoring (`km score --new-code-since REF`).
+//!
+//! Grades only what changed since a git ref, so a clean change to a messy
+//! repository is not drowned out by the existing code. Changed lines come
+//! from [`GitRepo::lines_changed_since`] (merge base to working directory).
+//! A file counts as new code when any of its lines changed; file-level
+//! dimensions (indentation, Halstead effort, size) are taken from the whole
+//! file. The complexity dimension only considers touched functions: a
+//! function spans from its declaration to the next detected function, and is
+//! touched when a changed line falls in that span. Duplication is the share
+//! of changed lines inside a duplicated block, whichever copy is newer.
+
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use super::analyzer::{ProjectScore, compute_project_score, score_to_grade};
+use super::collector::FileMetrics;
+use super::model::ScoreModel;
+use super::scoring::build_dimensions;
+use super::{Collected, ScoringModel, collect, score_collected, worst_files};
+use crate::git::GitRepo;
+use crate::loc::language::LanguageSpec;
+use crate::util::read_and_classify;
+use crate::walk::WalkConfig;
+use crate::{cogcom, cycom};
+
+/// A function containing changed lines.
+#[derive(Debug, Clone, Serialize)]
+pub struct TouchedFunction {
+    pub path: String,
+    pub name: String,
+    /// 1-based line of the declaration.
+    pub start_line: usize,
+    /// Cognitive (cogcom model) or cyclomatic (legacy model) complexity.
+    pub complexity: usize,
+}
+
+/// Score of the code changed since a ref, next to the overall score.
+#[derive(Debug, Clone)]
+pub struct NewCodeScore {
+    pub git_ref: String,
+    /// Changed lines in the scored source files.
+    pub lines_changed: usize,
+    /// Score of the changed files and functions only.
+    pub new_code: ProjectScore,
+    /// Score of the whole tree, for comparison.
+    pub overall: ProjectScore,
+    /// Touched functions, most complex first, truncated to `bottom`.
+    pub functions: Vec<TouchedFunction>,
+}
+
+/// Score the code under `cfg` that changed since `git_ref`.
+pub fn compute_new_code(
+    cfg: &WalkConfig<'_>,
+    git_ref: &str,
+    bottom: usize,
+    min_lines: usize,
+    model: &ScoreModel,
+) -> Result<NewCodeScore, Box<dyn Error>> {
+    let repo = GitRepo::open(cfg.path)?;
+    let (_, prefix) = repo.walk_prefix(cfg.path)?;
+    let changed: HashMap<PathBuf, Vec<usize>> = repo
+        .lines_changed_since(git_ref)?
+        .into_iter()
+        .map(|c| (c.path, c.lines))
+        .collect();
+    let changed_lines = |path: &Path| changed.get(&GitRepo::to_git_path(cfg.path, &prefix, path));
+
+    let collected = collect(cfg, min_lines, model);
+    let overall = score_collected(&collected, bottom, model);
+
+    let specs: HashMap<PathBuf, &'static LanguageSpec> = cfg
+        .source_files()
+        .into_iter()
+        .filter(|(path, _)| changed_lines(path).is_some())
+        .collect();
+
+    let mut new_files: Vec<FileMetrics> = Vec::new();
+    let mut functions: Vec<TouchedFunction> = Vec::new();
+    let mut lines_changed = 0;
+    let mut duplicated = 0;
+    for f in &collected.file_metrics {
+        let (Some(lines), Some(spec)) = (changed_lines(&f.path), specs.get(&f.path)) else {
+            continue;
+        };
+        let touched = touched_functions(&f.path, spec, lines, &model.kind);
+        let max = touched.iter().map(|t| t.complexity).max();
+        let mut metrics = f.clone();
+        match model.kind {
+            ScoringModel::Cognitive => metrics.max_cognitive = max,
+            ScoringModel::Legacy => metrics.max_complexity = max,
+        }
+        new_files.push(metrics);
+        functions.extend(touched);
+        lines_changed += lines.len();
+        duplicated += duplicated_changed_lines(&collected, &f.path, lines);
+    }
+
+    functions.sort_by(|a, b| b.complexity.cmp(&a.complexity).then(a.path.cmp(&b.path)));
+    functions.truncate(bottom);
+
+    let dup_percent = if lines_changed == 0 {
+        0.0
+    } else {
+        duplicated as f64 / lines_changed as f64 * 100.0
+    };
+    let total_loc: usize = new_files.iter().map(|f| f.code_lines).sum();
+    let dimensions = build_dimensions(&new_files, total_loc, dup_percent, model);
+    let score = if new_files.is_empty() {
+        0.0
+    } else {
+        compute_project_score(&dimensions)
+    };
+    let new_code = ProjectScore {
+        score,
+        grade: score_to_grade(score),
+        files_analyzed: new_files.len(),
+        total_loc,
+        dimensions,
+        needs_attention: worst_files(&new_files, bottom, model),
+        model: model.clone(),
+    };
+
+    Ok(NewCodeScore {
+        git_ref: git_ref.to_string(),
+        lines_changed,
+        new_code,
+        overall,
+        functions,
+    })
+}
+
+/// Functions of `path` whose span contains one of the changed `lines`.
+fn touched_functions(
+    path: &Path,
+    spec: &LanguageSpec,
+    lines: &[usize],
+    kind: &ScoringModel,
+) -> Vec<TouchedFunction> {
+    let Ok(Some((content, kinds))) = read_and_classify(path, spec) else {
+        return Vec::new();
+    };
+    // (name, start_line, complexity) in declaration order.
+    let mut spans: Vec<(String, usize, usize)> = match kind {
+        ScoringModel::Cognitive => cogcom::analyze_content(&content, &kinds, spec)
+            .map(|c| {
+                c.functions
+                    .into_iter()
+                    .map(|f| (f.name, f.start_line, f.complexity))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        ScoringModel::Legacy => cycom::analyze_content(&content, &kinds, spec)
+            .map(|c| {
+                c.functions
+                    .into_iter()
+                    .map(|f| (f.name, f.start_line, f.complexity))
+                    .collect()
+            })
+            .unwrap_or_default(),
+    };
+    spans.sort_by_key(|s| s.1);
+
+    let ends: Vec<usize> = spans
+        .iter()
+        .skip(1)
+        .map(|s| s.1)
+        .chain([usize::MAX])
+        .collect();
+    spans
+        .into_iter()
+        .zip(ends)
+        .filter(|((_, start, _), end)| {
+            let from = lines.partition_point(|l| l < start);
+            lines.get(from).is_some_and(|l| l < end)
+        })
+        .map(|((name, start_line, complexity), _)| TouchedFunction {
+            path: path.display().to_string(),
+            name,
+            start_line,
+            complexity,
+        })
+        .collect()
+}
+
+/// Number of changed `lines` of `path` inside any duplicated block.
+fn duplicated_changed_lines(collected: &Collected, path: &Path, lines: &[usize]) -> usize {
+    let mut dup: HashSet<usize> = HashSet::new();
+    for loc in collected
+        .dup_groups
+        .iter()
+        .flat_map(|g| &g.locations)
+        .filter(|loc| loc.file_path == path)
+    {
+        let from = lines.partition_point(|l| *l < loc.start_line);
+        let to = lines.partition_point(|l| *l <= loc.end_line);
+        dup.extend(&lines[from..to]);
+    }
+    dup.len()
+}
+
+#[cfg(test)]
+#[path = "new_code_test.rs"]
+mod tests;
@@ -0,0 +1,182 @@
+//! Report formatters for the new-code score.
+//!
+//! The table leads with the new-code grade and the overall grade side by
+//! side, then the new-code dimensions, the most complex touched functions,
+//! and the worst changed files.
+
+use serde::Serialize;
+
+use super::analyzer::{DimensionScore, FileScore, Grade};
+use super::model::ScoreModel;
+use super::new_code::{NewCodeScore, TouchedFunction};
+use super::report::format_thousands;
+use crate::report_helpers;
+
+/// Print the new-code score as a formatted table.
+pub fn print_report(result: &NewCodeScore) {
+    let separator = report_helpers::separator(66);
+    let new = &result.new_code;
+
+    println!("New Code Score since {}", result.git_ref);
+    println!("{separator}");
+    if new.files_analyzed == 0 {
+        println!(" No changed source files.");
+        println!(
+            " Overall:        {} ({:.1})",
+            result.overall.grade, result.overall.score
+        );
+        println!("{separator}");
+        return;
+    }
+    println!(" New Code:       {} ({:.1})", new.grade, new.score);
+    println!(
+        " Overall:        {} ({:.1})",
+        result.overall.grade, result.overall.score
+    );
+    println!(
+        " Changed:        {} files, {} lines",
+        new.files_analyzed,
+        format_thousands(result.lines_changed)
+    );
+    println!("{separator}");
+    println!(
+        " {:<25} {:>6}   {:>5}   {:<5}",
+        "Dimension", "Weight", "Score", "Grade"
+    );
+    println!("{separator}");
+    for d in &new.dimensions {
+        println!(
+            " {:<25} {:>5.0}%   {:>5.1}   {:<5}",
+            d.name,
+            d.weight * 100.0,
+            d.score,
+            d.grade.as_str(),
+        );
+    }
+    println!("{separator}");
+
+    if !result.functions.is_empty() {
+        println!();
+        println!(" Touched Functions (most complex first)");
+        println!("{separator}");
+        for f in &result.functions {
+            println!(
+                " {:>5}  {}:{}  {}",
+                f.complexity, f.path, f.start_line, f.name
+            );
+        }
+        println!("{separator}");
+    }
+
+    if !new.needs_attention.is_empty() {
+        println!();
+        println!(" Changed Files Needing Attention");
+        println!("{separator}");
+        for f in &new.needs_attention {
+            println!(
+                " {:>5.1}  {:<5}  {}   {}",
+                f.score,
+                f.grade.as_str(),
+                f.path.display(),
+                f.issues.join(", "),
+            );
+        }
+        println!("{separator}");
+    }
+}
+
+/// Print the new-code and overall scores as a single compact line.
+pub fn print_short(result: &NewCodeScore) {
+    println!(
+        "new-code ref:{} s:{:.1} g:{} files:{} lines:{} overall:{:.1} overall-g:{}",
+        result.git_ref,
+        result.new_code.score,
+        result.new_code.grade,
+        result.new_code.files_analyzed,
+        result.lines_changed,
+        result.overall.score,
+        result.overall.grade,
+    );
+}
+
+/// Print only the new-code score value.
+pub fn print_terse(result: &NewCodeScore) {
+    println!("{:.1}", result.new_code.score);
+}
+
+#[derive(Serialize)]
+struct JsonOverall {
+    score: f64,
+    grade: Grade,
+    files_analyzed: usize,
+    total_loc: usize,
+}
+
+#[derive(Serialize)]
+struct JsonNewCode<'a> {
+    score: f64,
+    grade: Grade,
+    files_analyzed: usize,
+    total_loc: usize,
+    lines_changed: usize,
+    dimensions: &'a [DimensionScore],
+    functions: &'a [TouchedFunction],
+    needs_attention: Vec<JsonFileScore<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonFileScore<'a> {
+    path: String,
+    score: f64,
+    grade: Grade,
+    issues: &'a [String],
+}
+
+#[derive(Serialize)]
+struct JsonNewCodeScore<'a> {
+    git_ref: &'a str,
+    new_code: JsonNewCode<'a>,
+    overall: JsonOverall,
+    model: &'a ScoreModel,
+}
+
+fn json_file(f: &FileScore) -> JsonFileScore<'_> {
+    JsonFileScore {
+        path: f.path.display().to_string(),
+        score: f.score,
+        grade: f.grade,
+        issues: &f.issues,
+    }
+}
+
+/// Serialize the new-code score as a pretty-printed JSON string.
+pub fn format_json(result: &NewCodeScore) -> Result<String, Box<dyn std::error::Error>> {
+    let new = &result.new_code;
+    let json = JsonNewCodeScore {
+        git_ref: &result.git_ref,
+        new_code: JsonNewCode {
+            score: new.score,
+            grade: new.grade,
+            files_analyzed: new.files_analyzed,
+            total_loc: new.total_loc,
+            lines_changed: result.lines_changed,
+            dimensions: &new.dimensions,
+            functions: &result.functions,
+            needs_attention: new.needs_attention.iter().map(json_file).collect(),
+        },
+        overall: JsonOverall {
+            score: result.overall.score,
+            grade: result.overall.grade,
+            files_analyzed: result.overall.files_analyzed,
+            total_loc: result.overall.total_loc,
+        },
+        model: &new.model,
+    };
+    report_helpers::json_string(&json)
+}
+
+/// Serialize the new-code score as pretty-printed JSON to stdout.
+pub fn print_json(result: &NewCodeScore) -> Result<(), Box<dyn std::error::Error>> {
+    println!("{}", format_json(result)?);
+    Ok(())
+}
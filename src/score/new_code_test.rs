@@ -0,0 +1,121 @@
+use super::*;
+use std::fs;
+
+use git2::Repository;
+
+use crate::walk::ExcludeFilter;
+
+const SIMPLE: &str = "fn simple() -> i32 {\n    1\n}\n";
+
+const NESTED: &str = "\
+fn nested(v: &[i32]) -> i32 {
+    let mut n = 0;
+    for x in v {
+        if *x > 0 {
+            while n < 10 {
+                if n % 2 == 0 {
+                    n += 1;
+                } else {
+                    n += 2;
+                }
+            }
+        }
+    }
+    n
+}
+";
+
+/// Create a repository whose only commit contains `files`.
+fn repo_with(files: &[(&str, &str)]) -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    let repo = Repository::init(dir.path()).unwrap();
+    let mut index = repo.index().unwrap();
+    for (path, content) in files {
+        fs::write(dir.path().join(path), content).unwrap();
+        index.add_path(Path::new(path)).unwrap();
+    }
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let sig =
+        git2::Signature::new("Test", "test@test.com", &git2::Time::new(1_700_000_000, 0)).unwrap();
+    repo.commit(Some("HEAD"), &sig, &sig, "base", &tree, &[])
+        .unwrap();
+    dir
+}
+
+fn new_code(root: &Path) -> NewCodeScore {
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(root, false, &filter);
+    compute_new_code(&cfg, "HEAD", 10, 6, &ScoreModel::default()).unwrap()
+}
+
+#[test]
+fn no_changes_scores_nothing() {
+    let dir = repo_with(&[("a.rs", SIMPLE)]);
+    let result = new_code(dir.path());
+    assert_eq!(result.lines_changed, 0);
+    assert_eq!(result.new_code.files_analyzed, 0);
+    assert_eq!(result.new_code.score, 0.0);
+    assert_eq!(result.overall.files_analyzed, 1);
+}
+
+#[test]
+fn only_changed_files_are_scored() {
+    let dir = repo_with(&[("old.rs", NESTED), ("a.rs", SIMPLE)]);
+    fs::write(
+        dir.path().join("a.rs"),
+        format!("{SIMPLE}\nfn other() -> i32 {{\n    2\n}}\n"),
+    )
+    .unwrap();
+
+    let result = new_code(dir.path());
+    assert_eq!(result.new_code.files_analyzed, 1);
+    assert_eq!(result.lines_changed, 4);
+    assert_eq!(result.overall.files_analyzed, 2);
+    assert!(
+        result.new_code.score > result.overall.score,
+        "the clean change is not dragged down by old.rs"
+    );
+}
+
+#[test]
+fn complexity_only_counts_touched_functions() {
+    let dir = repo_with(&[("a.rs", &format!("{NESTED}\n{SIMPLE}"))]);
+    let edited = format!("{NESTED}\n{}", SIMPLE.replace("    1\n", "    1 + 1\n"));
+    fs::write(dir.path().join("a.rs"), edited).unwrap();
+
+    let result = new_code(dir.path());
+    let names: Vec<&str> = result.functions.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(names, vec!["simple"]);
+    assert_eq!(result.functions[0].start_line, 17);
+    assert!(result.overall.score < result.new_code.score);
+}
+
+#[test]
+fn untracked_files_are_new_code() {
+    let dir = repo_with(&[("a.rs", SIMPLE)]);
+    fs::write(dir.path().join("b.rs"), NESTED).unwrap();
+
+    let result = new_code(dir.path());
+    assert_eq!(result.new_code.files_analyzed, 1);
+    assert_eq!(result.lines_changed, NESTED.lines().count());
+    assert_eq!(result.functions[0].name, "nested");
+    assert!(result.functions[0].complexity > 1);
+}
+
+#[test]
+fn json_reports_new_code_and_overall() {
+    let dir = repo_with(&[("a.rs", SIMPLE)]);
+    fs::write(dir.path().join("b.rs"), NESTED).unwrap();
+
+    let result = new_code(dir.path());
+    let json: serde_json::Value =
+        serde_json::from_str(&super::super::new_code_report::format_json(&result).unwrap())
+            .unwrap();
+    assert_eq!(json["git_ref"], "HEAD");
+    assert_eq!(json["new_code"]["files_analyzed"], 1);
+    assert_eq!(json["new_code"]["lines_changed"], NESTED.lines().count());
+    assert_eq!(json["new_code"]["functions"][0]["name"], "nested");
+    assert_eq!(json["overall"]["files_analyzed"], 2);
+    assert_eq!(json["model"]["name"], "cogcom");
+}
@@ -5,14 +5,19 @@
 //! values to a 0–100 score. Breakpoints are calibrated based on industry
 //! thresholds and empirical testing. Values between breakpoints are
 //! linearly interpolated; values beyond the endpoints are clamped.
+//!
+//! The constants here are the defaults; `ScoreModel` may replace any curve
+//! with one from `[score.curves.<dimension>]`, so every normalizer takes
+//! the curve to apply.
 
 /// A single point on a piecewise linear curve, mapping an `input` metric
 /// value to an output `score` (0–100).
-struct Breakpoint {
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Breakpoint {
     /// Raw metric value (e.g. complexity count, dup percentage).
-    input: f64,
+    pub input: f64,
     /// Corresponding normalized score on the 0–100 scale.
-    score: f64,
+    pub score: f64,
 }
 
 /// Piecewise linear interpolation.  Values below the first breakpoint clamp to its score;
@@ -40,7 +45,7 @@ fn piecewise(value: f64, curve: &[Breakpoint]) -> f64 {
 /// Cognitive complexity curve: max per-function cognitive complexity to 0–100.
 /// Based on SonarQube threshold (15) and Clippy threshold (25).
 /// ≤4 → 100 (simple), 9 → 85, 14 → 65, 24 → 35, 50 → 5, ≥100 → 0.
-const COGNITIVE_CURVE: &[Breakpoint] = &[
+pub(super) const COGNITIVE_CURVE: &[Breakpoint] = &[
     Breakpoint {
         input: 4.0,
         score: 100.0,
@@ -69,7 +74,7 @@ const COGNITIVE_CURVE: &[Breakpoint] = &[
 
 /// Duplication percentage curve: % of duplicated lines mapped to 0–100.
 /// ≤5% → 100 (excellent), 10% → 80, 20% → 40, 40% → 10, 100% → 0.
-const DUPLICATION_CURVE: &[Breakpoint] = &[
+pub(super) const DUPLICATION_CURVE: &[Breakpoint] = &[
     Breakpoint {
         input: 5.0,
         score: 100.0,
@@ -94,7 +99,7 @@ const DUPLICATION_CURVE: &[Breakpoint] = &[
 
 /// Indentation complexity curve: stddev of indent depth mapped to 0–100.
 /// ≤1.0 → 100 (flat), 1.5 → 80, 2.0 → 50, 3.0 → 20, ≥5.0 → 0 (deeply nested).
-const INDENT_CURVE: &[Breakpoint] = &[
+pub(super) const INDENT_CURVE: &[Breakpoint] = &[
     Breakpoint {
         input: 1.0,
         score: 100.0,
@@ -119,7 +124,7 @@ const INDENT_CURVE: &[Breakpoint] = &[
 
 /// Halstead effort-per-LOC curve: effort/code_lines mapped to 0–100.
 /// ≤1000 → 100 (low cognitive load), 5000 → 70, 10000 → 40, ≥20000 → 0.
-const HALSTEAD_EPL_CURVE: &[Breakpoint] = &[
+pub(super) const HALSTEAD_EPL_CURVE: &[Breakpoint] = &[
     Breakpoint {
         input: 1000.0,
         score: 100.0,
@@ -140,7 +145,7 @@ const HALSTEAD_EPL_CURVE: &[Breakpoint] = &[
 
 /// File size curve: code lines mapped to 0–100.
 /// ≤500 → 100 (ideal), 1000 → 60, 2000 → 20, ≥4000 → 0 (too large).
-const FILE_SIZE_CURVE: &[Breakpoint] = &[
+pub(super) const FILE_SIZE_CURVE: &[Breakpoint] = &[
     Breakpoint {
         input: 500.0,
         score: 100.0,
@@ -160,39 +165,39 @@ const FILE_SIZE_CURVE: &[Breakpoint] = &[
 ];
 
 /// Normalize max cognitive complexity to a 0–100 score (lower complexity = higher score).
-pub fn normalize_cognitive(max_complexity: usize) -> f64 {
-    piecewise(max_complexity as f64, COGNITIVE_CURVE)
+pub fn normalize_cognitive(max_complexity: usize, curve: &[Breakpoint]) -> f64 {
+    piecewise(max_complexity as f64, curve)
 }
 
 /// Normalize duplication percentage to a 0–100 score (lower duplication = higher score).
-pub fn normalize_duplication(dup_percent: f64) -> f64 {
-    piecewise(dup_percent, DUPLICATION_CURVE)
+pub fn normalize_duplication(dup_percent: f64, curve: &[Breakpoint]) -> f64 {
+    piecewise(dup_percent, curve)
 }
 
 /// Normalize indentation stddev to a 0–100 score (lower stddev = higher score).
-pub fn normalize_indent(stddev: f64) -> f64 {
-    piecewise(stddev, INDENT_CURVE)
+pub fn normalize_indent(stddev: f64, curve: &[Breakpoint]) -> f64 {
+    piecewise(stddev, curve)
 }
 
 /// Normalize Halstead effort per LOC to a 0–100 score.
 /// Returns 50 (neutral) when effort or code_lines is zero (missing data).
-pub fn normalize_halstead(effort: f64, code_lines: usize) -> f64 {
+pub fn normalize_halstead(effort: f64, code_lines: usize, curve: &[Breakpoint]) -> f64 {
     if effort <= 0.0 || code_lines == 0 {
         return 50.0; // neutral for missing data
     }
-    piecewise(effort / code_lines as f64, HALSTEAD_EPL_CURVE)
+    piecewise(effort / code_lines as f64, curve)
 }
 
 /// Normalize file size (code lines) to a 0–100 score (smaller files score higher).
-pub fn normalize_file_size(code_lines: usize) -> f64 {
-    piecewise(code_lines as f64, FILE_SIZE_CURVE)
+pub fn normalize_file_size(code_lines: usize, curve: &[Breakpoint]) -> f64 {
+    piecewise(code_lines as f64, curve)
 }
 
 // --- Legacy model curves (v0.13: MI + Cyclomatic) ---
 
 /// Maintainability Index curve (legacy model): MI score (0–100+) to 0–100.
 /// 100→100 (excellent), 75→90, 50→60, 25→20, 0→0 (unmaintainable).
-const MI_CURVE: &[Breakpoint] = &[
+pub(super) const MI_CURVE: &[Breakpoint] = &[
     Breakpoint {
         input: 0.0,
         score: 0.0,
@@ -217,7 +222,7 @@ const MI_CURVE: &[Breakpoint] = &[
 
 /// Cyclomatic complexity curve (legacy model): max per-function complexity to 0–100.
 /// ≤5→100, 10→85, 15→65, 25→35, 50→5, ≥100→0.
-const COMPLEXITY_CURVE: &[Breakpoint] = &[
+pub(super) const COMPLEXITY_CURVE: &[Breakpoint] = &[
     Breakpoint {
         input: 5.0,
         score: 100.0,
@@ -245,15 +250,16 @@ const COMPLEXITY_CURVE: &[Breakpoint] = &[
 ];
 
 /// Normalize Maintainability Index to a 0–100 score (legacy model).
-pub fn normalize_mi(mi_score: f64) -> f64 {
-    piecewise(mi_score, MI_CURVE)
+pub fn normalize_mi(mi_score: f64, curve: &[Breakpoint]) -> f64 {
+    piecewise(mi_score, curve)
 }
 
 /// Normalize max cyclomatic complexity to a 0–100 score (legacy model).
-pub fn normalize_complexity(max_complexity: usize) -> f64 {
-    piecewise(max_complexity as f64, COMPLEXITY_CURVE)
+pub fn normalize_complexity(max_complexity: usize, curve: &[Breakpoint]) -> f64 {
+    piecewise(max_complexity as f64, curve)
 }
 
 #[cfg(test)]
+#[allow(clippy::manual_range_contains)]
 #[path = "normalize_test.rs"]
 mod tests;
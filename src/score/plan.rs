@@ -0,0 +1,171 @@
+//! Refactoring planner (`km score plan --target GRADE`).
+//!
+//! Simulates bringing single dimensions of single files up to a healthy
+//! profile (a normalized score per dimension, 90 by default, tunable with
+//! `[score.healthy]`) and lists the steps that reach the target grade.
+//! Each step lifts one file's dimension score; since the project score is
+//! a LOC-weighted mean per dimension, a step's gain does not depend on the
+//! others, so taking the largest gains first gives the fewest steps.
+//! Duplication is project-level and is not simulated.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use super::analyzer::{Grade, score_to_grade};
+use super::model::{Dimension, ScoreModel};
+use super::scoring::normalize_file_metric;
+use super::{collect, score_collected};
+use crate::walk::WalkConfig;
+
+/// Healthy score for dimensions not listed in `[score.healthy]`.
+pub const DEFAULT_HEALTHY_SCORE: f64 = 90.0;
+
+/// One simulated refactoring: lift `dimension` of `path` to `target`.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlanStep {
+    pub path: PathBuf,
+    pub dimension: &'static str,
+    /// Current normalized score of the dimension for this file.
+    pub current: f64,
+    /// Healthy score the dimension is lifted to.
+    pub target: f64,
+    /// Project score points gained by this step.
+    pub gain: f64,
+    /// Project score after this and all previous steps.
+    pub projected_score: f64,
+    pub projected_grade: Grade,
+}
+
+/// The ranked steps towards a target grade.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScorePlan {
+    pub target: Grade,
+    pub score: f64,
+    pub grade: Grade,
+    /// Whether the steps reach the target; when `false`, `steps` lists every
+    /// possible improvement and the target needs more than file refactoring.
+    pub reached: bool,
+    /// Number of distinct files touched by the steps.
+    pub files: usize,
+    /// Healthy score per dimension key.
+    pub healthy: BTreeMap<&'static str, f64>,
+    pub steps: Vec<PlanStep>,
+    pub model: ScoreModel,
+}
+
+/// Healthy score per file-level dimension of `model`, with `overrides` from
+/// `[score.healthy]` applied.
+pub fn healthy_profile(
+    model: &ScoreModel,
+    overrides: &BTreeMap<String, f64>,
+) -> Result<BTreeMap<&'static str, f64>, Box<dyn Error>> {
+    let mut profile: BTreeMap<&'static str, f64> = model
+        .dimensions
+        .iter()
+        .filter(|d| d.dimension != Dimension::Duplication)
+        .map(|d| (d.dimension.key(), DEFAULT_HEALTHY_SCORE))
+        .collect();
+    for (key, &score) in overrides {
+        if !(0.0..=100.0).contains(&score) {
+            return Err(format!("[score.healthy]: {key} = {score} is outside 0-100").into());
+        }
+        let valid: Vec<&str> = profile.keys().copied().collect();
+        match profile.get_mut(key.as_str()) {
+            Some(slot) => *slot = score,
+            None => {
+                return Err(format!(
+                    "[score.healthy]: unknown dimension '{key}' (expected one of: {})",
+                    valid.join(", ")
+                )
+                .into());
+            }
+        }
+    }
+    Ok(profile)
+}
+
+/// Plan the file refactorings that bring the project under `cfg` to `target`.
+pub fn compute_plan(
+    cfg: &WalkConfig<'_>,
+    min_lines: usize,
+    model: &ScoreModel,
+    target: Grade,
+    healthy: BTreeMap<&'static str, f64>,
+) -> Result<ScorePlan, Box<dyn Error>> {
+    let collected = collect(cfg, min_lines, model);
+    let project = score_collected(&collected, 0, model);
+    let files = &collected.file_metrics;
+
+    // Every (file, dimension) pair below its healthy score, with its gain.
+    let mut candidates: Vec<PlanStep> = Vec::new();
+    for d in model.dimensions.iter().filter(|d| d.weight > 0.0) {
+        let Some(&healthy_score) = healthy.get(d.dimension.key()) else {
+            continue;
+        };
+        let scored: Vec<(usize, f64)> = files
+            .iter()
+            .enumerate()
+            .filter_map(|(i, f)| normalize_file_metric(f, d).map(|s| (i, s)))
+            .collect();
+        // Same weighting as `weighted_mean`: LOC, at least 1 per file.
+        let weight_sum: usize = scored
+            .iter()
+            .map(|&(i, _)| files[i].code_lines.max(1))
+            .sum();
+        for (i, current) in scored {
+            if current >= healthy_score {
+                continue;
+            }
+            let share = files[i].code_lines.max(1) as f64 / weight_sum as f64;
+            candidates.push(PlanStep {
+                path: files[i].path.clone(),
+                dimension: d.dimension.name(),
+                current,
+                target: healthy_score,
+                gain: d.weight * (healthy_score - current) * share,
+                projected_score: 0.0,
+                projected_grade: Grade::FMinusMinus,
+            });
+        }
+    }
+    candidates.sort_by(|a, b| {
+        b.gain
+            .total_cmp(&a.gain)
+            .then_with(|| a.path.cmp(&b.path))
+            .then_with(|| a.dimension.cmp(b.dimension))
+    });
+
+    let reached_at = |score: f64| score_to_grade(score).numeric_rank() >= target.numeric_rank();
+    let mut projected = project.score;
+    let mut steps = Vec::new();
+    for mut step in candidates {
+        if project.files_analyzed == 0 || reached_at(projected) {
+            break;
+        }
+        projected += step.gain;
+        step.projected_score = projected;
+        step.projected_grade = score_to_grade(projected);
+        steps.push(step);
+    }
+
+    let mut touched: Vec<&PathBuf> = steps.iter().map(|s| &s.path).collect();
+    touched.sort();
+    touched.dedup();
+    Ok(ScorePlan {
+        target,
+        score: project.score,
+        grade: project.grade,
+        reached: project.files_analyzed > 0 && reached_at(projected),
+        files: touched.len(),
+        healthy,
+        steps,
+        model: model.clone(),
+    })
+}
+
+#[cfg(test)]
+#[path = "plan_test.rs"]
+mod tests;
@@ -0,0 +1,102 @@
+//! Report formatters for the refactoring plan.
+//!
+//! The table lists the steps in order with the projected score and grade
+//! after each one, so the plan can be cut short at any intermediate grade.
+
+use super::plan::ScorePlan;
+use crate::report_helpers;
+
+/// One-line verdict: how many steps reach the target, or why they cannot.
+fn summary(plan: &ScorePlan) -> String {
+    if plan.steps.is_empty() && plan.reached {
+        return format!("Already at or above {}.", plan.target);
+    }
+    let steps = plural(plan.steps.len(), "step");
+    let files = plural(plan.files, "file");
+    if plan.reached {
+        format!("Reached after {steps} across {files}.")
+    } else {
+        let best = plan.steps.last().map_or(plan.score, |s| s.projected_score);
+        format!(
+            "{} is out of reach by refactoring files alone: all {steps} across {files} \
+             reach {:.1}; reduce duplication as well.",
+            plan.target, best
+        )
+    }
+}
+
+fn plural(n: usize, word: &str) -> String {
+    if n == 1 {
+        format!("{n} {word}")
+    } else {
+        format!("{n} {word}s")
+    }
+}
+
+/// Print the refactoring plan as a formatted table.
+pub fn print_report(plan: &ScorePlan) {
+    let separator = report_helpers::separator(84);
+
+    println!(
+        "Refactoring Plan: {} ({:.1}) → {}",
+        plan.grade, plan.score, plan.target
+    );
+    println!("{separator}");
+    println!(" {}", summary(plan));
+    println!("{separator}");
+    if plan.steps.is_empty() {
+        return;
+    }
+
+    println!(
+        " {:>4}  {:>5}  {:>9}  {:<23} {:>9}  File",
+        "Step", "Gain", "Projected", "Dimension", "Score"
+    );
+    println!("{separator}");
+    for (i, s) in plan.steps.iter().enumerate() {
+        let projected = format!(
+            "{:>5.1} {:<3}",
+            s.projected_score,
+            s.projected_grade.as_str()
+        );
+        let lift = format!("{:.0} → {:.0}", s.current, s.target);
+        println!(
+            " {:>4}  {:>+5.1}  {projected:>9}  {:<23} {lift:>9}  {}",
+            i + 1,
+            s.gain,
+            s.dimension,
+            s.path.display(),
+        );
+    }
+    println!("{separator}");
+}
+
+/// Print the plan outcome as a single compact line.
+pub fn print_short(plan: &ScorePlan) {
+    let projected = plan.steps.last().map_or(plan.score, |s| s.projected_score);
+    println!(
+        "plan target:{} reached:{} steps:{} files:{} score:{:.1} projected:{:.1}",
+        plan.target,
+        plan.reached,
+        plan.steps.len(),
+        plan.files,
+        plan.score,
+        projected,
+    );
+}
+
+/// Print only the number of steps.
+pub fn print_terse(plan: &ScorePlan) {
+    println!("{}", plan.steps.len());
+}
+
+/// Serialize the plan as a pretty-printed JSON string.
+pub fn format_json(plan: &ScorePlan) -> Result<String, Box<dyn std::error::Error>> {
+    report_helpers::json_string(plan)
+}
+
+/// Serialize the plan as pretty-printed JSON to stdout.
+pub fn print_json(plan: &ScorePlan) -> Result<(), Box<dyn std::error::Error>> {
+    println!("{}", format_json(plan)?);
+    Ok(())
+}
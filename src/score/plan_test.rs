@@ -0,0 +1,161 @@
+use super::*;
+use std::fs;
+use std::path::Path;
+
+use crate::walk::ExcludeFilter;
+
+const SIMPLE: &str = "fn simple() -> i32 {\n    1\n}\n";
+
+const NESTED: &str = "\
+fn nested(v: &[i32]) -> i32 {
+    let mut n = 0;
+    for x in v {
+        if *x > 0 {
+            while n < 10 {
+                if n % 2 == 0 {
+                    if n > 4 {
+                        n += 3;
+                    } else {
+                        n += 1;
+                    }
+                } else {
+                    n += 2;
+                }
+            }
+        }
+    }
+    n
+}
+";
+
+fn project(files: &[(&str, &str)]) -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    for (name, content) in files {
+        fs::write(dir.path().join(name), content).unwrap();
+    }
+    dir
+}
+
+fn plan(root: &Path, target: Grade) -> ScorePlan {
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(root, false, &filter);
+    let model = ScoreModel::default();
+    let healthy = healthy_profile(&model, &BTreeMap::new()).unwrap();
+    compute_plan(&cfg, 6, &model, target, healthy).unwrap()
+}
+
+#[test]
+fn healthy_profile_defaults_and_overrides() {
+    let model = ScoreModel::default();
+    let profile = healthy_profile(&model, &BTreeMap::new()).unwrap();
+    assert_eq!(
+        profile.keys().copied().collect::<Vec<_>>(),
+        vec!["cognitive", "halstead", "indent", "size"],
+        "duplication is project-level"
+    );
+    assert!(profile.values().all(|&v| v == DEFAULT_HEALTHY_SCORE));
+
+    let overrides = BTreeMap::from([("cognitive".to_string(), 80.0)]);
+    let profile = healthy_profile(&model, &overrides).unwrap();
+    assert_eq!(profile["cognitive"], 80.0);
+    assert_eq!(profile["size"], DEFAULT_HEALTHY_SCORE);
+}
+
+#[test]
+fn healthy_profile_rejects_bad_entries() {
+    let model = ScoreModel::default();
+    for (key, score, expected) in [
+        ("duplication", 90.0, "unknown dimension 'duplication'"),
+        ("mi", 90.0, "unknown dimension 'mi'"),
+        ("size", 120.0, "outside 0-100"),
+    ] {
+        let overrides = BTreeMap::from([(key.to_string(), score)]);
+        let err = healthy_profile(&model, &overrides).unwrap_err();
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+    let legacy = ScoreModel::defaults(crate::score::ScoringModel::Legacy);
+    let overrides = BTreeMap::from([("mi".to_string(), 70.0)]);
+    assert_eq!(healthy_profile(&legacy, &overrides).unwrap()["mi"], 70.0);
+}
+
+#[test]
+fn already_at_target_needs_no_steps() {
+    let dir = project(&[("a.rs", SIMPLE)]);
+    let result = plan(dir.path(), Grade::C);
+    assert!(result.reached);
+    assert!(result.steps.is_empty());
+    assert_eq!(result.files, 0);
+}
+
+#[test]
+fn steps_are_ranked_and_stop_at_target() {
+    let dir = project(&[("a.rs", SIMPLE), ("b.rs", NESTED), ("c.rs", NESTED)]);
+    let start = plan(dir.path(), Grade::FMinusMinus).score;
+    let target = score_to_grade(start + 2.0);
+    assert!(target.numeric_rank() > score_to_grade(start).numeric_rank());
+
+    let result = plan(dir.path(), target);
+    assert!(result.reached);
+    assert!(!result.steps.is_empty());
+    assert!(result.steps.windows(2).all(|w| w[0].gain >= w[1].gain));
+    assert!(
+        result
+            .steps
+            .windows(2)
+            .all(|w| w[0].projected_score < w[1].projected_score)
+    );
+    let last = result.steps.last().unwrap();
+    assert!(last.projected_grade.numeric_rank() >= target.numeric_rank());
+    let before_last = result.steps.len() - 1;
+    if before_last > 0 {
+        assert!(
+            result.steps[before_last - 1].projected_grade.numeric_rank() < target.numeric_rank(),
+            "the plan stops as soon as the target is reached"
+        );
+    }
+    assert!(
+        result
+            .steps
+            .iter()
+            .all(|s| !s.path.ends_with("a.rs") && s.current < s.target)
+    );
+}
+
+#[test]
+fn unreachable_target_lists_every_step() {
+    let dir = project(&[("b.rs", NESTED)]);
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(dir.path(), false, &filter);
+    let model = ScoreModel::default();
+    let overrides = BTreeMap::from([
+        ("cognitive".to_string(), 50.0),
+        ("indent".to_string(), 50.0),
+        ("halstead".to_string(), 50.0),
+        ("size".to_string(), 50.0),
+    ]);
+    let healthy = healthy_profile(&model, &overrides).unwrap();
+    let result = compute_plan(&cfg, 6, &model, Grade::APlusPlus, healthy).unwrap();
+    assert!(!result.reached);
+    assert!(result.steps.iter().all(|s| s.target == 50.0));
+}
+
+#[test]
+fn empty_project_cannot_reach_target() {
+    let dir = tempfile::tempdir().unwrap();
+    let result = plan(dir.path(), Grade::B);
+    assert!(!result.reached);
+    assert!(result.steps.is_empty());
+}
+
+#[test]
+fn json_lists_steps_and_profile() {
+    let dir = project(&[("a.rs", SIMPLE), ("b.rs", NESTED)]);
+    let result = plan(dir.path(), Grade::APlusPlus);
+    let json: serde_json::Value =
+        serde_json::from_str(&super::super::plan_report::format_json(&result).unwrap()).unwrap();
+    assert_eq!(json["target"], "A++");
+    assert_eq!(json["healthy"]["cognitive"], DEFAULT_HEALTHY_SCORE);
+    assert_eq!(json["steps"][0]["dimension"], "Cognitive Complexity");
+    assert!(json["steps"][0]["projected_grade"].is_string());
+    assert_eq!(json["model"]["name"], "cogcom");
+}
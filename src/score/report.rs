@@ -8,6 +8,7 @@
 use serde::Serialize;
 
 use super::analyzer::{Grade, ProjectScore};
+use super::model::ScoreModel;
 use crate::report_helpers;
 
 /// Print the project health score as a formatted table.
@@ -94,7 +95,7 @@ pub fn print_report(score: &ProjectScore, bottom: usize, target: Option<&str>) {
 }
 
 /// Format an integer with thousand separators (e.g. 1234567 → "1,234,567").
-fn format_thousands(n: usize) -> String {
+pub(super) fn format_thousands(n: usize) -> String {
     let s = n.to_string();
     let mut result = String::new();
     for (i, ch) in s.chars().rev().enumerate() {
@@ -180,13 +181,15 @@ struct JsonProjectScore {
     dimensions: Vec<JsonDimension>,
     /// Worst-scoring files that need attention.
     needs_attention: Vec<JsonFileScore>,
+    /// Resolved model: name, weights and normalization curves.
+    model: ScoreModel,
 }
 
-/// Serialize the project score to pretty-printed JSON and print to stdout.
-pub fn print_json(
+/// Serialize the project score to a pretty-printed JSON string.
+pub fn format_json(
     score: &ProjectScore,
     target: Option<&str>,
-) -> Result<(), Box<dyn std::error::Error>> {
+) -> Result<String, Box<dyn std::error::Error>> {
     let json = JsonProjectScore {
         target: target.map(String::from),
         score: score.score,
@@ -213,8 +216,18 @@ pub fn print_json(
                 issues: f.issues.clone(),
             })
             .collect(),
+        model: score.model.clone(),
     };
-    report_helpers::print_json_stdout(&json)
+    report_helpers::json_string(&json)
+}
+
+/// Serialize the project score to pretty-printed JSON and print to stdout.
+pub fn print_json(
+    score: &ProjectScore,
+    target: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    println!("{}", format_json(score, target)?);
+    Ok(())
 }
 
 #[cfg(test)]
@@ -1,6 +1,7 @@
 use std::path::PathBuf;
 
 use super::*;
+use crate::score::ScoreModel;
 use crate::score::analyzer::{DimensionScore, FileScore};
 
 fn sample_score() -> ProjectScore {
@@ -30,6 +31,7 @@ fn sample_score() -> ProjectScore {
             loc: 500,
             issues: vec!["Cognitive: 87".to_string()],
         }],
+        model: ScoreModel::default(),
     }
 }
 
@@ -41,6 +43,7 @@ fn empty_score() -> ProjectScore {
         total_loc: 0,
         dimensions: vec![],
         needs_attention: vec![],
+        model: ScoreModel::default(),
     }
 }
 
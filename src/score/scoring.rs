@@ -8,13 +8,18 @@
 //! Supports two scoring models:
 //! - **Cognitive** (v0.14+, default): 5 dimensions with cognitive complexity.
 //! - **Legacy** (v0.13): 6 dimensions with MI + cyclomatic complexity.
+//!
+//! Which dimensions are scored, their weights and curves come from a
+//! resolved `ScoreModel` (see `model.rs`); the constants below are defaults.
+
+use std::borrow::Borrow;
 
-use super::ScoringModel;
 use super::analyzer::{DimensionScore, FileScore, score_to_grade};
 use super::collector::FileMetrics;
+use super::model::{Dimension, ModelDimension, ScoreModel};
 use super::normalize::{
-    normalize_cognitive, normalize_complexity, normalize_duplication, normalize_file_size,
-    normalize_halstead, normalize_indent, normalize_mi,
+    Breakpoint, normalize_cognitive, normalize_complexity, normalize_duplication,
+    normalize_file_size, normalize_halstead, normalize_indent, normalize_mi,
 };
 
 // ─── Cognitive model weights (v0.14+, default) ───────────────────────
@@ -30,14 +35,6 @@ pub const W_INDENT: f64 = 0.15;
 pub const W_HAL: f64 = 0.20;
 pub const W_SIZE: f64 = 0.15;
 
-/// All per-file dimension weights for cognitive model (excludes duplication).
-pub const FILE_WEIGHTS: [(f64, &str); 4] = [
-    (W_COGCOM, "Cogcom"),
-    (W_INDENT, "Indent"),
-    (W_HAL, "Halstead"),
-    (W_SIZE, "Size"),
-];
-
 // ─── Legacy model weights (v0.13) ────────────────────────────────────
 
 /// Dimension weights for the legacy model (must sum to 1.0).
@@ -48,118 +45,96 @@ pub const W_INDENT_LEGACY: f64 = 0.15;
 pub const W_HAL_LEGACY: f64 = 0.15;
 pub const W_SIZE_LEGACY: f64 = 0.05;
 
-/// All per-file dimension weights for legacy model (excludes duplication).
-pub const FILE_WEIGHTS_LEGACY: [(f64, &str); 5] = [
-    (W_MI, "MI"),
-    (W_CYCOM, "Cycom"),
-    (W_INDENT_LEGACY, "Indent"),
-    (W_HAL_LEGACY, "Halstead"),
-    (W_SIZE_LEGACY, "Size"),
-];
-
 // ─── Shared ──────────────────────────────────────────────────────────
 
 /// Default score for missing dimensions (neutral).
 pub const MISSING_DIM_SCORE: f64 = 50.0;
 
 /// Build dimension scores for the active scoring model.
-pub fn build_dimensions(
-    file_metrics: &[FileMetrics],
+/// Accepts owned or borrowed metrics so subsets can be scored without copying.
+pub fn build_dimensions<F: Borrow<FileMetrics>>(
+    file_metrics: &[F],
     total_loc: usize,
     dup_percent: f64,
-    model: &ScoringModel,
+    model: &ScoreModel,
 ) -> Vec<DimensionScore> {
-    match model {
-        ScoringModel::Cognitive => build_cognitive_dimensions(file_metrics, total_loc, dup_percent),
-        ScoringModel::Legacy => build_legacy_dimensions(file_metrics, total_loc, dup_percent),
-    }
+    model
+        .dimensions
+        .iter()
+        .map(|d| {
+            let score = match d.dimension {
+                Dimension::Duplication => normalize_duplication(dup_percent, &d.curve),
+                _ => weighted_mean(file_metrics, total_loc, |f| normalize_file_metric(f, d)),
+            };
+            DimensionScore {
+                name: d.dimension.name(),
+                weight: d.weight,
+                score,
+                grade: score_to_grade(score),
+            }
+        })
+        .collect()
 }
 
 /// Build dimension scores for an empty project.
-pub fn build_empty_dimensions(model: &ScoringModel) -> Vec<DimensionScore> {
-    build_dimensions(&[], 0, 0.0, model)
+pub fn build_empty_dimensions(model: &ScoreModel) -> Vec<DimensionScore> {
+    build_dimensions::<FileMetrics>(&[], 0, 0.0, model)
 }
 
 /// Score a single file using the active scoring model.
-pub fn score_file(f: &FileMetrics, model: &ScoringModel) -> FileScore {
-    match model {
-        ScoringModel::Cognitive => score_file_cognitive(f),
-        ScoringModel::Legacy => score_file_legacy(f),
-    }
-}
-
-// ─── Cognitive model (v0.14+) ────────────────────────────────────────
-
-/// Build the five cognitive-model dimension scores from per-file metrics.
-fn build_cognitive_dimensions(
-    file_metrics: &[FileMetrics],
-    total_loc: usize,
-    dup_percent: f64,
-) -> Vec<DimensionScore> {
-    let cogcom_dim = weighted_mean(file_metrics, total_loc, |f| {
-        f.max_cognitive.map(normalize_cognitive)
-    });
-    let indent_dim = weighted_mean(file_metrics, total_loc, |f| {
-        f.indent_stddev.map(normalize_indent)
-    });
-    let hal_dim = weighted_mean(file_metrics, total_loc, |f| {
-        f.halstead_effort
-            .map(|e| normalize_halstead(e, f.code_lines))
-    });
-    let size_dim = weighted_mean(file_metrics, total_loc, |f| {
-        Some(normalize_file_size(f.code_lines))
-    });
-    let dup_dim = normalize_duplication(dup_percent);
-
-    let dim = |name, weight, score| DimensionScore {
-        name,
-        weight,
-        score,
-        grade: score_to_grade(score),
-    };
-
-    vec![
-        dim("Cognitive Complexity", W_COGCOM, cogcom_dim),
-        dim("Duplication", W_DUP, dup_dim),
-        dim("Indentation Complexity", W_INDENT, indent_dim),
-        dim("Halstead Effort", W_HAL, hal_dim),
-        dim("File Size", W_SIZE, size_dim),
-    ]
-}
-
-/// Score a single file using the cognitive model.
 /// Excludes duplication (project-level only). The weighted sum uses absolute
 /// dimension weights divided by the per-file weight sum, so the result is
 /// renormalized to 0–100 but not directly comparable to the project score.
-fn score_file_cognitive(f: &FileMetrics) -> FileScore {
+pub fn score_file(f: &FileMetrics, model: &ScoreModel) -> FileScore {
     let mut issues: Vec<String> = Vec::new();
-    let file_weight_sum: f64 = FILE_WEIGHTS.iter().map(|(w, _)| w).sum();
-
-    let cogcom_s = score_dim(
-        f.max_cognitive,
-        normalize_cognitive,
-        |v| format!("Cognitive: {v}"),
-        &mut issues,
-    );
-    let indent_s = score_dim(
-        f.indent_stddev,
-        normalize_indent,
-        |v| format!("Indent: {v:.1}"),
-        &mut issues,
-    );
-    let hal_s = score_dim(
-        f.halstead_effort,
-        |e| normalize_halstead(e, f.code_lines),
-        |v| format!("Effort: {v:.0}"),
-        &mut issues,
-    );
-
-    let size_s = normalize_file_size(f.code_lines);
-    if f.code_lines > 1000 {
-        issues.push(format!("Size: {} LOC", f.code_lines));
+    let mut weighted_sum = 0.0;
+    let mut file_weight_sum = 0.0;
+
+    for d in &model.dimensions {
+        let curve = d.curve.as_slice();
+        let s = match d.dimension {
+            Dimension::Duplication => continue,
+            Dimension::Cognitive => score_dim(
+                f.max_cognitive,
+                |v| normalize_cognitive(v, curve),
+                |v| format!("Cognitive: {v}"),
+                &mut issues,
+            ),
+            Dimension::MaintainabilityIndex => score_dim(
+                f.mi_score,
+                |v| normalize_mi(v, curve),
+                |v| format!("MI: {v:.1}"),
+                &mut issues,
+            ),
+            Dimension::Cyclomatic => score_dim(
+                f.max_complexity,
+                |v| normalize_complexity(v, curve),
+                |v| format!("Complexity: {v}"),
+                &mut issues,
+            ),
+            Dimension::Indent => score_dim(
+                f.indent_stddev,
+                |v| normalize_indent(v, curve),
+                |v| format!("Indent: {v:.1}"),
+                &mut issues,
+            ),
+            Dimension::Halstead => score_dim(
+                f.halstead_effort,
+                |e| normalize_halstead(e, f.code_lines, curve),
+                |v| format!("Effort: {v:.0}"),
+                &mut issues,
+            ),
+            Dimension::Size => {
+                if f.code_lines > 1000 {
+                    issues.push(format!("Size: {} LOC", f.code_lines));
+                }
+                normalize_file_size(f.code_lines, curve)
+            }
+        };
+        weighted_sum += s * d.weight;
+        file_weight_sum += d.weight;
     }
 
-    let weighted_sum = cogcom_s * W_COGCOM + indent_s * W_INDENT + hal_s * W_HAL + size_s * W_SIZE;
     let file_score = weighted_sum / file_weight_sum;
 
     FileScore {
@@ -171,98 +146,40 @@ fn score_file_cognitive(f: &FileMetrics) -> FileScore {
     }
 }
 
-// ─── Legacy model (v0.13) ────────────────────────────────────────────
-
-/// Build the six legacy-model dimension scores from per-file metrics.
-fn build_legacy_dimensions(
-    file_metrics: &[FileMetrics],
-    total_loc: usize,
-    dup_percent: f64,
-) -> Vec<DimensionScore> {
-    let mi_dim = weighted_mean(file_metrics, total_loc, |f| f.mi_score.map(normalize_mi));
-    let cycom_dim = weighted_mean(file_metrics, total_loc, |f| {
-        f.max_complexity.map(normalize_complexity)
-    });
-    let indent_dim = weighted_mean(file_metrics, total_loc, |f| {
-        f.indent_stddev.map(normalize_indent)
-    });
-    let hal_dim = weighted_mean(file_metrics, total_loc, |f| {
-        f.halstead_effort
-            .map(|e| normalize_halstead(e, f.code_lines))
-    });
-    let size_dim = weighted_mean(file_metrics, total_loc, |f| {
-        Some(normalize_file_size(f.code_lines))
-    });
-    let dup_dim = normalize_duplication(dup_percent);
-
-    let dim = |name, weight, score| DimensionScore {
-        name,
-        weight,
-        score,
-        grade: score_to_grade(score),
-    };
-
-    vec![
-        dim("Maintainability Index", W_MI, mi_dim),
-        dim("Cyclomatic Complexity", W_CYCOM, cycom_dim),
-        dim("Duplication", W_DUP_LEGACY, dup_dim),
-        dim("Indentation Complexity", W_INDENT_LEGACY, indent_dim),
-        dim("Halstead Effort", W_HAL_LEGACY, hal_dim),
-        dim("File Size", W_SIZE_LEGACY, size_dim),
-    ]
+/// Per-dimension scores of a single file, in model order. Duplication is
+/// project-level and skipped; missing metrics score `MISSING_DIM_SCORE`, as
+/// in `score_file`.
+pub fn score_file_dimensions(f: &FileMetrics, model: &ScoreModel) -> Vec<DimensionScore> {
+    model
+        .dimensions
+        .iter()
+        .filter(|d| d.dimension != Dimension::Duplication)
+        .map(|d| {
+            let score = normalize_file_metric(f, d).unwrap_or(MISSING_DIM_SCORE);
+            DimensionScore {
+                name: d.dimension.name(),
+                weight: d.weight,
+                score,
+                grade: score_to_grade(score),
+            }
+        })
+        .collect()
 }
 
-/// Score a single file using the legacy model.
-/// Excludes duplication (project-level only). The weighted sum uses absolute
-/// dimension weights divided by the per-file weight sum, so the result is
-/// renormalized to 0–100 but not directly comparable to the project score.
-fn score_file_legacy(f: &FileMetrics) -> FileScore {
-    let mut issues: Vec<String> = Vec::new();
-    let file_weight_sum: f64 = FILE_WEIGHTS_LEGACY.iter().map(|(w, _)| w).sum();
-
-    let mi_s = score_dim(
-        f.mi_score,
-        normalize_mi,
-        |v| format!("MI: {v:.1}"),
-        &mut issues,
-    );
-    let cycom_s = score_dim(
-        f.max_complexity,
-        normalize_complexity,
-        |v| format!("Complexity: {v}"),
-        &mut issues,
-    );
-    let indent_s = score_dim(
-        f.indent_stddev,
-        normalize_indent,
-        |v| format!("Indent: {v:.1}"),
-        &mut issues,
-    );
-    let hal_s = score_dim(
-        f.halstead_effort,
-        |e| normalize_halstead(e, f.code_lines),
-        |v| format!("Effort: {v:.0}"),
-        &mut issues,
-    );
-
-    let size_s = normalize_file_size(f.code_lines);
-    if f.code_lines > 1000 {
-        issues.push(format!("Size: {} LOC", f.code_lines));
-    }
-
-    let weighted_sum = mi_s * W_MI
-        + cycom_s * W_CYCOM
-        + indent_s * W_INDENT_LEGACY
-        + hal_s * W_HAL_LEGACY
-        + size_s * W_SIZE_LEGACY;
-    let file_score = weighted_sum / file_weight_sum;
-
-    FileScore {
-        path: f.path.clone(),
-        score: file_score,
-        grade: score_to_grade(file_score),
-        loc: f.code_lines,
-        issues,
+/// Normalize the per-file metric behind `d`, or `None` when the file has
+/// no value for it. Duplication is project-level and always `None`.
+pub(super) fn normalize_file_metric(f: &FileMetrics, d: &ModelDimension) -> Option<f64> {
+    let curve: &[Breakpoint] = &d.curve;
+    match d.dimension {
+        Dimension::Cognitive => f.max_cognitive.map(|v| normalize_cognitive(v, curve)),
+        Dimension::MaintainabilityIndex => f.mi_score.map(|v| normalize_mi(v, curve)),
+        Dimension::Cyclomatic => f.max_complexity.map(|v| normalize_complexity(v, curve)),
+        Dimension::Indent => f.indent_stddev.map(|v| normalize_indent(v, curve)),
+        Dimension::Halstead => f
+            .halstead_effort
+            .map(|e| normalize_halstead(e, f.code_lines, curve)),
+        Dimension::Size => Some(normalize_file_size(f.code_lines, curve)),
+        Dimension::Duplication => None,
     }
 }
 
@@ -292,8 +209,8 @@ fn score_dim<T: Copy>(
 /// Files with more code lines have proportionally more influence on
 /// the dimension score. Files where the metric is unavailable (None)
 /// are excluded from the mean.
-pub fn weighted_mean(
-    files: &[FileMetrics],
+pub fn weighted_mean<F: Borrow<FileMetrics>>(
+    files: &[F],
     total_loc: usize,
     score_fn: impl Fn(&FileMetrics) -> Option<f64>,
 ) -> f64 {
@@ -303,6 +220,7 @@ pub fn weighted_mean(
     let mut weighted_sum = 0.0;
     let mut weight_sum = 0usize;
     for f in files {
+        let f = f.borrow();
         if let Some(s) = score_fn(f) {
             let w = f.code_lines.max(1); // at least 1 to count the file
             weighted_sum += s * w as f64;
@@ -1,4 +1,5 @@
 use super::*;
+use crate::score::ScoringModel;
 
 #[test]
 fn cognitive_weights_sum_to_one() {
@@ -19,22 +20,34 @@ fn legacy_weights_sum_to_one() {
 }
 
 #[test]
-fn file_weights_match_cognitive_constants() {
-    let file_sum: f64 = FILE_WEIGHTS.iter().map(|(w, _)| w).sum();
+fn default_model_file_weights_match_cognitive_constants() {
+    let model = ScoreModel::defaults(ScoringModel::Cognitive);
+    let file_sum: f64 = model
+        .dimensions
+        .iter()
+        .filter(|d| d.dimension != Dimension::Duplication)
+        .map(|d| d.weight)
+        .sum();
     let expected = W_COGCOM + W_INDENT + W_HAL + W_SIZE;
     assert!(
         (file_sum - expected).abs() < 1e-10,
-        "FILE_WEIGHTS sum should match non-dup cognitive constants"
+        "per-file weights should match non-dup cognitive constants"
     );
 }
 
 #[test]
-fn file_weights_legacy_match_constants() {
-    let file_sum: f64 = FILE_WEIGHTS_LEGACY.iter().map(|(w, _)| w).sum();
+fn default_model_file_weights_match_legacy_constants() {
+    let model = ScoreModel::defaults(ScoringModel::Legacy);
+    let file_sum: f64 = model
+        .dimensions
+        .iter()
+        .filter(|d| d.dimension != Dimension::Duplication)
+        .map(|d| d.weight)
+        .sum();
     let expected = W_MI + W_CYCOM + W_INDENT_LEGACY + W_HAL_LEGACY + W_SIZE_LEGACY;
     assert!(
         (file_sum - expected).abs() < 1e-10,
-        "FILE_WEIGHTS_LEGACY sum should match non-dup legacy constants"
+        "per-file weights should match non-dup legacy constants"
     );
 }
 
@@ -125,7 +138,12 @@ fn build_cognitive_dimensions_count() {
         indent_stddev: Some(1.0),
         halstead_effort: Some(1000.0),
     }];
-    let dims = build_dimensions(&files, 100, 5.0, &ScoringModel::Cognitive);
+    let dims = build_dimensions(
+        &files,
+        100,
+        5.0,
+        &ScoreModel::defaults(ScoringModel::Cognitive),
+    );
     assert_eq!(dims.len(), 5, "cognitive model should have 5 dimensions");
     assert_eq!(dims[0].name, "Cognitive Complexity");
 }
@@ -141,7 +159,12 @@ fn build_legacy_dimensions_count() {
         indent_stddev: Some(1.0),
         halstead_effort: Some(1000.0),
     }];
-    let dims = build_dimensions(&files, 100, 5.0, &ScoringModel::Legacy);
+    let dims = build_dimensions(
+        &files,
+        100,
+        5.0,
+        &ScoreModel::defaults(ScoringModel::Legacy),
+    );
     assert_eq!(dims.len(), 6, "legacy model should have 6 dimensions");
     assert_eq!(dims[0].name, "Maintainability Index");
     assert_eq!(dims[1].name, "Cyclomatic Complexity");
@@ -158,7 +181,7 @@ fn score_file_cognitive_produces_issues() {
         indent_stddev: Some(4.0),
         halstead_effort: Some(2_000_000.0),
     };
-    let fs = score_file(&f, &ScoringModel::Cognitive);
+    let fs = score_file(&f, &ScoreModel::defaults(ScoringModel::Cognitive));
     assert!(!fs.issues.is_empty(), "complex file should have issues");
     assert!(fs.score < 50.0, "complex file should score low");
 }
@@ -174,7 +197,34 @@ fn score_file_legacy_produces_issues() {
         indent_stddev: Some(4.0),
         halstead_effort: Some(2_000_000.0),
     };
-    let fs = score_file(&f, &ScoringModel::Legacy);
+    let fs = score_file(&f, &ScoreModel::defaults(ScoringModel::Legacy));
     assert!(!fs.issues.is_empty(), "complex file should have issues");
     assert!(fs.score < 50.0, "complex file should score low");
 }
+
+#[test]
+fn score_file_dimensions_average_to_file_score() {
+    let f = FileMetrics {
+        path: "mixed.rs".into(),
+        code_lines: 400,
+        max_cognitive: Some(12),
+        mi_score: None,
+        max_complexity: None,
+        indent_stddev: None,
+        halstead_effort: Some(50_000.0),
+    };
+    let model = ScoreModel::defaults(ScoringModel::Cognitive);
+    let dims = score_file_dimensions(&f, &model);
+    let names: Vec<&str> = dims.iter().map(|d| d.name).collect();
+    assert!(!names.contains(&"Duplication"));
+    assert_eq!(
+        dims.iter()
+            .find(|d| d.name == "Indentation Complexity")
+            .unwrap()
+            .score,
+        MISSING_DIM_SCORE
+    );
+    let weight: f64 = dims.iter().map(|d| d.weight).sum();
+    let mean = dims.iter().map(|d| d.score * d.weight).sum::<f64>() / weight;
+    assert!((mean - score_file(&f, &model).score).abs() < 1e-9);
+}
@@ -41,6 +41,36 @@ impl SmellKind {
         }
     }
 
+    /// Kebab-case identifier used in SARIF rule ids (`smells/<slug>`).
+    pub fn slug(self) -> &'static str {
+        match self {
+            Self::LongFunction => "long-function",
+            Self::LongParameterList => "long-parameter-list",
+            Self::TodoDebt => "todo-debt",
+            Self::MagicNumber => "magic-number",
+            Self::CommentedOutCode => "commented-out-code",
+        }
+    }
+
+    /// One-sentence rationale, used as the SARIF rule description.
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::LongFunction => {
+                "Function body exceeds the configured line limit; long functions usually do too much and are hard to test."
+            }
+            Self::LongParameterList => {
+                "Function takes more parameters than the configured limit; consider grouping them into a type."
+            }
+            Self::TodoDebt => "TODO, FIXME, HACK or XXX comment marking known, unresolved work.",
+            Self::MagicNumber => {
+                "Unnamed numeric literal in code; a named constant documents intent and keeps values in sync."
+            }
+            Self::CommentedOutCode => {
+                "Block of commented-out code; version control already keeps old code, so it only adds noise."
+            }
+        }
+    }
+
     /// All smell kinds in a fixed canonical order (used for stable table columns).
     pub const fn all() -> [SmellKind; 5] {
         [
@@ -4,7 +4,7 @@
 //! TODO/FIXME debt, magic numbers, and commented-out code.
 
 mod analyzer;
-mod report;
+pub(crate) mod report;
 mod rules;
 
 use std::cmp::Reverse;
@@ -19,12 +19,15 @@ use crate::walk::WalkConfig;
 
 use analyzer::detect_smells;
 use report::{
-    FileSmellMetrics, print_codeclimate, print_github, print_json, print_report, print_short,
+    print_codeclimate, print_github, print_json, print_report, print_sarif, print_short,
     print_terse,
 };
 
+pub use analyzer::{FileSmells, SmellInstance, SmellKind};
+pub use report::FileSmellMetrics;
+
 /// Read a file, classify lines, and detect smells.
-fn analyze_file(
+pub fn analyze_file(
     path: &Path,
     spec: &LanguageSpec,
     max_lines: usize,
@@ -54,16 +57,13 @@ fn analyze_file(
     }))
 }
 
-/// Analyze an explicit list of file paths for smells.
-/// Skips paths that are not recognized source files or no longer exist.
-/// Used by `--files` and `--since-ref` to limit analysis to a PR's changed files.
-pub fn run_on_files(
+/// Detect smells in an explicit list of file paths, sorted by smell count
+/// descending. Skips paths that are not recognized source files.
+pub fn analyze_files(
     paths: &[PathBuf],
-    output: OutputMode,
-    top: usize,
     max_lines: usize,
     max_params: usize,
-) -> Result<(), Box<dyn Error>> {
+) -> Vec<FileSmellMetrics> {
     let mut results: Vec<FileSmellMetrics> = Vec::new();
 
     for path in paths {
@@ -78,33 +78,63 @@ pub fn run_on_files(
         }
     }
 
+    results.sort_by_key(|r| Reverse(r.total));
+    results
+}
+
+/// Analyze an explicit list of file paths for smells.
+/// Skips paths that are not recognized source files or no longer exist.
+/// Used by `--files` and `--since-ref` to limit analysis to a PR's changed files.
+pub fn run_on_files(
+    paths: &[PathBuf],
+    output: OutputMode,
+    top: usize,
+    max_lines: usize,
+    max_params: usize,
+) -> Result<(), Box<dyn Error>> {
+    let mut results = analyze_files(paths, max_lines, max_params);
+
     if results.is_empty() {
-        if output == OutputMode::Json {
-            return report::print_json(&[]);
+        match output {
+            OutputMode::Json => return report::print_json(&[]),
+            OutputMode::Sarif => return report::print_sarif(&[]),
+            OutputMode::Html | OutputMode::Markdown => {
+                return Err(crate::cli::ERR_REPORT_FORMAT_ONLY.into());
+            }
+            _ => {}
         }
         println!("No recognized source files in the provided list.");
         return Ok(());
     }
 
-    results.sort_by_key(|r| Reverse(r.total));
     results.truncate(top);
 
     dispatch_output(output, &results)
 }
 
-/// Walk source files, detect smells, sort by count, and output.
-pub fn run(
+/// Walk source files and detect smells, sorted by smell count descending.
+pub fn analyze_project(
     cfg: &WalkConfig<'_>,
-    output: OutputMode,
-    top: usize,
     max_lines: usize,
     max_params: usize,
-) -> Result<(), Box<dyn Error>> {
+) -> Vec<FileSmellMetrics> {
     let mut results =
         cfg.collect_analysis(|path, spec| analyze_file(path, spec, max_lines, max_params));
 
     // Sort by smell count descending
     results.sort_by_key(|r| Reverse(r.total));
+    results
+}
+
+/// Walk source files, detect smells, sort by count, and output.
+pub fn run(
+    cfg: &WalkConfig<'_>,
+    output: OutputMode,
+    top: usize,
+    max_lines: usize,
+    max_params: usize,
+) -> Result<(), Box<dyn Error>> {
+    let mut results = analyze_project(cfg, max_lines, max_params);
     results.truncate(top);
 
     dispatch_output(output, &results)
@@ -115,6 +145,10 @@ fn dispatch_output(output: OutputMode, results: &[FileSmellMetrics]) -> Result<(
         OutputMode::Json => print_json(results)?,
         OutputMode::Github => print_github(results),
         OutputMode::Codeclimate => print_codeclimate(results)?,
+        OutputMode::Sarif => print_sarif(results)?,
+        OutputMode::Html | OutputMode::Markdown => {
+            return Err(crate::cli::ERR_REPORT_FORMAT_ONLY.into());
+        }
         OutputMode::Short => print_short(results),
         OutputMode::Terse => print_terse(results),
         OutputMode::Table => print_report(results),
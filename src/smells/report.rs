@@ -6,6 +6,7 @@ use serde::Serialize;
 
 use super::analyzer::{FileSmells, SmellKind};
 use crate::report_helpers;
+use crate::sarif;
 
 /// Per-file smell metrics for reporting.
 pub struct FileSmellMetrics {
@@ -123,8 +124,8 @@ struct JsonFileEntry {
     total: usize,
 }
 
-/// Serialize per-file smell metrics as JSON to stdout.
-pub fn print_json(files: &[FileSmellMetrics]) -> Result<(), Box<dyn std::error::Error>> {
+/// Serialize per-file smell metrics as a pretty-printed JSON string.
+pub fn format_json(files: &[FileSmellMetrics]) -> Result<String, Box<dyn std::error::Error>> {
     let entries: Vec<JsonFileEntry> = files
         .iter()
         .map(|f| JsonFileEntry {
@@ -144,7 +145,13 @@ pub fn print_json(files: &[FileSmellMetrics]) -> Result<(), Box<dyn std::error::
         })
         .collect();
 
-    report_helpers::print_json_stdout(&entries)
+    report_helpers::json_string(&entries)
+}
+
+/// Serialize per-file smell metrics as JSON to stdout.
+pub fn print_json(files: &[FileSmellMetrics]) -> Result<(), Box<dyn std::error::Error>> {
+    println!("{}", format_json(files)?);
+    Ok(())
 }
 
 /// Print smells as a single compact line.
@@ -174,6 +181,55 @@ pub fn print_codeclimate(files: &[FileSmellMetrics]) -> Result<(), Box<dyn std::
     report_helpers::print_json_stdout(&entries)
 }
 
+/// SARIF level for each smell kind: structural smells are warnings,
+/// the rest are notes.
+fn sarif_level(kind: SmellKind) -> &'static str {
+    match kind {
+        SmellKind::LongFunction | SmellKind::LongParameterList => "warning",
+        SmellKind::TodoDebt | SmellKind::MagicNumber | SmellKind::CommentedOutCode => "note",
+    }
+}
+
+/// Build a SARIF 2.1.0 log with one rule per smell kind and one result per
+/// smell instance.
+pub fn format_sarif(files: &[FileSmellMetrics]) -> Result<String, Box<dyn std::error::Error>> {
+    let rules: Vec<sarif::Rule> = SmellKind::all()
+        .into_iter()
+        .map(|kind| sarif::Rule {
+            id: format!("smells/{}", kind.slug()),
+            name: kind.title().replace(['/', '-', ' '], ""),
+            short_description: kind.title().to_string(),
+            full_description: kind.description().to_string(),
+            level: sarif_level(kind),
+        })
+        .collect();
+    let findings: Vec<sarif::Finding> = files
+        .iter()
+        .flat_map(|f| {
+            let path = f.path.display().to_string();
+            f.smells.smells.iter().map(move |s| sarif::Finding {
+                rule_id: format!("smells/{}", s.kind.slug()),
+                message: s.detail.clone(),
+                location: sarif::Region {
+                    path: path.clone(),
+                    start_line: s.line,
+                    end_line: None,
+                },
+                related: Vec::new(),
+                fingerprint: format!("smells:{}:{path}:{}", s.kind.slug(), s.detail),
+                level: None,
+            })
+        })
+        .collect();
+    sarif::format_log(&rules, &findings)
+}
+
+/// Print the SARIF log for `format_sarif` to stdout.
+pub fn print_sarif(files: &[FileSmellMetrics]) -> Result<(), Box<dyn std::error::Error>> {
+    println!("{}", format_sarif(files)?);
+    Ok(())
+}
+
 /// Emit one GitHub Actions warning annotation per smell instance.
 /// Each annotation links directly to the file and line in the PR diff.
 pub fn print_github(files: &[FileSmellMetrics]) {
@@ -280,4 +336,36 @@ mod tests {
         let counts = kind_counts(&FileSmells { smells: vec![] });
         assert_eq!(counts, [0; 5]);
     }
+
+    #[test]
+    fn format_sarif_has_rule_per_kind_and_result_per_smell() {
+        let files = vec![make_file_metrics(
+            "src/a.rs",
+            "Rust",
+            vec![
+                make_smell(
+                    SmellKind::LongFunction,
+                    3,
+                    "function `f` has 80 lines (max 50)",
+                ),
+                make_smell(SmellKind::MagicNumber, 9, "magic number in code"),
+                make_smell(SmellKind::MagicNumber, 12, "magic number in code"),
+            ],
+        )];
+        let log: serde_json::Value = serde_json::from_str(&format_sarif(&files).unwrap()).unwrap();
+        let run = &log["runs"][0];
+        let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+        assert_eq!(rules.len(), SmellKind::all().len());
+        assert!(rules.iter().any(|r| r["id"] == "smells/long-function"));
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0]["ruleId"], "smells/long-function");
+        assert_eq!(results[0]["level"], "warning");
+        assert_eq!(results[1]["level"], "note");
+        assert_ne!(
+            results[1]["partialFingerprints"], results[2]["partialFingerprints"],
+            "repeated smells get distinct fingerprints"
+        );
+    }
 }
@@ -5,7 +5,7 @@
 //! hidden dependencies that should be made explicit or decoupled.
 
 pub mod analyzer;
-mod report;
+pub(crate) mod report;
 
 use std::cmp::Reverse;
 use std::collections::HashMap;
@@ -14,9 +14,12 @@ use std::path::{Path, PathBuf};
 
 use crate::cli::OutputMode;
 use crate::git::GitRepo;
+use crate::report_helpers::empty_history_note;
 use crate::util::parse_since;
-use crate::walk;
+use crate::walk::{self, WalkConfig};
 use analyzer::compute_coupling;
+
+pub use analyzer::FileCoupling;
 use report::{print_json, print_report, print_short, print_terse};
 
 /// Check whether a git-relative path is inside a test directory or is a test file.
@@ -31,37 +34,36 @@ fn is_test_path(path: &Path) -> bool {
     walk::is_test_file(path)
 }
 
-#[allow(clippy::too_many_arguments)]
-pub fn run(
-    path: &Path,
-    output: OutputMode,
-    include_tests: bool,
-    top: usize,
+/// Compute temporal coupling between file pairs from the git history of
+/// `cfg.path`, sorted by `sort_by` ("strength" or "shared"). Returns an
+/// empty list when there is not enough history to pair files. The history
+/// read follows the commit filter, exclusions and rename threshold of
+/// `cfg`; its file filter does not apply.
+pub fn analyze_project(
+    cfg: &WalkConfig<'_>,
     sort_by: &str,
     since: Option<&str>,
     min_degree: usize,
     min_strength: Option<f64>,
-) -> Result<(), Box<dyn Error>> {
+) -> Result<Vec<FileCoupling>, Box<dyn Error>> {
     if min_degree == 0 {
         return Err("--min-degree must be at least 1".into());
     }
 
-    let git_repo =
-        GitRepo::open(path).map_err(|e| format!("not a git repository (or any parent): {e}"))?;
+    let git_repo = GitRepo::open(cfg.path)
+        .map_err(|e| format!("not a git repository (or any parent): {e}"))?
+        .with_rename_threshold(cfg.rename_threshold)
+        .with_exclusions(cfg.exclusions)
+        .with_commit_filter(cfg.commits)?;
 
     let since_ts = since.map(parse_since).transpose()?;
 
     // Build freq_map: path → commits, filtering by min_degree and optionally test files
     let freqs = git_repo.file_frequencies(since_ts)?;
     if freqs.is_empty() {
-        if since.is_some() {
-            eprintln!("No commits found in the specified time range.");
-        } else {
-            eprintln!("No commits found in the repository.");
-        }
-        return Ok(());
+        return Ok(Vec::new());
     }
-    let exclude_tests = !include_tests;
+    let exclude_tests = cfg.exclude_tests();
     let freq_map: HashMap<PathBuf, usize> = freqs
         .into_iter()
         .filter(|f| f.commits >= min_degree)
@@ -70,15 +72,13 @@ pub fn run(
         .collect();
 
     if freq_map.is_empty() {
-        eprintln!("No files with >= {min_degree} commits found. Try a lower --min-degree value.");
-        return Ok(());
+        return Ok(Vec::new());
     }
 
     // Get co-changing commit groups
     let co_changes = git_repo.co_changing_commits(since_ts)?;
     if co_changes.is_empty() {
-        eprintln!("No commits with multiple files found.");
-        return Ok(());
+        return Ok(Vec::new());
     }
 
     let mut results = compute_coupling(&co_changes, &freq_map, min_degree);
@@ -88,8 +88,6 @@ pub fn run(
         results.retain(|r| r.strength >= min_s);
     }
 
-    let total = results.len();
-
     // Sort by chosen metric
     match sort_by {
         "shared" => results.sort_by_key(|r| Reverse(r.shared_commits)),
@@ -100,15 +98,38 @@ pub fn run(
         }),
     }
 
+    Ok(results)
+}
+
+pub fn run(
+    cfg: &WalkConfig<'_>,
+    output: OutputMode,
+    top: usize,
+    sort_by: &str,
+    since: Option<&str>,
+    min_degree: usize,
+    min_strength: Option<f64>,
+) -> Result<(), Box<dyn Error>> {
+    let mut results = analyze_project(cfg, sort_by, since, min_degree, min_strength)?;
+    if results.is_empty()
+        && let Some(note) = empty_history_note(since.is_some(), cfg.commits.is_some())
+    {
+        eprintln!("{note}");
+        return Ok(());
+    }
+    let total = results.len();
     results.truncate(top);
 
     match output {
         OutputMode::Json => print_json(&results)?,
         OutputMode::Short => print_short(&results, total),
         OutputMode::Terse => print_terse(total),
-        OutputMode::Github | OutputMode::Codeclimate => {
+        OutputMode::Github | OutputMode::Codeclimate | OutputMode::Sarif => {
             return Err(crate::cli::ERR_CI_FORMAT_ONLY.into());
         }
+        OutputMode::Html | OutputMode::Markdown => {
+            return Err(crate::cli::ERR_REPORT_FORMAT_ONLY.into());
+        }
         OutputMode::Table => print_report(&results, total),
     }
 
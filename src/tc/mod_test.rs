@@ -1,5 +1,6 @@
 use super::*;
 use crate::cli::OutputMode;
+use crate::walk::ExcludeFilter;
 use std::fs;
 use std::path::Path as StdPath;
 
@@ -41,9 +42,8 @@ fn run_on_non_git_dir() {
     let sub = dir.path().join("not_a_repo");
     fs::create_dir_all(&sub).unwrap();
     let err = run(
-        &sub,
+        &WalkConfig::new(&sub, false, &ExcludeFilter::default()),
         OutputMode::Table,
-        false,
         20,
         "strength",
         None,
@@ -60,9 +60,8 @@ fn run_on_non_git_dir() {
 #[test]
 fn run_min_degree_zero_rejected() {
     let err = run(
-        StdPath::new("."),
+        &WalkConfig::new(StdPath::new("."), false, &ExcludeFilter::default()),
         OutputMode::Table,
-        false,
         20,
         "strength",
         None,
@@ -105,9 +104,8 @@ fn integration_basic() {
 
     // Also verify run() succeeds
     let result = run(
-        dir.path(),
+        &WalkConfig::new(dir.path(), false, &ExcludeFilter::default()),
         OutputMode::Table,
-        false,
         20,
         "strength",
         None,
@@ -131,9 +129,8 @@ fn integration_json() {
         );
     }
     let result = run(
-        dir.path(),
+        &WalkConfig::new(dir.path(), false, &ExcludeFilter::default()),
         OutputMode::Json,
-        false,
         20,
         "strength",
         None,
@@ -162,9 +159,8 @@ fn integration_no_coupling() {
         );
     }
     let result = run(
-        dir.path(),
+        &WalkConfig::new(dir.path(), false, &ExcludeFilter::default()),
         OutputMode::Table,
-        false,
         20,
         "strength",
         None,
@@ -189,9 +185,8 @@ fn integration_min_degree_filter() {
         "c2",
     );
     let result = run(
-        dir.path(),
+        &WalkConfig::new(dir.path(), false, &ExcludeFilter::default()),
         OutputMode::Table,
-        false,
         20,
         "strength",
         None,
@@ -243,9 +238,8 @@ fn test_is_test_path() {
 fn run_on_current_repo() {
     // Smoke test on the actual repo
     let result = run(
-        StdPath::new("."),
+        &WalkConfig::new(StdPath::new("."), false, &ExcludeFilter::default()),
         OutputMode::Table,
-        false,
         5,
         "strength",
         None,
@@ -258,9 +252,8 @@ fn run_on_current_repo() {
 #[test]
 fn run_on_current_repo_json() {
     let result = run(
-        StdPath::new("."),
+        &WalkConfig::new(StdPath::new("."), false, &ExcludeFilter::default()),
         OutputMode::Json,
-        false,
         5,
         "strength",
         None,
@@ -273,9 +266,8 @@ fn run_on_current_repo_json() {
 #[test]
 fn run_short_format() {
     let result = run(
-        StdPath::new("."),
+        &WalkConfig::new(StdPath::new("."), false, &ExcludeFilter::default()),
         OutputMode::Short,
-        false,
         5,
         "strength",
         None,
@@ -288,9 +280,8 @@ fn run_short_format() {
 #[test]
 fn run_terse_format() {
     let result = run(
-        StdPath::new("."),
+        &WalkConfig::new(StdPath::new("."), false, &ExcludeFilter::default()),
         OutputMode::Terse,
-        false,
         5,
         "strength",
         None,
@@ -68,8 +68,8 @@ struct JsonEntry {
     level: String,
 }
 
-/// Serialize coupled file pairs as pretty-printed JSON to stdout.
-pub fn print_json(pairs: &[FileCoupling]) -> Result<(), Box<dyn std::error::Error>> {
+/// Serialize coupled file pairs as a pretty-printed JSON string.
+pub fn format_json(pairs: &[FileCoupling]) -> Result<String, Box<dyn std::error::Error>> {
     let entries: Vec<JsonEntry> = pairs
         .iter()
         .map(|p| JsonEntry {
@@ -83,7 +83,13 @@ pub fn print_json(pairs: &[FileCoupling]) -> Result<(), Box<dyn std::error::Erro
         })
         .collect();
 
-    report_helpers::print_json_stdout(&entries)
+    report_helpers::json_string(&entries)
+}
+
+/// Serialize coupled file pairs as pretty-printed JSON to stdout.
+pub fn print_json(pairs: &[FileCoupling]) -> Result<(), Box<dyn std::error::Error>> {
+    println!("{}", format_json(pairs)?);
+    Ok(())
 }
 
 /// Print temporal coupling as a single compact line.
@@ -2,17 +2,17 @@
 //!
 //! Provides binary file detection, streaming file hashing (for dedup),
 //! string masking (for complexity analysis), file reading with line
-//! classification, time-period parsing for `--since` flags, and
+//! classification, time-period parsing for `--since` and `--until` flags, and
 //! `#[cfg(test)]` block detection for duplication exclusion.
 
 use std::error::Error;
-use std::fs::File;
 use std::io::{self, BufReader, Read, Seek, SeekFrom};
 use std::path::Path;
 use std::time::SystemTime;
 
 use crate::loc::counter::{LineKind, classify_reader};
 use crate::loc::language::LanguageSpec;
+use crate::rev;
 
 /// Lines of source code with per-line kind classification (blank/comment/code).
 pub type ClassifiedSource = (Vec<String>, Vec<LineKind>);
@@ -30,8 +30,7 @@ pub fn is_binary_reader<R: Read + Seek>(reader: &mut R) -> io::Result<bool> {
 /// Deterministic across Rust versions (unlike `DefaultHasher`).
 /// Returns `None` if the file cannot be opened or read.
 pub fn hash_file(path: &Path) -> Option<u64> {
-    let file = File::open(path).ok()?;
-    let mut reader = BufReader::new(file);
+    let mut reader = BufReader::new(rev::open(path).ok()?);
     let mut hash: u64 = 0xcbf29ce484222325; // FNV offset basis
     let mut buf = [0u8; 8192];
     loop {
@@ -117,8 +116,7 @@ pub fn mask_strings(line: &str, line_comments: &[&str]) -> String {
 /// Returns None for binary files. On success returns the split lines
 /// and the per-line classification.
 pub fn read_and_classify(path: &Path, spec: &LanguageSpec) -> io::Result<Option<ClassifiedSource>> {
-    let file = File::open(path)?;
-    let mut reader = BufReader::new(file);
+    let mut reader = BufReader::new(rev::open(path)?);
 
     if is_binary_reader(&mut reader)? {
         return Ok(None);
@@ -158,32 +156,41 @@ pub fn indent_level(line: &str) -> usize {
     spaces
 }
 
-/// Parse a duration string like "6m", "1y", "30d" into a Unix timestamp
-/// representing that far back from now.
+/// Parse a time period like "6m", "2w", "1y", "30d" into seconds. `flag`
+/// names the option in error messages.
 ///
 /// Approximations: 1 month = 30 days, 1 year = 365 days.
-pub fn parse_since(s: &str) -> Result<i64, Box<dyn Error>> {
+pub fn parse_period(s: &str, flag: &str) -> Result<u64, Box<dyn Error>> {
     let s = s.trim();
     if s.is_empty() {
-        return Err("empty --since value".into());
+        return Err(format!("empty {flag} value").into());
     }
 
     let split_pos = s.find(|c: char| !c.is_ascii_digit()).ok_or_else(|| {
-        format!("invalid --since value: {s:?} (no unit, expected e.g. 6m, 1y, 30d)")
+        format!("invalid {flag} value: {s:?} (no unit, expected e.g. 6m, 1y, 30d)")
     })?;
 
     let (num_str, unit) = s.split_at(split_pos);
     let n: u64 = num_str
         .parse()
-        .map_err(|_| format!("invalid --since value: {s:?} (expected e.g. 6m, 1y, 30d)"))?;
+        .map_err(|_| format!("invalid {flag} value: {s:?} (expected e.g. 6m, 1y, 30d)"))?;
 
-    let seconds = match unit {
+    match unit {
         "d" | "day" | "days" => n.checked_mul(86_400),
+        "w" | "wk" | "week" | "weeks" => n.checked_mul(7 * 86_400),
         "m" | "mo" | "month" | "months" => n.checked_mul(30 * 86_400),
         "y" | "yr" | "year" | "years" => n.checked_mul(365 * 86_400),
-        _ => return Err(format!("unknown unit in --since: {s:?} (use d, m, or y)").into()),
+        _ => return Err(format!("unknown unit in {flag}: {s:?} (use d, w, m, or y)").into()),
     }
-    .ok_or("--since value too large")?;
+    .ok_or_else(|| format!("{flag} value too large").into())
+}
+
+/// Parse a duration string like "6m", "1y", "30d" into a Unix timestamp
+/// representing that far back from now.
+///
+/// Approximations: 1 month = 30 days, 1 year = 365 days.
+pub fn parse_since(s: &str) -> Result<i64, Box<dyn Error>> {
+    let seconds = parse_period(s, "--since")?;
 
     let now = SystemTime::now()
         .duration_since(SystemTime::UNIX_EPOCH)?
@@ -196,6 +203,28 @@ pub fn parse_since(s: &str) -> Result<i64, Box<dyn Error>> {
     Ok(ts as i64)
 }
 
+/// Parse an `--until` value into a Unix timestamp: a date (`2024-06-30`,
+/// up to the end of that day in UTC) or a duration back from now, as
+/// accepted by [`parse_since`] (`3m` for three months ago).
+pub fn parse_until(s: &str) -> Result<i64, Box<dyn Error>> {
+    let s = s.trim();
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        let end_of_day = date.and_hms_opt(23, 59, 59).expect("valid time of day");
+        return Ok(end_of_day.and_utc().timestamp());
+    }
+    if s.starts_with(|c: char| c.is_ascii_digit()) && s.contains('-') {
+        return Err(format!("invalid --until date: {s:?} (expected YYYY-MM-DD)").into());
+    }
+    let seconds = parse_period(s, "--until")?;
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)?
+        .as_secs();
+    let ts = now
+        .checked_sub(seconds)
+        .ok_or("--until value goes before Unix epoch")?;
+    Ok(ts as i64)
+}
+
 #[cfg(test)]
 #[path = "util_test.rs"]
 mod tests;
@@ -66,3 +66,18 @@ fn mask_strings_comment_marker_inside_string() {
         r#"x = "          "; if y"#
     );
 }
+
+#[test]
+fn parse_until_accepts_dates_and_durations() {
+    // 2024-06-30 23:59:59 UTC: the whole day is included.
+    assert_eq!(parse_until("2024-06-30").unwrap(), 1_719_791_999);
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+    let ts = parse_until("30d").unwrap();
+    assert!((now - 30 * 86_400 - ts).abs() < 5);
+    let err = parse_until("2024-13-01").unwrap_err();
+    assert!(err.to_string().contains("YYYY-MM-DD"), "{err}");
+    assert!(parse_until("soon").is_err());
+}
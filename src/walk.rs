@@ -4,15 +4,22 @@
 //! `.git` directories, filters test directories/files when requested,
 //! and detects source file languages by extension or shebang line.
 //! Uses the `ignore` crate for efficient `.gitignore`-aware traversal.
+//! Per-file analysis fans out over a scoped thread pool (`par_map`) while
+//! keeping results in walk order, so output stays deterministic.
 use std::collections::HashSet;
-use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use globset::{Glob, GlobSet, GlobSetBuilder};
 use ignore::WalkBuilder;
 
+use crate::cache::{self, AnalysisCache};
+use crate::git::{
+    AuthorAliases, CommitExclusions, CommitFilter, DEFAULT_RENAME_THRESHOLD, FixPatterns,
+};
 use crate::loc::language::{LanguageSpec, detect, detect_by_shebang};
+use crate::rev::{self, RevTree};
 
 /// Filter that excludes files by extension, directory name, or glob pattern.
 ///
@@ -30,6 +37,8 @@ pub struct ExcludeFilter {
     dirs: HashSet<Box<str>>,
     /// Compiled glob patterns for file path matching.
     globs: Option<GlobSet>,
+    /// Source of `globs`, kept for [`ExcludeFilter::fingerprint`].
+    glob_patterns: Vec<String>,
 }
 
 impl ExcludeFilter {
@@ -59,15 +68,39 @@ impl ExcludeFilter {
         };
         let extensions = normalize_exts(extensions);
         let dirs = dirs.iter().map(|d| d.clone().into_boxed_str()).collect();
+        let glob_patterns = globs.to_vec();
         let globs = Self::build_glob_set(globs);
         Self {
             include_extensions,
             extensions,
             dirs,
             globs,
+            glob_patterns,
         }
     }
 
+    /// A stable description of the filter, equal for filters that select
+    /// the same files. Used to key results stored across runs.
+    pub fn fingerprint(&self) -> String {
+        let sorted = |set: &HashSet<Box<str>>| {
+            let mut v: Vec<&str> = set.iter().map(|s| &**s).collect();
+            v.sort_unstable();
+            v.join(",")
+        };
+        let mut globs: Vec<&str> = self.glob_patterns.iter().map(String::as_str).collect();
+        globs.sort_unstable();
+        format!(
+            "include-ext={};exclude-ext={};exclude-dir={};exclude={}",
+            self.include_extensions
+                .as_ref()
+                .map(sorted)
+                .unwrap_or_default(),
+            sorted(&self.extensions),
+            sorted(&self.dirs),
+            globs.join(","),
+        )
+    }
+
     /// Returns `true` if no filters have been configured.
     #[cfg(test)]
     pub fn is_empty(&self) -> bool {
@@ -150,9 +183,9 @@ impl ExcludeFilter {
 
 /// Configuration for directory walking and file filtering.
 ///
-/// Bundles the analysis root path, test inclusion flag, and exclude filter
-/// into a single struct passed to all analysis modules. This eliminates
-/// the need to thread three separate parameters through every `run()` function.
+/// Bundles the analysis root path, test inclusion flag, exclude filter, and
+/// worker count into a single struct passed to all analysis modules. This
+/// eliminates the need to thread separate parameters through every `run()` function.
 #[derive(Debug)]
 pub struct WalkConfig<'a> {
     /// Root directory to analyze.
@@ -161,17 +194,94 @@ pub struct WalkConfig<'a> {
     pub include_tests: bool,
     /// File and directory exclusion filter.
     pub filter: &'a ExcludeFilter,
+    /// Number of worker threads for per-file analysis (1 = sequential).
+    pub jobs: usize,
+    /// On-disk cache of per-file results, if enabled.
+    pub cache: Option<&'a AnalysisCache>,
+    /// Author aliases for the blame-based analyses (`[authors.aliases]`).
+    pub aliases: Option<&'a AuthorAliases>,
+    /// Bug-fix commit patterns (`[defects] patterns`); `None` uses the defaults.
+    pub fix_patterns: Option<&'a FixPatterns>,
+    /// Range, date, author and path restrictions on git history
+    /// (`--range`, `--until`, `--author`, `--path`); `None` reads all of it.
+    pub commits: Option<&'a CommitFilter>,
+    /// Bot and mass-change commits left out of git history and blame
+    /// (`[git] ignore_authors`, `ignore_commits`, `max_changeset_size`).
+    pub exclusions: Option<&'a CommitExclusions>,
+    /// Similarity (percent) for following renames in git history; 0 = off.
+    pub rename_threshold: u16,
+    /// Revision tree the path is mounted from (`--rev`), entered on every
+    /// worker thread; `None` analyzes the working tree.
+    pub tree: Option<&'a RevTree>,
 }
 
 impl<'a> WalkConfig<'a> {
+    /// Create a config that analyzes files on all available cores.
     pub fn new(path: &'a Path, include_tests: bool, filter: &'a ExcludeFilter) -> Self {
         Self {
             path,
             include_tests,
             filter,
+            jobs: default_jobs(),
+            cache: None,
+            aliases: None,
+            fix_patterns: None,
+            commits: None,
+            exclusions: None,
+            rename_threshold: DEFAULT_RENAME_THRESHOLD,
+            tree: None,
         }
     }
 
+    /// Override the worker count (`--jobs`). Zero means "all available cores".
+    pub fn with_jobs(mut self, jobs: usize) -> Self {
+        self.jobs = if jobs == 0 { default_jobs() } else { jobs };
+        self
+    }
+
+    /// Reuse per-file results from `cache` for files whose content is unchanged.
+    pub fn with_cache(mut self, cache: Option<&'a AnalysisCache>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Merge author identities with `aliases` on top of `.mailmap`.
+    pub fn with_aliases(mut self, aliases: Option<&'a AuthorAliases>) -> Self {
+        self.aliases = aliases;
+        self
+    }
+
+    /// Classify bug-fix commits with `patterns` instead of the defaults.
+    pub fn with_fix_patterns(mut self, patterns: Option<&'a FixPatterns>) -> Self {
+        self.fix_patterns = patterns;
+        self
+    }
+
+    /// Read only the git history that passes `filter`.
+    pub fn with_commit_filter(mut self, filter: Option<&'a CommitFilter>) -> Self {
+        self.commits = filter;
+        self
+    }
+
+    /// Leave the commits matched by `exclusions` out of git history and blame.
+    pub fn with_exclusions(mut self, exclusions: Option<&'a CommitExclusions>) -> Self {
+        self.exclusions = exclusions;
+        self
+    }
+
+    /// Follow renames in git history at `threshold` percent similarity
+    /// (`[git] rename_threshold`); 0 turns rename tracking off.
+    pub fn with_rename_threshold(mut self, threshold: u16) -> Self {
+        self.rename_threshold = threshold;
+        self
+    }
+
+    /// Read files from `tree`, mounted over `path`, on every worker thread.
+    pub fn with_tree(mut self, tree: Option<&'a RevTree>) -> Self {
+        self.tree = tree;
+        self
+    }
+
     /// Whether test files/directories should be excluded (inverse of `include_tests`).
     pub fn exclude_tests(&self) -> bool {
         !self.include_tests
@@ -182,15 +292,95 @@ impl<'a> WalkConfig<'a> {
         source_files(self.path, self.exclude_tests(), self.filter)
     }
 
-    /// Walk and analyze source files using this config.
-    pub fn collect_analysis<T>(
+    /// Walk and analyze source files using this config, running `f` on
+    /// `jobs` threads. Results are returned in walk order.
+    pub fn collect_analysis<T: Send>(
         &self,
-        f: impl Fn(&Path, &LanguageSpec) -> Result<Option<T>, Box<dyn std::error::Error>>,
+        f: impl Fn(&Path, &LanguageSpec) -> Result<Option<T>, Box<dyn std::error::Error>> + Sync,
     ) -> Vec<T> {
-        collect_analysis(self.path, self.exclude_tests(), self.filter, f)
+        let files = self.source_files();
+        let outcomes = self.par_map(&files, |(file_path, spec)| {
+            f(file_path, spec).map_err(|err| err.to_string())
+        });
+        let mut results = Vec::new();
+        for ((file_path, _), outcome) in files.iter().zip(outcomes) {
+            match outcome {
+                Ok(Some(m)) => results.push(m),
+                Ok(None) => {}
+                Err(err) => {
+                    eprintln!("warning: {}: {err}", file_path.display());
+                }
+            }
+        }
+        results
+    }
+
+    /// Map `f` over `items` on this config's worker threads, preserving order.
+    /// Each worker reads from this config's tree, if any.
+    pub fn par_map<T: Sync, R: Send>(&self, items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+        par_map_in(items, self.jobs, self.tree, f)
     }
 }
 
+/// Number of worker threads used when `--jobs` is not given: one per
+/// available core, falling back to 1 when parallelism cannot be queried.
+pub fn default_jobs() -> usize {
+    std::thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// Apply `f` to every item on up to `jobs` scoped threads and return the
+/// results in input order. Workers pull the next index from a shared
+/// counter, so slow files do not stall a fixed chunk. With `jobs <= 1` or a
+/// single item, runs inline on the calling thread.
+pub fn par_map<T: Sync, R: Send>(items: &[T], jobs: usize, f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    par_map_in(items, jobs, None, f)
+}
+
+/// [`par_map`] with `tree` mounted on every worker thread.
+fn par_map_in<T: Sync, R: Send>(
+    items: &[T],
+    jobs: usize,
+    tree: Option<&RevTree>,
+    f: impl Fn(&T) -> R + Sync,
+) -> Vec<R> {
+    let in_tree = |work: &dyn Fn() -> Vec<(usize, R)>| match tree {
+        Some(tree) => tree.scope(work),
+        None => work(),
+    };
+    let workers = jobs.min(items.len());
+    if workers <= 1 {
+        let inline = || items.iter().map(&f).enumerate().collect();
+        return in_tree(&inline).into_iter().map(|(_, r)| r).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let mut indexed: Vec<(usize, R)> = std::thread::scope(|s| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                s.spawn(|| {
+                    in_tree(&|| {
+                        let mut local = Vec::new();
+                        loop {
+                            let i = next.fetch_add(1, Ordering::Relaxed);
+                            let Some(item) = items.get(i) else {
+                                break;
+                            };
+                            local.push((i, f(item)));
+                        }
+                        local
+                    })
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().expect("analysis worker panicked"))
+            .collect()
+    });
+    indexed.sort_unstable_by_key(|(i, _)| *i);
+    indexed.into_iter().map(|(_, r)| r).collect()
+}
+
 /// Test directory names to exclude when `--exclude-tests` is active.
 pub const TEST_DIRS: &[&str] = &["tests", "test", "__tests__", "spec"];
 
@@ -295,8 +485,7 @@ pub fn is_test_file(path: &Path) -> bool {
 
 /// Try to detect a language by reading the shebang line of a file.
 pub fn try_detect_shebang(path: &Path) -> Option<&'static LanguageSpec> {
-    let file = File::open(path).ok()?;
-    let mut reader = BufReader::new(file);
+    let mut reader = BufReader::new(rev::open(path).ok()?);
     let mut first_line = String::new();
     reader.read_line(&mut first_line).ok()?;
     detect_by_shebang(&first_line)
@@ -305,12 +494,16 @@ pub fn try_detect_shebang(path: &Path) -> Option<&'static LanguageSpec> {
 /// Walk the directory tree and return all recognized source files with their
 /// detected language spec. Handles errors, filters non-files, excludes test
 /// files when requested, applies the `ExcludeFilter`, and detects language by
-/// extension or shebang.
+/// extension or shebang. A directory inside a mounted `--rev` tree is listed
+/// from the tree (see [`crate::rev`]) with the same rules.
 pub fn source_files(
     path: &Path,
     exclude_tests: bool,
     filter: &ExcludeFilter,
 ) -> Vec<(PathBuf, &'static LanguageSpec)> {
+    if let Some(files) = rev::files_under(path) {
+        return tree_source_files(path, files, exclude_tests, filter);
+    }
     let mut result = Vec::new();
     for entry in walk(path, exclude_tests, filter) {
         let entry = match entry {
@@ -334,25 +527,49 @@ pub fn source_files(
     result
 }
 
+/// Apply the walker's rules to the files of a mounted tree under `path`:
+/// skipped and excluded directories, test files, and the `ExcludeFilter`.
+fn tree_source_files(
+    path: &Path,
+    files: Vec<PathBuf>,
+    exclude_tests: bool,
+    filter: &ExcludeFilter,
+) -> Vec<(PathBuf, &'static LanguageSpec)> {
+    let skips_dir = |name: &str| {
+        name == ".git"
+            || name == cache::DEFAULT_DIR
+            || filter.excludes_dir(name)
+            || (exclude_tests && TEST_DIRS.contains(&name))
+    };
+    files
+        .into_iter()
+        .filter(|file| {
+            let rel = file.strip_prefix(path).unwrap_or(file);
+            let in_skipped_dir = rel.parent().is_some_and(|dir| {
+                dir.components()
+                    .any(|c| c.as_os_str().to_str().is_some_and(skips_dir))
+            });
+            let excluded = in_skipped_dir
+                || (exclude_tests && is_test_file(file))
+                || filter.excludes_file(file, path);
+            !excluded
+        })
+        .filter_map(|file| {
+            let spec = detect(&file).or_else(|| try_detect_shebang(&file))?;
+            Some((file, spec))
+        })
+        .collect()
+}
+
 /// Walk source files, analyze each with `f`, and collect successful results.
 /// Handles the common Ok(Some)/Ok(None)/Err pattern used across modules.
-pub fn collect_analysis<T>(
+pub fn collect_analysis<T: Send>(
     path: &Path,
     exclude_tests: bool,
     filter: &ExcludeFilter,
-    f: impl Fn(&Path, &LanguageSpec) -> Result<Option<T>, Box<dyn std::error::Error>>,
+    f: impl Fn(&Path, &LanguageSpec) -> Result<Option<T>, Box<dyn std::error::Error>> + Sync,
 ) -> Vec<T> {
-    let mut results = Vec::new();
-    for (file_path, spec) in source_files(path, exclude_tests, filter) {
-        match f(&file_path, spec) {
-            Ok(Some(m)) => results.push(m),
-            Ok(None) => {}
-            Err(err) => {
-                eprintln!("warning: {}: {err}", file_path.display());
-            }
-        }
-    }
-    results
+    WalkConfig::new(path, !exclude_tests, filter).collect_analysis(f)
 }
 
 /// Build a directory walker that respects `.gitignore`, skips `.git`,
@@ -367,10 +584,11 @@ pub(crate) fn walk(path: &Path, exclude_tests: bool, filter: &ExcludeFilter) ->
     WalkBuilder::new(path)
         .hidden(false)
         .follow_links(false)
+        .sort_by_file_name(|a, b| a.cmp(b))
         .filter_entry(move |entry| {
             let ft = entry.file_type();
             if ft.is_some_and(|ft| ft.is_dir()) {
-                if entry.file_name() == ".git" {
+                if entry.file_name() == ".git" || entry.file_name() == cache::DEFAULT_DIR {
                     return false;
                 }
                 if let Some(name) = entry.file_name().to_str() {
@@ -31,6 +31,30 @@ fn exclude_filter_is_empty() {
     assert!(!f.is_empty());
 }
 
+#[test]
+fn fingerprint_ignores_order_and_normalization() {
+    let strs = |v: &[&str]| v.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+    let a = ExcludeFilter::new(
+        &[],
+        &strs(&["js", ".TS"]),
+        &strs(&["b", "a"]),
+        &strs(&["*.g"]),
+    );
+    let b = ExcludeFilter::new(
+        &[],
+        &strs(&["ts", "js"]),
+        &strs(&["a", "b"]),
+        &strs(&["*.g"]),
+    );
+    assert_eq!(a.fingerprint(), b.fingerprint());
+    assert_ne!(a.fingerprint(), ExcludeFilter::default().fingerprint());
+    let c = ExcludeFilter::new(&strs(&["js"]), &[], &[], &[]);
+    assert_ne!(
+        c.fingerprint(),
+        ExcludeFilter::new(&[], &strs(&["js"]), &[], &[]).fingerprint()
+    );
+}
+
 // ── Include-ext (allowlist mode) ─────────────────────────────────────
 
 #[test]
@@ -555,6 +579,50 @@ fn collect_analysis_err_skips_with_warning() {
     assert!(results.is_empty(), "Err should be skipped");
 }
 
+#[test]
+fn collect_analysis_order_is_independent_of_jobs() {
+    let dir = tempdir().unwrap();
+    for i in 0..40 {
+        fs::write(dir.path().join(format!("f{i:02}.rs")), "fn main() {}").unwrap();
+    }
+
+    let filter = ExcludeFilter::default();
+    let analyze = |jobs: usize| -> Vec<PathBuf> {
+        WalkConfig::new(dir.path(), false, &filter)
+            .with_jobs(jobs)
+            .collect_analysis(|path, _spec| Ok(Some(path.to_path_buf())))
+    };
+
+    let sequential = analyze(1);
+    assert_eq!(sequential.len(), 40);
+    assert!(sequential.windows(2).all(|w| w[0] < w[1]), "walk is sorted");
+    assert_eq!(analyze(8), sequential);
+}
+
+// ── par_map ─────────────────────────────────────────────────────────────
+
+#[test]
+fn par_map_preserves_input_order() {
+    let items: Vec<usize> = (0..500).collect();
+    let doubled = par_map(&items, 7, |n| n * 2);
+    assert_eq!(doubled, items.iter().map(|n| n * 2).collect::<Vec<_>>());
+}
+
+#[test]
+fn par_map_handles_empty_and_single_job() {
+    let empty: Vec<u8> = Vec::new();
+    assert!(par_map(&empty, 4, |b| *b).is_empty());
+    assert_eq!(par_map(&[1, 2, 3], 1, |n| n + 1), vec![2, 3, 4]);
+}
+
+#[test]
+fn with_jobs_zero_uses_default() {
+    let filter = ExcludeFilter::default();
+    let cfg = WalkConfig::new(Path::new("."), false, &filter).with_jobs(0);
+    assert_eq!(cfg.jobs, default_jobs());
+    assert_eq!(cfg.with_jobs(3).jobs, 3);
+}
+
 // ── print_excluded_files ────────────────────────────────────────────────
 
 #[test]